@@ -1,237 +1,176 @@
-use serde::{Deserialize, Serialize};
-
-/// Recognized fiat currency codes. Prevents false positives on tokens like `1inch` or `3btc`.
-const KNOWN_FIAT: &[&str] = &[
-    "USD", "EUR", "GBP", "JPY", "CNY", "CAD", "AUD", "CHF", "KRW", "INR", "BRL", "RUB", "TRY",
-    "ZAR", "MXN", "SGD", "HKD", "NOK", "SEK", "DKK", "NZD", "PLN", "THB", "TWD", "CZK", "HUF",
-    "ILS", "PHP", "MYR", "ARS", "CLP", "COP", "IDR", "SAR", "AED", "NGN", "VND", "PKR", "BDT",
-    "EGP",
+pub mod alerts;
+pub mod align;
+pub mod allocation;
+pub mod amount;
+pub mod compare;
+pub mod currency;
+pub mod downsample;
+pub mod fill;
+pub mod movers;
+pub mod period;
+pub mod resample;
+pub mod search;
+pub mod stats;
+
+pub use alerts::{AlertDirection, AlertResult, PriceAlert, check_alert, parse_price_alert};
+pub use allocation::{Allocation, portfolio_allocation};
+pub use amount::{CryptoAmount, FiatAmount, parse_crypto_amount, parse_fiat_amount};
+pub use compare::{
+    ProviderComparison, ProviderQuote, TwoDateComparison, compare_provider_quotes,
+    compute_two_date_change,
+};
+pub use currency::{
+    Conversion, ConversionMatrix, CurrencySupport, conversion_matrix, convert_prices,
+    currency_support, fiat_name, foreign_quote_currencies, is_crypto_quote_asset, is_known_fiat,
+};
+pub use downsample::downsample_uniform;
+pub use movers::{Movers, split_movers};
+pub use period::{
+    Performance, PerformancePeriod, change_since, months_before, performance, period_return,
+    period_to_days,
+};
+pub use search::{AssetType, SearchFilter, exchange_listings};
+pub use stats::{HistoryStats, ReturnStats, history_stats, return_stats};
+
+use std::cmp::Ordering;
+
+use tracing::warn;
+
+use crate::provider::CoinPrice;
+
+/// Major USD- and EUR-pegged stablecoins, dropped or kept by `--exclude-stablecoins`
+/// and `--include-only-stablecoins`.
+const KNOWN_STABLECOINS: &[&str] = &[
+    "USDT", "USDC", "DAI", "BUSD", "TUSD", "USDP", "FDUSD", "PYUSD", "USDE", "USDD", "GUSD",
+    "FRAX", "LUSD", "USDS", "EURC", "EURT",
 ];
 
-/// A parsed fiat amount from user input (e.g. `3.5EUR`).
-#[derive(Debug, Clone)]
-pub struct FiatAmount {
-    pub amount: f64,
-    pub currency: String,
+/// Returns `true` when `symbol` (case-insensitive) is a known stablecoin.
+pub fn is_stablecoin(symbol: &str) -> bool {
+    KNOWN_STABLECOINS.contains(&symbol.trim().to_uppercase().as_str())
 }
 
-/// A parsed crypto amount from user input (e.g. `2.5XMR`).
-#[derive(Debug, Clone)]
-pub struct CryptoAmount {
-    pub amount: f64,
-    pub symbol: String,
-}
-
-/// Result of a fiat-to-crypto conversion.
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct Conversion {
-    pub from_amount: f64,
-    pub from_currency: String,
-    pub to_symbol: String,
-    pub to_name: String,
-    pub to_amount: f64,
-    pub rate: f64,
-    pub provider: String,
-    pub timestamp: chrono::DateTime<chrono::Utc>,
-}
-
-/// Try to parse a string like `3.5EUR` or `100usd` into a `FiatAmount`.
-///
-/// Returns `None` when the input does not match `<number><fiat_code>`, letting
-/// the caller fall through to normal price-lookup mode.
-pub fn parse_fiat_amount(s: &str) -> Option<FiatAmount> {
-    // Find where the alphabetic suffix starts.
-    let alpha_start = s.find(|c: char| c.is_ascii_alphabetic())?;
-    if alpha_start == 0 {
-        return None;
-    }
-
-    let (num_part, code_part) = s.split_at(alpha_start);
-    let code_upper = code_part.to_uppercase();
+/// Drop stablecoins from `prices` (`exclude`), or keep only stablecoins.
+pub fn filter_stablecoins(prices: Vec<CoinPrice>, exclude: bool) -> Vec<CoinPrice> {
+    let had_prices = !prices.is_empty();
+    let kept: Vec<CoinPrice> = prices
+        .into_iter()
+        .filter(|p| is_stablecoin(&p.symbol) != exclude)
+        .collect();
 
-    if !KNOWN_FIAT.contains(&code_upper.as_str()) {
-        return None;
+    if had_prices && kept.is_empty() {
+        if exclude {
+            warn!("--exclude-stablecoins removed every result");
+        } else {
+            warn!("--include-only-stablecoins removed every result");
+        }
     }
 
-    let amount: f64 = num_part.parse().ok()?;
-    if amount <= 0.0 || !amount.is_finite() {
-        return None;
-    }
-
-    Some(FiatAmount {
-        amount,
-        currency: code_upper,
-    })
+    kept
 }
 
-/// Try to parse a string like `2.5XMR` or `0.1btc` into a `CryptoAmount`.
-///
-/// Succeeds when the alphabetic suffix is NOT a known fiat currency, treating it
-/// as a crypto symbol. Returns `None` for fiat codes, plain words, or invalid numbers.
-pub fn parse_crypto_amount(s: &str) -> Option<CryptoAmount> {
-    let alpha_start = s.find(|c: char| c.is_ascii_alphabetic())?;
-    if alpha_start == 0 {
-        return None;
-    }
-
-    let (num_part, code_part) = s.split_at(alpha_start);
-    let code_upper = code_part.to_uppercase();
-
-    // If it's a known fiat code, this isn't a crypto amount.
-    if KNOWN_FIAT.contains(&code_upper.as_str()) {
-        return None;
-    }
-
-    let amount: f64 = num_part.parse().ok()?;
-    if amount <= 0.0 || !amount.is_finite() {
-        return None;
-    }
-
-    Some(CryptoAmount {
-        amount,
-        symbol: code_upper,
-    })
-}
-
-/// Returns `true` when `s` (case-insensitive) is a recognized fiat currency code.
-pub fn is_known_fiat(s: &str) -> bool {
-    KNOWN_FIAT.contains(&s.to_uppercase().as_str())
+/// Ordering applied to price results with `--sort-by`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PriceSort {
+    /// Market-cap rank, ascending.
+    Rank,
+    /// Price, descending.
+    Price,
+    /// 24h change, descending.
+    Change,
+    /// Market cap, descending.
+    MarketCap,
+    /// Symbol, alphabetical.
+    Symbol,
 }
 
-/// Human-readable name for a fiat currency code. Falls back to the code itself.
-pub fn fiat_name(code: &str) -> &str {
-    match code.to_uppercase().as_str() {
-        "USD" => "US Dollar",
-        "EUR" => "Euro",
-        "GBP" => "British Pound",
-        "JPY" => "Japanese Yen",
-        "CNY" => "Chinese Yuan",
-        "CAD" => "Canadian Dollar",
-        "AUD" => "Australian Dollar",
-        "CHF" => "Swiss Franc",
-        "KRW" => "South Korean Won",
-        "INR" => "Indian Rupee",
-        "BRL" => "Brazilian Real",
-        "RUB" => "Russian Ruble",
-        "TRY" => "Turkish Lira",
-        "ZAR" => "South African Rand",
-        "MXN" => "Mexican Peso",
-        "SGD" => "Singapore Dollar",
-        "HKD" => "Hong Kong Dollar",
-        "NOK" => "Norwegian Krone",
-        "SEK" => "Swedish Krona",
-        "DKK" => "Danish Krone",
-        "NZD" => "New Zealand Dollar",
-        "PLN" => "Polish Zloty",
-        "THB" => "Thai Baht",
-        "TWD" => "New Taiwan Dollar",
-        "CZK" => "Czech Koruna",
-        "HUF" => "Hungarian Forint",
-        "ILS" => "Israeli Shekel",
-        "PHP" => "Philippine Peso",
-        "MYR" => "Malaysian Ringgit",
-        "ARS" => "Argentine Peso",
-        "CLP" => "Chilean Peso",
-        "COP" => "Colombian Peso",
-        "IDR" => "Indonesian Rupiah",
-        "SAR" => "Saudi Riyal",
-        "AED" => "UAE Dirham",
-        "NGN" => "Nigerian Naira",
-        "VND" => "Vietnamese Dong",
-        "PKR" => "Pakistani Rupee",
-        "BDT" => "Bangladeshi Taka",
-        "EGP" => "Egyptian Pound",
-        _ => code,
+/// Sort prices in place; entries missing the sort field keep their order at the end.
+pub fn sort_prices(prices: &mut [CoinPrice], sort: PriceSort) {
+    fn missing_last<T>(a: Option<T>, b: Option<T>, cmp: impl Fn(T, T) -> Ordering) -> Ordering {
+        match (a, b) {
+            (Some(a), Some(b)) => cmp(a, b),
+            (Some(_), None) => Ordering::Less,
+            (None, Some(_)) => Ordering::Greater,
+            (None, None) => Ordering::Equal,
+        }
+    }
+
+    match sort {
+        PriceSort::Rank => prices.sort_by(|a, b| missing_last(a.rank, b.rank, |a, b| a.cmp(&b))),
+        PriceSort::Price => prices.sort_by(|a, b| b.price.total_cmp(&a.price)),
+        PriceSort::Change => {
+            prices.sort_by(|a, b| missing_last(a.change_24h, b.change_24h, |a, b| b.total_cmp(&a)))
+        }
+        PriceSort::MarketCap => {
+            prices.sort_by(|a, b| missing_last(a.market_cap, b.market_cap, |a, b| b.total_cmp(&a)))
+        }
+        PriceSort::Symbol => prices.sort_by_key(|p| p.symbol.to_uppercase()),
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::provider::test_price;
 
-    #[test]
-    fn parse_basic_cases() {
-        let fa = parse_fiat_amount("3.5EUR").unwrap();
-        assert!((fa.amount - 3.5).abs() < f64::EPSILON);
-        assert_eq!(fa.currency, "EUR");
-
-        let fa = parse_fiat_amount("100usd").unwrap();
-        assert!((fa.amount - 100.0).abs() < f64::EPSILON);
-        assert_eq!(fa.currency, "USD");
-    }
-
-    #[test]
-    fn parse_lowercase_currency() {
-        let fa = parse_fiat_amount("42gbp").unwrap();
-        assert_eq!(fa.currency, "GBP");
-    }
-
-    #[test]
-    fn rejects_crypto_symbols() {
-        assert!(parse_fiat_amount("1inch").is_none());
-        assert!(parse_fiat_amount("3btc").is_none());
-    }
-
-    #[test]
-    fn rejects_plain_words() {
-        assert!(parse_fiat_amount("btc").is_none());
-        assert!(parse_fiat_amount("hello").is_none());
-    }
-
-    #[test]
-    fn rejects_negative_and_zero() {
-        assert!(parse_fiat_amount("-5USD").is_none());
-        assert!(parse_fiat_amount("0USD").is_none());
-    }
-
-    #[test]
-    fn rejects_no_number() {
-        assert!(parse_fiat_amount("EUR").is_none());
-    }
-
-    #[test]
-    fn is_known_fiat_works() {
-        assert!(is_known_fiat("USD"));
-        assert!(is_known_fiat("eur"));
-        assert!(is_known_fiat("Gbp"));
-        assert!(!is_known_fiat("BTC"));
-        assert!(!is_known_fiat("ETH"));
-        assert!(!is_known_fiat(""));
-    }
-
-    #[test]
-    fn fiat_name_known_codes() {
-        assert_eq!(fiat_name("USD"), "US Dollar");
-        assert_eq!(fiat_name("eur"), "Euro");
-        assert_eq!(fiat_name("GBP"), "British Pound");
-    }
-
-    #[test]
-    fn fiat_name_unknown_returns_code() {
-        assert_eq!(fiat_name("XYZ"), "XYZ");
+    fn symbols_of(prices: &[CoinPrice]) -> Vec<&str> {
+        prices.iter().map(|p| p.symbol.as_str()).collect()
     }
 
     #[test]
-    fn parse_crypto_basic() {
-        let ca = parse_crypto_amount("2.5XMR").unwrap();
-        assert!((ca.amount - 2.5).abs() < f64::EPSILON);
-        assert_eq!(ca.symbol, "XMR");
-
-        let ca = parse_crypto_amount("0.1btc").unwrap();
-        assert!((ca.amount - 0.1).abs() < f64::EPSILON);
-        assert_eq!(ca.symbol, "BTC");
+    fn sort_prices_orders_by_key_with_missing_values_last() {
+        let ranked = |symbol: &str, value: f64, rank: Option<u32>, change: Option<f64>| CoinPrice {
+            rank,
+            change_24h: change,
+            ..test_price(symbol, value)
+        };
+        let mut prices = vec![
+            ranked("ETH", 2500.0, Some(2), Some(-1.5)),
+            ranked("AAPL", 190.0, None, None),
+            ranked("BTC", 50000.0, Some(1), Some(3.0)),
+        ];
+        let symbols = |prices: &[CoinPrice]| -> Vec<String> {
+            prices.iter().map(|p| p.symbol.clone()).collect()
+        };
+
+        sort_prices(&mut prices, PriceSort::Rank);
+        assert_eq!(symbols(&prices), vec!["BTC", "ETH", "AAPL"]);
+
+        sort_prices(&mut prices, PriceSort::Symbol);
+        assert_eq!(symbols(&prices), vec!["AAPL", "BTC", "ETH"]);
+
+        sort_prices(&mut prices, PriceSort::Change);
+        assert_eq!(symbols(&prices), vec!["BTC", "ETH", "AAPL"]);
+
+        sort_prices(&mut prices, PriceSort::Price);
+        assert_eq!(symbols(&prices), vec!["BTC", "ETH", "AAPL"]);
     }
 
     #[test]
-    fn parse_crypto_rejects_fiat() {
-        assert!(parse_crypto_amount("100USD").is_none());
-        assert!(parse_crypto_amount("3.5eur").is_none());
+    fn filter_stablecoins_excludes_or_keeps_only_stablecoins() {
+        let mixed = || {
+            vec![
+                test_price("BTC", 1.0),
+                test_price("usdt", 1.0),
+                test_price("ETH", 1.0),
+                test_price("DAI", 1.0),
+            ]
+        };
+
+        assert_eq!(
+            symbols_of(&filter_stablecoins(mixed(), true)),
+            vec!["BTC", "ETH"]
+        );
+        assert_eq!(
+            symbols_of(&filter_stablecoins(mixed(), false)),
+            vec!["usdt", "DAI"]
+        );
     }
 
     #[test]
-    fn parse_crypto_rejects_invalid() {
-        assert!(parse_crypto_amount("btc").is_none());
-        assert!(parse_crypto_amount("0BTC").is_none());
-        assert!(parse_crypto_amount("-1ETH").is_none());
+    fn filter_stablecoins_can_remove_every_result() {
+        assert!(filter_stablecoins(vec![test_price("USDC", 1.0)], true).is_empty());
+        assert!(filter_stablecoins(vec![test_price("BTC", 1.0)], false).is_empty());
+        assert!(filter_stablecoins(Vec::new(), true).is_empty());
     }
 }
@@ -0,0 +1,253 @@
+//! Price alerts checked by `--price-alert`.
+
+use serde::{Deserialize, Serialize};
+
+use crate::provider::CoinPrice;
+
+/// Comparison made by a `--price-alert` check.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum AlertDirection {
+    /// Price at or above an absolute value.
+    Above,
+    /// Price at or below an absolute value.
+    Below,
+    /// Price at least the given percent above the price 24h ago.
+    AbovePct,
+    /// Price at least the given percent below the price 24h ago.
+    BelowPct,
+}
+
+impl AlertDirection {
+    /// Render the direction as its CLI-facing lowercase string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Above => "above",
+            Self::Below => "below",
+            Self::AbovePct => "above-pct",
+            Self::BelowPct => "below-pct",
+        }
+    }
+}
+
+/// A one-shot price threshold, written `SYMBOL:DIRECTION:VALUE` on the command line.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PriceAlert {
+    pub symbol: String,
+    pub direction: AlertDirection,
+    pub value: f64,
+}
+
+/// Parse `SYMBOL:DIRECTION:VALUE`, e.g. `BTC:ABOVE:100000` or `eth:below-pct:5`.
+///
+/// Directions are case-insensitive; the value must be a finite, non-negative number.
+pub fn parse_price_alert(s: &str) -> Option<PriceAlert> {
+    let mut parts = s.trim().splitn(3, ':');
+    let symbol = parts.next()?.trim();
+    let direction = match parts.next()?.trim().to_ascii_lowercase().as_str() {
+        "above" => AlertDirection::Above,
+        "below" => AlertDirection::Below,
+        "above-pct" => AlertDirection::AbovePct,
+        "below-pct" => AlertDirection::BelowPct,
+        _ => return None,
+    };
+    let value = parts
+        .next()?
+        .trim()
+        .replace(',', "")
+        .parse::<f64>()
+        .ok()
+        .filter(|value| value.is_finite() && *value >= 0.0)?;
+    if symbol.is_empty() {
+        return None;
+    }
+
+    Some(PriceAlert {
+        symbol: symbol.to_string(),
+        direction,
+        value,
+    })
+}
+
+/// Outcome of checking a [`PriceAlert`] against a fetched price.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlertResult {
+    pub symbol: String,
+    pub price: f64,
+    pub currency: String,
+    pub provider: String,
+    pub direction: AlertDirection,
+    /// Price the alert compares against; `None` when a percent alert has no 24h change.
+    pub threshold: Option<f64>,
+    pub triggered: bool,
+}
+
+impl AlertResult {
+    /// One-line verdict, e.g. `ALERT: BTC is $102,000.00 (above threshold $100,000.00)`.
+    pub fn to_display_string(&self) -> String {
+        let format = |value: f64| crate::output::format::format_price(value, &self.currency);
+        let price = format(self.price);
+        let side = match self.direction {
+            AlertDirection::Above | AlertDirection::AbovePct => "above",
+            AlertDirection::Below | AlertDirection::BelowPct => "below",
+        };
+        match self.threshold {
+            Some(threshold) if self.triggered => format!(
+                "ALERT: {} is {} ({} threshold {})",
+                self.symbol,
+                price,
+                side,
+                format(threshold)
+            ),
+            Some(threshold) => format!(
+                "OK: {} is {} (threshold {} not reached)",
+                self.symbol,
+                price,
+                format(threshold)
+            ),
+            None => format!(
+                "OK: {} is {} (no 24h change reported to compare against)",
+                self.symbol, price
+            ),
+        }
+    }
+}
+
+/// Compare `price` with `alert`; thresholds are inclusive.
+///
+/// Percent alerts measure from the price 24h ago, derived from `change_24h`, so
+/// `BTC:ABOVE-PCT:5` triggers once BTC is up at least 5% on the day. They never
+/// trigger when the provider reported no 24h change.
+pub fn check_alert(price: &CoinPrice, alert: &PriceAlert) -> AlertResult {
+    let reference = || {
+        price
+            .change_24h
+            .filter(|change| change.is_finite() && *change > -100.0)
+            .map(|change| price.price / (1.0 + change / 100.0))
+    };
+    let threshold = match alert.direction {
+        AlertDirection::Above | AlertDirection::Below => Some(alert.value),
+        AlertDirection::AbovePct => reference().map(|base| base * (1.0 + alert.value / 100.0)),
+        AlertDirection::BelowPct => reference().map(|base| base * (1.0 - alert.value / 100.0)),
+    };
+    let triggered = threshold.is_some_and(|threshold| match alert.direction {
+        AlertDirection::Above | AlertDirection::AbovePct => price.price >= threshold,
+        AlertDirection::Below | AlertDirection::BelowPct => price.price <= threshold,
+    });
+
+    AlertResult {
+        symbol: price.symbol.clone(),
+        price: price.price,
+        currency: price.currency.clone(),
+        provider: price.provider.clone(),
+        direction: alert.direction,
+        threshold,
+        triggered,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_price;
+
+    fn alert(direction: AlertDirection, value: f64) -> PriceAlert {
+        PriceAlert {
+            symbol: "BTC".to_string(),
+            direction,
+            value,
+        }
+    }
+
+    #[test]
+    fn parse_price_alert_accepts_each_direction_case_insensitively() {
+        assert_eq!(
+            parse_price_alert("BTC:ABOVE:100000"),
+            Some(alert(AlertDirection::Above, 100_000.0))
+        );
+        assert_eq!(
+            parse_price_alert("BTC:below:95,000.5"),
+            Some(alert(AlertDirection::Below, 95_000.5))
+        );
+        assert_eq!(
+            parse_price_alert("BTC:Above-Pct:5"),
+            Some(alert(AlertDirection::AbovePct, 5.0))
+        );
+        assert_eq!(
+            parse_price_alert("BTC:BELOW-PCT:2.5"),
+            Some(alert(AlertDirection::BelowPct, 2.5))
+        );
+
+        for invalid in [
+            "BTC",
+            "BTC:ABOVE",
+            ":ABOVE:1",
+            "BTC:OVER:1",
+            "BTC:ABOVE:x",
+            "BTC:ABOVE:-1",
+            "BTC:ABOVE:inf",
+        ] {
+            assert_eq!(parse_price_alert(invalid), None, "{invalid}");
+        }
+    }
+
+    #[test]
+    fn check_alert_above_and_below_compare_inclusively() {
+        let btc = test_price("BTC", 102_000.0);
+
+        let result = check_alert(&btc, &alert(AlertDirection::Above, 100_000.0));
+        assert!(result.triggered);
+        assert_eq!(
+            result.to_display_string(),
+            "ALERT: BTC is $102,000.00 (above threshold $100,000.00)"
+        );
+        assert!(check_alert(&btc, &alert(AlertDirection::Above, 102_000.0)).triggered);
+
+        let result = check_alert(&btc, &alert(AlertDirection::Below, 100_000.0));
+        assert!(!result.triggered);
+        assert_eq!(
+            result.to_display_string(),
+            "OK: BTC is $102,000.00 (threshold $100,000.00 not reached)"
+        );
+        assert!(check_alert(&btc, &alert(AlertDirection::Below, 102_000.0)).triggered);
+    }
+
+    #[test]
+    fn check_alert_pct_directions_measure_from_the_price_24h_ago() {
+        // Up 10% from 100 to 110.
+        let up = CoinPrice {
+            change_24h: Some(10.0),
+            ..test_price("BTC", 110.0)
+        };
+        let result = check_alert(&up, &alert(AlertDirection::AbovePct, 5.0));
+        assert!(result.triggered);
+        assert!((result.threshold.unwrap() - 105.0).abs() < 1e-9);
+        assert!(!check_alert(&up, &alert(AlertDirection::AbovePct, 12.0)).triggered);
+        assert!(!check_alert(&up, &alert(AlertDirection::BelowPct, 1.0)).triggered);
+
+        // Down 8% from 100 to 92.
+        let down = CoinPrice {
+            change_24h: Some(-8.0),
+            ..test_price("BTC", 92.0)
+        };
+        let result = check_alert(&down, &alert(AlertDirection::BelowPct, 5.0));
+        assert!(result.triggered);
+        assert!((result.threshold.unwrap() - 95.0).abs() < 1e-9);
+        assert_eq!(
+            result.to_display_string(),
+            "ALERT: BTC is $92.00 (below threshold $95.00)"
+        );
+        assert!(!check_alert(&down, &alert(AlertDirection::BelowPct, 10.0)).triggered);
+    }
+
+    #[test]
+    fn check_alert_pct_without_24h_change_does_not_trigger() {
+        let result = check_alert(
+            &test_price("BTC", 92.0),
+            &alert(AlertDirection::BelowPct, 5.0),
+        );
+        assert!(!result.triggered);
+        assert_eq!(result.threshold, None);
+        assert!(result.to_display_string().starts_with("OK: BTC is $92.00"));
+    }
+}
@@ -0,0 +1,193 @@
+//! Aligning several price histories onto one shared timestamp axis.
+
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::provider::PriceHistory;
+
+/// Price histories aligned onto shared timestamps ("wide" format).
+///
+/// `columns[i].prices[j]` is the price of series `i` at `timestamps[j]`, or `None`
+/// when that series has no point near the timestamp.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlignedHistories {
+    pub timestamps: Vec<DateTime<Utc>>,
+    pub columns: Vec<AlignedColumn>,
+}
+
+/// One series of [`AlignedHistories`].
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AlignedColumn {
+    pub symbol: String,
+    pub name: String,
+    pub currency: String,
+    pub provider: String,
+    pub prices: Vec<Option<f64>>,
+}
+
+/// Align `histories` onto a sorted set of shared timestamps.
+///
+/// The timestamps of every series are merged and grouped into slots: a slot starts at
+/// the earliest timestamp not yet covered and takes every point less than `interval`
+/// after it, so daily closes recorded at different times of day share a row. Each
+/// slot is labelled with its starting timestamp. A series with several points in one
+/// slot keeps the earliest; a series with none gets `None`.
+pub fn align_histories(histories: &[PriceHistory], interval: chrono::Duration) -> AlignedHistories {
+    let mut all: Vec<DateTime<Utc>> = histories
+        .iter()
+        .flat_map(|history| history.points.iter().map(|point| point.timestamp))
+        .collect();
+    all.sort();
+
+    let mut timestamps: Vec<DateTime<Utc>> = Vec::new();
+    for timestamp in all {
+        match timestamps.last() {
+            Some(&start) if timestamp - start < interval => {}
+            _ => timestamps.push(timestamp),
+        }
+    }
+
+    let columns = histories
+        .iter()
+        .map(|history| {
+            let mut prices = vec![None; timestamps.len()];
+            for point in &history.points {
+                // Index of the slot whose start is the latest one at or before the point.
+                let slot = timestamps.partition_point(|start| *start <= point.timestamp) - 1;
+                prices[slot].get_or_insert(point.price);
+            }
+            AlignedColumn {
+                symbol: history.symbol.clone(),
+                name: history.name.clone(),
+                currency: history.currency.clone(),
+                provider: history.provider.clone(),
+                prices,
+            }
+        })
+        .collect();
+
+    AlignedHistories {
+        timestamps,
+        columns,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::PricePoint;
+
+    fn history(symbol: &str, points: &[(&str, f64)]) -> PriceHistory {
+        PriceHistory {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            currency: "USD".to_string(),
+            provider: "Test".to_string(),
+            points: points
+                .iter()
+                .map(|(at, price)| PricePoint {
+                    timestamp: DateTime::parse_from_rfc3339(at).unwrap().to_utc(),
+                    price: *price,
+                    filled: false,
+                })
+                .collect(),
+            events: Vec::new(),
+        }
+    }
+
+    fn labels(aligned: &AlignedHistories) -> Vec<String> {
+        aligned
+            .timestamps
+            .iter()
+            .map(|at| at.format("%m-%d %H:%M").to_string())
+            .collect()
+    }
+
+    #[test]
+    fn align_histories_fills_gaps_in_shorter_series_with_none() {
+        let btc = history(
+            "BTC",
+            &[
+                ("2025-03-07T00:00:00Z", 90.0),
+                ("2025-03-08T00:00:00Z", 91.0),
+                ("2025-03-09T00:00:00Z", 92.0),
+                ("2025-03-10T00:00:00Z", 93.0),
+            ],
+        );
+        let eur = history(
+            "EUR",
+            &[
+                ("2025-03-07T00:00:00Z", 1.08),
+                ("2025-03-10T00:00:00Z", 1.09),
+            ],
+        );
+
+        let aligned = align_histories(&[btc, eur], chrono::Duration::days(1));
+
+        assert_eq!(
+            labels(&aligned),
+            ["03-07 00:00", "03-08 00:00", "03-09 00:00", "03-10 00:00"]
+        );
+        assert_eq!(
+            aligned.columns[0].prices,
+            [Some(90.0), Some(91.0), Some(92.0), Some(93.0)]
+        );
+        assert_eq!(
+            aligned.columns[1].prices,
+            [Some(1.08), None, None, Some(1.09)]
+        );
+    }
+
+    #[test]
+    fn align_histories_matches_offset_daily_points_within_the_interval() {
+        // Crypto closes at midnight UTC, the stock series at the US market open.
+        let btc = history(
+            "BTC",
+            &[
+                ("2025-03-03T00:00:00Z", 90.0),
+                ("2025-03-04T00:00:00Z", 91.0),
+                ("2025-03-05T00:00:00Z", 92.0),
+            ],
+        );
+        let aapl = history(
+            "AAPL",
+            &[
+                ("2025-03-04T14:30:00Z", 235.0),
+                ("2025-03-05T14:30:00Z", 236.0),
+                ("2025-03-06T14:30:00Z", 237.0),
+            ],
+        );
+
+        let aligned = align_histories(&[btc, aapl], chrono::Duration::days(1));
+
+        assert_eq!(
+            labels(&aligned),
+            ["03-03 00:00", "03-04 00:00", "03-05 00:00", "03-06 14:30"]
+        );
+        assert_eq!(
+            aligned.columns[0].prices,
+            [Some(90.0), Some(91.0), Some(92.0), None]
+        );
+        assert_eq!(
+            aligned.columns[1].prices,
+            [None, Some(235.0), Some(236.0), Some(237.0)]
+        );
+    }
+
+    #[test]
+    fn align_histories_handles_empty_input_and_series() {
+        let aligned = align_histories(&[], chrono::Duration::hours(1));
+        assert!(aligned.timestamps.is_empty() && aligned.columns.is_empty());
+
+        let aligned = align_histories(
+            &[
+                history("ETH", &[]),
+                history("SOL", &[("2025-03-03T10:00:00Z", 140.0)]),
+            ],
+            chrono::Duration::hours(1),
+        );
+        assert_eq!(aligned.timestamps.len(), 1);
+        assert_eq!(aligned.columns[0].prices, [None]);
+        assert_eq!(aligned.columns[1].prices, [Some(140.0)]);
+    }
+}
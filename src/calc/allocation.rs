@@ -0,0 +1,96 @@
+//! Portfolio allocation by market value.
+
+use serde::{Deserialize, Serialize};
+
+use crate::portfolio::{self, Holding};
+use crate::provider::CoinPrice;
+
+/// Share of total portfolio value held in a single position.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Allocation {
+    pub symbol: String,
+    pub name: String,
+    pub value: f64,
+    pub percent: f64,
+    pub currency: String,
+}
+
+/// Compute each holding's share of total portfolio value, largest first.
+///
+/// Holdings without a price are left out; when the total is zero every
+/// share is reported as `0.0`.
+pub fn portfolio_allocation(holdings: &[Holding], prices: &[CoinPrice]) -> Vec<Allocation> {
+    let values = portfolio::calc_portfolio_value(holdings, prices);
+    let total: f64 = values.iter().map(|v| v.current_value).sum();
+
+    let mut allocations: Vec<Allocation> = values
+        .into_iter()
+        .map(|v| Allocation {
+            percent: if total.abs() > f64::EPSILON {
+                (v.current_value / total) * 100.0
+            } else {
+                0.0
+            },
+            symbol: v.symbol,
+            name: v.name,
+            value: v.current_value,
+            currency: v.currency,
+        })
+        .collect();
+
+    allocations.sort_by(|a, b| {
+        b.value
+            .total_cmp(&a.value)
+            .then_with(|| a.symbol.cmp(&b.symbol))
+    });
+    allocations
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_price;
+
+    fn holding(symbol: &str, quantity: f64) -> Holding {
+        Holding {
+            symbol: symbol.to_string(),
+            quantity,
+            purchase_price: 1.0,
+            purchase_date: None,
+        }
+    }
+
+    #[test]
+    fn portfolio_allocation_sorts_by_share_descending() {
+        let holdings = vec![
+            holding("ETH", 1.0),
+            holding("BTC", 1.0),
+            holding("SOL", 10.0),
+        ];
+        let prices = vec![
+            test_price("BTC", 600.0),
+            test_price("ETH", 300.0),
+            test_price("SOL", 10.0),
+        ];
+
+        let allocations = portfolio_allocation(&holdings, &prices);
+
+        let symbols: Vec<&str> = allocations.iter().map(|a| a.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["BTC", "ETH", "SOL"]);
+        assert!((allocations[0].percent - 60.0).abs() < 1e-9);
+        assert!((allocations[1].percent - 30.0).abs() < 1e-9);
+        assert!((allocations[2].percent - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn portfolio_allocation_handles_zero_total() {
+        let holdings = vec![holding("DEAD", 5.0)];
+        let prices = vec![test_price("DEAD", 0.0)];
+
+        let allocations = portfolio_allocation(&holdings, &prices);
+
+        assert_eq!(allocations.len(), 1);
+        assert_eq!(allocations[0].percent, 0.0);
+        assert!(portfolio_allocation(&holdings, &[]).is_empty());
+    }
+}
@@ -0,0 +1,271 @@
+//! Fiat and crypto amounts parsed from user input such as `100usd` or `2.5btc`.
+
+use super::currency::KNOWN_FIAT;
+
+/// A parsed fiat amount from user input (e.g. `3.5EUR`).
+#[derive(Debug, Clone)]
+pub struct FiatAmount {
+    pub amount: f64,
+    pub currency: String,
+}
+
+/// A parsed crypto amount from user input (e.g. `2.5XMR`).
+#[derive(Debug, Clone)]
+pub struct CryptoAmount {
+    pub amount: f64,
+    pub symbol: String,
+}
+
+/// Leading currency symbols accepted by [`parse_fiat_amount`] and their ISO codes.
+const CURRENCY_SYMBOLS: &[(char, &str)] = &[('$', "USD"), ('€', "EUR"), ('£', "GBP"), ('¥', "JPY")];
+
+/// Try to parse a string like `3.5EUR`, `1,500usd`, `1.5kEUR`, `$100`, or (as one
+/// quoted token) `100 GBP` into a `FiatAmount`.
+///
+/// Returns `None` when the input does not match `<number>[k|m]<fiat_code>` or
+/// `<symbol><number>[k|m]`, letting the caller fall through to normal price-lookup mode.
+pub fn parse_fiat_amount(s: &str) -> Option<FiatAmount> {
+    let s = s.trim();
+
+    if let Some(&(symbol, code)) = CURRENCY_SYMBOLS.iter().find(|(c, _)| s.starts_with(*c)) {
+        return Some(FiatAmount {
+            amount: parse_scaled_amount(&s[symbol.len_utf8()..])?,
+            currency: code.to_string(),
+        });
+    }
+
+    // Find where the alphabetic suffix starts.
+    let alpha_start = s.find(|c: char| c.is_ascii_alphabetic())?;
+    if alpha_start == 0 {
+        return None;
+    }
+
+    let (num_part, code_part) = s.split_at(alpha_start);
+    let num_part = num_part.trim_end();
+    let code_upper = code_part.to_uppercase();
+
+    // A full fiat code wins over a magnitude suffix, so `1mxn` stays 1 MXN.
+    if KNOWN_FIAT.contains(&code_upper.as_str()) {
+        return Some(FiatAmount {
+            amount: parse_scaled_amount(num_part)?,
+            currency: code_upper,
+        });
+    }
+
+    let (magnitude, code) = code_upper.split_at(1);
+    let code = code.trim_start();
+    if !matches!(magnitude, "K" | "M") || !KNOWN_FIAT.contains(&code) {
+        return None;
+    }
+
+    Some(FiatAmount {
+        amount: parse_scaled_amount(&format!("{}{}", num_part, magnitude))?,
+        currency: code.to_string(),
+    })
+}
+
+/// Parse a positive amount with optional `,` thousands separators and a `k`/`m` suffix.
+///
+/// Separators must group exactly three digits, so a decimal comma like `1,5` is rejected.
+fn parse_scaled_amount(raw: &str) -> Option<f64> {
+    let (digits, scale) = match raw.strip_suffix(['k', 'K']) {
+        Some(digits) => (digits, 1e3),
+        None => match raw.strip_suffix(['m', 'M']) {
+            Some(digits) => (digits, 1e6),
+            None => (raw, 1.0),
+        },
+    };
+
+    let (int_part, frac_part) = match digits.split_once('.') {
+        Some((int_part, frac_part)) => (int_part, Some(frac_part)),
+        None => (digits, None),
+    };
+    let int_digits = if int_part.contains(',') {
+        let mut groups = int_part.split(',');
+        let lead = groups.next()?;
+        let lead_ok = (1..=3).contains(&lead.len());
+        if !lead_ok || !groups.all(|group| group.len() == 3) {
+            return None;
+        }
+        int_part.replace(',', "")
+    } else {
+        int_part.to_string()
+    };
+    if frac_part.is_some_and(|frac| frac.contains(',')) {
+        return None;
+    }
+    let normalized = match frac_part {
+        Some(frac) => format!("{}.{}", int_digits, frac),
+        None => int_digits,
+    };
+
+    let amount = normalized.parse::<f64>().ok()? * scale;
+    (amount > 0.0 && amount.is_finite()).then_some(amount)
+}
+
+/// Try to parse a string like `2.5XMR` or `0.1btc` into a `CryptoAmount`.
+///
+/// Succeeds when the alphabetic suffix is NOT a known fiat currency, treating it
+/// as a crypto symbol. Returns `None` for fiat codes, plain words, or invalid numbers.
+pub fn parse_crypto_amount(s: &str) -> Option<CryptoAmount> {
+    let alpha_start = s.find(|c: char| c.is_ascii_alphabetic())?;
+    if alpha_start == 0 {
+        return None;
+    }
+
+    let (num_part, code_part) = s.split_at(alpha_start);
+    let code_upper = code_part.to_uppercase();
+
+    // If it's a known fiat code, this isn't a crypto amount.
+    if KNOWN_FIAT.contains(&code_upper.as_str()) {
+        return None;
+    }
+
+    let amount: f64 = num_part.parse().ok()?;
+    if amount <= 0.0 || !amount.is_finite() {
+        return None;
+    }
+
+    Some(CryptoAmount {
+        amount,
+        symbol: code_upper,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_basic_cases() {
+        let fa = parse_fiat_amount("3.5EUR").unwrap();
+        assert!((fa.amount - 3.5).abs() < f64::EPSILON);
+        assert_eq!(fa.currency, "EUR");
+
+        let fa = parse_fiat_amount("100usd").unwrap();
+        assert!((fa.amount - 100.0).abs() < f64::EPSILON);
+        assert_eq!(fa.currency, "USD");
+    }
+
+    #[test]
+    fn parse_lowercase_currency() {
+        let fa = parse_fiat_amount("42gbp").unwrap();
+        assert_eq!(fa.currency, "GBP");
+    }
+
+    #[test]
+    fn rejects_crypto_symbols() {
+        assert!(parse_fiat_amount("1inch").is_none());
+        assert!(parse_fiat_amount("3btc").is_none());
+    }
+
+    #[test]
+    fn rejects_plain_words() {
+        assert!(parse_fiat_amount("btc").is_none());
+        assert!(parse_fiat_amount("hello").is_none());
+    }
+
+    #[test]
+    fn rejects_negative_and_zero() {
+        assert!(parse_fiat_amount("-5USD").is_none());
+        assert!(parse_fiat_amount("0USD").is_none());
+    }
+
+    #[test]
+    fn rejects_no_number() {
+        assert!(parse_fiat_amount("EUR").is_none());
+    }
+
+    #[test]
+    fn parse_thousands_separators() {
+        let fa = parse_fiat_amount("1,500USD").unwrap();
+        assert_eq!(fa.amount, 1500.0);
+        assert_eq!(fa.currency, "USD");
+        assert_eq!(
+            parse_fiat_amount("1,234,567.89eur").unwrap().amount,
+            1_234_567.89
+        );
+
+        // Separators must group three digits; a decimal comma is not guessed at.
+        assert!(parse_fiat_amount("1,5EUR").is_none());
+        assert!(parse_fiat_amount("1,50,000INR").is_none());
+        assert!(parse_fiat_amount(",500USD").is_none());
+        assert!(parse_fiat_amount("1.500,25EUR").is_none());
+    }
+
+    #[test]
+    fn parse_magnitude_suffixes() {
+        let fa = parse_fiat_amount("1.5kEUR").unwrap();
+        assert_eq!(fa.amount, 1500.0);
+        assert_eq!(fa.currency, "EUR");
+        assert_eq!(parse_fiat_amount("2musd").unwrap().amount, 2_000_000.0);
+        assert_eq!(parse_fiat_amount("1,500kusd").unwrap().amount, 1_500_000.0);
+        assert_eq!(parse_fiat_amount("1.5k EUR").unwrap().amount, 1500.0);
+
+        // A full fiat code beats a magnitude prefix.
+        let fa = parse_fiat_amount("1mxn").unwrap();
+        assert_eq!(fa.amount, 1.0);
+        assert_eq!(fa.currency, "MXN");
+
+        assert!(parse_fiat_amount("1kk").is_none());
+        assert!(parse_fiat_amount("1kbtc").is_none());
+        assert!(parse_fiat_amount("1minch").is_none());
+        assert!(parse_fiat_amount("1xusd").is_none());
+    }
+
+    #[test]
+    fn parse_leading_currency_symbols() {
+        for (token, amount, currency) in [
+            ("$100", 100.0, "USD"),
+            ("€2.5k", 2500.0, "EUR"),
+            ("£1,000", 1000.0, "GBP"),
+            ("¥10000", 10000.0, "JPY"),
+        ] {
+            let fa = parse_fiat_amount(token).unwrap();
+            assert_eq!(fa.amount, amount, "{token}");
+            assert_eq!(fa.currency, currency, "{token}");
+        }
+
+        assert!(parse_fiat_amount("$").is_none());
+        assert!(parse_fiat_amount("$0").is_none());
+        assert!(parse_fiat_amount("$-5").is_none());
+        assert!(parse_fiat_amount("$100usd").is_none());
+        assert!(parse_fiat_amount("$btc").is_none());
+    }
+
+    #[test]
+    fn parse_space_between_number_and_code() {
+        let fa = parse_fiat_amount("100 GBP").unwrap();
+        assert_eq!(fa.amount, 100.0);
+        assert_eq!(fa.currency, "GBP");
+        assert_eq!(parse_fiat_amount(" 1,500  usd ").unwrap().amount, 1500.0);
+
+        assert!(parse_fiat_amount("1 inch").is_none());
+        assert!(parse_fiat_amount("3 btc").is_none());
+        assert!(parse_fiat_amount("1 0 USD").is_none());
+    }
+
+    #[test]
+    fn parse_crypto_basic() {
+        let ca = parse_crypto_amount("2.5XMR").unwrap();
+        assert!((ca.amount - 2.5).abs() < f64::EPSILON);
+        assert_eq!(ca.symbol, "XMR");
+
+        let ca = parse_crypto_amount("0.1btc").unwrap();
+        assert!((ca.amount - 0.1).abs() < f64::EPSILON);
+        assert_eq!(ca.symbol, "BTC");
+    }
+
+    #[test]
+    fn parse_crypto_rejects_fiat() {
+        assert!(parse_crypto_amount("100USD").is_none());
+        assert!(parse_crypto_amount("3.5eur").is_none());
+    }
+
+    #[test]
+    fn parse_crypto_rejects_invalid() {
+        assert!(parse_crypto_amount("btc").is_none());
+        assert!(parse_crypto_amount("0BTC").is_none());
+        assert!(parse_crypto_amount("-1ETH").is_none());
+    }
+}
@@ -0,0 +1,173 @@
+//! Price comparisons across providers and between two dates.
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+/// One provider's answer for a symbol in `--all-providers` mode.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderQuote {
+    pub provider: String,
+    pub price: Option<f64>,
+    pub change_24h: Option<f64>,
+    pub currency: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+/// The same symbol quoted by several providers, with the median and max spread.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderComparison {
+    pub symbol: String,
+    pub name: String,
+    pub currency: String,
+    pub quotes: Vec<ProviderQuote>,
+    pub median: Option<f64>,
+    pub spread_pct: Option<f64>,
+}
+
+/// Summarize provider quotes for one symbol.
+///
+/// The median and spread only use priced quotes in the same currency as the
+/// first priced quote; `spread_pct` is `(max - min) / min * 100`.
+pub fn compare_provider_quotes(
+    symbol: &str,
+    name: &str,
+    quotes: Vec<ProviderQuote>,
+) -> ProviderComparison {
+    let currency = quotes
+        .iter()
+        .find(|q| q.price.is_some())
+        .and_then(|q| q.currency.clone())
+        .unwrap_or_default();
+
+    let mut prices: Vec<f64> = quotes
+        .iter()
+        .filter(|q| q.currency.as_deref().unwrap_or_default() == currency)
+        .filter_map(|q| q.price)
+        .filter(|p| p.is_finite())
+        .collect();
+    prices.sort_by(f64::total_cmp);
+
+    let median = match prices.len() {
+        0 => None,
+        n if n % 2 == 1 => Some(prices[n / 2]),
+        n => Some((prices[n / 2 - 1] + prices[n / 2]) / 2.0),
+    };
+    let spread_pct = match (prices.first(), prices.last()) {
+        (Some(&min), Some(&max)) if min > 0.0 => Some(((max - min) / min) * 100.0),
+        _ => None,
+    };
+
+    ProviderComparison {
+        symbol: symbol.to_string(),
+        name: name.to_string(),
+        currency,
+        quotes,
+        median,
+        spread_pct,
+    }
+}
+
+/// A symbol's price on two dates, shown by `--historical-compare`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TwoDateComparison {
+    pub symbol: String,
+    pub name: String,
+    pub currency: String,
+    pub provider: String,
+    pub date1: NaiveDate,
+    pub price1: f64,
+    pub date2: NaiveDate,
+    pub price2: f64,
+    pub change: f64,
+    /// Percent change from `price1`; `None` when `price1` is zero.
+    pub change_pct: Option<f64>,
+}
+
+/// Absolute and percent change from `price1` to `price2`.
+///
+/// The percent change is NaN when `price1` is zero, since no ratio exists.
+pub fn compute_two_date_change(price1: f64, price2: f64) -> (f64, f64) {
+    let change = price2 - price1;
+    let pct = if price1 == 0.0 {
+        f64::NAN
+    } else {
+        change / price1.abs() * 100.0
+    };
+    (change, pct)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn quote(provider: &str, price: Option<f64>, currency: &str) -> ProviderQuote {
+        ProviderQuote {
+            provider: provider.to_string(),
+            price,
+            change_24h: None,
+            currency: price.map(|_| currency.to_string()),
+            error: price.is_none().then(|| "unavailable".to_string()),
+        }
+    }
+
+    #[test]
+    fn compute_two_date_change_reports_gains_and_losses() {
+        let (change, pct) = compute_two_date_change(40_000.0, 100_000.0);
+        assert!((change - 60_000.0).abs() < 1e-9);
+        assert!((pct - 150.0).abs() < 1e-9);
+
+        let (change, pct) = compute_two_date_change(200.0, 150.0);
+        assert!((change + 50.0).abs() < 1e-9);
+        assert!((pct + 25.0).abs() < 1e-9);
+
+        assert_eq!(compute_two_date_change(5.0, 5.0), (0.0, 0.0));
+    }
+
+    #[test]
+    fn compute_two_date_change_has_no_percent_from_zero() {
+        let (change, pct) = compute_two_date_change(0.0, 12.5);
+        assert!((change - 12.5).abs() < 1e-9);
+        assert!(pct.is_nan());
+    }
+
+    #[test]
+    fn compare_provider_quotes_computes_median_and_spread() {
+        let quotes = vec![
+            quote("CoinGecko", Some(100.0), "USD"),
+            quote("CoinMarketCap", Some(102.0), "USD"),
+            quote("Yahoo Finance", Some(101.0), "USD"),
+            quote("Stooq", None, "USD"),
+        ];
+
+        let comparison = compare_provider_quotes("BTC", "Bitcoin", quotes);
+
+        assert_eq!(comparison.currency, "USD");
+        assert_eq!(comparison.quotes.len(), 4);
+        assert_eq!(comparison.median, Some(101.0));
+        assert!((comparison.spread_pct.unwrap() - 2.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compare_provider_quotes_ignores_other_currencies_and_averages_even_counts() {
+        let quotes = vec![
+            quote("CoinGecko", Some(100.0), "USD"),
+            quote("Yahoo Finance", Some(90.0), "EUR"),
+            quote("CoinMarketCap", Some(110.0), "USD"),
+        ];
+
+        let comparison = compare_provider_quotes("BTC", "Bitcoin", quotes);
+
+        assert_eq!(comparison.median, Some(105.0));
+        assert!((comparison.spread_pct.unwrap() - 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn compare_provider_quotes_without_prices_has_no_summary() {
+        let comparison =
+            compare_provider_quotes("BTC", "BTC", vec![quote("CoinGecko", None, "USD")]);
+
+        assert_eq!(comparison.median, None);
+        assert_eq!(comparison.spread_pct, None);
+    }
+}
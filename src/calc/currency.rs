@@ -0,0 +1,408 @@
+//! Known currencies, price conversion between them, and what each provider can quote in.
+
+use std::collections::{HashMap, HashSet};
+
+use serde::{Deserialize, Serialize};
+
+use crate::provider::CoinPrice;
+
+/// Recognized fiat currency codes. Prevents false positives on tokens like `1inch` or `3btc`.
+pub(super) const KNOWN_FIAT: &[&str] = &[
+    "USD", "EUR", "GBP", "JPY", "CNY", "CAD", "AUD", "CHF", "KRW", "INR", "BRL", "RUB", "TRY",
+    "ZAR", "MXN", "SGD", "HKD", "NOK", "SEK", "DKK", "NZD", "PLN", "THB", "TWD", "CZK", "HUF",
+    "ILS", "PHP", "MYR", "ARS", "CLP", "COP", "IDR", "SAR", "AED", "NGN", "VND", "PKR", "BDT",
+    "EGP",
+];
+
+/// Crypto assets CoinGecko and CoinMarketCap accept as a quote currency (`vs_currency` / `convert`).
+const KNOWN_CRYPTO_QUOTES: &[&str] = &[
+    "BTC", "ETH", "LTC", "BCH", "BNB", "XRP", "XLM", "DOT", "SOL",
+];
+
+/// Result of a fiat-to-crypto conversion.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Conversion {
+    pub from_amount: f64,
+    pub from_currency: String,
+    pub to_symbol: String,
+    pub to_name: String,
+    pub to_amount: f64,
+    pub rate: f64,
+    pub provider: String,
+    pub timestamp: chrono::DateTime<chrono::Utc>,
+}
+
+/// Which providers can quote prices in one currency, for `pricr currencies`.
+///
+/// `None` means the provider could not be asked.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CurrencySupport {
+    pub code: String,
+    pub name: String,
+    pub fiat_rates: Option<bool>,
+    pub crypto_quotes: Option<bool>,
+}
+
+/// Square forex table for `pricr matrix`.
+///
+/// `rates[i][j]` is 1 unit of `currencies[i]` in `currencies[j]`; `None` when the
+/// rate could not be fetched.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConversionMatrix {
+    pub currencies: Vec<String>,
+    pub rates: Vec<Vec<Option<f64>>>,
+}
+
+/// Returns `true` when `s` (case-insensitive) is a recognized fiat currency code.
+pub fn is_known_fiat(s: &str) -> bool {
+    KNOWN_FIAT.contains(&s.to_uppercase().as_str())
+}
+
+/// Returns `true` when `s` (case-insensitive) is a crypto asset usable as a quote currency.
+pub fn is_crypto_quote_asset(s: &str) -> bool {
+    KNOWN_CRYPTO_QUOTES.contains(&s.trim().to_uppercase().as_str())
+}
+
+/// Merge known fiat codes with the currencies Frankfurter and CoinGecko report.
+///
+/// `fiat_rates` maps Frankfurter codes to names; `crypto_quotes` lists CoinGecko
+/// `vs_currencies`. Known fiat codes come first in their usual order, followed by
+/// the remaining provider codes alphabetically.
+pub fn currency_support(
+    fiat_rates: Option<&HashMap<String, String>>,
+    crypto_quotes: Option<&[String]>,
+) -> Vec<CurrencySupport> {
+    let fiat_codes: HashSet<String> = fiat_rates
+        .map(|rates| rates.keys().map(|code| code.to_uppercase()).collect())
+        .unwrap_or_default();
+    let quote_codes: HashSet<String> = crypto_quotes
+        .map(|codes| codes.iter().map(|code| code.to_uppercase()).collect())
+        .unwrap_or_default();
+
+    let mut extra: Vec<&String> = fiat_codes
+        .union(&quote_codes)
+        .filter(|code| !is_known_fiat(code))
+        .collect();
+    extra.sort();
+
+    KNOWN_FIAT
+        .iter()
+        .map(|code| code.to_string())
+        .chain(extra.into_iter().cloned())
+        .map(|code| {
+            let name = if is_known_fiat(&code) {
+                fiat_name(&code).to_string()
+            } else {
+                fiat_rates
+                    .and_then(|rates| {
+                        rates
+                            .iter()
+                            .find(|(key, _)| key.eq_ignore_ascii_case(&code))
+                            .map(|(_, name)| name.clone())
+                    })
+                    .unwrap_or_else(|| code.clone())
+            };
+            CurrencySupport {
+                fiat_rates: fiat_rates.map(|_| fiat_codes.contains(&code)),
+                crypto_quotes: crypto_quotes.map(|_| quote_codes.contains(&code)),
+                name,
+                code,
+            }
+        })
+        .collect()
+}
+
+/// Assemble the `currencies` x `currencies` matrix from per-base rate maps.
+///
+/// `rates` maps each base code to its `target -> rate` map (as returned by
+/// Frankfurter); the diagonal is always `1`.
+pub fn conversion_matrix(
+    currencies: &[String],
+    rates: &HashMap<String, HashMap<String, f64>>,
+) -> ConversionMatrix {
+    let rates = currencies
+        .iter()
+        .map(|base| {
+            currencies
+                .iter()
+                .map(|target| {
+                    if base.eq_ignore_ascii_case(target) {
+                        return Some(1.0);
+                    }
+                    rates
+                        .get(base)?
+                        .iter()
+                        .find(|(code, _)| code.eq_ignore_ascii_case(target))
+                        .map(|(_, rate)| *rate)
+                        .filter(|rate| rate.is_finite())
+                })
+                .collect()
+        })
+        .collect();
+
+    ConversionMatrix {
+        currencies: currencies.to_vec(),
+        rates,
+    }
+}
+
+/// Fiat currencies other than `target` that `prices` are quoted in, sorted and deduplicated.
+///
+/// Empty when `target` is not a fiat code; crypto-quoted rows are never converted.
+pub fn foreign_quote_currencies(prices: &[CoinPrice], target: &str) -> Vec<String> {
+    if !is_known_fiat(target) {
+        return Vec::new();
+    }
+    let mut currencies: Vec<String> = prices
+        .iter()
+        .map(|p| p.currency.trim().to_uppercase())
+        .filter(|currency| is_known_fiat(currency) && !currency.eq_ignore_ascii_case(target))
+        .collect();
+    currencies.sort();
+    currencies.dedup();
+    currencies
+}
+
+/// Rewrite rows quoted in another currency into `target` using ECB `rates`.
+///
+/// `rates` maps a source currency to "1 source = rate target". Converted rows keep
+/// the provider's quote in `native_price`/`native_currency` and have " via ECB"
+/// appended to their provider; rows without a rate are left as quoted.
+pub fn convert_prices(prices: &mut [CoinPrice], target: &str, rates: &HashMap<String, f64>) {
+    let target = target.trim().to_uppercase();
+    for price in prices.iter_mut() {
+        let source = price.currency.trim().to_uppercase();
+        if source == target {
+            continue;
+        }
+        let Some(&rate) = rates
+            .get(&source)
+            .filter(|rate| rate.is_finite() && **rate > 0.0)
+        else {
+            continue;
+        };
+
+        let convert = |value: &mut Option<f64>| {
+            if let Some(value) = value.as_mut() {
+                *value *= rate;
+            }
+        };
+        price.native_price = Some(price.price);
+        price.native_currency = Some(source);
+        price.price *= rate;
+        convert(&mut price.market_cap);
+        convert(&mut price.open_24h);
+        convert(&mut price.high_24h);
+        convert(&mut price.low_24h);
+        if let Some(fundamentals) = price.fundamentals.as_mut() {
+            convert(&mut fundamentals.week_52_high);
+            convert(&mut fundamentals.week_52_low);
+        }
+        price.currency = target.clone();
+        price.provider = format!("{} via ECB", price.provider);
+    }
+}
+
+/// Human-readable name for a fiat currency code. Falls back to the code itself.
+pub fn fiat_name(code: &str) -> &str {
+    match code.to_uppercase().as_str() {
+        "USD" => "US Dollar",
+        "EUR" => "Euro",
+        "GBP" => "British Pound",
+        "JPY" => "Japanese Yen",
+        "CNY" => "Chinese Yuan",
+        "CAD" => "Canadian Dollar",
+        "AUD" => "Australian Dollar",
+        "CHF" => "Swiss Franc",
+        "KRW" => "South Korean Won",
+        "INR" => "Indian Rupee",
+        "BRL" => "Brazilian Real",
+        "RUB" => "Russian Ruble",
+        "TRY" => "Turkish Lira",
+        "ZAR" => "South African Rand",
+        "MXN" => "Mexican Peso",
+        "SGD" => "Singapore Dollar",
+        "HKD" => "Hong Kong Dollar",
+        "NOK" => "Norwegian Krone",
+        "SEK" => "Swedish Krona",
+        "DKK" => "Danish Krone",
+        "NZD" => "New Zealand Dollar",
+        "PLN" => "Polish Zloty",
+        "THB" => "Thai Baht",
+        "TWD" => "New Taiwan Dollar",
+        "CZK" => "Czech Koruna",
+        "HUF" => "Hungarian Forint",
+        "ILS" => "Israeli Shekel",
+        "PHP" => "Philippine Peso",
+        "MYR" => "Malaysian Ringgit",
+        "ARS" => "Argentine Peso",
+        "CLP" => "Chilean Peso",
+        "COP" => "Colombian Peso",
+        "IDR" => "Indonesian Rupiah",
+        "SAR" => "Saudi Riyal",
+        "AED" => "UAE Dirham",
+        "NGN" => "Nigerian Naira",
+        "VND" => "Vietnamese Dong",
+        "PKR" => "Pakistani Rupee",
+        "BDT" => "Bangladeshi Taka",
+        "EGP" => "Egyptian Pound",
+        _ => code,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_price;
+
+    fn quoted_in(symbol: &str, value: f64, currency: &str) -> CoinPrice {
+        CoinPrice {
+            currency: currency.to_string(),
+            provider: "Yahoo Finance".to_string(),
+            market_cap: Some(value * 1000.0),
+            high_24h: Some(value + 1.0),
+            low_24h: Some(value - 1.0),
+            ..test_price(symbol, value)
+        }
+    }
+
+    #[test]
+    fn is_known_fiat_works() {
+        assert!(is_known_fiat("USD"));
+        assert!(is_known_fiat("eur"));
+        assert!(is_known_fiat("Gbp"));
+        assert!(!is_known_fiat("BTC"));
+        assert!(!is_known_fiat("ETH"));
+        assert!(!is_known_fiat(""));
+    }
+
+    #[test]
+    fn is_crypto_quote_asset_works() {
+        assert!(is_crypto_quote_asset("BTC"));
+        assert!(is_crypto_quote_asset(" eth "));
+        assert!(!is_crypto_quote_asset("USD"));
+        assert!(!is_crypto_quote_asset("XMR"));
+    }
+
+    #[test]
+    fn fiat_name_known_codes() {
+        assert_eq!(fiat_name("USD"), "US Dollar");
+        assert_eq!(fiat_name("eur"), "Euro");
+        assert_eq!(fiat_name("GBP"), "British Pound");
+    }
+
+    #[test]
+    fn fiat_name_unknown_returns_code() {
+        assert_eq!(fiat_name("XYZ"), "XYZ");
+    }
+
+    #[test]
+    fn conversion_matrix_assembles_rows_per_base_currency() {
+        let currencies: Vec<String> = ["USD", "EUR", "JPY"].map(String::from).to_vec();
+        let rates: HashMap<String, HashMap<String, f64>> = HashMap::from([
+            (
+                "USD".to_string(),
+                HashMap::from([("EUR".to_string(), 0.92), ("JPY".to_string(), 150.0)]),
+            ),
+            (
+                "EUR".to_string(),
+                HashMap::from([("USD".to_string(), 1.087), ("JPY".to_string(), 163.0)]),
+            ),
+            // JPY's request failed.
+        ]);
+
+        let matrix = conversion_matrix(&currencies, &rates);
+
+        assert_eq!(matrix.currencies, currencies);
+        assert_eq!(
+            matrix.rates,
+            vec![
+                vec![Some(1.0), Some(0.92), Some(150.0)],
+                vec![Some(1.087), Some(1.0), Some(163.0)],
+                vec![None, None, Some(1.0)],
+            ]
+        );
+    }
+
+    #[test]
+    fn currency_support_merges_provider_listings() {
+        let fiat_rates = HashMap::from([
+            ("USD".to_string(), "United States Dollar".to_string()),
+            ("ISK".to_string(), "Icelandic Króna".to_string()),
+        ]);
+        let crypto_quotes = vec!["usd".to_string(), "btc".to_string()];
+
+        let support = currency_support(Some(&fiat_rates), Some(&crypto_quotes));
+
+        assert_eq!(support.len(), KNOWN_FIAT.len() + 2);
+        assert_eq!(support[0].code, "USD");
+        assert_eq!(support[0].name, "US Dollar");
+        assert_eq!(support[0].fiat_rates, Some(true));
+        assert_eq!(support[0].crypto_quotes, Some(true));
+
+        let idr = support.iter().find(|c| c.code == "IDR").unwrap();
+        assert_eq!(idr.fiat_rates, Some(false));
+        assert_eq!(idr.crypto_quotes, Some(false));
+
+        let extra: Vec<&str> = support[KNOWN_FIAT.len()..]
+            .iter()
+            .map(|c| c.code.as_str())
+            .collect();
+        assert_eq!(extra, vec!["BTC", "ISK"]);
+        assert_eq!(support[KNOWN_FIAT.len() + 1].name, "Icelandic Króna");
+    }
+
+    #[test]
+    fn currency_support_marks_unreachable_providers_unknown() {
+        let support = currency_support(None, Some(&["eur".to_string()]));
+
+        assert_eq!(support.len(), KNOWN_FIAT.len());
+        assert!(support.iter().all(|c| c.fiat_rates.is_none()));
+        let eur = support.iter().find(|c| c.code == "EUR").unwrap();
+        assert_eq!(eur.crypto_quotes, Some(true));
+    }
+
+    #[test]
+    fn foreign_quote_currencies_lists_other_fiat_codes_once() {
+        let prices = vec![
+            quoted_in("SAP.DE", 200.0, "EUR"),
+            quoted_in("AAPL", 180.0, "USD"),
+            quoted_in("BMW.DE", 90.0, "eur"),
+            quoted_in("VOD.L", 70.0, "GBP"),
+            quoted_in("ETHBTC", 0.05, "BTC"),
+        ];
+
+        assert_eq!(foreign_quote_currencies(&prices, "usd"), vec!["EUR", "GBP"]);
+        assert!(foreign_quote_currencies(&prices, "BTC").is_empty());
+    }
+
+    #[test]
+    fn convert_prices_rewrites_foreign_rows_and_keeps_native_quote() {
+        let mut prices = vec![
+            quoted_in("SAP.DE", 200.0, "EUR"),
+            quoted_in("AAPL", 180.0, "USD"),
+            quoted_in("7203.T", 3000.0, "JPY"),
+        ];
+        let rates = HashMap::from([("EUR".to_string(), 1.1)]);
+
+        convert_prices(&mut prices, "usd", &rates);
+
+        let sap = &prices[0];
+        assert!((sap.price - 220.0).abs() < 1e-9);
+        assert!((sap.market_cap.unwrap() - 220_000.0).abs() < 1e-6);
+        assert!((sap.high_24h.unwrap() - 221.1).abs() < 1e-9);
+        assert!((sap.low_24h.unwrap() - 218.9).abs() < 1e-9);
+        assert_eq!(sap.currency, "USD");
+        assert_eq!(sap.provider, "Yahoo Finance via ECB");
+        assert_eq!(sap.native_price, Some(200.0));
+        assert_eq!(sap.native_currency.as_deref(), Some("EUR"));
+
+        assert_eq!(prices[1].provider, "Yahoo Finance");
+        assert_eq!(prices[1].native_price, None);
+
+        let toyota = &prices[2];
+        assert_eq!(toyota.price, 3000.0);
+        assert_eq!(toyota.currency, "JPY");
+        assert_eq!(toyota.native_currency, None);
+    }
+}
@@ -0,0 +1,228 @@
+//! Largest-Triangle-Three-Buckets downsampling for price history series.
+
+use crate::provider::PricePoint;
+
+/// Reduce `points` to at most `threshold` points with Largest-Triangle-Three-Buckets.
+///
+/// The first and last points are always kept. The points in between are split into
+/// `threshold - 2` buckets, and from each bucket the point forming the largest
+/// triangle with the previously kept point and the average of the next bucket is
+/// kept, so peaks and dips survive where plain striding would skip them. Series
+/// that are already short enough (or a `threshold` below 3) are returned unchanged.
+pub fn lttb(points: &[PricePoint], threshold: usize) -> Vec<PricePoint> {
+    let len = points.len();
+    if threshold < 3 || threshold >= len {
+        return points.to_vec();
+    }
+
+    let xy = |point: &PricePoint| (point.timestamp.timestamp_millis() as f64, point.price);
+    let bucket_size = (len - 2) as f64 / (threshold - 2) as f64;
+    // Bucket `b` covers `bound(b)..bound(b + 1)`; every bucket holds at least one point.
+    let bound = |bucket: usize| ((bucket as f64 * bucket_size) as usize + 1).min(len);
+
+    let mut sampled = Vec::with_capacity(threshold);
+    sampled.push(points[0].clone());
+    let mut selected = 0;
+
+    for bucket in 0..threshold - 2 {
+        let next = &points[bound(bucket + 1)..bound(bucket + 2)];
+        let (sum_x, sum_y) = next
+            .iter()
+            .map(xy)
+            .fold((0.0, 0.0), |(sx, sy), (x, y)| (sx + x, sy + y));
+        let (avg_x, avg_y) = (sum_x / next.len() as f64, sum_y / next.len() as f64);
+        let (ax, ay) = xy(&points[selected]);
+
+        let start = bound(bucket);
+        let mut max_area = -1.0;
+        for (offset, point) in points[start..bound(bucket + 1)].iter().enumerate() {
+            let (x, y) = xy(point);
+            let area = ((ax - avg_x) * (y - ay) - (ax - x) * (avg_y - ay)).abs() * 0.5;
+            if area > max_area {
+                max_area = area;
+                selected = start + offset;
+            }
+        }
+        sampled.push(points[selected].clone());
+    }
+
+    sampled.push(points[len - 1].clone());
+    sampled
+}
+
+/// Reduce `points` to at most `max_points` evenly spaced points.
+///
+/// Keeps roughly every `len / max_points`-th point, always including the first and
+/// last, so the full time span survives. Series already within the limit are
+/// returned unchanged.
+pub fn downsample_uniform(points: &[PricePoint], max_points: usize) -> Vec<PricePoint> {
+    let len = points.len();
+    if len <= max_points {
+        return points.to_vec();
+    }
+    if max_points < 2 {
+        return points.iter().take(max_points).cloned().collect();
+    }
+
+    (0..max_points)
+        .map(|idx| points[idx * (len - 1) / (max_points - 1)].clone())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn series(prices: &[f64]) -> Vec<PricePoint> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(idx, &price)| PricePoint {
+                timestamp: chrono::DateTime::from_timestamp(1_700_000_000 + idx as i64 * 3600, 0)
+                    .unwrap(),
+                price,
+                filled: false,
+            })
+            .collect()
+    }
+
+    /// Direct port of the reference implementation from Sveinn Steinarsson's thesis code.
+    fn reference_lttb(data: &[(f64, f64)], threshold: usize) -> Vec<(f64, f64)> {
+        let data_length = data.len();
+        if threshold >= data_length || threshold == 0 {
+            return data.to_vec();
+        }
+
+        let mut sampled = Vec::new();
+        let every = (data_length - 2) as f64 / (threshold - 2) as f64;
+        let mut a = 0;
+        sampled.push(data[a]);
+
+        for i in 0..threshold - 2 {
+            let mut avg_x = 0.0;
+            let mut avg_y = 0.0;
+            let avg_range_start = ((i + 1) as f64 * every).floor() as usize + 1;
+            let avg_range_end = (((i + 2) as f64 * every).floor() as usize + 1).min(data_length);
+            let avg_range_length = (avg_range_end - avg_range_start) as f64;
+            for point in &data[avg_range_start..avg_range_end] {
+                avg_x += point.0;
+                avg_y += point.1;
+            }
+            avg_x /= avg_range_length;
+            avg_y /= avg_range_length;
+
+            let range_offs = (i as f64 * every).floor() as usize + 1;
+            let range_to = ((i + 1) as f64 * every).floor() as usize + 1;
+            let (point_ax, point_ay) = data[a];
+            let mut max_area = -1.0;
+            let mut max_area_point = data[range_offs];
+            let mut next_a = range_offs;
+            for (j, point) in data.iter().enumerate().take(range_to).skip(range_offs) {
+                let area = ((point_ax - avg_x) * (point.1 - point_ay)
+                    - (point_ax - point.0) * (avg_y - point_ay))
+                    .abs()
+                    * 0.5;
+                if area > max_area {
+                    max_area = area;
+                    max_area_point = *point;
+                    next_a = j;
+                }
+            }
+            sampled.push(max_area_point);
+            a = next_a;
+        }
+
+        sampled.push(data[data_length - 1]);
+        sampled
+    }
+
+    /// A gently oscillating series with a single one-point spike and dip.
+    fn spike_series() -> Vec<PricePoint> {
+        let prices: Vec<f64> = (0..1000)
+            .map(|idx| match idx {
+                537 => 500.0,
+                811 => 20.0,
+                _ => 100.0 + (idx as f64 / 25.0).sin(),
+            })
+            .collect();
+        series(&prices)
+    }
+
+    #[test]
+    fn downsample_uniform_leaves_short_series_unchanged() {
+        let points = series(&[1.0, 2.0, 3.0]);
+        assert_eq!(downsample_uniform(&points, 3).len(), 3);
+        assert_eq!(downsample_uniform(&points, 500).len(), 3);
+
+        let pair = series(&[1.0, 2.0]);
+        let sampled = downsample_uniform(&pair, 2);
+        assert_eq!(sampled.len(), 2);
+        assert_eq!(sampled[0].price, 1.0);
+        assert_eq!(sampled[1].price, 2.0);
+    }
+
+    #[test]
+    fn downsample_uniform_keeps_ends_and_even_spacing() {
+        let prices: Vec<f64> = (0..1000).map(f64::from).collect();
+        let sampled = downsample_uniform(&series(&prices), 100);
+
+        assert_eq!(sampled.len(), 100);
+        assert_eq!(sampled[0].price, 0.0);
+        assert_eq!(sampled[99].price, 999.0);
+        for pair in sampled.windows(2) {
+            let gap = pair[1].price - pair[0].price;
+            assert!((10.0..=11.0).contains(&gap), "gap {gap}");
+        }
+
+        let endpoints = downsample_uniform(&series(&prices), 2);
+        assert_eq!(endpoints.len(), 2);
+        assert_eq!(endpoints[1].price, 999.0);
+    }
+
+    #[test]
+    fn lttb_matches_reference_implementation() {
+        let points = spike_series();
+        let data: Vec<(f64, f64)> = points
+            .iter()
+            .map(|p| (p.timestamp.timestamp_millis() as f64, p.price))
+            .collect();
+
+        for threshold in [3, 10, 96, 384, 999] {
+            let sampled: Vec<(f64, f64)> = lttb(&points, threshold)
+                .iter()
+                .map(|p| (p.timestamp.timestamp_millis() as f64, p.price))
+                .collect();
+            assert_eq!(
+                sampled,
+                reference_lttb(&data, threshold),
+                "threshold {threshold}"
+            );
+        }
+    }
+
+    #[test]
+    fn lttb_keeps_spikes_that_striding_drops() {
+        let points = spike_series();
+
+        let sampled = lttb(&points, 50);
+        assert_eq!(sampled.len(), 50);
+        assert_eq!(sampled[0].timestamp, points[0].timestamp);
+        assert_eq!(sampled[49].timestamp, points[999].timestamp);
+        assert!(sampled.iter().any(|p| p.price == 500.0));
+        assert!(sampled.iter().any(|p| p.price == 20.0));
+
+        let strided: Vec<f64> = points.iter().step_by(20).map(|p| p.price).collect();
+        assert!(!strided.contains(&500.0));
+        assert!(!strided.contains(&20.0));
+    }
+
+    #[test]
+    fn lttb_leaves_short_series_unchanged() {
+        let points = series(&[1.0, 2.0, 3.0, 4.0]);
+
+        assert_eq!(lttb(&points, 4).len(), 4);
+        assert_eq!(lttb(&points, 100).len(), 4);
+        assert_eq!(lttb(&points, 2).len(), 4);
+        assert_eq!(lttb(&[], 10).len(), 0);
+    }
+}
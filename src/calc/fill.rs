@@ -0,0 +1,190 @@
+//! Gap filling for price history series that skip weekends and holidays.
+
+use crate::provider::PricePoint;
+
+/// How calendar gaps in a history series are treated before charting.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FillMode {
+    /// Leave gaps as they are.
+    None,
+    /// Carry the last known price forward across gaps.
+    Forward,
+}
+
+/// Insert synthetic points every `step` across gaps in `points`, carrying the last known price.
+///
+/// A gap is any spacing of more than one and a half steps, so a weekend in a daily
+/// series (Friday to Monday) gains Saturday and Sunday points at Friday's price. The
+/// inserted points keep the time of day of the point before the gap and are marked
+/// `filled`; the original points are kept unchanged.
+pub fn forward_fill(points: &[PricePoint], step: chrono::Duration) -> Vec<PricePoint> {
+    if step <= chrono::Duration::zero() {
+        return points.to_vec();
+    }
+
+    let mut filled = Vec::with_capacity(points.len());
+    for pair in points.windows(2) {
+        let (prev, next) = (&pair[0], &pair[1]);
+        filled.push(prev.clone());
+        if next.timestamp - prev.timestamp <= step + step / 2 {
+            continue;
+        }
+        let mut timestamp = prev.timestamp + step;
+        while next.timestamp - timestamp >= step / 2 {
+            filled.push(PricePoint {
+                timestamp,
+                price: prev.price,
+                filled: true,
+            });
+            timestamp += step;
+        }
+    }
+    if let Some(last) = points.last() {
+        filled.push(last.clone());
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(date: &str, price: f64) -> PricePoint {
+        PricePoint {
+            timestamp: chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d")
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+                .and_utc(),
+            price,
+            filled: false,
+        }
+    }
+
+    fn dates(points: &[PricePoint]) -> Vec<(String, f64, bool)> {
+        points
+            .iter()
+            .map(|p| {
+                (
+                    p.timestamp.format("%Y-%m-%d").to_string(),
+                    p.price,
+                    p.filled,
+                )
+            })
+            .collect()
+    }
+
+    #[test]
+    fn forward_fill_carries_friday_price_across_weekend() {
+        let points = [point("2025-03-07", 1.08), point("2025-03-10", 1.09)];
+        let filled = forward_fill(&points, chrono::Duration::days(1));
+
+        assert_eq!(
+            dates(&filled),
+            vec![
+                ("2025-03-07".into(), 1.08, false),
+                ("2025-03-08".into(), 1.08, true),
+                ("2025-03-09".into(), 1.08, true),
+                ("2025-03-10".into(), 1.09, false),
+            ]
+        );
+    }
+
+    #[test]
+    fn forward_fill_crosses_month_and_leap_day_boundaries() {
+        let points = [point("2025-01-30", 100.0), point("2025-02-02", 101.0)];
+        let filled = forward_fill(&points, chrono::Duration::days(1));
+        let days: Vec<_> = dates(&filled).into_iter().map(|(day, ..)| day).collect();
+        assert_eq!(
+            days,
+            ["2025-01-30", "2025-01-31", "2025-02-01", "2025-02-02"]
+        );
+
+        let points = [point("2024-02-27", 100.0), point("2024-03-02", 99.0)];
+        let filled = forward_fill(&points, chrono::Duration::days(1));
+        let days: Vec<_> = dates(&filled).into_iter().map(|(day, ..)| day).collect();
+        assert_eq!(
+            days,
+            [
+                "2024-02-27",
+                "2024-02-28",
+                "2024-02-29",
+                "2024-03-01",
+                "2024-03-02"
+            ]
+        );
+    }
+
+    #[test]
+    fn forward_fill_spans_multi_day_holidays() {
+        // Christmas and Boxing Day closures after Christmas Eve, then New Year's Day.
+        let points = [
+            point("2024-12-24", 1.04),
+            point("2024-12-27", 1.05),
+            point("2024-12-31", 1.035),
+            point("2025-01-02", 1.03),
+        ];
+        let filled = forward_fill(&points, chrono::Duration::days(1));
+
+        assert_eq!(filled.len(), 10);
+        let synthetic: Vec<_> = dates(&filled)
+            .into_iter()
+            .filter(|(_, _, filled)| *filled)
+            .map(|(day, price, _)| (day, price))
+            .collect();
+        assert_eq!(
+            synthetic,
+            vec![
+                ("2024-12-25".into(), 1.04),
+                ("2024-12-26".into(), 1.04),
+                ("2024-12-28".into(), 1.05),
+                ("2024-12-29".into(), 1.05),
+                ("2024-12-30".into(), 1.05),
+                ("2025-01-01".into(), 1.035),
+            ]
+        );
+    }
+
+    #[test]
+    fn forward_fill_leaves_contiguous_and_short_series_alone() {
+        let points = [
+            point("2025-03-03", 1.0),
+            point("2025-03-04", 2.0),
+            point("2025-03-05", 3.0),
+        ];
+        let filled = forward_fill(&points, chrono::Duration::days(1));
+        assert_eq!(filled.len(), 3);
+        assert!(filled.iter().all(|p| !p.filled));
+
+        assert!(forward_fill(&[], chrono::Duration::days(1)).is_empty());
+        assert_eq!(
+            forward_fill(&points[..1], chrono::Duration::days(1)).len(),
+            1
+        );
+    }
+
+    #[test]
+    fn forward_fill_keeps_time_of_day_with_hourly_steps() {
+        let start = point("2025-03-07", 5.0).timestamp + chrono::Duration::minutes(30);
+        let points = [
+            PricePoint {
+                timestamp: start,
+                price: 5.0,
+                filled: false,
+            },
+            PricePoint {
+                timestamp: start + chrono::Duration::hours(3),
+                price: 6.0,
+                filled: false,
+            },
+        ];
+        let filled = forward_fill(&points, chrono::Duration::hours(1));
+
+        let times: Vec<_> = filled
+            .iter()
+            .map(|p| p.timestamp.format("%H:%M").to_string())
+            .collect();
+        assert_eq!(times, ["00:30", "01:30", "02:30", "03:30"]);
+        assert!(filled[1].filled && filled[2].filled);
+    }
+}
@@ -0,0 +1,77 @@
+//! Biggest gainers and losers for `pricr movers`.
+
+use serde::{Deserialize, Serialize};
+
+use super::{PriceSort, sort_prices};
+use crate::provider::CoinPrice;
+
+/// Largest 24h gainers and losers among a set of listings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Movers {
+    pub gainers: Vec<CoinPrice>,
+    pub losers: Vec<CoinPrice>,
+}
+
+/// Pick the `count` biggest 24h gainers and losers.
+///
+/// Listings without a 24h change are ignored; with `min_market_cap`, so are
+/// listings below it or without a market cap.
+pub fn split_movers(prices: Vec<CoinPrice>, count: usize, min_market_cap: Option<f64>) -> Movers {
+    let mut eligible: Vec<CoinPrice> = prices
+        .into_iter()
+        .filter(|p| p.change_24h.is_some_and(f64::is_finite))
+        .filter(|p| min_market_cap.is_none_or(|min| p.market_cap.is_some_and(|cap| cap >= min)))
+        .collect();
+    sort_prices(&mut eligible, PriceSort::Change);
+
+    let gainers = eligible
+        .iter()
+        .filter(|p| p.change_24h.is_some_and(|c| c > 0.0))
+        .take(count)
+        .cloned()
+        .collect();
+    let losers = eligible
+        .iter()
+        .rev()
+        .filter(|p| p.change_24h.is_some_and(|c| c < 0.0))
+        .take(count)
+        .cloned()
+        .collect();
+
+    Movers { gainers, losers }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_price;
+
+    #[test]
+    fn split_movers_picks_biggest_gainers_and_losers_above_min_market_cap() {
+        let listing = |symbol: &str, change: Option<f64>, cap: Option<f64>| CoinPrice {
+            change_24h: change,
+            market_cap: cap,
+            ..test_price(symbol, 1.0)
+        };
+        let prices = vec![
+            listing("BTC", Some(2.0), Some(1.0e12)),
+            listing("ETH", Some(-3.0), Some(4.0e11)),
+            listing("SOL", Some(9.0), Some(8.0e10)),
+            listing("DOGE", Some(-7.5), Some(2.0e10)),
+            listing("MICRO", Some(80.0), Some(1.0e6)),
+            listing("FLAT", Some(0.0), Some(5.0e10)),
+            listing("NEW", None, Some(5.0e10)),
+        ];
+        let symbols = |prices: &[CoinPrice]| -> Vec<String> {
+            prices.iter().map(|p| p.symbol.clone()).collect()
+        };
+
+        let movers = split_movers(prices.clone(), 2, Some(1.0e9));
+        assert_eq!(symbols(&movers.gainers), vec!["SOL", "BTC"]);
+        assert_eq!(symbols(&movers.losers), vec!["DOGE", "ETH"]);
+
+        let movers = split_movers(prices, 1, None);
+        assert_eq!(symbols(&movers.gainers), vec!["MICRO"]);
+        assert_eq!(symbols(&movers.losers), vec!["DOGE"]);
+    }
+}
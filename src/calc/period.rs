@@ -0,0 +1,278 @@
+//! Returns over calendar periods for `--performance` and `--period`.
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::provider::{PriceHistory, PricePoint};
+
+/// Step `months` calendar months back from `end`.
+///
+/// Days past the end of the target month clamp to its last day; dates chrono
+/// cannot represent fall back to an average month length.
+pub fn months_before(end: NaiveDate, months: u32) -> NaiveDate {
+    end.checked_sub_months(chrono::Months::new(months))
+        .unwrap_or(end - chrono::Duration::days(i64::from(months) * 365 / 12))
+}
+
+/// Trailing return periods shown by `--performance`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PerformancePeriod {
+    OneMonth,
+    ThreeMonths,
+    SixMonths,
+    Ytd,
+    OneYear,
+}
+
+impl PerformancePeriod {
+    pub const ALL: [Self; 5] = [
+        Self::OneMonth,
+        Self::ThreeMonths,
+        Self::SixMonths,
+        Self::Ytd,
+        Self::OneYear,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::OneMonth => "1M",
+            Self::ThreeMonths => "3M",
+            Self::SixMonths => "6M",
+            Self::Ytd => "YTD",
+            Self::OneYear => "1Y",
+        }
+    }
+
+    /// First day of the period ending on `end`.
+    pub fn start_date(self, end: NaiveDate) -> NaiveDate {
+        match self {
+            Self::OneMonth => months_before(end, 1),
+            Self::ThreeMonths => months_before(end, 3),
+            Self::SixMonths => months_before(end, 6),
+            Self::Ytd => NaiveDate::from_ymd_opt(end.year(), 1, 1).unwrap_or(end),
+            Self::OneYear => months_before(end, 12),
+        }
+    }
+}
+
+/// Trailing percent returns for one symbol, measured up to its latest close.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Performance {
+    pub symbol: String,
+    pub name: String,
+    pub currency: String,
+    pub provider: String,
+    pub price: f64,
+    pub as_of: NaiveDate,
+    #[serde(rename = "1M")]
+    pub one_month: Option<f64>,
+    #[serde(rename = "3M")]
+    pub three_months: Option<f64>,
+    #[serde(rename = "6M")]
+    pub six_months: Option<f64>,
+    #[serde(rename = "YTD")]
+    pub ytd: Option<f64>,
+    #[serde(rename = "1Y")]
+    pub one_year: Option<f64>,
+}
+
+impl Performance {
+    pub fn get(&self, period: PerformancePeriod) -> Option<f64> {
+        match period {
+            PerformancePeriod::OneMonth => self.one_month,
+            PerformancePeriod::ThreeMonths => self.three_months,
+            PerformancePeriod::SixMonths => self.six_months,
+            PerformancePeriod::Ytd => self.ytd,
+            PerformancePeriod::OneYear => self.one_year,
+        }
+    }
+}
+
+/// Percent change from the last close on or before `start` to the latest close.
+///
+/// Returns `None` when the series does not reach back to `start` or the base
+/// price is unusable.
+pub fn period_return(points: &[PricePoint], start: NaiveDate) -> Option<f64> {
+    let latest = points.iter().max_by_key(|p| p.timestamp)?;
+    let base = points
+        .iter()
+        .filter(|p| p.timestamp.date_naive() <= start)
+        .max_by_key(|p| p.timestamp)?;
+
+    if !(base.price.is_finite() && base.price > 0.0 && latest.price.is_finite()) {
+        return None;
+    }
+    Some((latest.price / base.price - 1.0) * 100.0)
+}
+
+/// Lookback periods accepted by `--period`, with their length in days.
+const CHANGE_PERIODS: &[(&str, u32)] =
+    &[("24h", 1), ("7d", 7), ("30d", 30), ("90d", 90), ("1y", 365)];
+
+/// Length in days of a `--period` value such as `7d` or `1y` (case-insensitive).
+pub fn period_to_days(period: &str) -> Option<u32> {
+    let period = period.trim();
+    CHANGE_PERIODS
+        .iter()
+        .find(|(name, _)| name.eq_ignore_ascii_case(period))
+        .map(|(_, days)| *days)
+}
+
+/// Percent change from the last close on or before `start` to the current `price`.
+///
+/// Returns `None` when the series does not reach back to `start` or the base
+/// price is unusable.
+pub fn change_since(points: &[PricePoint], start: NaiveDate, price: f64) -> Option<f64> {
+    let base = points
+        .iter()
+        .filter(|p| p.timestamp.date_naive() <= start)
+        .max_by_key(|p| p.timestamp)?;
+
+    if !(base.price.is_finite() && base.price > 0.0 && price.is_finite()) {
+        return None;
+    }
+    Some((price / base.price - 1.0) * 100.0)
+}
+
+/// Compute every standard period return for a daily history, anchored on its
+/// latest point. Returns `None` for an empty history.
+pub fn performance(history: &PriceHistory) -> Option<Performance> {
+    let latest = history.points.iter().max_by_key(|p| p.timestamp)?;
+    let as_of = latest.timestamp.date_naive();
+    let period = |p: PerformancePeriod| period_return(&history.points, p.start_date(as_of));
+
+    Some(Performance {
+        symbol: history.symbol.clone(),
+        name: history.name.clone(),
+        currency: history.currency.clone(),
+        provider: history.provider.clone(),
+        price: latest.price,
+        as_of,
+        one_month: period(PerformancePeriod::OneMonth),
+        three_months: period(PerformancePeriod::ThreeMonths),
+        six_months: period(PerformancePeriod::SixMonths),
+        ytd: period(PerformancePeriod::Ytd),
+        one_year: period(PerformancePeriod::OneYear),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daily_history(first: NaiveDate, last: NaiveDate) -> PriceHistory {
+        let points = first
+            .iter_days()
+            .take_while(|day| *day <= last)
+            .map(|day| PricePoint {
+                timestamp: day.and_hms_opt(0, 0, 0).unwrap().and_utc(),
+                price: 100.0 + (day - first).num_days() as f64,
+                filled: false,
+            })
+            .collect();
+        PriceHistory {
+            symbol: "AAPL".to_string(),
+            name: "Apple Inc.".to_string(),
+            currency: "USD".to_string(),
+            provider: "Test".to_string(),
+            points,
+            events: Vec::new(),
+        }
+    }
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn performance_period_start_dates_clamp_to_month_end() {
+        let end = ymd(2026, 3, 31);
+
+        assert_eq!(
+            PerformancePeriod::OneMonth.start_date(end),
+            ymd(2026, 2, 28)
+        );
+        assert_eq!(
+            PerformancePeriod::ThreeMonths.start_date(end),
+            ymd(2025, 12, 31)
+        );
+        assert_eq!(
+            PerformancePeriod::SixMonths.start_date(end),
+            ymd(2025, 9, 30)
+        );
+        assert_eq!(PerformancePeriod::Ytd.start_date(end), ymd(2026, 1, 1));
+        assert_eq!(PerformancePeriod::OneYear.start_date(end), ymd(2025, 3, 31));
+    }
+
+    #[test]
+    fn performance_computes_returns_from_the_close_at_each_period_start() {
+        let first = ymd(2025, 6, 1);
+        let history = daily_history(first, ymd(2026, 3, 31));
+        let price_on = |day: NaiveDate| 100.0 + (day - first).num_days() as f64;
+        let expected =
+            |start: NaiveDate| (price_on(ymd(2026, 3, 31)) / price_on(start) - 1.0) * 100.0;
+
+        let perf = performance(&history).unwrap();
+
+        assert_eq!(perf.as_of, ymd(2026, 3, 31));
+        assert_eq!(perf.price, price_on(ymd(2026, 3, 31)));
+        assert!((perf.one_month.unwrap() - expected(ymd(2026, 2, 28))).abs() < 1e-9);
+        assert!((perf.three_months.unwrap() - expected(ymd(2025, 12, 31))).abs() < 1e-9);
+        assert!((perf.six_months.unwrap() - expected(ymd(2025, 9, 30))).abs() < 1e-9);
+        assert!((perf.ytd.unwrap() - expected(ymd(2026, 1, 1))).abs() < 1e-9);
+        assert_eq!(perf.one_year, None);
+        assert_eq!(perf.get(PerformancePeriod::Ytd), perf.ytd);
+    }
+
+    #[test]
+    fn period_return_uses_the_last_close_before_a_gap() {
+        let history = daily_history(ymd(2025, 12, 29), ymd(2026, 1, 9));
+        // Drop the holiday closes so YTD falls back to the last close of the prior year.
+        let points: Vec<PricePoint> = history
+            .points
+            .into_iter()
+            .filter(|p| !(ymd(2025, 12, 31)..=ymd(2026, 1, 2)).contains(&p.timestamp.date_naive()))
+            .collect();
+
+        let ytd = period_return(&points, ymd(2026, 1, 1)).unwrap();
+
+        assert!((ytd - (111.0 / 101.0 - 1.0) * 100.0).abs() < 1e-9);
+        assert_eq!(period_return(&points, ymd(2025, 12, 1)), None);
+        assert_eq!(period_return(&[], ymd(2026, 1, 1)), None);
+    }
+
+    #[test]
+    fn period_to_days_accepts_the_supported_periods() {
+        assert_eq!(period_to_days("24h"), Some(1));
+        assert_eq!(period_to_days("7d"), Some(7));
+        assert_eq!(period_to_days("30D"), Some(30));
+        assert_eq!(period_to_days(" 90d "), Some(90));
+        assert_eq!(period_to_days("1y"), Some(365));
+        assert_eq!(period_to_days("2w"), None);
+        assert_eq!(period_to_days(""), None);
+    }
+
+    #[test]
+    fn change_since_compares_the_base_close_with_the_current_price() {
+        let history = daily_history(ymd(2026, 1, 1), ymd(2026, 1, 9));
+        let base = history
+            .points
+            .iter()
+            .find(|p| p.timestamp.date_naive() == ymd(2026, 1, 2))
+            .unwrap()
+            .price;
+
+        let change = change_since(&history.points, ymd(2026, 1, 2), base * 1.5).unwrap();
+
+        assert!((change - 50.0).abs() < 1e-9);
+        assert_eq!(change_since(&history.points, ymd(2025, 12, 31), 1.0), None);
+    }
+
+    #[test]
+    fn performance_is_none_for_an_empty_history() {
+        let mut history = daily_history(ymd(2026, 1, 1), ymd(2026, 1, 1));
+        history.points.clear();
+
+        assert!(performance(&history).is_none());
+    }
+}
@@ -0,0 +1,157 @@
+//! Client-side resampling of daily price histories to coarser periods.
+
+use chrono::{Datelike, NaiveDate};
+
+use crate::provider::PricePoint;
+
+/// Calendar period a daily series is resampled to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResamplePeriod {
+    /// ISO weeks, Monday through Sunday.
+    Weekly,
+    /// Calendar months.
+    Monthly,
+}
+
+impl ResamplePeriod {
+    /// Render the period as its CLI-facing lowercase string.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Weekly => "weekly",
+            Self::Monthly => "monthly",
+        }
+    }
+
+    /// Last calendar day of the period containing `date`.
+    pub fn period_end(self, date: NaiveDate) -> NaiveDate {
+        match self {
+            Self::Weekly => {
+                let days_left = 6 - date.weekday().num_days_from_monday();
+                date + chrono::Duration::days(days_left as i64)
+            }
+            Self::Monthly => {
+                let (year, month) = if date.month() == 12 {
+                    (date.year() + 1, 1)
+                } else {
+                    (date.year(), date.month() + 1)
+                };
+                NaiveDate::from_ymd_opt(year, month, 1)
+                    .and_then(|first| first.pred_opt())
+                    .unwrap_or(date)
+            }
+        }
+    }
+}
+
+/// Keep the last point of each period in `points`, timestamped at the period end.
+///
+/// Points are bucketed by their UTC date. Every period is labelled with its last day
+/// at midnight UTC, including a partial first period. The final period keeps its
+/// own timestamp when the series stops before the period ends, since that period
+/// is not over yet.
+pub fn resample(points: &[PricePoint], period: ResamplePeriod) -> Vec<PricePoint> {
+    let mut periods: Vec<(NaiveDate, PricePoint)> = Vec::new();
+    for point in points {
+        let end = period.period_end(point.timestamp.date_naive());
+        match periods.last_mut() {
+            Some((last_end, last)) if *last_end == end => *last = point.clone(),
+            _ => periods.push((end, point.clone())),
+        }
+    }
+
+    let count = periods.len();
+    periods
+        .into_iter()
+        .enumerate()
+        .map(|(idx, (end, mut point))| {
+            if idx + 1 < count || point.timestamp.date_naive() == end {
+                point.timestamp = end.and_time(chrono::NaiveTime::MIN).and_utc();
+            }
+            point
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn daily(from: &str, to: &str) -> Vec<PricePoint> {
+        let from = NaiveDate::parse_from_str(from, "%Y-%m-%d").unwrap();
+        let to = NaiveDate::parse_from_str(to, "%Y-%m-%d").unwrap();
+        from.iter_days()
+            .take_while(|day| *day <= to)
+            .enumerate()
+            .map(|(idx, day)| PricePoint {
+                timestamp: day.and_hms_opt(14, 30, 0).unwrap().and_utc(),
+                price: idx as f64,
+                filled: false,
+            })
+            .collect()
+    }
+
+    fn summary(points: &[PricePoint]) -> Vec<(String, f64)> {
+        points
+            .iter()
+            .map(|p| (p.timestamp.format("%Y-%m-%d %H:%M").to_string(), p.price))
+            .collect()
+    }
+
+    #[test]
+    fn weekly_resample_labels_iso_week_ends_and_keeps_partial_last_week() {
+        // Wednesday 2025-01-01 through Wednesday 2025-01-15.
+        let sampled = resample(&daily("2025-01-01", "2025-01-15"), ResamplePeriod::Weekly);
+
+        assert_eq!(
+            summary(&sampled),
+            vec![
+                ("2025-01-05 00:00".to_string(), 4.0),
+                ("2025-01-12 00:00".to_string(), 11.0),
+                ("2025-01-15 14:30".to_string(), 14.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn weekly_resample_uses_last_trading_day_of_each_week() {
+        let weekdays: Vec<PricePoint> = daily("2024-12-30", "2025-01-12")
+            .into_iter()
+            .filter(|p| p.timestamp.weekday().num_days_from_monday() < 5)
+            .collect();
+        let sampled = resample(&weekdays, ResamplePeriod::Weekly);
+
+        // Friday prices, labelled with the Sunday that ends each ISO week (the week
+        // of 2024-12-30 spans the year boundary); the last week may still be open.
+        assert_eq!(
+            summary(&sampled),
+            vec![
+                ("2025-01-05 00:00".to_string(), 4.0),
+                ("2025-01-10 14:30".to_string(), 11.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn monthly_resample_handles_partial_first_and_last_months() {
+        let sampled = resample(&daily("2024-01-20", "2024-04-10"), ResamplePeriod::Monthly);
+
+        assert_eq!(
+            summary(&sampled),
+            vec![
+                ("2024-01-31 00:00".to_string(), 11.0),
+                ("2024-02-29 00:00".to_string(), 40.0),
+                ("2024-03-31 00:00".to_string(), 71.0),
+                ("2024-04-10 14:30".to_string(), 81.0),
+            ]
+        );
+    }
+
+    #[test]
+    fn resample_labels_a_complete_final_period_with_its_end() {
+        let sampled = resample(&daily("2024-11-15", "2024-12-31"), ResamplePeriod::Monthly);
+        let labels: Vec<_> = summary(&sampled).into_iter().map(|(at, _)| at).collect();
+        assert_eq!(labels, ["2024-11-30 00:00", "2024-12-31 00:00"]);
+
+        assert!(resample(&[], ResamplePeriod::Weekly).is_empty());
+    }
+}
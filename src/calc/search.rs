@@ -0,0 +1,172 @@
+//! Ticker search filters by asset type and exchange.
+
+use serde::{Deserialize, Serialize};
+
+use crate::provider::TickerMatch;
+
+/// Asset class vocabulary for filtering ticker search results with `--type`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetType {
+    Stock,
+    Etf,
+    Crypto,
+    Index,
+    Future,
+    Currency,
+}
+
+impl AssetType {
+    /// Map a provider's asset type label (e.g. Yahoo's `Equity`) onto the filter vocabulary.
+    pub fn from_label(label: &str) -> Option<Self> {
+        match label.trim().to_lowercase().as_str() {
+            "stock" | "equity" | "common stock" => Some(Self::Stock),
+            "etf" | "exchange traded fund" => Some(Self::Etf),
+            "crypto" | "cryptocurrency" => Some(Self::Crypto),
+            "index" => Some(Self::Index),
+            "future" | "futures" => Some(Self::Future),
+            "currency" | "forex" => Some(Self::Currency),
+            _ => None,
+        }
+    }
+}
+
+/// `--type` / `--exchange` filters applied to merged ticker search results.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct SearchFilter {
+    #[serde(rename = "type", skip_serializing_if = "Option::is_none")]
+    pub asset_type: Option<AssetType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub exchange: Option<String>,
+}
+
+impl SearchFilter {
+    /// Returns `true` when no filter is set.
+    pub fn is_empty(&self) -> bool {
+        self.asset_type.is_none() && self.exchange.is_none()
+    }
+
+    /// Returns `true` when `candidate` passes every set filter (case-insensitive).
+    pub fn matches(&self, candidate: &TickerMatch) -> bool {
+        let type_ok = self
+            .asset_type
+            .is_none_or(|wanted| AssetType::from_label(&candidate.asset_type) == Some(wanted));
+        let exchange_ok = self.exchange.as_deref().is_none_or(|wanted| {
+            candidate
+                .exchange
+                .trim()
+                .eq_ignore_ascii_case(wanted.trim())
+        });
+        type_ok && exchange_ok
+    }
+}
+
+/// Search results that are listings of `symbol` on `exchange`, one per ticker.
+///
+/// A candidate's ticker must be `symbol`, optionally followed by a `.` suffix, and
+/// either its exchange name must equal `exchange` or its ticker must end in `suffix`.
+pub fn exchange_listings(
+    matches: &[TickerMatch],
+    symbol: &str,
+    exchange: &str,
+    suffix: Option<&str>,
+) -> Vec<TickerMatch> {
+    let symbol = symbol.trim();
+    let mut listings: Vec<TickerMatch> = Vec::new();
+    for candidate in matches {
+        let ticker = candidate.symbol.trim();
+        let base = ticker.split_once('.').map_or(ticker, |(base, _)| base);
+        if !base.eq_ignore_ascii_case(symbol) {
+            continue;
+        }
+        let on_exchange = candidate
+            .exchange
+            .trim()
+            .eq_ignore_ascii_case(exchange.trim())
+            || suffix.is_some_and(|suffix| {
+                ticker
+                    .to_ascii_uppercase()
+                    .ends_with(&suffix.to_ascii_uppercase())
+            });
+        if on_exchange
+            && !listings
+                .iter()
+                .any(|listing| listing.symbol.eq_ignore_ascii_case(ticker))
+        {
+            listings.push(candidate.clone());
+        }
+    }
+    listings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ticker(asset_type: &str, exchange: &str) -> TickerMatch {
+        TickerMatch {
+            symbol: "AAPL".to_string(),
+            name: "Apple Inc.".to_string(),
+            exchange: exchange.to_string(),
+            asset_type: asset_type.to_string(),
+            provider: "Yahoo".to_string(),
+            rank: None,
+        }
+    }
+
+    fn symbols_of_matches(matches: &[TickerMatch]) -> Vec<&str> {
+        matches.iter().map(|m| m.symbol.as_str()).collect()
+    }
+
+    #[test]
+    fn exchange_listings_match_by_exchange_name_or_suffix() {
+        let listing = |symbol: &str, exchange: &str| TickerMatch {
+            symbol: symbol.to_string(),
+            exchange: exchange.to_string(),
+            ..ticker("Equity", exchange)
+        };
+        let matches = vec![
+            listing("SAP", "NYSE"),
+            listing("SAP.DE", "XETRA"),
+            listing("SAP.F", "Frankfurt"),
+            listing("SAPX.DE", "XETRA"),
+            listing("SAP.DE", "XETRA"),
+        ];
+
+        let xetra = exchange_listings(&matches, "sap", "xetra", Some(".DE"));
+        assert_eq!(symbols_of_matches(&xetra), vec!["SAP.DE"]);
+
+        let by_suffix = exchange_listings(&matches, "SAP", "FRA", Some(".F"));
+        assert_eq!(symbols_of_matches(&by_suffix), vec!["SAP.F"]);
+
+        let nyse = exchange_listings(&matches, "SAP", "NYSE", None);
+        assert_eq!(symbols_of_matches(&nyse), vec!["SAP"]);
+
+        assert!(exchange_listings(&matches, "SAP", "Tokyo", Some(".T")).is_empty());
+    }
+
+    #[test]
+    fn asset_type_normalizes_provider_labels() {
+        assert_eq!(AssetType::from_label("Equity"), Some(AssetType::Stock));
+        assert_eq!(AssetType::from_label("ETF"), Some(AssetType::Etf));
+        assert_eq!(
+            AssetType::from_label("Cryptocurrency"),
+            Some(AssetType::Crypto)
+        );
+        assert_eq!(AssetType::from_label("Futures"), Some(AssetType::Future));
+        assert_eq!(AssetType::from_label("Warrant"), None);
+    }
+
+    #[test]
+    fn search_filter_matches_type_and_exchange_case_insensitively() {
+        let filter = SearchFilter {
+            asset_type: Some(AssetType::Stock),
+            exchange: Some("nasdaq".to_string()),
+        };
+
+        assert!(filter.matches(&ticker("Equity", "NASDAQ")));
+        assert!(!filter.matches(&ticker("ETF", "NASDAQ")));
+        assert!(!filter.matches(&ticker("Equity", "NYSE")));
+        assert!(SearchFilter::default().matches(&ticker("Warrant", "Unknown")));
+    }
+}
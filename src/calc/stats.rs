@@ -0,0 +1,162 @@
+//! Return and volatility statistics for `--stats`.
+
+use serde::Serialize;
+
+use crate::provider::{PriceHistory, PricePoint};
+
+/// Descriptive statistics of a price series (`--stats`).
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ReturnStats {
+    pub points: usize,
+    pub mean: f64,
+    /// Sample standard deviation of the prices.
+    pub std_dev: f64,
+    pub min: f64,
+    pub max: f64,
+    /// Annualized standard deviation of period-over-period returns, in percent.
+    ///
+    /// `None` when the series has fewer than 3 points, since one return has no
+    /// spread.
+    pub volatility: Option<f64>,
+}
+
+/// [`ReturnStats`] of one symbol's history.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct HistoryStats {
+    pub symbol: String,
+    pub name: String,
+    pub currency: String,
+    pub provider: String,
+    #[serde(flatten)]
+    pub stats: ReturnStats,
+}
+
+/// Compute mean, standard deviation, range, and annualized return volatility.
+///
+/// Returns are scaled to a year by the number of returns per year the series
+/// actually has, so daily stock series (about 252 closes a year), daily crypto
+/// series (365), and hourly series are all annualized correctly. Returns `None`
+/// for an empty series.
+pub fn return_stats(points: &[PricePoint]) -> Option<ReturnStats> {
+    let prices: Vec<f64> = points
+        .iter()
+        .map(|p| p.price)
+        .filter(|price| price.is_finite())
+        .collect();
+    if prices.is_empty() {
+        return None;
+    }
+
+    let (mean, std_dev) = mean_and_sample_std_dev(&prices);
+    let min = prices.iter().copied().fold(f64::INFINITY, f64::min);
+    let max = prices.iter().copied().fold(f64::NEG_INFINITY, f64::max);
+
+    let mut sorted: Vec<&PricePoint> = points.iter().filter(|p| p.price.is_finite()).collect();
+    sorted.sort_by_key(|p| p.timestamp);
+    let returns: Vec<f64> = sorted
+        .windows(2)
+        .filter(|pair| pair[0].price > 0.0)
+        .map(|pair| pair[1].price / pair[0].price - 1.0)
+        .collect();
+    let span_years = match (sorted.first(), sorted.last()) {
+        (Some(first), Some(last)) => {
+            (last.timestamp - first.timestamp).num_seconds() as f64 / (365.25 * 86_400.0)
+        }
+        _ => 0.0,
+    };
+    let volatility = (returns.len() >= 2 && span_years > 0.0).then(|| {
+        let periods_per_year = returns.len() as f64 / span_years;
+        mean_and_sample_std_dev(&returns).1 * periods_per_year.sqrt() * 100.0
+    });
+
+    Some(ReturnStats {
+        points: prices.len(),
+        mean,
+        std_dev,
+        min,
+        max,
+        volatility,
+    })
+}
+
+/// [`return_stats`] for a history, labelled with its symbol.
+pub fn history_stats(history: &PriceHistory) -> Option<HistoryStats> {
+    Some(HistoryStats {
+        symbol: history.symbol.clone(),
+        name: history.name.clone(),
+        currency: history.currency.clone(),
+        provider: history.provider.clone(),
+        stats: return_stats(&history.points)?,
+    })
+}
+
+/// Mean and sample (n - 1) standard deviation; the deviation of one value is `0`.
+fn mean_and_sample_std_dev(values: &[f64]) -> (f64, f64) {
+    let n = values.len() as f64;
+    let mean = values.iter().sum::<f64>() / n;
+    if values.len() < 2 {
+        return (mean, 0.0);
+    }
+    let variance = values.iter().map(|v| (v - mean).powi(2)).sum::<f64>() / (n - 1.0);
+    (mean, variance.sqrt())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::NaiveDate;
+
+    fn ymd(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    fn series(prices: &[f64]) -> Vec<PricePoint> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(day, price)| PricePoint {
+                timestamp: (ymd(2026, 1, 1) + chrono::Duration::days(day as i64))
+                    .and_hms_opt(0, 0, 0)
+                    .unwrap()
+                    .and_utc(),
+                price: *price,
+                filled: false,
+            })
+            .collect()
+    }
+
+    #[test]
+    fn return_stats_of_a_known_daily_series() {
+        let stats = return_stats(&series(&[100.0, 110.0, 99.0, 108.9])).unwrap();
+
+        assert_eq!(stats.points, 4);
+        assert!((stats.mean - 104.475).abs() < 1e-9);
+        // Deviations: -4.475, 5.525, -5.475, 4.425; squares sum to 100.1075 over n - 1 = 3.
+        assert!((stats.std_dev - (100.1075_f64 / 3.0).sqrt()).abs() < 1e-9);
+        assert_eq!(stats.min, 99.0);
+        assert_eq!(stats.max, 110.0);
+
+        // Daily returns are +10%, -10%, +10%...
+        let returns = [0.1, -0.1, 0.1];
+        let mean = returns.iter().sum::<f64>() / 3.0;
+        let sample_sd = (returns.iter().map(|r| (r - mean).powi(2)).sum::<f64>() / 2.0).sqrt();
+        // ...and 3 returns over 3 days is 365.25 returns a year.
+        let expected = sample_sd * 365.25_f64.sqrt() * 100.0;
+        assert!((stats.volatility.unwrap() - expected).abs() < 1e-9);
+    }
+
+    #[test]
+    fn return_stats_needs_three_points_for_volatility() {
+        let single = return_stats(&series(&[42.0])).unwrap();
+        assert_eq!(single.points, 1);
+        assert_eq!(single.mean, 42.0);
+        assert_eq!(single.std_dev, 0.0);
+        assert_eq!(single.volatility, None);
+
+        let pair = return_stats(&series(&[100.0, 110.0])).unwrap();
+        assert_eq!(pair.points, 2);
+        assert_eq!(pair.volatility, None);
+
+        assert_eq!(return_stats(&[]), None);
+    }
+}
@@ -0,0 +1,474 @@
+//! Command-line arguments.
+
+mod parse;
+mod range;
+mod values;
+
+use std::path::PathBuf;
+
+use chrono::NaiveDate;
+use clap::{ArgGroup, Parser};
+use pricr::calc;
+
+pub use parse::CompareDate;
+use parse::{
+    parse_category, parse_change_arrow_threshold, parse_chart_end_date, parse_compare_date,
+    parse_price_alert,
+};
+pub use range::{ChartRange, ChartRangeArg, format_chart_range_label, parse_chart_range};
+pub use values::{
+    AssetTypeArg, ColorArg, FillArg, FormatArg, HistoryFormatArg, PeriodArg, SamplingArg,
+    SortByArg, SymbolCaseArg,
+};
+
+#[derive(Parser)]
+#[command(
+    name = "pricr",
+    version = crate::APP_VERSION,
+    about = "Fetch crypto and stock prices from your terminal",
+    after_help = crate::EXIT_CODES_HELP,
+    disable_version_flag = true,
+    group(ArgGroup::new("history_window").args(["chart", "stats"]).multiple(true))
+)]
+pub struct Cli {
+    /// Asset symbols to look up (e.g. btc eth aapl msft) or watchlists via @name
+    pub symbols: Vec<String>,
+
+    /// Print version (with --json: name, version, and provider ids)
+    #[arg(short = 'V', long)]
+    pub version: bool,
+
+    /// Output as JSON
+    #[arg(long)]
+    pub json: bool,
+
+    /// Print price lookups as CSV with a header row
+    #[arg(long, conflicts_with = "json")]
+    pub csv: bool,
+
+    /// Field separator for --csv output
+    #[arg(long, value_name = "CHAR", default_value_t = ',', requires = "csv")]
+    pub csv_delimiter: char,
+
+    /// Quote character for --csv fields containing the separator or quotes
+    #[arg(long, value_name = "CHAR", default_value_t = '"', requires = "csv")]
+    pub csv_quote_char: char,
+
+    /// Table layout: bordered `table` or borderless `plain` (default: plain when stdout is not a terminal)
+    #[arg(long, value_enum, value_name = "FORMAT")]
+    pub format: Option<FormatArg>,
+
+    /// Colorize output: `auto` (only on a terminal), `always`, or `never`
+    #[arg(long, value_enum, value_name = "WHEN", default_value = "auto")]
+    pub color: ColorArg,
+
+    /// Letter case of symbols in table and JSON output
+    #[arg(long, value_enum, value_name = "CASE", default_value = "upper")]
+    pub symbol_case: SymbolCaseArg,
+
+    /// Truncate names in tables to N terminal cells, ending them with `…` (default no limit)
+    #[arg(
+        long,
+        visible_alias = "name-width",
+        value_name = "N",
+        value_parser = clap::value_parser!(u16).range(1..)
+    )]
+    pub max_name_width: Option<u16>,
+
+    /// Wrap JSON price output in an object with `prices` and per-symbol `errors`
+    #[arg(long, requires = "json")]
+    pub json_envelope: bool,
+
+    /// Print only the JSON nodes matching a JSONPath expression (e.g. '$[*].price')
+    #[arg(long, value_name = "EXPR", requires = "json")]
+    pub json_path: Option<String>,
+
+    /// Print JSON on a single line instead of pretty-printing it
+    #[arg(long, requires = "json")]
+    pub json_compact: bool,
+
+    /// Skip "did you mean" ticker search suggestions for unresolved symbols
+    #[arg(long)]
+    pub no_suggest: bool,
+
+    /// Show 24h high/low columns in the price table when providers supply them
+    #[arg(long)]
+    pub show_high_low: bool,
+
+    /// Order price results (default: input order, or rank for `top`)
+    #[arg(
+        long,
+        value_enum,
+        conflicts_with_all = ["chart", "search", "portfolio_file", "all_providers"]
+    )]
+    pub sort_by: Option<SortByArg>,
+
+    /// Period the change column covers; longer periods fetch daily history per symbol
+    #[arg(
+        long,
+        value_enum,
+        default_value = "24h",
+        conflicts_with_all = ["chart", "search", "portfolio_file", "all_providers"]
+    )]
+    pub period: PeriodArg,
+
+    /// Show whether each quote's market is open, closed, pre/post-market, or 24/7
+    #[arg(long)]
+    pub market_status: bool,
+
+    /// Fill in Yahoo Finance market caps (one extra request per symbol)
+    #[arg(long)]
+    pub enrich: bool,
+
+    /// Add P/E, dividend yield, and 52-week range columns for Yahoo Finance stocks (one extra request per symbol)
+    #[arg(long)]
+    pub fundamentals: bool,
+
+    /// Convert quotes in another currency (e.g. EUR for `.DE` tickers) into the requested currency using ECB rates
+    #[arg(long, visible_alias = "quote-currency-auto")]
+    pub convert: bool,
+
+    /// Prefix 24h changes with a direction arrow (↑, ↓, or → when flat)
+    #[arg(long)]
+    pub show_change_arrow: bool,
+
+    /// Changes smaller than this percent count as flat for --show-change-arrow
+    #[arg(
+        long,
+        value_name = "PCT",
+        default_value_t = 0.01,
+        value_parser = parse_change_arrow_threshold,
+        requires = "show_change_arrow"
+    )]
+    pub change_arrow_threshold: f64,
+
+    /// Show conversion rates in both directions (e.g. 1 EUR = $1.08 and 1 USD = €0.9259)
+    #[arg(long)]
+    pub both_rates: bool,
+
+    /// Exit with code 5 when any requested symbol has no data
+    #[arg(long)]
+    pub strict: bool,
+
+    /// With --provider, keep the prices it returned and warn about symbols it failed on
+    #[arg(long, requires = "provider")]
+    pub allow_partial: bool,
+
+    /// Plot historical price charts
+    #[arg(long)]
+    pub chart: bool,
+
+    /// Print mean, std dev, min, max, and annualized volatility of the history window instead of a chart
+    #[arg(
+        long,
+        conflicts_with_all = ["search", "portfolio_file", "all_providers", "watch", "warm_cache", "ohlc", "performance", "overlay"]
+    )]
+    pub stats: bool,
+
+    /// Also save the fetched chart history as JSON to this file
+    #[arg(long, value_name = "PATH", requires = "history_window")]
+    pub history_export: Option<PathBuf>,
+
+    /// Render charts from a file written by --history-export instead of fetching
+    #[arg(
+        long,
+        value_name = "PATH",
+        requires = "chart",
+        conflicts_with = "history_export"
+    )]
+    pub history_import: Option<PathBuf>,
+
+    /// Draw charts with plain ASCII characters (for limited terminals and CI logs)
+    #[arg(long, requires = "chart")]
+    pub ascii: bool,
+
+    /// Draw all chart series on one chart with a shared price axis (up to 3 symbols)
+    #[arg(long, requires = "chart", conflicts_with = "ascii")]
+    pub overlay: bool,
+
+    /// Mark each chart's lowest and highest points and label them with their prices
+    #[arg(long, requires = "chart", conflicts_with_all = ["ascii", "overlay"])]
+    pub annotate: bool,
+
+    /// Downsample each chart series to at most N points [default: 384 for charts, unlimited for JSON]
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "chart",
+        value_parser = clap::value_parser!(u32).range(3..)
+    )]
+    pub points: Option<u32>,
+
+    /// Keep at most N evenly spaced points per --json history series; ignored with --points, which downsamples instead
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "chart",
+        value_parser = clap::value_parser!(u32).range(2..)
+    )]
+    pub max_history_points: Option<u32>,
+
+    /// Carry the last price across weekend and holiday gaps before charting [default: forward for daily series]
+    #[arg(long, value_enum, requires = "chart")]
+    pub fill: Option<FillArg>,
+
+    /// Fetch fiat chart rates for every calendar day, carrying Friday's rate over weekends
+    #[arg(long, alias = "include-weekends", requires = "chart")]
+    pub fill_weekends: bool,
+
+    /// Show dividends and splits on stock charts (Yahoo Finance)
+    #[arg(long, requires = "chart")]
+    pub events: bool,
+
+    /// Chart the split- and dividend-adjusted close instead of the raw close (Yahoo Finance)
+    #[arg(long, requires = "history_window")]
+    pub adjusted: bool,
+
+    /// Chart JSON layout: one series per symbol (long) or aligned columns (wide)
+    #[arg(
+        long,
+        value_enum,
+        default_value_t = HistoryFormatArg::Long,
+        requires = "chart",
+        requires = "json"
+    )]
+    pub history_format: HistoryFormatArg,
+
+    /// strftime pattern for chart dates (default %Y-%m-%d)
+    #[arg(long, value_name = "FORMAT")]
+    pub date_format: Option<String>,
+
+    /// strftime pattern for the time of day in intraday charts (default %H:%M)
+    #[arg(long, value_name = "FORMAT")]
+    pub time_format: Option<String>,
+
+    /// Time zone for displayed timestamps: local, utc, or an IANA name like Europe/Paris (JSON stays UTC)
+    #[arg(long, value_name = "ZONE", default_value = "local")]
+    pub tz: String,
+
+    /// Chart interval preset (1D, 5D, 1M, 3M, 6M, YTD, 1Y, 2Y, 5Y, 10Y, ALL/MAX) or a duration (e.g. 45d, 6w, 18m, 2y) [default: 1M]
+    #[arg(long, value_name = "RANGE", value_parser = parse_chart_range)]
+    pub interval: Option<ChartRange>,
+
+    /// Sampling density for chart mode
+    #[arg(long, value_enum, default_value_t = SamplingArg::Auto)]
+    pub sampling: SamplingArg,
+
+    /// End date for chart mode in UTC (YYYY-MM-DD)
+    #[arg(long, value_parser = parse_chart_end_date, requires = "history_window")]
+    pub end_date: Option<NaiveDate>,
+
+    /// Start date for chart mode in UTC (YYYY-MM-DD). Overrides --interval preset.
+    #[arg(long, value_parser = parse_chart_end_date, requires = "history_window")]
+    pub start_date: Option<NaiveDate>,
+
+    /// Price provider to use
+    #[arg(long, short)]
+    pub provider: Option<String>,
+
+    /// Currency for prices: a fiat code, or a crypto quote asset like btc (coingecko, cmc)
+    #[arg(long, short, visible_alias = "quote-asset")]
+    pub currency: Option<String>,
+
+    /// API key for providers that require one
+    #[arg(long, env = "COINMARKETCAP_API_KEY")]
+    pub api_key: Option<String>,
+
+    /// Use only providers that work without an API key (skips CoinMarketCap)
+    #[arg(long)]
+    pub free_only: bool,
+
+    /// Explicit config file path (overrides XDG lookup)
+    #[arg(long)]
+    pub config: Option<PathBuf>,
+
+    /// List available providers
+    #[arg(long)]
+    pub list_providers: bool,
+
+    /// Show 1M, 3M, 6M, YTD, and 1Y returns for each symbol
+    #[arg(
+        long,
+        conflicts_with_all = ["chart", "search", "portfolio_file", "all_providers", "watch", "warm_cache", "ohlc", "resolve"]
+    )]
+    pub performance: bool,
+
+    /// Compare each symbol's price on two dates (YYYY-MM-DD or `today`)
+    #[arg(
+        long,
+        num_args = 2,
+        value_names = ["DATE1", "DATE2"],
+        value_parser = parse_compare_date,
+        conflicts_with_all = ["chart", "search", "portfolio_file", "all_providers", "watch", "warm_cache", "ohlc", "resolve", "performance"]
+    )]
+    pub historical_compare: Option<Vec<CompareDate>>,
+
+    /// List the top coins by market cap in a CoinGecko category (e.g. layer-1, meme-token)
+    #[arg(
+        long,
+        value_name = "ID",
+        value_parser = parse_category,
+        conflicts_with_all = ["symbols", "chart", "search", "portfolio_file", "all_providers", "watch", "warm_cache", "ohlc", "resolve", "performance", "historical_compare"]
+    )]
+    pub category: Option<String>,
+
+    /// Show CoinGecko metadata (description, links, categories) for each symbol
+    #[arg(
+        long,
+        conflicts_with_all = ["chart", "search", "portfolio_file", "all_providers", "watch", "warm_cache", "ohlc", "resolve", "performance"]
+    )]
+    pub details: bool,
+
+    /// Value holdings from a TOML portfolio file
+    #[arg(
+        long,
+        value_name = "PATH",
+        conflicts_with = "chart",
+        conflicts_with = "search",
+        conflicts_with = "symbols"
+    )]
+    pub portfolio_file: Option<PathBuf>,
+
+    /// Read additional symbols (or @watchlists) from a whitespace/newline-delimited file ('-' for stdin)
+    #[arg(
+        long,
+        visible_alias = "symbols-file",
+        value_name = "PATH",
+        conflicts_with = "search",
+        conflicts_with = "portfolio_file"
+    )]
+    pub symbol_file: Option<PathBuf>,
+
+    /// Uppercase symbols and strip spaces and hyphens before lookup (`b t c` -> BTC, `btc-eth` -> BTCETH)
+    #[arg(long)]
+    pub normalize_symbols: bool,
+
+    /// Show how each provider would resolve the given symbols, without fetching prices
+    #[arg(
+        long,
+        visible_alias = "explain-symbol",
+        conflicts_with_all = ["chart", "search", "portfolio_file", "warm_cache", "watch", "all_providers"]
+    )]
+    pub resolve: bool,
+
+    /// Query every provider for each symbol and compare their prices
+    #[arg(
+        long,
+        conflicts_with_all = ["provider", "chart", "search", "portfolio_file", "warm_cache", "watch"]
+    )]
+    pub all_providers: bool,
+
+    /// Check one price threshold (e.g. BTC:ABOVE:100000) and exit 0 if it triggered, 1 if not
+    #[arg(
+        long,
+        value_name = "SYMBOL:DIRECTION:VALUE",
+        value_parser = parse_price_alert,
+        conflicts_with_all = ["symbols", "symbol_file", "chart", "search", "portfolio_file", "all_providers", "watch", "warm_cache", "performance", "details", "ohlc", "resolve", "quiet"]
+    )]
+    pub price_alert: Option<calc::PriceAlert>,
+
+    /// Show the session open, high, low, and close for each symbol
+    #[arg(
+        long,
+        conflicts_with_all = ["chart", "search", "portfolio_file", "all_providers", "watch", "warm_cache"]
+    )]
+    pub ohlc: bool,
+
+    /// Show each holding's share of total portfolio value instead of the holdings table
+    #[arg(long, requires = "portfolio_file")]
+    pub allocation: bool,
+
+    /// Prefetch prices for the given symbols (or all watchlists) into the cache
+    #[arg(
+        long,
+        conflicts_with = "chart",
+        conflicts_with = "search",
+        conflicts_with = "portfolio_file"
+    )]
+    pub warm_cache: bool,
+
+    /// Delete the oldest cache files beyond [cache].max_entries and exit
+    #[arg(long)]
+    pub prune_cache: bool,
+
+    /// Re-fetch and redraw prices every N seconds (press q to quit)
+    #[arg(
+        long,
+        value_name = "INTERVAL_SECS",
+        value_parser = clap::value_parser!(u64).range(1..),
+        conflicts_with_all = ["chart", "search", "portfolio_file", "warm_cache", "json"]
+    )]
+    pub watch: Option<u64>,
+
+    /// Stop after N refreshes in watch mode (default unlimited)
+    #[arg(
+        long,
+        value_name = "N",
+        requires = "watch",
+        value_parser = clap::value_parser!(u64).range(1..)
+    )]
+    pub watch_count: Option<u64>,
+
+    /// Search ticker symbols by keyword (provider-dependent)
+    #[arg(
+        long,
+        short = 's',
+        conflicts_with = "chart",
+        conflicts_with = "symbols"
+    )]
+    pub search: Option<String>,
+
+    /// Only keep search results of this asset type
+    #[arg(long = "type", value_name = "TYPE")]
+    pub asset_type: Option<AssetTypeArg>,
+
+    /// Only keep search results listed on this exchange (e.g. NASDAQ); for price lookups, quote each symbol's listing there (e.g. XETRA)
+    #[arg(long, value_name = "EXCHANGE")]
+    pub exchange: Option<String>,
+
+    /// Max ticker search results
+    #[arg(
+        long,
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u8).range(1..=50)
+    )]
+    pub search_limit: u8,
+
+    /// Results to request from each provider in search mode before merging (default: --search-limit)
+    #[arg(long, value_parser = clap::value_parser!(u8).range(1..=50))]
+    pub search_limit_per_provider: Option<u8>,
+
+    /// Number of gainers and losers shown by `pricr movers`, or coins listed by `--category`
+    #[arg(
+        long,
+        value_name = "N",
+        default_value_t = 10,
+        value_parser = clap::value_parser!(u8).range(1..=50)
+    )]
+    pub top: u8,
+
+    /// Ignore coins below this market cap in `pricr movers`
+    #[arg(long, value_name = "AMOUNT")]
+    pub min_market_cap: Option<f64>,
+
+    /// Drop stablecoins (USDT, USDC, DAI, ...) from price results
+    #[arg(long, conflicts_with = "include_only_stablecoins")]
+    pub exclude_stablecoins: bool,
+
+    /// Keep only stablecoins in price results
+    #[arg(long)]
+    pub include_only_stablecoins: bool,
+
+    /// Print only the price of each symbol (or each converted amount), one per line, for scripts
+    #[arg(
+        short,
+        long,
+        visible_alias = "raw",
+        conflicts_with_all = ["json", "json_envelope", "chart", "search", "portfolio_file", "all_providers", "watch", "performance", "ohlc", "resolve", "verbose"]
+    )]
+    pub quiet: bool,
+
+    /// Increase log verbosity (-v, -vv, -vvv)
+    #[arg(short, long, action = clap::ArgAction::Count)]
+    pub verbose: u8,
+}
@@ -0,0 +1,117 @@
+//! Value parsers for flags that take dates, thresholds, alerts, or ids.
+
+use chrono::NaiveDate;
+use pricr::calc;
+use pricr::clock::Clock;
+use pricr::error::{self, Result};
+
+pub fn parse_change_arrow_threshold(raw: &str) -> std::result::Result<f64, String> {
+    raw.trim()
+        .parse::<f64>()
+        .ok()
+        .filter(|pct| pct.is_finite() && *pct >= 0.0)
+        .ok_or_else(|| {
+            format!(
+                "invalid threshold '{}', expected a non-negative percent",
+                raw
+            )
+        })
+}
+
+/// A `--historical-compare` date; `today` is resolved against the run clock.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CompareDate {
+    Today,
+    On(NaiveDate),
+}
+
+impl CompareDate {
+    /// The calendar date (UTC); a date after `clock`'s today is a config error.
+    pub fn resolve(self, clock: &dyn Clock) -> Result<NaiveDate> {
+        let today = clock.today();
+        match self {
+            Self::Today => Ok(today),
+            Self::On(date) if date > today => Err(error::Error::Config(format!(
+                "--historical-compare date {} is in the future",
+                date
+            ))),
+            Self::On(date) => Ok(date),
+        }
+    }
+}
+
+/// Parse a `--historical-compare` date: `today` or `YYYY-MM-DD`.
+pub fn parse_compare_date(raw: &str) -> std::result::Result<CompareDate, String> {
+    let trimmed = raw.trim();
+    if trimmed.eq_ignore_ascii_case("today") {
+        return Ok(CompareDate::Today);
+    }
+    NaiveDate::parse_from_str(trimmed, "%Y-%m-%d")
+        .map(CompareDate::On)
+        .map_err(|_| format!("invalid date '{}', expected YYYY-MM-DD or today", raw))
+}
+
+pub fn parse_price_alert(raw: &str) -> std::result::Result<calc::PriceAlert, String> {
+    calc::parse_price_alert(raw).ok_or_else(|| {
+        format!(
+            "invalid price alert '{}', expected SYMBOL:DIRECTION:VALUE with DIRECTION one of above, below, above-pct, below-pct (e.g. BTC:ABOVE:100000)",
+            raw
+        )
+    })
+}
+
+pub fn parse_chart_end_date(raw: &str) -> std::result::Result<NaiveDate, String> {
+    chrono::NaiveDate::parse_from_str(raw, "%Y-%m-%d")
+        .map_err(|_| "invalid end date, expected format YYYY-MM-DD".to_string())
+}
+
+/// Parse a CoinGecko category id such as `layer-1` or `meme-token`.
+pub fn parse_category(raw: &str) -> std::result::Result<String, String> {
+    let category = raw.trim().to_lowercase();
+    if category.is_empty()
+        || !category
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-')
+    {
+        return Err(format!(
+            "invalid category '{}', expected a CoinGecko category id such as layer-1",
+            raw
+        ));
+    }
+    Ok(category)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricr::clock::FixedClock;
+
+    #[test]
+    fn compare_dates_resolve_against_the_run_clock() {
+        let clock = FixedClock::at(2024, 6, 1, 23, 59, 59);
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+
+        assert_eq!(parse_compare_date(" Today ").unwrap(), CompareDate::Today);
+        assert_eq!(CompareDate::Today.resolve(&clock).unwrap(), today);
+        assert_eq!(
+            parse_compare_date("2024-06-01")
+                .unwrap()
+                .resolve(&clock)
+                .unwrap(),
+            today
+        );
+        assert!(matches!(
+            parse_compare_date("2024-06-02").unwrap().resolve(&clock),
+            Err(error::Error::Config(_))
+        ));
+        assert!(parse_compare_date("June 1").is_err());
+    }
+
+    #[test]
+    fn parse_category_accepts_coingecko_ids_only() {
+        assert_eq!(parse_category(" Layer-1 ").unwrap(), "layer-1");
+        assert_eq!(parse_category("meme-token").unwrap(), "meme-token");
+        assert!(parse_category("").is_err());
+        assert!(parse_category("meme&per_page=250").is_err());
+    }
+}
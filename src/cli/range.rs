@@ -0,0 +1,241 @@
+//! `--interval` chart windows: presets and custom durations.
+
+use chrono::{Datelike, NaiveDate};
+use clap::ValueEnum;
+use pricr::calc;
+
+/// Longest custom `--interval` duration, in days (about 100 years).
+const MAX_CHART_RANGE_DAYS: u32 = 36_500;
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ChartRangeArg {
+    #[value(name = "1D")]
+    OneDay,
+    #[value(name = "5D")]
+    FiveDays,
+    #[value(name = "1M")]
+    OneMonth,
+    #[value(name = "3M")]
+    ThreeMonths,
+    #[value(name = "6M")]
+    SixMonths,
+    #[value(name = "YTD")]
+    Ytd,
+    #[value(name = "1Y")]
+    OneYear,
+    #[value(name = "2Y")]
+    TwoYears,
+    #[value(name = "5Y")]
+    FiveYears,
+    #[value(name = "10Y")]
+    TenYears,
+    #[value(name = "ALL")]
+    All,
+    /// Same window as `ALL`, labelled `MAX`.
+    #[value(name = "MAX")]
+    Max,
+}
+
+impl ChartRangeArg {
+    fn label(self) -> &'static str {
+        match self {
+            Self::OneDay => "1D",
+            Self::FiveDays => "5D",
+            Self::OneMonth => "1M",
+            Self::ThreeMonths => "3M",
+            Self::SixMonths => "6M",
+            Self::Ytd => "YTD",
+            Self::OneYear => "1Y",
+            Self::TwoYears => "2Y",
+            Self::FiveYears => "5Y",
+            Self::TenYears => "10Y",
+            Self::All => "ALL",
+            Self::Max => "MAX",
+        }
+    }
+
+    fn start_date(self, end_date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::OneDay => Some(end_date - chrono::Duration::days(1)),
+            Self::FiveDays => Some(end_date - chrono::Duration::days(5)),
+            Self::OneMonth => Some(calc::months_before(end_date, 1)),
+            Self::ThreeMonths => Some(calc::months_before(end_date, 3)),
+            Self::SixMonths => Some(calc::months_before(end_date, 6)),
+            Self::Ytd => chrono::NaiveDate::from_ymd_opt(end_date.year(), 1, 1),
+            Self::OneYear => Some(calc::months_before(end_date, 12)),
+            Self::TwoYears => Some(calc::months_before(end_date, 24)),
+            Self::FiveYears => Some(calc::months_before(end_date, 60)),
+            Self::TenYears => Some(calc::months_before(end_date, 120)),
+            Self::All | Self::Max => None,
+        }
+    }
+}
+
+/// Chart window: a named preset or a custom `<N>[d|w|m|y]` duration before the end date.
+#[derive(Debug, Clone, Copy)]
+pub enum ChartRange {
+    Preset(ChartRangeArg),
+    Days(u32),
+    Weeks(u32),
+    Months(u32),
+    Years(u32),
+}
+
+impl ChartRange {
+    fn label(self) -> String {
+        match self {
+            Self::Preset(preset) => preset.label().to_string(),
+            Self::Days(days) => format!("{}d", days),
+            Self::Weeks(weeks) => format!("{}w", weeks),
+            Self::Months(months) => format!("{}m", months),
+            Self::Years(years) => format!("{}y", years),
+        }
+    }
+
+    pub fn start_date(self, end_date: NaiveDate) -> Option<NaiveDate> {
+        match self {
+            Self::Preset(preset) => preset.start_date(end_date),
+            Self::Days(days) => Some(end_date - chrono::Duration::days(days.into())),
+            Self::Weeks(weeks) => Some(end_date - chrono::Duration::weeks(weeks.into())),
+            Self::Months(months) => Some(calc::months_before(end_date, months)),
+            Self::Years(years) => Some(calc::months_before(end_date, years.saturating_mul(12))),
+        }
+    }
+}
+
+/// Parse `--interval` as a preset first, then as a duration such as `45d`, `6w`, `18m`, or `3y`.
+pub fn parse_chart_range(raw: &str) -> std::result::Result<ChartRange, String> {
+    if let Ok(preset) = ChartRangeArg::from_str(raw, false) {
+        return Ok(ChartRange::Preset(preset));
+    }
+
+    let invalid = || {
+        format!(
+            "invalid interval '{}', expected a preset (1D, 5D, 1M, 3M, 6M, YTD, 1Y, 2Y, 5Y, 10Y, ALL, MAX) or a duration like 45d, 6w, 18m, or 3y (up to {} days)",
+            raw, MAX_CHART_RANGE_DAYS
+        )
+    };
+    let raw_trimmed = raw.trim();
+    let unit = raw_trimmed.chars().last().ok_or_else(invalid)?;
+    let count = raw_trimmed[..raw_trimmed.len() - unit.len_utf8()]
+        .parse::<u32>()
+        .ok()
+        .filter(|count| *count > 0)
+        .ok_or_else(invalid)?;
+    let (range, unit_days) = match unit.to_ascii_lowercase() {
+        'd' => (ChartRange::Days(count), 1),
+        'w' => (ChartRange::Weeks(count), 7),
+        'm' => (ChartRange::Months(count), 30),
+        'y' => (ChartRange::Years(count), 365),
+        _ => return Err(invalid()),
+    };
+    if count.saturating_mul(unit_days) > MAX_CHART_RANGE_DAYS {
+        return Err(invalid());
+    }
+    Ok(range)
+}
+
+pub fn format_chart_range_label(
+    start_date: Option<NaiveDate>,
+    end_date: NaiveDate,
+    fallback_interval: ChartRange,
+) -> String {
+    match start_date {
+        Some(start) => format!(
+            "{}..{}",
+            start.format("%Y-%m-%d"),
+            end_date.format("%Y-%m-%d")
+        ),
+        None => fallback_interval.label(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_chart_range_prefers_presets_then_day_counts() {
+        let end = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+
+        let preset = parse_chart_range("5D").unwrap();
+        assert!(matches!(
+            preset,
+            ChartRange::Preset(ChartRangeArg::FiveDays)
+        ));
+        assert_eq!(preset.start_date(end), NaiveDate::from_ymd_opt(2024, 3, 26));
+
+        let custom = parse_chart_range("14d").unwrap();
+        assert!(matches!(custom, ChartRange::Days(14)));
+        assert_eq!(custom.start_date(end), NaiveDate::from_ymd_opt(2024, 3, 17));
+        assert!(matches!(
+            parse_chart_range("3D").unwrap(),
+            ChartRange::Days(3)
+        ));
+        assert!(matches!(
+            parse_chart_range("90d").unwrap(),
+            ChartRange::Days(90)
+        ));
+
+        assert!(parse_chart_range("0d").is_err());
+        assert!(parse_chart_range("14").is_err());
+        assert!(parse_chart_range("2x").is_err());
+        assert!(parse_chart_range("d").is_err());
+        assert!(parse_chart_range("101y").is_err());
+    }
+
+    #[test]
+    fn parse_chart_range_accepts_new_presets_and_durations() {
+        let end = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let start = |raw: &str| parse_chart_range(raw).unwrap().start_date(end);
+
+        assert_eq!(start("3M"), NaiveDate::from_ymd_opt(2023, 12, 31));
+        assert_eq!(start("2Y"), NaiveDate::from_ymd_opt(2022, 3, 31));
+        assert_eq!(start("10Y"), NaiveDate::from_ymd_opt(2014, 3, 31));
+        assert_eq!(start("MAX"), None);
+        assert_eq!(start("45d"), NaiveDate::from_ymd_opt(2024, 2, 15));
+        assert_eq!(start("2w"), NaiveDate::from_ymd_opt(2024, 3, 17));
+        assert_eq!(start("18m"), NaiveDate::from_ymd_opt(2022, 9, 30));
+        assert_eq!(start("3y"), NaiveDate::from_ymd_opt(2021, 3, 31));
+
+        for raw in ["MAX", "ALL", "45d", "6w", "18m", "2y"] {
+            assert_eq!(parse_chart_range(raw).unwrap().label(), raw);
+        }
+        assert_eq!(
+            format_chart_range_label(None, end, parse_chart_range("MAX").unwrap()),
+            "MAX"
+        );
+    }
+
+    #[test]
+    fn month_durations_clamp_to_the_end_of_shorter_months() {
+        let start = |raw: &str, end: NaiveDate| parse_chart_range(raw).unwrap().start_date(end);
+        let jan_31 = NaiveDate::from_ymd_opt(2024, 1, 31).unwrap();
+        let mar_31 = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let leap_day = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+
+        assert_eq!(start("1m", jan_31), NaiveDate::from_ymd_opt(2023, 12, 31));
+        assert_eq!(start("1M", mar_31), NaiveDate::from_ymd_opt(2024, 2, 29));
+        assert_eq!(start("13m", mar_31), NaiveDate::from_ymd_opt(2023, 2, 28));
+        assert_eq!(start("1y", leap_day), NaiveDate::from_ymd_opt(2023, 2, 28));
+        assert_eq!(
+            start("3M", NaiveDate::from_ymd_opt(2024, 5, 31).unwrap()),
+            NaiveDate::from_ymd_opt(2024, 2, 29)
+        );
+    }
+
+    #[test]
+    fn format_chart_range_label_uses_dates_for_custom_windows() {
+        let end = NaiveDate::from_ymd_opt(2024, 3, 31).unwrap();
+        let custom = parse_chart_range("14d").unwrap();
+
+        assert_eq!(
+            format_chart_range_label(custom.start_date(end), end, custom),
+            "2024-03-17..2024-03-31"
+        );
+        assert_eq!(
+            format_chart_range_label(None, end, parse_chart_range("ALL").unwrap()),
+            "ALL"
+        );
+    }
+}
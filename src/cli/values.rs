@@ -0,0 +1,177 @@
+//! Value enums for CLI flags and their mapping onto library types.
+
+use pricr::{calc, output, provider};
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum SamplingArg {
+    Auto,
+    Hourly,
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl SamplingArg {
+    /// Period that fetched daily data is resampled to on the client.
+    pub fn resample_period(self) -> Option<calc::resample::ResamplePeriod> {
+        match self {
+            Self::Weekly => Some(calc::resample::ResamplePeriod::Weekly),
+            Self::Monthly => Some(calc::resample::ResamplePeriod::Monthly),
+            Self::Auto | Self::Hourly | Self::Daily => None,
+        }
+    }
+}
+
+/// Providers only serve hourly and daily data; weekly and monthly fetch daily points.
+impl From<SamplingArg> for provider::HistoryInterval {
+    fn from(value: SamplingArg) -> Self {
+        match value {
+            SamplingArg::Auto => Self::Auto,
+            SamplingArg::Hourly => Self::Hourly,
+            SamplingArg::Daily | SamplingArg::Weekly | SamplingArg::Monthly => Self::Daily,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum FormatArg {
+    Table,
+    Plain,
+}
+
+impl From<FormatArg> for output::table::OutputStyle {
+    fn from(value: FormatArg) -> Self {
+        match value {
+            FormatArg::Table => Self::Table,
+            FormatArg::Plain => Self::Plain,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum SymbolCaseArg {
+    Upper,
+    Lower,
+    Preserve,
+}
+
+impl From<SymbolCaseArg> for output::format::SymbolCase {
+    fn from(value: SymbolCaseArg) -> Self {
+        match value {
+            SymbolCaseArg::Upper => Self::Upper,
+            SymbolCaseArg::Lower => Self::Lower,
+            SymbolCaseArg::Preserve => Self::Preserve,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum HistoryFormatArg {
+    /// One series per symbol, each with its own points
+    Long,
+    /// Shared timestamps with one price column per symbol
+    Wide,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum FillArg {
+    None,
+    Forward,
+}
+
+impl From<FillArg> for calc::fill::FillMode {
+    fn from(value: FillArg) -> Self {
+        match value {
+            FillArg::None => Self::None,
+            FillArg::Forward => Self::Forward,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum ColorArg {
+    Auto,
+    Always,
+    Never,
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum SortByArg {
+    Rank,
+    Price,
+    Change,
+    MarketCap,
+    Symbol,
+}
+
+impl From<SortByArg> for calc::PriceSort {
+    fn from(value: SortByArg) -> Self {
+        match value {
+            SortByArg::Rank => Self::Rank,
+            SortByArg::Price => Self::Price,
+            SortByArg::Change => Self::Change,
+            SortByArg::MarketCap => Self::MarketCap,
+            SortByArg::Symbol => Self::Symbol,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, clap::ValueEnum)]
+pub enum PeriodArg {
+    #[value(name = "24h")]
+    Hours24,
+    #[value(name = "7d")]
+    Days7,
+    #[value(name = "30d")]
+    Days30,
+    #[value(name = "90d")]
+    Days90,
+    #[value(name = "1y")]
+    Year1,
+}
+
+impl PeriodArg {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Hours24 => "24h",
+            Self::Days7 => "7d",
+            Self::Days30 => "30d",
+            Self::Days90 => "90d",
+            Self::Year1 => "1y",
+        }
+    }
+
+    /// Change basis recorded on quotes whose change was recomputed for this period.
+    pub fn change_basis(self) -> Option<provider::ChangeBasis> {
+        match self {
+            Self::Hours24 => None,
+            Self::Days7 => Some(provider::ChangeBasis::Days7),
+            Self::Days30 => Some(provider::ChangeBasis::Days30),
+            Self::Days90 => Some(provider::ChangeBasis::Days90),
+            Self::Year1 => Some(provider::ChangeBasis::Year1),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, clap::ValueEnum)]
+pub enum AssetTypeArg {
+    Stock,
+    Etf,
+    Crypto,
+    Index,
+    Future,
+    Currency,
+}
+
+impl From<AssetTypeArg> for calc::AssetType {
+    fn from(value: AssetTypeArg) -> Self {
+        match value {
+            AssetTypeArg::Stock => Self::Stock,
+            AssetTypeArg::Etf => Self::Etf,
+            AssetTypeArg::Crypto => Self::Crypto,
+            AssetTypeArg::Index => Self::Index,
+            AssetTypeArg::Future => Self::Future,
+            AssetTypeArg::Currency => Self::Currency,
+        }
+    }
+}
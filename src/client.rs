@@ -0,0 +1,1728 @@
+//! High-level API for embedding pricr in other Rust programs.
+//!
+//! [`Pricr`] bundles the providers, their fallback order, watchlists, and symbol
+//! pins the CLI uses, so a lookup behaves like the matching `pricr` command:
+//!
+//! ```no_run
+//! # async fn example() -> pricr::error::Result<()> {
+//! use pricr::client::Pricr;
+//!
+//! let pricr = Pricr::builder()
+//!     .currency("eur")
+//!     .provider_order(["yahoo", "coingecko"])
+//!     .build()?;
+//!
+//! for price in pricr.prices(&["btc", "aapl", "yahoo:brk-b"]).await? {
+//!     println!("{}", price.to_display_string());
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The free functions below are the building blocks [`Pricr`] is made of, for
+//! callers that manage their own provider lists.
+
+use std::collections::{HashMap, HashSet};
+
+use futures::future::join_all;
+use tracing::{debug, info, warn};
+
+use crate::calc::{self, Conversion, FiatAmount};
+use crate::clock::SharedClock;
+use crate::config::{self, AppConfig};
+use crate::error::{self, Result};
+use crate::provider::coingecko::ApiKey;
+use crate::provider::context::{DEFAULT_MAX_RETRY_AFTER_SECS, ProviderContext};
+use crate::provider::registry::ProviderRegistry;
+use crate::provider::yahoo::YahooOptions;
+use crate::provider::{
+    self, CoinPrice, HistoryInterval, PriceHistory, PriceProvider, ProviderOptions, TickerMatch,
+};
+
+/// Results requested from each provider when `--type`/`--exchange` filter a search.
+pub const MAX_SEARCH_LIMIT: usize = 50;
+
+/// Matches [`Pricr::search`] returns.
+pub const DEFAULT_SEARCH_LIMIT: usize = 10;
+
+/// API keys for providers that take one; both work without a key.
+#[derive(Debug, Clone, Default)]
+pub struct ApiKeys {
+    pub coinmarketcap: Option<String>,
+    pub coingecko: Option<ApiKey>,
+}
+
+/// Builder for [`Pricr`]; see [`Pricr::builder`].
+#[derive(Debug, Default)]
+pub struct PricrBuilder {
+    currency: Option<String>,
+    provider: Option<String>,
+    provider_order: Option<Vec<String>>,
+    api_keys: ApiKeys,
+    coingecko_base_url: Option<String>,
+    watchlists: HashMap<String, Vec<String>>,
+    symbol_providers: HashMap<String, String>,
+    registry: Option<ProviderRegistry>,
+    registered: ProviderRegistry,
+    free_only: bool,
+    provider_options: ProviderOptions,
+    search_limit_per_provider: Option<usize>,
+}
+
+impl PricrBuilder {
+    /// Start from a loaded config: currency, provider order, CoinGecko settings,
+    /// API keys, watchlists, `[symbol_providers]`, the user agent, and the
+    /// `Retry-After` and cache limits.
+    ///
+    /// A user agent that cannot be sent as a header is an
+    /// [`Error::Config`](error::Error::Config).
+    pub fn from_config(config: &AppConfig) -> Result<Self> {
+        Ok(Self {
+            currency: config.defaults.currency.clone(),
+            provider: None,
+            provider_order: config.defaults.provider_order.clone(),
+            api_keys: ApiKeys {
+                coinmarketcap: config.coinmarketcap.api_key.clone(),
+                coingecko: config.coingecko.api_key.clone().map(|key| ApiKey {
+                    key,
+                    tier: config.coingecko.api_tier.unwrap_or_default(),
+                }),
+            },
+            coingecko_base_url: config.coingecko.base_url.clone(),
+            watchlists: config.watchlists.clone(),
+            symbol_providers: config.symbol_providers.clone(),
+            registry: None,
+            registered: ProviderRegistry::new(),
+            free_only: false,
+            provider_options: ProviderOptions {
+                context: provider_context(config)?,
+                yahoo: YahooOptions::default(),
+            },
+            search_limit_per_provider: None,
+        })
+    }
+
+    /// Results to request from each provider when searching (default: the search limit).
+    pub fn search_limit_per_provider(mut self, limit: usize) -> Self {
+        self.search_limit_per_provider = Some(limit);
+        self
+    }
+
+    /// Quote currency, e.g. `usd`, `eur`, or `btc` (default `usd`).
+    pub fn currency(mut self, currency: impl Into<String>) -> Self {
+        self.currency = Some(currency.into());
+        self
+    }
+
+    /// Use only this provider, like `--provider`.
+    pub fn provider(mut self, provider_id: impl Into<String>) -> Self {
+        self.provider = Some(provider_id.into());
+        self
+    }
+
+    /// Providers to try first, like `[defaults].provider_order`; the rest follow.
+    pub fn provider_order<I, S>(mut self, provider_ids: I) -> Self
+    where
+        I: IntoIterator<Item = S>,
+        S: Into<String>,
+    {
+        self.provider_order = Some(provider_ids.into_iter().map(Into::into).collect());
+        self
+    }
+
+    pub fn api_keys(mut self, api_keys: ApiKeys) -> Self {
+        self.api_keys = api_keys;
+        self
+    }
+
+    /// Point CoinGecko at a proxy or mock server.
+    pub fn coingecko_base_url(mut self, base_url: impl Into<String>) -> Self {
+        self.coingecko_base_url = Some(base_url.into());
+        self
+    }
+
+    /// Symbol groups callable as `@name` in [`Pricr::prices`].
+    pub fn watchlists(mut self, watchlists: HashMap<String, Vec<String>>) -> Self {
+        self.watchlists = watchlists;
+        self
+    }
+
+    /// Symbol to provider id, forcing that symbol's lookups to one provider.
+    pub fn symbol_providers(mut self, symbol_providers: HashMap<String, String>) -> Self {
+        self.symbol_providers = symbol_providers;
+        self
+    }
+
+    /// Add a provider after the built-in ones, or in place of the built-in one
+    /// with the same id; see [`ProviderRegistry::register`].
+    pub fn register(mut self, provider: Box<dyn PriceProvider>) -> Self {
+        self.registered.register(provider);
+        self
+    }
+
+    /// Use these providers instead of the built-in ones; API keys and the
+    /// CoinGecko base URL are then ignored.
+    pub fn registry(mut self, registry: ProviderRegistry) -> Self {
+        self.registry = Some(registry);
+        self
+    }
+
+    /// Clock the built-in providers read "now" from (default: the wall clock).
+    pub fn clock(mut self, clock: SharedClock) -> Self {
+        self.provider_options.context.clock = clock;
+        self
+    }
+
+    /// HTTP client the built-in providers share (default: [`provider::http::client`]).
+    pub fn client(mut self, client: reqwest::Client) -> Self {
+        self.provider_options.context.client = client;
+        self
+    }
+
+    /// Client, clock, and limits the built-in providers share, replacing the
+    /// ones set so far.
+    pub fn context(mut self, context: ProviderContext) -> Self {
+        self.provider_options.context = context;
+        self
+    }
+
+    /// Optional Yahoo Finance requests, like `--enrich`, `--fundamentals`,
+    /// `--events`, and `--adjusted`.
+    pub fn yahoo_options(mut self, options: YahooOptions) -> Self {
+        self.provider_options.yahoo = options;
+        self
+    }
+
+    /// Leave out providers that only work with an API key, like `--free-only`.
+    pub fn free_only(mut self, free_only: bool) -> Self {
+        self.free_only = free_only;
+        self
+    }
+
+    /// Create the providers and check the provider ids.
+    ///
+    /// Unknown ids in `provider`, `provider_order`, or `symbol_providers` are
+    /// [`Error::Config`](error::Error::Config) errors. With
+    /// [`free_only`](Self::free_only), key-only providers named in
+    /// `provider_order` or `symbol_providers` are skipped instead.
+    pub fn build(self) -> Result<Pricr> {
+        let mut registry = self.registry.unwrap_or_else(|| {
+            ProviderRegistry::builtin(
+                self.api_keys.coinmarketcap,
+                self.coingecko_base_url,
+                self.api_keys.coingecko,
+                &self.provider_options,
+            )
+        });
+        registry.extend(self.registered);
+        let mut provider_order = self.provider_order;
+        let mut configured_pins = self.symbol_providers;
+        if self.free_only {
+            let key_only: Vec<String> = registry
+                .providers()
+                .iter()
+                .filter(|p| p.requires_key())
+                .map(|p| p.id().to_string())
+                .collect();
+            let is_key_only = |id: &str| key_only.iter().any(|k| k.eq_ignore_ascii_case(id.trim()));
+            if let Some(order) = provider_order.as_mut() {
+                order.retain(|id| !is_key_only(id));
+            }
+            configured_pins.retain(|_, id| !is_key_only(id));
+            registry.retain_free();
+        }
+        let provider_indices =
+            registry.lookup_order(self.provider.as_deref(), provider_order.as_deref())?;
+        let symbol_providers = resolve_symbol_providers(&registry, &configured_pins)?;
+
+        Ok(Pricr {
+            context: self.provider_options.context,
+            registry,
+            provider_indices,
+            pinned: self.provider.is_some(),
+            currency: self
+                .currency
+                .unwrap_or_else(|| config::DEFAULT_CURRENCY.to_string()),
+            watchlists: self.watchlists,
+            symbol_providers,
+            search_limit_per_provider: self.search_limit_per_provider,
+        })
+    }
+}
+
+/// Provider context carrying the config's user agent, `Retry-After` cap, and
+/// cache entry limit.
+fn provider_context(config: &AppConfig) -> Result<ProviderContext> {
+    let user_agent = config
+        .defaults
+        .user_agent
+        .as_deref()
+        .map(str::trim)
+        .filter(|agent| !agent.is_empty())
+        .unwrap_or(provider::http::DEFAULT_USER_AGENT);
+    Ok(ProviderContext::with_user_agent(user_agent)?
+        .with_max_retry_after_secs(
+            config
+                .network
+                .max_retry_after_secs
+                .unwrap_or(DEFAULT_MAX_RETRY_AFTER_SECS),
+        )
+        .with_cache_max_entries(config.cache.max_entries.unwrap_or(0)))
+}
+
+/// Price lookups with provider fallback, watchlists, and symbol pins.
+///
+/// ```
+/// use pricr::client::Pricr;
+///
+/// let pricr = Pricr::builder().currency("eur").provider("yahoo").build().unwrap();
+/// assert_eq!(pricr.currency(), "eur");
+/// assert_eq!(pricr.provider_ids(), ["yahoo"]);
+///
+/// assert!(Pricr::builder().provider_order(["nope"]).build().is_err());
+/// ```
+pub struct Pricr {
+    context: ProviderContext,
+    registry: ProviderRegistry,
+    provider_indices: Vec<usize>,
+    pinned: bool,
+    currency: String,
+    watchlists: HashMap<String, Vec<String>>,
+    symbol_providers: HashMap<String, usize>,
+    search_limit_per_provider: Option<usize>,
+}
+
+impl Pricr {
+    pub fn builder() -> PricrBuilder {
+        PricrBuilder::default()
+    }
+
+    pub fn currency(&self) -> &str {
+        &self.currency
+    }
+
+    /// Every available provider, including ones left out of the lookup order.
+    pub fn providers(&self) -> &[Box<dyn PriceProvider>] {
+        self.registry.providers()
+    }
+
+    pub fn registry(&self) -> &ProviderRegistry {
+        &self.registry
+    }
+
+    /// Client and clock the built-in providers were built with, for creating
+    /// standalone providers that share them.
+    pub fn context(&self) -> &ProviderContext {
+        &self.context
+    }
+
+    /// Indices into [`providers`](Self::providers) in lookup order.
+    pub fn provider_indices(&self) -> &[usize] {
+        &self.provider_indices
+    }
+
+    /// Ids of the providers in lookup order.
+    pub fn provider_ids(&self) -> Vec<String> {
+        provider_ids_for_indices(self.providers(), &self.provider_indices)
+    }
+
+    /// Whether a single provider was chosen with [`PricrBuilder::provider`].
+    pub fn is_pinned(&self) -> bool {
+        self.pinned
+    }
+
+    /// `[symbol_providers]` pins as uppercase symbol to provider index.
+    pub fn symbol_providers(&self) -> &HashMap<String, usize> {
+        &self.symbol_providers
+    }
+
+    /// Lookup order limited to providers that can quote in [`currency`](Self::currency).
+    pub fn quote_provider_indices(&self) -> Result<Vec<usize>> {
+        restrict_to_quote_currency(
+            self.providers(),
+            self.provider_indices.clone(),
+            self.pinned,
+            &self.currency,
+        )
+    }
+
+    /// Current prices for `symbols`, in input order.
+    ///
+    /// Symbols accept the CLI forms: `@watchlist`, and `provider:symbol` or
+    /// `symbol@provider` pins. Symbols no provider knows are left out; an error is
+    /// returned only when nothing was found.
+    pub async fn prices(&self, symbols: &[&str]) -> Result<Vec<CoinPrice>> {
+        let tokens: Vec<String> = symbols.iter().map(|s| s.to_string()).collect();
+        let expanded = expand_symbol_tokens(&tokens, &self.watchlists)?;
+        let (symbols, pins) = split_symbol_pins(&self.registry, expanded)?;
+        let mut symbol_providers = self.symbol_providers.clone();
+        symbol_providers.extend(pins);
+
+        fetch_prices(
+            self.providers(),
+            &self.quote_provider_indices()?,
+            self.pinned,
+            &symbols,
+            &self.currency,
+            false,
+            &symbol_providers,
+        )
+        .await
+    }
+
+    /// Daily (or finer, for short ranges) price history over the last `days` days.
+    ///
+    /// Each symbol is fetched on its own from the first provider that has it;
+    /// symbols no provider has are left out.
+    pub async fn history(&self, symbols: &[&str], days: u32) -> Result<Vec<PriceHistory>> {
+        let provider_indices = self.quote_provider_indices()?;
+        let lookups = symbols.iter().map(|symbol| {
+            let symbol = [symbol.trim().to_string()];
+            let provider_indices = &provider_indices;
+            async move {
+                first_provider_result(
+                    self.providers(),
+                    provider_indices,
+                    "history",
+                    async |prov| {
+                        prov.get_price_history(&symbol, &self.currency, days, HistoryInterval::Auto)
+                            .await
+                    },
+                )
+                .await
+            }
+        });
+
+        let mut histories = Vec::new();
+        let mut last_non_ignorable_error = None;
+        for result in join_all(lookups).await {
+            match result {
+                Ok(found) => histories.extend(found),
+                Err(err) if is_ignorable_price_error(&err) => {}
+                Err(err) => last_non_ignorable_error = Some(err),
+            }
+        }
+
+        if histories.is_empty() {
+            return Err(last_non_ignorable_error.unwrap_or(error::Error::NoResults));
+        }
+        Ok(histories)
+    }
+
+    /// Convert a fiat amount into each target, like `pricr 100usd btc eur`.
+    ///
+    /// Fiat targets use Frankfurter (ECB) rates; other targets are priced in the
+    /// amount's currency by the crypto providers.
+    ///
+    /// ```no_run
+    /// # async fn example() -> pricr::error::Result<()> {
+    /// use pricr::calc::parse_fiat_amount;
+    /// use pricr::client::Pricr;
+    ///
+    /// let pricr = Pricr::builder().build()?;
+    /// let amount = parse_fiat_amount("1,500usd").unwrap();
+    /// for conversion in pricr.convert(&amount, &["btc", "eur"]).await? {
+    ///     println!("{} {}", conversion.to_amount, conversion.to_symbol);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn convert(&self, amount: &FiatAmount, targets: &[&str]) -> Result<Vec<Conversion>> {
+        let (fiat_targets, crypto_targets): (Vec<String>, Vec<String>) = targets
+            .iter()
+            .map(|target| target.to_string())
+            .partition(|target| calc::is_known_fiat(target));
+
+        let fiat_provider = provider::frankfurter::Frankfurter::with_context(self.context.clone());
+        let fiat_lookup = async {
+            if fiat_targets.is_empty() {
+                return Ok(HashMap::new());
+            }
+            fiat_provider
+                .get_rates(&amount.currency, &fiat_targets)
+                .await
+        };
+        let crypto_lookup = async {
+            if crypto_targets.is_empty() {
+                return Ok(Vec::new());
+            }
+            fetch_prices(
+                self.providers(),
+                &self.provider_indices,
+                self.pinned,
+                &crypto_targets,
+                &amount.currency,
+                false,
+                &HashMap::new(),
+            )
+            .await
+        };
+        let (rates, prices) = futures::join!(fiat_lookup, crypto_lookup);
+        let (rates, prices) = (rates?, prices?);
+
+        let mut conversions = Vec::new();
+        for target in &fiat_targets {
+            let upper = target.to_uppercase();
+            if let Some(&rate) = rates.get(&upper) {
+                conversions.push(Conversion {
+                    from_amount: amount.amount,
+                    from_currency: amount.currency.clone(),
+                    to_symbol: upper.clone(),
+                    to_name: calc::fiat_name(&upper).to_string(),
+                    to_amount: amount.amount * rate,
+                    rate: 1.0 / rate,
+                    provider: "Frankfurter/ECB".to_string(),
+                    timestamp: self.context.clock.now(),
+                });
+            }
+        }
+        for p in &prices {
+            conversions.push(Conversion {
+                from_amount: amount.amount,
+                from_currency: amount.currency.clone(),
+                to_symbol: p.symbol.clone(),
+                to_name: p.name.clone(),
+                to_amount: amount.amount / p.price,
+                rate: p.price,
+                provider: p.provider.clone(),
+                timestamp: self.context.clock.now(),
+            });
+        }
+
+        Ok(conversions)
+    }
+
+    /// Ticker search across providers, best matches first.
+    pub async fn search(&self, query: &str) -> Result<Vec<TickerMatch>> {
+        self.search_filtered(query, DEFAULT_SEARCH_LIMIT, &calc::SearchFilter::default())
+            .await
+    }
+
+    /// Ticker search keeping up to `limit` matches that pass `filter`.
+    pub async fn search_filtered(
+        &self,
+        query: &str,
+        limit: usize,
+        filter: &calc::SearchFilter,
+    ) -> Result<Vec<TickerMatch>> {
+        if !self.pinned {
+            info!(
+                providers = ?self.provider_ids(),
+                query = %query,
+                limit,
+                filter = ?filter,
+                "searching tickers across providers"
+            );
+            return search_tickers_across_providers(
+                self.providers(),
+                &self.provider_indices,
+                query,
+                limit,
+                self.search_limit_per_provider,
+                filter,
+            )
+            .await;
+        }
+
+        let prov = &self.providers()[self.provider_indices[0]];
+        info!(provider = prov.id(), query = %query, limit, filter = ?filter, "searching tickers");
+        let mut matches = prov
+            .search_tickers(
+                query,
+                search_fetch_limit(limit, self.search_limit_per_provider, filter),
+            )
+            .await?;
+        matches.retain(|m| filter.matches(m));
+        matches.truncate(limit);
+        if matches.is_empty() {
+            return Err(error::Error::NoResults);
+        }
+        Ok(matches)
+    }
+}
+
+fn resolve_watchlist<'a>(
+    watchlists: &'a HashMap<String, Vec<String>>,
+    name: &str,
+) -> Option<&'a Vec<String>> {
+    watchlists
+        .iter()
+        .find(|(key, _)| key.eq_ignore_ascii_case(name))
+        .map(|(_, symbols)| symbols)
+}
+
+/// Replace `@watchlist` tokens with the watchlist's symbols and drop repeated symbols.
+pub fn expand_symbol_tokens(
+    raw_symbols: &[String],
+    watchlists: &HashMap<String, Vec<String>>,
+) -> Result<Vec<String>> {
+    let mut expanded = Vec::new();
+
+    for token in raw_symbols {
+        if let Some(name) = token.strip_prefix('@') {
+            let trimmed_name = name.trim();
+            if trimmed_name.is_empty() {
+                return Err(error::Error::Config(
+                    "watchlist name cannot be empty after '@'".into(),
+                ));
+            }
+
+            let symbols = resolve_watchlist(watchlists, trimmed_name).ok_or_else(|| {
+                error::Error::Config(format!(
+                    "unknown watchlist '{}' -- define it under [watchlists] in config",
+                    trimmed_name
+                ))
+            })?;
+
+            let mut added = 0usize;
+            for symbol in symbols {
+                let trimmed_symbol = symbol.trim();
+                if trimmed_symbol.is_empty() {
+                    continue;
+                }
+                expanded.push(trimmed_symbol.to_string());
+                added += 1;
+            }
+
+            if added == 0 {
+                return Err(error::Error::Config(format!(
+                    "watchlist '{}' is empty -- add symbols under [watchlists].{}",
+                    trimmed_name, trimmed_name
+                )));
+            }
+
+            continue;
+        }
+
+        expanded.push(token.clone());
+    }
+
+    Ok(dedup_symbols(expanded))
+}
+
+/// Split an inline provider pin off a symbol token: `yahoo:brk-b` or `brk-b@yahoo`.
+///
+/// Returns `(provider_id, symbol)`, or `None` for a plain symbol.
+fn parse_symbol_pin(token: &str) -> Option<(&str, &str)> {
+    let token = token.trim();
+    let (provider_id, symbol) = match token.split_once(':') {
+        Some(parts) => parts,
+        None if !token.starts_with('@') => {
+            let (symbol, provider_id) = token.rsplit_once('@')?;
+            (provider_id, symbol)
+        }
+        None => return None,
+    };
+    let (provider_id, symbol) = (provider_id.trim(), symbol.trim());
+    (!provider_id.is_empty() && !symbol.is_empty()).then_some((provider_id, symbol))
+}
+
+/// Strip inline provider pins from `symbols`, returning the bare symbols and the
+/// pins keyed like [`resolve_symbol_providers`] (uppercase symbol to provider index).
+pub fn split_symbol_pins(
+    registry: &ProviderRegistry,
+    symbols: Vec<String>,
+) -> Result<(Vec<String>, HashMap<String, usize>)> {
+    let mut bare = Vec::with_capacity(symbols.len());
+    let mut pins = HashMap::new();
+
+    for token in symbols {
+        let Some((provider_id, symbol)) = parse_symbol_pin(&token) else {
+            bare.push(token);
+            continue;
+        };
+        let idx = registry.index_of(provider_id).ok_or_else(|| {
+            error::Error::Config(format!(
+                "unknown provider '{}' in '{}' -- valid providers: {}",
+                provider_id,
+                token,
+                registry.ids().join(", ")
+            ))
+        })?;
+        pins.insert(symbol.to_uppercase(), idx);
+        bare.push(symbol.to_string());
+    }
+
+    Ok((dedup_symbols(bare), pins))
+}
+
+/// Drop repeated symbols (case-insensitive), keeping the first occurrence.
+fn dedup_symbols(symbols: Vec<String>) -> Vec<String> {
+    let mut seen = HashSet::new();
+    symbols
+        .into_iter()
+        .filter(|symbol| {
+            let first = seen.insert(symbol.trim().to_uppercase());
+            if !first {
+                debug!("Ignoring duplicate symbol '{}'", symbol);
+            }
+            first
+        })
+        .collect()
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+struct TickerMatchKey {
+    symbol: String,
+    name: String,
+    exchange: String,
+    asset_type: String,
+}
+
+fn ticker_match_key(candidate: &provider::TickerMatch) -> TickerMatchKey {
+    TickerMatchKey {
+        symbol: candidate.symbol.trim().to_uppercase(),
+        name: candidate.name.trim().to_lowercase(),
+        exchange: candidate.exchange.trim().to_lowercase(),
+        asset_type: candidate.asset_type.trim().to_lowercase(),
+    }
+}
+
+fn append_provider_name(existing: &mut String, provider_name: &str) {
+    if existing
+        .split(',')
+        .map(str::trim)
+        .any(|name| name.eq_ignore_ascii_case(provider_name))
+    {
+        return;
+    }
+
+    if existing.trim().is_empty() {
+        *existing = provider_name.to_string();
+        return;
+    }
+
+    existing.push_str(", ");
+    existing.push_str(provider_name);
+}
+
+/// Ids of the providers at `indices`, in order.
+pub fn provider_ids_for_indices(
+    providers: &[Box<dyn provider::PriceProvider>],
+    indices: &[usize],
+) -> Vec<String> {
+    indices
+        .iter()
+        .filter_map(|&idx| providers.get(idx).map(|p| p.id().to_string()))
+        .collect()
+}
+
+/// Keep only providers that can quote prices in `currency` when it is a crypto asset.
+///
+/// A pinned provider without crypto quote support is an error rather than a silent skip.
+pub fn restrict_to_quote_currency(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: Vec<usize>,
+    pinned: bool,
+    currency: &str,
+) -> Result<Vec<usize>> {
+    if !calc::is_crypto_quote_asset(currency) {
+        return Ok(provider_indices);
+    }
+
+    let supports = |idx: &usize| {
+        providers[*idx]
+            .capabilities()
+            .features
+            .contains(&provider::Feature::CryptoQuote)
+    };
+
+    if pinned && !supports(&provider_indices[0]) {
+        return Err(error::Error::Unsupported {
+            provider: providers[provider_indices[0]].id().to_string(),
+            operation: format!(
+                "'{}' as a quote currency (crypto quotes need coingecko or cmc)",
+                currency.to_uppercase()
+            ),
+        });
+    }
+
+    let restricted: Vec<usize> = provider_indices.into_iter().filter(supports).collect();
+    if restricted.is_empty() {
+        return Err(error::Error::Config(format!(
+            "no available provider can quote prices in '{}'",
+            currency.to_uppercase()
+        )));
+    }
+
+    Ok(restricted)
+}
+
+/// Search errors that just mean "try the next provider".
+pub fn is_ignorable_search_error(err: &error::Error) -> bool {
+    matches!(
+        err,
+        error::Error::NoResults | error::Error::Unsupported { .. }
+    )
+}
+
+/// Price errors that move on to the next provider without being reported as failures.
+pub fn is_ignorable_price_error(err: &error::Error) -> bool {
+    matches!(
+        err,
+        error::Error::NoResults
+            | error::Error::SymbolNotFound { .. }
+            | error::Error::MissingApiKey { .. }
+            | error::Error::Unsupported { .. }
+    )
+}
+
+/// Return the first successful `fetch` result across providers, in order.
+///
+/// Unsupported or empty providers are skipped; when none succeed, the last
+/// real failure (or `NoResults`) is returned.
+pub async fn first_provider_result<T>(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    operation: &str,
+    fetch: impl AsyncFn(&dyn provider::PriceProvider) -> Result<T>,
+) -> Result<T> {
+    let mut last_non_ignorable_error = None;
+
+    for provider_idx in provider_indices {
+        let prov = providers[*provider_idx].as_ref();
+        match fetch(prov).await {
+            Ok(found) => return Ok(found),
+            Err(err) if is_ignorable_price_error(&err) => {
+                info!(provider = prov.id(), operation, error = %err, "skipping provider");
+            }
+            Err(err) => {
+                warn!(provider = prov.id(), operation, error = %err, "provider request failed");
+                last_non_ignorable_error = Some(err);
+            }
+        }
+    }
+
+    Err(last_non_ignorable_error.unwrap_or(error::Error::NoResults))
+}
+
+/// Search every provider and merge duplicate matches, keeping up to `limit` results.
+///
+/// Each provider is asked for `per_provider_limit` results (default `limit`) on its
+/// own, so one provider filling the limit does not crowd out the others.
+/// `filter` drops non-matching candidates before the limit applies, so providers are
+/// asked for more results whenever a filter is set.
+pub async fn search_tickers_across_providers(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    query: &str,
+    limit: usize,
+    per_provider_limit: Option<usize>,
+    filter: &calc::SearchFilter,
+) -> Result<Vec<provider::TickerMatch>> {
+    let mut matches: Vec<provider::TickerMatch> = Vec::new();
+    let mut by_key: HashMap<TickerMatchKey, usize> = HashMap::new();
+    let mut provider_counts: Vec<usize> = Vec::new();
+    let mut last_non_ignorable_error = None;
+    let fetch_limit = search_fetch_limit(limit, per_provider_limit, filter);
+
+    for provider_idx in provider_indices {
+        let prov = &providers[*provider_idx];
+        match prov.search_tickers(query, fetch_limit).await {
+            Ok(found) => {
+                for candidate in found.into_iter().filter(|m| filter.matches(m)) {
+                    let key = ticker_match_key(&candidate);
+                    if let Some(existing_idx) = by_key.get(&key).copied() {
+                        let existing = &mut matches[existing_idx];
+                        append_provider_name(&mut existing.provider, &candidate.provider);
+                        existing.rank = existing.rank.or(candidate.rank);
+                        provider_counts[existing_idx] += 1;
+                        continue;
+                    }
+
+                    let idx = matches.len();
+                    by_key.insert(key, idx);
+                    matches.push(candidate);
+                    provider_counts.push(1);
+                }
+            }
+            Err(err) if is_ignorable_search_error(&err) => {
+                info!(provider = prov.id(), error = %err, "skipping unsupported or empty search provider");
+            }
+            Err(err) => {
+                warn!(provider = prov.id(), error = %err, "ticker search failed for provider");
+                last_non_ignorable_error = Some(err);
+            }
+        }
+    }
+
+    if matches.is_empty() {
+        if let Some(err) = last_non_ignorable_error {
+            return Err(err);
+        }
+        return Err(error::Error::NoResults);
+    }
+
+    let mut matches = sort_ticker_matches_by_relevance(matches, &provider_counts, query);
+    matches.truncate(limit);
+    Ok(matches)
+}
+
+/// Results to request from a provider: `per_provider` (default `limit`), raised so
+/// that `limit` matches can survive `filter`.
+fn search_fetch_limit(
+    limit: usize,
+    per_provider: Option<usize>,
+    filter: &calc::SearchFilter,
+) -> usize {
+    let requested = per_provider.unwrap_or(limit);
+    if filter.is_empty() {
+        requested
+    } else {
+        requested.max(MAX_SEARCH_LIMIT)
+    }
+}
+
+/// How well a search candidate matches the query, best first.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum SearchRelevance {
+    ExactSymbol,
+    SymbolPrefix,
+    NameWord,
+    Substring,
+    Other,
+}
+
+fn search_relevance(candidate: &provider::TickerMatch, query: &str) -> SearchRelevance {
+    let words = |text: &str| -> Vec<String> {
+        text.split(|c: char| !c.is_alphanumeric())
+            .filter(|word| !word.is_empty())
+            .map(str::to_string)
+            .collect()
+    };
+    let query = query.trim().to_lowercase();
+    let symbol = candidate.symbol.trim().to_lowercase();
+    let name = candidate.name.trim().to_lowercase();
+    let query_words = words(&query);
+    let name_words = words(&name);
+
+    if query.is_empty() {
+        SearchRelevance::Other
+    } else if symbol == query {
+        SearchRelevance::ExactSymbol
+    } else if symbol.starts_with(&query) {
+        SearchRelevance::SymbolPrefix
+    } else if !query_words.is_empty()
+        && name_words
+            .windows(query_words.len())
+            .any(|window| window == query_words.as_slice())
+    {
+        SearchRelevance::NameWord
+    } else if symbol.contains(&query) || name.contains(&query) {
+        SearchRelevance::Substring
+    } else {
+        SearchRelevance::Other
+    }
+}
+
+/// Order merged search matches by relevance to `query`.
+///
+/// Exact symbol matches come first, then symbol prefixes, whole-word name matches,
+/// and substrings. Ties go to candidates more providers returned, then ranked ones
+/// (ascending rank), then the original order. `provider_counts` is parallel to `matches`.
+fn sort_ticker_matches_by_relevance(
+    matches: Vec<provider::TickerMatch>,
+    provider_counts: &[usize],
+    query: &str,
+) -> Vec<provider::TickerMatch> {
+    let mut scored: Vec<(usize, provider::TickerMatch)> = matches.into_iter().enumerate().collect();
+    scored.sort_by_key(|(idx, candidate)| {
+        (
+            search_relevance(candidate, query),
+            std::cmp::Reverse(provider_counts.get(*idx).copied().unwrap_or(1)),
+            candidate.rank.is_none(),
+            candidate.rank,
+            *idx,
+        )
+    });
+    scored.into_iter().map(|(_, candidate)| candidate).collect()
+}
+
+/// Fetch prices from each provider in turn, asking later providers only for unresolved symbols.
+///
+/// With `ohlc` set, providers are asked for quotes carrying the session open/high/low.
+pub async fn fetch_prices_with_provider_fallback(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    symbols: &[String],
+    currency: &str,
+    ohlc: bool,
+) -> Result<Vec<provider::CoinPrice>> {
+    let mut pending: Vec<(usize, String)> = symbols
+        .iter()
+        .enumerate()
+        .map(|(idx, symbol)| (idx, symbol.clone()))
+        .collect();
+    let mut resolved: Vec<Option<provider::CoinPrice>> = vec![None; symbols.len()];
+    let mut last_non_ignorable_error = None;
+
+    for provider_idx in provider_indices {
+        if pending.is_empty() {
+            break;
+        }
+
+        let request_symbols: Vec<String> =
+            pending.iter().map(|(_, symbol)| symbol.clone()).collect();
+        let prov = &providers[*provider_idx];
+
+        let result = if ohlc {
+            prov.get_ohlc(&request_symbols, currency).await
+        } else {
+            prov.get_prices(&request_symbols, currency).await
+        };
+        match result {
+            Ok(found) => {
+                let mut found_by_symbol: HashMap<String, Vec<provider::CoinPrice>> = HashMap::new();
+                for price in found {
+                    found_by_symbol
+                        .entry(price.symbol.trim().to_uppercase())
+                        .or_default()
+                        .push(price);
+                }
+
+                let mut next_pending = Vec::new();
+                for (original_idx, symbol) in pending {
+                    let key = symbol.trim().to_uppercase();
+                    let price = found_by_symbol
+                        .get_mut(&key)
+                        .and_then(|bucket| bucket.pop());
+                    if let Some(price) = price {
+                        resolved[original_idx] = Some(price);
+                    } else {
+                        next_pending.push((original_idx, symbol));
+                    }
+                }
+                pending = next_pending;
+            }
+            Err(err) if is_ignorable_price_error(&err) => {
+                info!(provider = prov.id(), error = %err, "skipping provider during price fallback");
+            }
+            Err(err @ error::Error::RateLimited { .. }) => {
+                info!(provider = prov.id(), error = %err, "provider rate limited, trying next provider");
+                last_non_ignorable_error = Some(err);
+            }
+            Err(err) => {
+                warn!(provider = prov.id(), error = %err, "price lookup failed for provider");
+                last_non_ignorable_error = Some(err);
+            }
+        }
+    }
+
+    let prices: Vec<provider::CoinPrice> = resolved.into_iter().flatten().collect();
+    if prices.is_empty() {
+        if let Some(err) = last_non_ignorable_error {
+            return Err(err);
+        }
+        return Err(error::Error::NoResults);
+    }
+
+    Ok(prices)
+}
+
+/// Map `[symbol_providers]` entries to uppercase symbols and provider indices.
+pub fn resolve_symbol_providers(
+    registry: &ProviderRegistry,
+    configured: &HashMap<String, String>,
+) -> Result<HashMap<String, usize>> {
+    configured
+        .iter()
+        .map(|(symbol, provider_id)| {
+            let idx = registry.index_of(provider_id.trim()).ok_or_else(|| {
+                error::Error::Config(format!(
+                    "unknown provider '{}' for '{}' in [symbol_providers] -- use --list-providers to see options",
+                    provider_id, symbol
+                ))
+            })?;
+            Ok((symbol.trim().to_uppercase(), idx))
+        })
+        .collect()
+}
+
+/// Split symbols into per-provider groups forced by `[symbol_providers]` and the
+/// rest, which use normal fallback.
+///
+/// Groups are ordered by each provider's first forced symbol.
+fn group_symbols_by_provider(
+    symbols: &[String],
+    symbol_providers: &HashMap<String, usize>,
+) -> (Vec<(usize, Vec<String>)>, Vec<String>) {
+    let mut forced: Vec<(usize, Vec<String>)> = Vec::new();
+    let mut fallback = Vec::new();
+
+    for symbol in symbols {
+        match symbol_providers.get(&symbol.trim().to_uppercase()) {
+            Some(&provider_idx) => match forced.iter_mut().find(|(idx, _)| *idx == provider_idx) {
+                Some((_, group)) => group.push(symbol.clone()),
+                None => forced.push((provider_idx, vec![symbol.clone()])),
+            },
+            None => fallback.push(symbol.clone()),
+        }
+    }
+
+    (forced, fallback)
+}
+
+/// Fetch prices from the pinned provider, or with fallback across `provider_indices`.
+///
+/// Symbols in `symbol_providers` (uppercase symbol to provider index) are fetched
+/// from that provider unless the whole lookup is `pinned`.
+pub async fn fetch_prices(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    pinned: bool,
+    symbols: &[String],
+    currency: &str,
+    ohlc: bool,
+    symbol_providers: &HashMap<String, usize>,
+) -> Result<Vec<provider::CoinPrice>> {
+    if !pinned && !symbol_providers.is_empty() {
+        return fetch_prices_with_symbol_providers(
+            providers,
+            provider_indices,
+            symbols,
+            currency,
+            ohlc,
+            symbol_providers,
+        )
+        .await;
+    }
+
+    if pinned {
+        let prov = &providers[provider_indices[0]];
+        info!(
+            provider = prov.id(),
+            symbols = ?symbols,
+            currency = %currency,
+            "fetching prices"
+        );
+        if ohlc {
+            prov.get_ohlc(symbols, currency).await
+        } else {
+            prov.get_prices(symbols, currency).await
+        }
+    } else {
+        let ordered_ids = provider_ids_for_indices(providers, provider_indices);
+        info!(
+            providers = ?ordered_ids,
+            symbols = ?symbols,
+            currency = %currency,
+            "fetching prices with provider fallback"
+        );
+        fetch_prices_with_provider_fallback(providers, provider_indices, symbols, currency, ohlc)
+            .await
+    }
+}
+
+/// Fetch `symbols` from one provider, keeping whatever it returns when some fail.
+///
+/// A batch request that fails on a per-symbol error is retried one symbol at a
+/// time; symbols that still have no price are logged with `warn!` and left out.
+/// Only when nothing resolves is an error returned.
+pub async fn fetch_partial_prices(
+    prov: &dyn PriceProvider,
+    symbols: &[String],
+    currency: &str,
+    ohlc: bool,
+) -> Result<Vec<CoinPrice>> {
+    let fetch = async |symbols: &[String]| {
+        if ohlc {
+            prov.get_ohlc(symbols, currency).await
+        } else {
+            prov.get_prices(symbols, currency).await
+        }
+    };
+
+    info!(
+        provider = prov.id(),
+        symbols = ?symbols,
+        currency = %currency,
+        "fetching prices, allowing partial results"
+    );
+    let prices = match fetch(symbols).await {
+        Ok(prices) => prices,
+        Err(err) if symbols.len() > 1 && is_per_symbol_error(&err) => {
+            info!(provider = prov.id(), error = %err, "batch lookup failed, retrying each symbol");
+            let lookups = symbols
+                .iter()
+                .map(|symbol| async move { (symbol, fetch(std::slice::from_ref(symbol)).await) });
+            let mut prices = Vec::new();
+            let mut last_error = err;
+            for (symbol, result) in join_all(lookups).await {
+                match result {
+                    Ok(found) => prices.extend(found),
+                    Err(err) => {
+                        debug!(provider = prov.id(), symbol = %symbol, error = %err, "symbol lookup failed");
+                        last_error = err;
+                    }
+                }
+            }
+            if prices.is_empty() {
+                return Err(last_error);
+            }
+            prices
+        }
+        Err(err) => return Err(err),
+    };
+
+    let resolved: HashSet<String> = prices
+        .iter()
+        .map(|price| price.symbol.trim().to_uppercase())
+        .collect();
+    for symbol in symbols {
+        let key = symbol.trim().to_uppercase();
+        if !resolved.contains(&key) {
+            warn!(provider = prov.id(), symbol = %key, "no price returned, continuing without it");
+        }
+    }
+    Ok(prices)
+}
+
+/// Errors a single bad symbol can cause for a whole batch request.
+fn is_per_symbol_error(err: &error::Error) -> bool {
+    matches!(
+        err,
+        error::Error::NoResults
+            | error::Error::SymbolNotFound { .. }
+            | error::Error::Api(_)
+            | error::Error::Parse(_)
+    )
+}
+
+/// Fetch symbols pinned to a provider (inline or by `[symbol_providers]`) from
+/// that provider and the rest with normal fallback, all concurrently, returning
+/// prices in the order of `symbols`.
+async fn fetch_prices_with_symbol_providers(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    symbols: &[String],
+    currency: &str,
+    ohlc: bool,
+    symbol_providers: &HashMap<String, usize>,
+) -> Result<Vec<provider::CoinPrice>> {
+    let (forced, fallback) = group_symbols_by_provider(symbols, symbol_providers);
+    let forced_lookups = forced.iter().map(|(provider_idx, group)| async move {
+        let prov = &providers[*provider_idx];
+        info!(provider = prov.id(), symbols = ?group, currency = %currency, "fetching prices pinned to a provider");
+        let result = if ohlc {
+            prov.get_ohlc(group, currency).await
+        } else {
+            prov.get_prices(group, currency).await
+        };
+        (prov, result)
+    });
+    let fallback_lookup = async {
+        if fallback.is_empty() {
+            return Ok(Vec::new());
+        }
+        fetch_prices_with_provider_fallback(providers, provider_indices, &fallback, currency, ohlc)
+            .await
+    };
+    let (forced_results, fallback_result) =
+        futures::join!(futures::future::join_all(forced_lookups), fallback_lookup);
+
+    let mut prices = Vec::new();
+    let mut last_non_ignorable_error = None;
+    for (prov, result) in forced_results {
+        match result {
+            Ok(found) => prices.extend(found),
+            Err(err) if is_ignorable_price_error(&err) => {
+                info!(provider = prov.id(), error = %err, "no data from pinned provider");
+            }
+            Err(err) => {
+                warn!(provider = prov.id(), error = %err, "price lookup failed for pinned provider");
+                last_non_ignorable_error = Some(err);
+            }
+        }
+    }
+    match fallback_result {
+        Ok(found) => prices.extend(found),
+        Err(error::Error::NoResults) => {}
+        Err(err) => last_non_ignorable_error = Some(err),
+    }
+
+    if prices.is_empty() {
+        return Err(last_non_ignorable_error.unwrap_or(error::Error::NoResults));
+    }
+
+    prices.sort_by_key(|price| {
+        symbols
+            .iter()
+            .position(|symbol| symbol.trim().eq_ignore_ascii_case(price.symbol.trim()))
+            .unwrap_or(usize::MAX)
+    });
+    Ok(prices)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchlists_for_tests() -> HashMap<String, Vec<String>> {
+        HashMap::from([
+            (
+                "commodities".to_string(),
+                vec!["GC=F".to_string(), "SI=F".to_string(), "CL=F".to_string()],
+            ),
+            (
+                "metals".to_string(),
+                vec!["GC=F".to_string(), "SI=F".to_string()],
+            ),
+        ])
+    }
+
+    #[test]
+    fn is_ignorable_price_error_skips_missing_keys_and_symbols_but_not_auth_or_rate_limits() {
+        assert!(is_ignorable_price_error(&error::Error::MissingApiKey {
+            provider: "CoinMarketCap".into(),
+            hint: "set COINMARKETCAP_API_KEY".into(),
+        }));
+        assert!(!is_ignorable_price_error(&error::Error::AuthFailed {
+            provider: "CoinMarketCap".into(),
+            detail: "401 Unauthorized: invalid api key".into(),
+        }));
+        assert!(is_ignorable_price_error(&error::Error::SymbolNotFound {
+            provider: "Yahoo Finance".into(),
+            symbol: "NOPE".into(),
+        }));
+        assert!(!is_ignorable_price_error(&error::Error::RateLimited {
+            provider: "CoinGecko".into(),
+            retry_after: Some(30),
+        }));
+        assert!(!is_ignorable_search_error(&error::Error::MissingApiKey {
+            provider: "CoinMarketCap".into(),
+            hint: "set COINMARKETCAP_API_KEY".into(),
+        }));
+    }
+
+    #[test]
+    fn append_provider_name_adds_unique_values() {
+        let mut provider = "Yahoo Finance".to_string();
+        append_provider_name(&mut provider, "Stooq");
+        append_provider_name(&mut provider, "stooq");
+        assert_eq!(provider, "Yahoo Finance, Stooq");
+    }
+
+    #[test]
+    fn restrict_to_quote_currency_keeps_crypto_quote_providers() {
+        let registry = ProviderRegistry::builtin(None, None, None, &ProviderOptions::default());
+        let providers = registry.providers();
+        let all = registry.lookup_order(None, None).unwrap();
+
+        let fiat = restrict_to_quote_currency(providers, all.clone(), false, "usd").unwrap();
+        assert_eq!(fiat, all);
+
+        let crypto = restrict_to_quote_currency(providers, all, false, "btc").unwrap();
+        let mut ids = provider_ids_for_indices(providers, &crypto);
+        ids.sort();
+        assert_eq!(ids, vec!["cmc", "coingecko"]);
+
+        let yahoo = registry.lookup_order(Some("yahoo"), None).unwrap();
+        let err = restrict_to_quote_currency(providers, yahoo, true, "btc").unwrap_err();
+        assert!(matches!(err, error::Error::Unsupported { provider, .. } if provider == "yahoo"));
+    }
+
+    #[test]
+    fn expand_symbol_tokens_resolves_watchlists() {
+        let raw = vec!["@commodities".to_string(), "btc".to_string()];
+        let expanded = expand_symbol_tokens(&raw, &watchlists_for_tests()).unwrap();
+
+        assert_eq!(expanded, vec!["GC=F", "SI=F", "CL=F", "btc"]);
+    }
+
+    #[test]
+    fn expand_symbol_tokens_resolves_watchlists_case_insensitively() {
+        let raw = vec!["@MeTaLs".to_string()];
+        let expanded = expand_symbol_tokens(&raw, &watchlists_for_tests()).unwrap();
+
+        assert_eq!(expanded, vec!["GC=F", "SI=F"]);
+    }
+
+    #[test]
+    fn expand_symbol_tokens_drops_duplicate_symbols() {
+        let raw = vec![
+            "btc".to_string(),
+            "BTC".to_string(),
+            "@metals".to_string(),
+            "gc=f".to_string(),
+        ];
+        let expanded = expand_symbol_tokens(&raw, &watchlists_for_tests()).unwrap();
+
+        assert_eq!(expanded, vec!["btc", "GC=F", "SI=F"]);
+    }
+
+    fn ticker(symbol: &str, name: &str, rank: Option<u32>) -> provider::TickerMatch {
+        provider::TickerMatch {
+            symbol: symbol.to_string(),
+            name: name.to_string(),
+            exchange: "Test".to_string(),
+            asset_type: "Test".to_string(),
+            provider: "Test".to_string(),
+            rank,
+        }
+    }
+
+    #[test]
+    fn sort_ticker_matches_by_relevance_puts_ranked_first_among_equals() {
+        let matches = vec![
+            ticker("AAPL", "AAPL", None),
+            ticker("ETH", "ETH", Some(2)),
+            ticker("APE", "APE", None),
+            ticker("BTC", "BTC", Some(1)),
+            ticker("AMZN", "AMZN", None),
+        ];
+
+        let sorted = sort_ticker_matches_by_relevance(matches, &[1; 5], "zzz");
+
+        let symbols: Vec<&str> = sorted.iter().map(|m| m.symbol.as_str()).collect();
+        assert_eq!(symbols, vec!["BTC", "ETH", "AAPL", "APE", "AMZN"]);
+    }
+
+    #[test]
+    fn sort_ticker_matches_by_relevance_prefers_exact_then_prefix_then_name_word() {
+        let matches = vec![
+            ticker("PINEAPPLE", "Pineapple Energy", None),
+            ticker("APLE", "Apple Hospitality REIT", None),
+            ticker("AAPL.MX", "Apple Inc.", None),
+            ticker("APPLEX", "Applex Corp", None),
+            ticker("apple", "Apple Token", Some(900)),
+            ticker("AAPL", "Apple Inc.", None),
+        ];
+
+        let sorted = sort_ticker_matches_by_relevance(matches, &[1, 1, 1, 1, 1, 2], "Apple");
+
+        let symbols: Vec<&str> = sorted.iter().map(|m| m.symbol.as_str()).collect();
+        assert_eq!(
+            symbols,
+            vec!["apple", "APPLEX", "AAPL", "APLE", "AAPL.MX", "PINEAPPLE"]
+        );
+    }
+
+    #[tokio::test]
+    async fn search_filters_apply_before_limit_truncation() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/search-filter/v1/finance/search"))
+            .and(query_param("quotesCount", "50"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "quotes": [
+                    { "symbol": "APLE", "shortname": "Apple Hospitality", "exchDisp": "NYSE", "typeDisp": "Equity" },
+                    { "symbol": "AAPY", "shortname": "Apple Income ETF", "exchDisp": "NASDAQ", "typeDisp": "ETF" },
+                    { "symbol": "AAPL", "shortname": "Apple Inc.", "exchDisp": "NASDAQ", "typeDisp": "Equity" }
+                ]
+            })))
+            .mount(&server)
+            .await;
+
+        let providers: Vec<Box<dyn provider::PriceProvider>> = vec![Box::new(
+            provider::yahoo::YahooFinance::with_base_url(format!("{}/search-filter", server.uri())),
+        )];
+        let filter = calc::SearchFilter {
+            asset_type: Some(calc::AssetType::Stock),
+            exchange: Some("nasdaq".to_string()),
+        };
+
+        let matches = search_tickers_across_providers(&providers, &[0], "apple", 1, None, &filter)
+            .await
+            .unwrap();
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].symbol, "AAPL");
+        assert_eq!(
+            search_fetch_limit(10, None, &calc::SearchFilter::default()),
+            10
+        );
+        assert_eq!(search_fetch_limit(10, Some(25), &filter), MAX_SEARCH_LIMIT);
+    }
+
+    #[test]
+    fn group_symbols_by_provider_splits_forced_and_fallback_symbols() {
+        let symbol_providers = HashMap::from([("THIN".to_string(), 1), ("AAPL".to_string(), 2)]);
+        let symbols: Vec<String> = ["btc", "aapl", "thin", "eth", "msft"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let (forced, fallback) = group_symbols_by_provider(&symbols, &symbol_providers);
+
+        assert_eq!(
+            forced,
+            vec![(2, vec!["aapl".to_string()]), (1, vec!["thin".to_string()])]
+        );
+        assert_eq!(fallback, vec!["btc", "eth", "msft"]);
+
+        let (forced, fallback) = group_symbols_by_provider(&symbols, &HashMap::new());
+        assert!(forced.is_empty());
+        assert_eq!(fallback, symbols);
+    }
+
+    #[test]
+    fn resolve_symbol_providers_rejects_unknown_provider() {
+        let registry = ProviderRegistry::builtin(None, None, None, &ProviderOptions::default());
+        let configured = HashMap::from([(" brk.b ".to_string(), "yahoo".to_string())]);
+        let resolved = resolve_symbol_providers(&registry, &configured).unwrap();
+        assert_eq!(resolved.get("BRK.B").copied(), registry.index_of("yahoo"));
+
+        let configured = HashMap::from([("thin".to_string(), "kraken".to_string())]);
+        let err = resolve_symbol_providers(&registry, &configured).unwrap_err();
+        assert!(err.to_string().contains("[symbol_providers]"), "{err}");
+    }
+
+    #[test]
+    fn parse_symbol_pin_accepts_prefix_and_suffix_forms() {
+        assert_eq!(parse_symbol_pin("yahoo:brk-b"), Some(("yahoo", "brk-b")));
+        assert_eq!(parse_symbol_pin("brk-b@yahoo"), Some(("yahoo", "brk-b")));
+        assert_eq!(
+            parse_symbol_pin(" coingecko : btc "),
+            Some(("coingecko", "btc"))
+        );
+        assert_eq!(parse_symbol_pin("aapl"), None);
+        assert_eq!(parse_symbol_pin("@metals"), None);
+        assert_eq!(parse_symbol_pin("yahoo:"), None);
+        assert_eq!(parse_symbol_pin("btc@"), None);
+    }
+
+    #[test]
+    fn expand_symbol_tokens_rejects_unknown_watchlist() {
+        let raw = vec!["@unknown".to_string()];
+        let err = expand_symbol_tokens(&raw, &watchlists_for_tests()).unwrap_err();
+
+        match err {
+            error::Error::Config(message) => {
+                assert!(message.contains("unknown watchlist"));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    /// Fails a whole batch when any symbol is unknown, like strict upstream APIs.
+    struct AllOrNothingProvider;
+
+    #[async_trait::async_trait]
+    impl PriceProvider for AllOrNothingProvider {
+        fn name(&self) -> &str {
+            "Strict"
+        }
+
+        fn id(&self) -> &str {
+            "strict"
+        }
+
+        fn capabilities(&self) -> provider::ProviderCapabilities {
+            provider::ProviderCapabilities {
+                api_key: provider::ApiKeyStatus::NotRequired,
+                features: vec![provider::Feature::Prices],
+                asset_classes: vec![provider::AssetClass::Crypto],
+                base_url: String::new(),
+            }
+        }
+
+        async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
+            symbols
+                .iter()
+                .map(|symbol| {
+                    let symbol = symbol.to_uppercase();
+                    if symbol != "BTC" {
+                        return Err(error::Error::SymbolNotFound {
+                            provider: "Strict".to_string(),
+                            symbol,
+                        });
+                    }
+                    Ok(CoinPrice {
+                        name: "Bitcoin".to_string(),
+                        symbol,
+                        price: 97_000.0,
+                        change_24h: None,
+                        market_cap: None,
+                        open_24h: None,
+                        high_24h: None,
+                        low_24h: None,
+                        market_state: None,
+                        change_basis: None,
+                        rank: None,
+                        fundamentals: None,
+                        native_price: None,
+                        native_currency: None,
+                        currency: currency.to_uppercase(),
+                        provider: "Strict".to_string(),
+                        timestamp: chrono::Utc::now(),
+                    })
+                })
+                .collect()
+        }
+    }
+
+    #[tokio::test]
+    async fn fetch_partial_prices_keeps_symbols_a_failed_batch_would_drop() {
+        let symbols = vec!["btc".to_string(), "aapl".to_string()];
+
+        let batch = AllOrNothingProvider.get_prices(&symbols, "usd").await;
+        assert!(matches!(batch, Err(error::Error::SymbolNotFound { .. })));
+
+        let prices = fetch_partial_prices(&AllOrNothingProvider, &symbols, "usd", false)
+            .await
+            .unwrap();
+        assert_eq!(prices.len(), 1);
+        assert_eq!(prices[0].symbol, "BTC");
+
+        let none =
+            fetch_partial_prices(&AllOrNothingProvider, &["aapl".to_string()], "usd", false).await;
+        assert!(matches!(none, Err(error::Error::SymbolNotFound { .. })));
+    }
+
+    /// Quotes a fixed set of symbols, for registering at runtime.
+    struct StaticProvider {
+        id: &'static str,
+        quotes: Vec<(&'static str, f64)>,
+    }
+
+    #[async_trait::async_trait]
+    impl PriceProvider for StaticProvider {
+        fn name(&self) -> &str {
+            self.id
+        }
+
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn capabilities(&self) -> provider::ProviderCapabilities {
+            provider::ProviderCapabilities {
+                api_key: provider::ApiKeyStatus::NotRequired,
+                features: vec![provider::Feature::Prices],
+                asset_classes: vec![provider::AssetClass::Stock],
+                base_url: String::new(),
+            }
+        }
+
+        async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
+            let found: Vec<CoinPrice> = symbols
+                .iter()
+                .filter_map(|symbol| {
+                    let &(symbol, price) = self
+                        .quotes
+                        .iter()
+                        .find(|(quoted, _)| quoted.eq_ignore_ascii_case(symbol))?;
+                    Some(CoinPrice {
+                        symbol: symbol.to_string(),
+                        name: symbol.to_string(),
+                        price,
+                        change_24h: None,
+                        market_cap: None,
+                        open_24h: None,
+                        high_24h: None,
+                        low_24h: None,
+                        market_state: None,
+                        change_basis: None,
+                        rank: None,
+                        fundamentals: None,
+                        native_price: None,
+                        native_currency: None,
+                        currency: currency.to_uppercase(),
+                        provider: self.id.to_string(),
+                        timestamp: chrono::Utc::now(),
+                    })
+                })
+                .collect();
+            if found.is_empty() {
+                return Err(error::Error::NoResults);
+            }
+            Ok(found)
+        }
+
+        async fn search_tickers(&self, query: &str, limit: usize) -> Result<Vec<TickerMatch>> {
+            Ok(self
+                .quotes
+                .iter()
+                .filter(|(symbol, _)| symbol.contains(&query.to_uppercase()))
+                .take(limit)
+                .map(|&(symbol, _)| TickerMatch {
+                    symbol: symbol.to_string(),
+                    name: symbol.to_string(),
+                    exchange: "TEST".to_string(),
+                    asset_type: "Equity".to_string(),
+                    provider: self.id.to_string(),
+                    rank: None,
+                })
+                .collect())
+        }
+    }
+
+    #[tokio::test]
+    async fn search_asks_each_provider_for_the_limit_then_dedups() {
+        const TICKERS: [&str; 10] = [
+            "APP0", "APP1", "APP2", "APP3", "APP4", "APP5", "APP6", "APP7", "APP8", "APP9",
+        ];
+        let providers: Vec<Box<dyn PriceProvider>> = ["alpha", "beta"]
+            .into_iter()
+            .map(|id| {
+                Box::new(StaticProvider {
+                    id,
+                    quotes: TICKERS.iter().map(|&t| (t, 1.0)).collect(),
+                }) as Box<dyn PriceProvider>
+            })
+            .collect();
+        let filter = calc::SearchFilter::default();
+
+        let matches =
+            search_tickers_across_providers(&providers, &[0, 1], "app", 10, None, &filter)
+                .await
+                .unwrap();
+        assert_eq!(matches.len(), 10);
+        assert!(matches.iter().all(|m| m.provider == "alpha, beta"));
+
+        let capped =
+            search_tickers_across_providers(&providers, &[0, 1], "app", 10, Some(3), &filter)
+                .await
+                .unwrap();
+        assert_eq!(capped.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn registered_providers_take_part_in_price_fallback() {
+        let pricr = Pricr::builder()
+            .registry(ProviderRegistry::new())
+            .register(Box::new(StaticProvider {
+                id: "alpha",
+                quotes: vec![("BTC", 100.0)],
+            }))
+            .register(Box::new(StaticProvider {
+                id: "beta",
+                quotes: vec![("BTC", 101.0), ("AAPL", 200.0)],
+            }))
+            .build()
+            .unwrap();
+
+        let prices = pricr.prices(&["aapl", "btc", "missing"]).await.unwrap();
+        let rows: Vec<(&str, &str)> = prices
+            .iter()
+            .map(|p| (p.symbol.as_str(), p.provider.as_str()))
+            .collect();
+        assert_eq!(rows, [("AAPL", "beta"), ("BTC", "alpha")]);
+
+        let pinned = pricr.prices(&["beta:btc"]).await.unwrap();
+        assert_eq!(pinned[0].price, 101.0);
+    }
+
+    #[tokio::test]
+    async fn registered_provider_can_lead_the_built_in_ones() {
+        let pricr = Pricr::builder()
+            .register(Box::new(StaticProvider {
+                id: "desk",
+                quotes: vec![("ACME", 12.5)],
+            }))
+            .provider_order(["desk"])
+            .build()
+            .unwrap();
+
+        assert_eq!(pricr.provider_ids()[0], "desk");
+        assert_eq!(
+            pricr.providers().len(),
+            ProviderRegistry::builtin(None, None, None, &ProviderOptions::default()).len() + 1
+        );
+        let prices = pricr.prices(&["acme"]).await.unwrap();
+        assert_eq!(prices[0].provider, "desk");
+    }
+}
@@ -0,0 +1,74 @@
+//! Source of the current time for cache TTLs, history windows, and date checks.
+//!
+//! Providers hold a [`SharedClock`] so tests can pin "now" with [`FixedClock`]
+//! instead of depending on the wall clock.
+
+use std::fmt;
+use std::sync::Arc;
+
+use chrono::{DateTime, NaiveDate, Utc};
+
+/// The current time, as seen by a provider or a run.
+pub trait Clock: fmt::Debug + Send + Sync {
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Today's date in UTC.
+    fn today(&self) -> NaiveDate {
+        self.now().date_naive()
+    }
+}
+
+/// Clock handle shared between providers.
+pub type SharedClock = Arc<dyn Clock>;
+
+/// The wall clock.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+}
+
+/// A shared [`SystemClock`], the default for every provider.
+pub fn system() -> SharedClock {
+    Arc::new(SystemClock)
+}
+
+/// A clock that always reports the same instant.
+///
+/// ```
+/// use pricr::clock::{Clock, FixedClock};
+///
+/// let clock = FixedClock::at(2024, 6, 1, 23, 59, 59);
+/// assert_eq!(clock.today().to_string(), "2024-06-01");
+/// ```
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock(pub DateTime<Utc>);
+
+impl FixedClock {
+    /// Capture the wall clock once, so every later read agrees on "now".
+    pub fn frozen() -> Self {
+        Self(Utc::now())
+    }
+
+    /// A clock stopped at the given UTC date and time.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the fields do not form a valid date and time.
+    pub fn at(year: i32, month: u32, day: u32, hour: u32, min: u32, sec: u32) -> Self {
+        let now = NaiveDate::from_ymd_opt(year, month, day)
+            .and_then(|date| date.and_hms_opt(hour, min, sec))
+            .expect("valid date and time")
+            .and_utc();
+        Self(now)
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
@@ -0,0 +1,189 @@
+//! Command handlers dispatched from `run` once providers are set up.
+//!
+//! Each handler prints its own output and returns the process exit code.
+
+pub mod about;
+pub mod alert;
+pub mod all_providers;
+pub mod cache;
+pub mod chart;
+pub mod compare;
+pub mod convert;
+pub mod currencies;
+pub mod details;
+pub mod history;
+pub mod market;
+pub mod performance;
+pub mod portfolio;
+pub mod quotes;
+pub mod search;
+pub mod symbols;
+pub mod unresolved;
+pub mod watch;
+
+#[cfg(test)]
+mod test_support;
+
+use std::collections::HashMap;
+
+use pricr::clock::SharedClock;
+use pricr::error::Result;
+use pricr::{calc, client, config, output, provider};
+
+use crate::EXIT_PARTIAL_RESULTS;
+use crate::cli::Cli;
+
+/// What `run` has resolved by the time it hands a command to its handler.
+pub struct Session<'a> {
+    pub cli: &'a Cli,
+    pub config: &'a config::AppConfig,
+    pub api_keys: &'a client::ApiKeys,
+    pub pricr: &'a client::Pricr,
+    /// Providers to try, in order; the first one is the primary provider.
+    pub provider_indices: Vec<usize>,
+    /// Provider index per uppercase symbol, from the config and inline pins.
+    pub symbol_providers: HashMap<String, usize>,
+    pub currency: String,
+    /// The run clock: frozen for one-shot runs, the wall clock in watch mode.
+    pub clock: SharedClock,
+    pub table_options: output::table::TableOptions,
+    pub json_options: &'a output::json::JsonOptions,
+}
+
+impl Session<'_> {
+    pub fn providers(&self) -> &[Box<dyn provider::PriceProvider>] {
+        self.pricr.providers()
+    }
+
+    /// The provider used when a command does not fall back across providers.
+    pub fn primary_provider(&self) -> &dyn provider::PriceProvider {
+        self.providers()[self.provider_indices[0]].as_ref()
+    }
+
+    /// Ids of the providers tried, in order, for unresolved symbol reports.
+    pub fn ordered_ids(&self) -> Vec<String> {
+        client::provider_ids_for_indices(self.providers(), &self.provider_indices)
+    }
+
+    /// Quotes for `symbols`, honoring `--provider` and per-symbol provider pins.
+    pub async fn fetch_prices(
+        &self,
+        symbols: &[String],
+        include_ohlc: bool,
+    ) -> Result<Vec<provider::CoinPrice>> {
+        client::fetch_prices(
+            self.providers(),
+            &self.provider_indices,
+            self.cli.provider.is_some(),
+            symbols,
+            &self.currency,
+            include_ohlc,
+            &self.symbol_providers,
+        )
+        .await
+    }
+
+    /// Symbols in `symbols` without a price in `found`, with search suggestions
+    /// unless `--no-suggest` is set.
+    pub async fn unresolved_symbols(
+        &self,
+        symbols: &[String],
+        found: &[provider::CoinPrice],
+    ) -> Vec<provider::SymbolError> {
+        let mut symbol_errors = unresolved::unresolved_symbols(symbols, found, &self.ordered_ids());
+        if !self.cli.no_suggest {
+            self.attach_suggestions(&mut symbol_errors).await;
+        }
+        symbol_errors
+    }
+
+    pub async fn attach_suggestions(&self, symbol_errors: &mut [provider::SymbolError]) {
+        unresolved::attach_symbol_suggestions(
+            self.providers(),
+            &self.provider_indices,
+            symbol_errors,
+        )
+        .await;
+    }
+
+    /// Print `symbol_errors` after a command's output and pick the exit code:
+    /// partial results only fail the run under `--strict`.
+    pub fn finish(&self, symbol_errors: &[provider::SymbolError]) -> i32 {
+        output::table::print_symbol_errors(symbol_errors);
+        if self.cli.strict && !symbol_errors.is_empty() {
+            EXIT_PARTIAL_RESULTS
+        } else {
+            0
+        }
+    }
+}
+
+/// Apply `--exclude-stablecoins` / `--include-only-stablecoins` to fetched prices.
+pub fn apply_stablecoin_filter(
+    prices: Vec<provider::CoinPrice>,
+    exclude: bool,
+    include_only: bool,
+) -> Vec<provider::CoinPrice> {
+    if exclude {
+        calc::filter_stablecoins(prices, true)
+    } else if include_only {
+        calc::filter_stablecoins(prices, false)
+    } else {
+        prices
+    }
+}
+
+/// Run `fetch` for each symbol on its own, concurrently, ticking `progress` as each
+/// finishes. Results keep the order of `symbols`; the first failure is returned.
+pub async fn fetch_each_symbol<T>(
+    symbols: &[String],
+    progress: &output::progress::Progress,
+    fetch: impl AsyncFn(&[String]) -> Result<Vec<T>>,
+) -> Result<Vec<T>> {
+    let lookups = symbols.iter().map(|symbol| async {
+        let result = fetch(std::slice::from_ref(symbol)).await;
+        progress.inc();
+        result
+    });
+    let results = futures::future::join_all(lookups).await;
+    progress.finish();
+
+    let mut found = Vec::new();
+    for result in results {
+        found.extend(result?);
+    }
+    Ok(found)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricr::error;
+
+    #[tokio::test]
+    async fn fetch_each_symbol_keeps_symbol_order_and_reports_failures() {
+        let symbols: Vec<String> = ["btc", "eth", "sol"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let progress = output::progress::Progress::hidden();
+
+        let found = fetch_each_symbol(&symbols, &progress, async |symbol: &[String]| {
+            Ok(vec![symbol[0].to_uppercase()])
+        })
+        .await
+        .unwrap();
+        assert_eq!(found, vec!["BTC", "ETH", "SOL"]);
+
+        let err = fetch_each_symbol(&symbols, &progress, async |symbol: &[String]| {
+            if symbol[0] == "eth" {
+                Err(error::Error::NoResults)
+            } else {
+                Ok(vec![symbol[0].clone()])
+            }
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, error::Error::NoResults));
+    }
+}
@@ -0,0 +1,52 @@
+//! `--version` and `--list-providers`: what this build can query.
+
+use pricr::error::Result;
+use pricr::{client, config, output, provider};
+
+use crate::APP_VERSION;
+use crate::cli::Cli;
+
+/// Print the version; with `--json`, also the ids of the built-in providers.
+pub fn version(json: bool, json_options: &output::json::JsonOptions) -> Result<i32> {
+    if json {
+        let provider_ids: Vec<String> =
+            provider::available_providers(None, None, None, &provider::ProviderOptions::default())
+                .iter()
+                .map(|p| p.id().to_string())
+                .collect();
+        output::json::print_version_json("pricr", APP_VERSION, &provider_ids, json_options)?;
+    } else {
+        println!("pricr {}", APP_VERSION);
+    }
+    Ok(0)
+}
+
+/// List the built-in providers with their capabilities, keeping only free ones
+/// under `--free-only`.
+pub fn list_providers(
+    cli: &Cli,
+    app_config: &config::AppConfig,
+    api_keys: client::ApiKeys,
+    json_options: &output::json::JsonOptions,
+) -> Result<i32> {
+    let mut registry = provider::registry::ProviderRegistry::builtin(
+        api_keys.coinmarketcap,
+        app_config.coingecko.base_url.clone(),
+        api_keys.coingecko,
+        &provider::ProviderOptions::default(),
+    );
+    if cli.free_only {
+        registry.retain_free();
+    }
+    let infos: Vec<provider::ProviderInfo> = registry
+        .providers()
+        .iter()
+        .map(|p| provider::ProviderInfo::from_provider(p.as_ref()))
+        .collect();
+    if cli.json {
+        output::json::print_providers_json(&infos, json_options)?;
+    } else {
+        output::table::print_providers(&infos);
+    }
+    Ok(0)
+}
@@ -0,0 +1,29 @@
+//! `--price-alert`: check one symbol against a threshold.
+
+use pricr::calc;
+use pricr::error::{self, Result};
+use pricr::output;
+
+use super::Session;
+use crate::EXIT_ALERT_NOT_TRIGGERED;
+
+/// Fetch the alert's symbol and report whether its threshold was crossed.
+pub async fn run(session: &Session<'_>, alert: &calc::PriceAlert) -> Result<i32> {
+    let prices = session
+        .fetch_prices(std::slice::from_ref(&alert.symbol), false)
+        .await?;
+    let price = prices.first().ok_or(error::Error::NoResults)?;
+    let result = calc::check_alert(price, alert);
+
+    if session.cli.json {
+        output::json::print_alert_json(&result, session.json_options)?;
+    } else {
+        println!("{}", result.to_display_string());
+    }
+
+    Ok(if result.triggered {
+        0
+    } else {
+        EXIT_ALERT_NOT_TRIGGERED
+    })
+}
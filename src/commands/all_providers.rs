@@ -0,0 +1,198 @@
+//! `--all-providers`: every provider's quote for each symbol.
+
+use std::collections::HashMap;
+
+use pricr::client::is_ignorable_price_error;
+use pricr::error::{self, Result};
+use pricr::{calc, output, provider};
+use tracing::{info, warn};
+
+use super::Session;
+
+/// `--all-providers`: quote every symbol from every provider side by side.
+pub async fn run(session: &Session<'_>, symbols: &[String]) -> Result<i32> {
+    let cli = session.cli;
+    let (comparisons, prices) = fetch_prices_from_all_providers(
+        session.providers(),
+        &session.provider_indices,
+        symbols,
+        &session.currency,
+        &output::progress::Progress::new(session.provider_indices.len(), cli.json),
+    )
+    .await?;
+    let symbol_errors = session.unresolved_symbols(symbols, &prices).await;
+
+    if cli.json {
+        output::json::print_provider_comparisons_json(&comparisons, session.json_options)?;
+    } else {
+        output::table::print_provider_comparison_table(&comparisons, &session.table_options);
+    }
+
+    Ok(session.finish(&symbol_errors))
+}
+
+/// Query every provider for every symbol concurrently, keeping each provider's quote.
+///
+/// Providers that cannot serve a symbol are left out of its group; providers that
+/// fail outright are recorded as unavailable. Returns the per-symbol comparisons
+/// together with every resolved price, in provider order.
+async fn fetch_prices_from_all_providers(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    symbols: &[String],
+    currency: &str,
+    progress: &output::progress::Progress,
+) -> Result<(Vec<calc::ProviderComparison>, Vec<provider::CoinPrice>)> {
+    let mut quotes: Vec<Vec<calc::ProviderQuote>> = vec![Vec::new(); symbols.len()];
+    let mut names: Vec<Option<String>> = vec![None; symbols.len()];
+    let mut resolved = Vec::new();
+    let mut last_non_ignorable_error = None;
+
+    let lookups = provider_indices.iter().map(|provider_idx| async {
+        let prov = &providers[*provider_idx];
+        info!(provider = prov.id(), symbols = ?symbols, currency = %currency, "fetching prices for comparison");
+        let result = prov.get_prices(symbols, currency).await;
+        progress.inc();
+        result
+    });
+    let results = futures::future::join_all(lookups).await;
+    progress.finish();
+
+    for (provider_idx, result) in provider_indices.iter().zip(results) {
+        let prov = &providers[*provider_idx];
+        match result {
+            Ok(found) => {
+                let mut found_by_symbol: HashMap<String, provider::CoinPrice> = found
+                    .into_iter()
+                    .map(|price| (price.symbol.trim().to_uppercase(), price))
+                    .collect();
+
+                for (idx, symbol) in symbols.iter().enumerate() {
+                    let Some(price) = found_by_symbol.remove(&symbol.trim().to_uppercase()) else {
+                        continue;
+                    };
+                    names[idx].get_or_insert_with(|| price.name.clone());
+                    quotes[idx].push(calc::ProviderQuote {
+                        provider: price.provider.clone(),
+                        price: Some(price.price),
+                        change_24h: price.change_24h,
+                        currency: Some(price.currency.clone()),
+                        error: None,
+                    });
+                    resolved.push(price);
+                }
+            }
+            Err(err) if is_ignorable_price_error(&err) => {
+                info!(provider = prov.id(), error = %err, "skipping provider during comparison");
+            }
+            Err(err) => {
+                warn!(provider = prov.id(), error = %err, "price lookup failed for provider");
+                for symbol_quotes in &mut quotes {
+                    symbol_quotes.push(calc::ProviderQuote {
+                        provider: prov.name().to_string(),
+                        price: None,
+                        change_24h: None,
+                        currency: None,
+                        error: Some(err.to_string()),
+                    });
+                }
+                last_non_ignorable_error = Some(err);
+            }
+        }
+    }
+
+    if resolved.is_empty() {
+        if let Some(err) = last_non_ignorable_error {
+            return Err(err);
+        }
+        return Err(error::Error::NoResults);
+    }
+
+    let comparisons = symbols
+        .iter()
+        .zip(quotes)
+        .zip(names)
+        .filter(|((_, symbol_quotes), _)| symbol_quotes.iter().any(|q| q.price.is_some()))
+        .map(|((symbol, symbol_quotes), name)| {
+            let symbol = symbol.trim().to_uppercase();
+            let name = name.unwrap_or_else(|| symbol.clone());
+            calc::compare_provider_quotes(&symbol, &name, symbol_quotes)
+        })
+        .collect();
+
+    Ok((comparisons, resolved))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::MockProvider;
+
+    #[tokio::test]
+    async fn fetch_prices_from_all_providers_keeps_every_provider_quote() {
+        let providers: Vec<Box<dyn provider::PriceProvider>> = vec![
+            Box::new(MockProvider {
+                id: "alpha",
+                quotes: vec![("BTC", 100.0), ("ETH", 10.0)],
+                fail: false,
+            }),
+            Box::new(MockProvider {
+                id: "beta",
+                quotes: vec![("BTC", 102.0)],
+                fail: false,
+            }),
+            Box::new(MockProvider {
+                id: "gamma",
+                quotes: Vec::new(),
+                fail: true,
+            }),
+        ];
+        let symbols = vec!["btc".to_string(), "eth".to_string()];
+
+        let (comparisons, prices) = fetch_prices_from_all_providers(
+            &providers,
+            &[0, 1, 2],
+            &symbols,
+            "usd",
+            &output::progress::Progress::hidden(),
+        )
+        .await
+        .unwrap();
+
+        let rows: Vec<(&str, &str)> = prices
+            .iter()
+            .map(|p| (p.symbol.as_str(), p.provider.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![("BTC", "alpha"), ("ETH", "alpha"), ("BTC", "beta")]
+        );
+
+        assert_eq!(comparisons.len(), 2);
+        let btc: Vec<(&str, Option<f64>)> = comparisons[0]
+            .quotes
+            .iter()
+            .map(|q| (q.provider.as_str(), q.price))
+            .collect();
+        assert_eq!(
+            btc,
+            vec![
+                ("alpha", Some(100.0)),
+                ("beta", Some(102.0)),
+                ("gamma", None)
+            ]
+        );
+        assert_eq!(comparisons[1].quotes.len(), 2);
+
+        let err = fetch_prices_from_all_providers(
+            &providers,
+            &[2],
+            &symbols,
+            "usd",
+            &output::progress::Progress::hidden(),
+        )
+        .await
+        .unwrap_err();
+        assert!(matches!(err, error::Error::Api(_)));
+    }
+}
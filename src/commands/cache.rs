@@ -0,0 +1,110 @@
+//! Cache maintenance: `--prune-cache` and `--warm-cache`.
+
+use pricr::client::{expand_symbol_tokens, is_ignorable_price_error, split_symbol_pins};
+use pricr::error::{self, Result};
+use pricr::provider::context::ProviderContext;
+use pricr::{config, provider};
+use tracing::{info, warn};
+
+use super::Session;
+use super::symbols::watchlist_symbols;
+
+/// `--prune-cache`: trim each provider's cache to `[cache].max_entries` files.
+pub async fn prune(app_config: &config::AppConfig) -> Result<i32> {
+    let Some(max_entries) = app_config.cache.max_entries else {
+        return Err(error::Error::Config(
+            "--prune-cache requires [cache].max_entries in the config file".into(),
+        ));
+    };
+    let removed = provider::prune_cache(max_entries).await;
+    println!(
+        "Pruned {} cache file(s), keeping at most {} per provider",
+        removed, max_entries
+    );
+    Ok(0)
+}
+
+/// `--warm-cache`: fetch `raw_symbols` (or every watchlist) from each provider so
+/// later lookups hit the cache.
+pub async fn warm(session: &Session<'_>, raw_symbols: &[String]) -> Result<i32> {
+    let watchlists = &session.config.watchlists;
+    let (mut symbols, _) = split_symbol_pins(
+        session.pricr.registry(),
+        expand_symbol_tokens(raw_symbols, watchlists)?,
+    )?;
+    if symbols.is_empty() {
+        symbols = watchlist_symbols(watchlists);
+    }
+    if symbols.is_empty() {
+        return Err(error::Error::Config(
+            "no symbols to warm -- usage: pricr --warm-cache btc eth (or define [watchlists] in config)"
+                .into(),
+        ));
+    }
+
+    let summary = warm_cache(
+        session.providers(),
+        &session.provider_indices,
+        &symbols,
+        &session.currency,
+        session.pricr.context(),
+    )
+    .await;
+    println!(
+        "Cache warmed: {} entries written ({} symbols, {} providers ok, {} skipped, {} failed)",
+        summary.entries_written,
+        symbols.len(),
+        summary.providers_ok,
+        summary.providers_skipped,
+        summary.providers_failed
+    );
+
+    if summary.providers_ok == 0 {
+        return Err(error::Error::NoResults);
+    }
+
+    Ok(0)
+}
+
+/// Outcome of a `--warm-cache` run.
+#[derive(Debug, Default)]
+struct WarmCacheSummary {
+    providers_ok: usize,
+    providers_skipped: usize,
+    providers_failed: usize,
+    entries_written: usize,
+}
+
+async fn warm_cache(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    symbols: &[String],
+    currency: &str,
+    ctx: &ProviderContext,
+) -> WarmCacheSummary {
+    let writes_before = ctx.cache_write_count();
+    let mut summary = WarmCacheSummary::default();
+
+    for provider_idx in provider_indices {
+        let prov = &providers[*provider_idx];
+        info!(provider = prov.id(), symbols = ?symbols, currency = %currency, "warming cache");
+
+        match prov.get_prices(symbols, currency).await {
+            Ok(found) => {
+                info!(provider = prov.id(), count = found.len(), "cached prices");
+                summary.providers_ok += 1;
+            }
+            Err(err) if is_ignorable_price_error(&err) => {
+                info!(provider = prov.id(), error = %err, "skipping provider during cache warm-up");
+                summary.providers_skipped += 1;
+            }
+            Err(err) => {
+                warn!(provider = prov.id(), error = %err, "cache warm-up failed for provider");
+                summary.providers_failed += 1;
+            }
+        }
+    }
+
+    summary.entries_written = ctx.cache_write_count() - writes_before;
+    summary
+}
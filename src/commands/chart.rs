@@ -0,0 +1,228 @@
+//! `--chart` and `--stats` for crypto, stock, and fiat symbols.
+
+use chrono::{DateTime, NaiveDate, Utc};
+use pricr::clock::Clock;
+use pricr::error::{self, Result};
+use pricr::{calc, output, provider};
+use tracing::info;
+
+use super::history::{HistoryOutput, emit_histories, filter_histories_by_time_window};
+use super::{Session, fetch_each_symbol};
+use crate::cli::{ChartRange, SamplingArg, format_chart_range_label};
+
+const MAX_CHART_FETCH_DAYS: u32 = 36_500;
+
+/// The dates a chart covers, from `--interval`, `--start-date`, and `--end-date`.
+pub struct ChartWindow {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: NaiveDate,
+    pub label: String,
+    pub start_ts: Option<DateTime<Utc>>,
+    pub end_ts: DateTime<Utc>,
+    /// Days of history to request so the window is covered.
+    pub fetch_days: u32,
+}
+
+impl ChartWindow {
+    /// Resolve the window against `clock`; an end date in the future or a start
+    /// date after the end date is a config error.
+    pub fn resolve(
+        start_date: Option<NaiveDate>,
+        end_date: Option<NaiveDate>,
+        interval: ChartRange,
+        clock: &dyn Clock,
+    ) -> Result<Self> {
+        let end_date = resolve_chart_end_date(end_date, clock)?;
+        let start_date = start_date.or_else(|| interval.start_date(end_date));
+        if let Some(start) = start_date
+            && start > end_date
+        {
+            return Err(error::Error::Config(
+                "chart start date cannot be after chart end date".into(),
+            ));
+        }
+
+        Ok(Self {
+            start_date,
+            end_date,
+            label: format_chart_range_label(start_date, end_date, interval),
+            start_ts: start_date
+                .and_then(|d| d.and_hms_opt(0, 0, 0))
+                .map(|dt| dt.and_utc()),
+            end_ts: end_date
+                .and_hms_opt(23, 59, 59)
+                .ok_or_else(|| error::Error::Config("invalid chart end date".into()))?
+                .and_utc(),
+            fetch_days: compute_chart_fetch_days(start_date, clock),
+        })
+    }
+}
+
+/// `--chart usd eur gbp`: daily ECB rates from the first currency to the others.
+pub async fn fiat(
+    session: &Session<'_>,
+    symbols: &[String],
+    window: &ChartWindow,
+    history_output: &HistoryOutput<'_>,
+) -> Result<i32> {
+    let cli = session.cli;
+    let base = symbols[0].to_uppercase();
+    let targets: Vec<String> = symbols[1..].iter().map(|s| s.to_uppercase()).collect();
+
+    if targets.is_empty() {
+        return Err(error::Error::Config(
+            "fiat chart mode requires a base and at least one target currency -- usage: pricr --chart usd eur"
+                .into(),
+        ));
+    }
+
+    if targets.iter().any(|t| !calc::is_known_fiat(t)) {
+        return Err(error::Error::Config(
+            "fiat chart mode only supports fiat currency codes (example: usd eur gbp)".into(),
+        ));
+    }
+
+    if matches!(cli.sampling, SamplingArg::Hourly) {
+        return Err(error::Error::Config(
+            "fiat chart mode supports daily history only -- use --sampling auto or --sampling daily"
+                .into(),
+        ));
+    }
+
+    info!(
+        base = %base,
+        targets = ?targets,
+        range = %window.label,
+        start_date = ?window.start_date,
+        end_date = %window.end_date,
+        fetch_days = window.fetch_days,
+        "fetching fiat historical rates"
+    );
+
+    let fiat_provider =
+        provider::frankfurter::Frankfurter::with_context(session.pricr.context().clone())
+            .with_weekend_fill(cli.fill_weekends);
+    let mut histories = fiat_provider
+        .get_history(&base, &targets, window.fetch_days)
+        .await?;
+    filter_histories_by_time_window(&mut histories, window.start_ts, window.end_ts);
+    if histories.is_empty() {
+        return Err(error::Error::NoResults);
+    }
+
+    emit_histories(
+        &histories,
+        &window.label,
+        provider::HistoryInterval::Daily,
+        cli.history_export.as_deref(),
+        history_output,
+    )?;
+
+    Ok(0)
+}
+
+/// `--chart` for crypto and stock symbols, from the primary provider.
+pub async fn run(
+    session: &Session<'_>,
+    symbols: &[String],
+    window: &ChartWindow,
+    history_output: &HistoryOutput<'_>,
+) -> Result<i32> {
+    let cli = session.cli;
+    if cli.fill_weekends {
+        return Err(error::Error::Config(
+            "--fill-weekends only applies to fiat charts (example: pricr --chart usd eur)".into(),
+        ));
+    }
+    let prov = session.primary_provider();
+    let currency = session.currency.as_str();
+    info!(
+        provider = prov.id(),
+        symbols = ?symbols,
+        currency = %currency,
+        range = %window.label,
+        start_date = ?window.start_date,
+        end_date = %window.end_date,
+        fetch_days = window.fetch_days,
+        "fetching historical prices"
+    );
+
+    let progress = output::progress::Progress::new(symbols.len(), cli.json);
+    let mut histories = fetch_each_symbol(symbols, &progress, async |symbol| {
+        match prov
+            .get_price_history_window(
+                symbol,
+                currency,
+                window.start_ts,
+                window.end_ts,
+                cli.sampling.into(),
+            )
+            .await
+        {
+            Err(error::Error::Unsupported { .. }) => {
+                prov.get_price_history(symbol, currency, window.fetch_days, cli.sampling.into())
+                    .await
+            }
+            other => other,
+        }
+    })
+    .await?;
+    filter_histories_by_time_window(&mut histories, window.start_ts, window.end_ts);
+    if histories.is_empty() {
+        return Err(error::Error::NoResults);
+    }
+
+    emit_histories(
+        &histories,
+        &window.label,
+        cli.sampling.into(),
+        cli.history_export.as_deref(),
+        history_output,
+    )?;
+
+    Ok(0)
+}
+
+/// `--end-date`, or today; an end date after today is a config error.
+fn resolve_chart_end_date(end_date: Option<NaiveDate>, clock: &dyn Clock) -> Result<NaiveDate> {
+    let today = clock.today();
+    let end_date = end_date.unwrap_or(today);
+    if end_date > today {
+        return Err(error::Error::Config(
+            "chart end date cannot be in the future".into(),
+        ));
+    }
+    Ok(end_date)
+}
+
+fn compute_chart_fetch_days(start_date: Option<NaiveDate>, clock: &dyn Clock) -> u32 {
+    match start_date {
+        Some(start) => {
+            let days = (clock.today() - start).num_days().max(1);
+            (days as u32).min(MAX_CHART_FETCH_DAYS)
+        }
+        None => MAX_CHART_FETCH_DAYS,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricr::clock::FixedClock;
+
+    #[test]
+    fn chart_dates_follow_the_run_clock() {
+        let clock = FixedClock::at(2024, 6, 1, 23, 59, 59);
+        let today = NaiveDate::from_ymd_opt(2024, 6, 1).unwrap();
+
+        assert_eq!(resolve_chart_end_date(None, &clock).unwrap(), today);
+        assert!(matches!(
+            resolve_chart_end_date(today.succ_opt(), &clock),
+            Err(error::Error::Config(_))
+        ));
+        assert_eq!(
+            compute_chart_fetch_days(NaiveDate::from_ymd_opt(2024, 5, 1), &clock),
+            31
+        );
+    }
+}
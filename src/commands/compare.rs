@@ -0,0 +1,157 @@
+//! `--historical-compare`: prices on two dates side by side.
+
+use chrono::NaiveDate;
+use pricr::client::{
+    fetch_prices_with_provider_fallback, first_provider_result, is_ignorable_price_error,
+};
+use pricr::clock::Clock;
+use pricr::error::{self, Result};
+use pricr::{calc, output, provider};
+use tracing::info;
+
+use super::Session;
+use crate::cli::CompareDate;
+
+/// `--historical-compare DATE1 DATE2`: each symbol's price on both dates and the
+/// change between them.
+pub async fn run(
+    session: &Session<'_>,
+    symbols: &[String],
+    date1: CompareDate,
+    date2: CompareDate,
+) -> Result<i32> {
+    let cli = session.cli;
+    let clock = &*session.clock;
+    let (date1, date2) = (date1.resolve(clock)?, date2.resolve(clock)?);
+    info!(
+        providers = ?session.ordered_ids(),
+        symbols = ?symbols,
+        currency = %session.currency,
+        %date1,
+        %date2,
+        "fetching prices on two dates"
+    );
+
+    let fetch_on = |date| {
+        fetch_prices_on_date(
+            session.providers(),
+            &session.provider_indices,
+            symbols,
+            &session.currency,
+            date,
+            clock,
+        )
+    };
+    let (prices1, prices2) = tokio::join!(fetch_on(date1), fetch_on(date2));
+    let (prices1, prices2) = (prices1?, prices2?);
+    let comparisons = pair_two_date_prices(symbols, &prices1, &prices2, date1, date2);
+    if comparisons.is_empty() {
+        return Err(error::Error::NoResults);
+    }
+
+    let found: Vec<provider::CoinPrice> = prices1
+        .into_iter()
+        .filter(|price| {
+            comparisons
+                .iter()
+                .any(|c| c.symbol.eq_ignore_ascii_case(&price.symbol))
+        })
+        .collect();
+    let symbol_errors = session.unresolved_symbols(symbols, &found).await;
+
+    if cli.json {
+        output::json::print_two_date_json(&comparisons, session.json_options)?;
+    } else {
+        output::table::print_two_date_table(&comparisons, &session.table_options);
+    }
+
+    Ok(session.finish(&symbol_errors))
+}
+
+/// Fetch each symbol's price on `date` for `--historical-compare`: the current
+/// price for today, otherwise the daily close, trying providers in order per symbol.
+///
+/// Symbols no provider could price are left out; a provider failure is only
+/// returned when no symbol resolved.
+async fn fetch_prices_on_date(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    symbols: &[String],
+    currency: &str,
+    date: NaiveDate,
+    clock: &dyn Clock,
+) -> Result<Vec<provider::CoinPrice>> {
+    if date >= clock.today() {
+        return fetch_prices_with_provider_fallback(
+            providers,
+            provider_indices,
+            symbols,
+            currency,
+            false,
+        )
+        .await;
+    }
+
+    let lookups = symbols.iter().map(|symbol| {
+        first_provider_result(providers, provider_indices, "price at date", async |prov| {
+            prov.get_price_at_date(std::slice::from_ref(symbol), currency, date)
+                .await
+        })
+    });
+
+    let mut prices = Vec::new();
+    let mut last_non_ignorable_error = None;
+    for result in futures::future::join_all(lookups).await {
+        match result {
+            Ok(found) => prices.extend(found),
+            Err(err) if is_ignorable_price_error(&err) => {}
+            Err(err) => last_non_ignorable_error = Some(err),
+        }
+    }
+
+    if prices.is_empty() {
+        return Err(last_non_ignorable_error.unwrap_or(error::Error::NoResults));
+    }
+    Ok(prices)
+}
+
+/// Pair prices from two dates by symbol, in `symbols` order.
+///
+/// Symbols missing on either date, or priced in different currencies by
+/// different providers, are skipped.
+fn pair_two_date_prices(
+    symbols: &[String],
+    prices1: &[provider::CoinPrice],
+    prices2: &[provider::CoinPrice],
+    date1: NaiveDate,
+    date2: NaiveDate,
+) -> Vec<calc::TwoDateComparison> {
+    let find = |prices: &[provider::CoinPrice], symbol: &str| {
+        prices
+            .iter()
+            .find(|p| p.symbol.trim().eq_ignore_ascii_case(symbol.trim()))
+            .cloned()
+    };
+
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            let first = find(prices1, symbol)?;
+            let second = find(prices2, symbol)
+                .filter(|second| second.currency.eq_ignore_ascii_case(&first.currency))?;
+            let (change, change_pct) = calc::compute_two_date_change(first.price, second.price);
+            Some(calc::TwoDateComparison {
+                symbol: first.symbol,
+                name: first.name,
+                currency: first.currency,
+                provider: first.provider,
+                date1,
+                price1: first.price,
+                date2,
+                price2: second.price,
+                change,
+                change_pct: Some(change_pct).filter(|pct| pct.is_finite()),
+            })
+        })
+        .collect()
+}
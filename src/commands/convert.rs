@@ -0,0 +1,255 @@
+//! Amount conversion (`pricr 3.5EUR xmr`, `pricr 2.5XMR eur`) and `--convert`.
+
+use std::collections::HashMap;
+
+use pricr::client::fetch_prices_with_provider_fallback;
+use pricr::error::{self, Result};
+use pricr::provider::context::ProviderContext;
+use pricr::{calc, output, provider};
+use tracing::{info, warn};
+
+use super::Session;
+use crate::EXIT_PARTIAL_RESULTS;
+
+/// `pricr 3.5EUR xmr`: how much of each target a fiat amount buys.
+pub async fn from_fiat(
+    session: &Session<'_>,
+    fiat: &calc::FiatAmount,
+    targets: &[String],
+) -> Result<i32> {
+    let cli = session.cli;
+    if cli.chart {
+        return Err(error::Error::Config(
+            "chart mode is only available for direct symbol lookup".into(),
+        ));
+    }
+
+    if targets.is_empty() {
+        return Err(error::Error::Config(
+            "calc mode requires at least one target coin -- usage: pricr 3.5EUR xmr".into(),
+        ));
+    }
+
+    info!(
+        providers = ?session.pricr.provider_ids(),
+        amount = fiat.amount,
+        currency = %fiat.currency,
+        targets = ?targets,
+        "calc mode: fetching prices for conversion"
+    );
+    let target_refs: Vec<&str> = targets.iter().map(String::as_str).collect();
+    let conversions = session.pricr.convert(fiat, &target_refs).await?;
+
+    print_conversions(session, &conversions, targets.len())
+}
+
+/// `pricr 2.5XMR eur btc`: what a crypto amount is worth in fiat currencies and
+/// other coins.
+pub async fn from_crypto(
+    session: &Session<'_>,
+    crypto: &calc::CryptoAmount,
+    targets: &[String],
+) -> Result<i32> {
+    let cli = session.cli;
+    if cli.chart {
+        return Err(error::Error::Config(
+            "chart mode is only available for direct symbol lookup".into(),
+        ));
+    }
+
+    if targets.is_empty() {
+        return Err(error::Error::Config(
+            "calc mode requires at least one target -- usage: pricr 2.5XMR eur".into(),
+        ));
+    }
+
+    // Partition targets into fiat currencies and crypto symbols.
+    let (fiat_targets, crypto_targets): (Vec<&String>, Vec<&String>) =
+        targets.iter().partition(|t| calc::is_known_fiat(t));
+
+    info!(
+        providers = ?session.ordered_ids(),
+        amount = crypto.amount,
+        symbol = %crypto.symbol,
+        fiat_targets = ?fiat_targets,
+        crypto_targets = ?crypto_targets,
+        "calc mode (crypto source): fetching prices for conversion"
+    );
+
+    let mut conversions = Vec::new();
+    if !fiat_targets.is_empty() {
+        conversions.extend(crypto_to_fiat(session, crypto, &fiat_targets).await?);
+    }
+    if !crypto_targets.is_empty() {
+        conversions.extend(crypto_to_crypto(session, crypto, &crypto_targets).await?);
+    }
+
+    print_conversions(session, &conversions, targets.len())
+}
+
+/// Quotes for `symbols` in `currency` from the pinned provider, or the first
+/// provider that has them.
+async fn fetch_quotes(
+    session: &Session<'_>,
+    symbols: &[String],
+    currency: &str,
+) -> Result<Vec<provider::CoinPrice>> {
+    if session.cli.provider.is_some() {
+        session
+            .primary_provider()
+            .get_prices(symbols, currency)
+            .await
+    } else {
+        fetch_prices_with_provider_fallback(
+            session.providers(),
+            &session.provider_indices,
+            symbols,
+            currency,
+            false,
+        )
+        .await
+    }
+}
+
+/// Look up the source crypto price in the first fiat target, then multiply;
+/// Frankfurter supplies cross rates for the remaining fiat targets.
+async fn crypto_to_fiat(
+    session: &Session<'_>,
+    crypto: &calc::CryptoAmount,
+    fiat_targets: &[&String],
+) -> Result<Vec<calc::Conversion>> {
+    let base_fiat = fiat_targets[0].to_uppercase();
+    let prices = fetch_quotes(session, std::slice::from_ref(&crypto.symbol), &base_fiat).await?;
+    let Some(p) = prices.first() else {
+        return Ok(Vec::new());
+    };
+
+    // Direct conversion for the base fiat target.
+    let mut conversions = vec![calc::Conversion {
+        from_amount: crypto.amount,
+        from_currency: crypto.symbol.clone(),
+        to_symbol: base_fiat.clone(),
+        to_name: calc::fiat_name(&base_fiat).to_string(),
+        to_amount: crypto.amount * p.price,
+        rate: p.price,
+        provider: p.provider.clone(),
+        timestamp: session.clock.now(),
+    }];
+
+    // Cross-rate conversions for remaining fiat targets via Frankfurter.
+    if fiat_targets.len() > 1 {
+        let other_fiats: Vec<String> = fiat_targets[1..].iter().map(|s| s.to_uppercase()).collect();
+        let fiat_provider =
+            provider::frankfurter::Frankfurter::with_context(session.pricr.context().clone());
+        let rates = fiat_provider.get_rates(&base_fiat, &other_fiats).await?;
+        let base_value = crypto.amount * p.price;
+        for target in &other_fiats {
+            if let Some(&rate) = rates.get(target) {
+                conversions.push(calc::Conversion {
+                    from_amount: crypto.amount,
+                    from_currency: crypto.symbol.clone(),
+                    to_symbol: target.clone(),
+                    to_name: calc::fiat_name(target).to_string(),
+                    to_amount: base_value * rate,
+                    rate: p.price * rate,
+                    provider: format!("{} + Frankfurter/ECB", p.provider),
+                    timestamp: session.clock.now(),
+                });
+            }
+        }
+    }
+
+    Ok(conversions)
+}
+
+/// Look up the source and each target in USD and convert through the cross rate.
+async fn crypto_to_crypto(
+    session: &Session<'_>,
+    crypto: &calc::CryptoAmount,
+    crypto_targets: &[&String],
+) -> Result<Vec<calc::Conversion>> {
+    let mut all_symbols = vec![crypto.symbol.clone()];
+    all_symbols.extend(crypto_targets.iter().map(|s| s.to_string()));
+    let prices = fetch_quotes(session, &all_symbols, "USD").await?;
+
+    let Some(src_price) = prices
+        .iter()
+        .find(|p| p.symbol.eq_ignore_ascii_case(&crypto.symbol))
+        .map(|p| p.price)
+    else {
+        return Ok(Vec::new());
+    };
+
+    Ok(crypto_targets
+        .iter()
+        .filter_map(|target_sym| {
+            let tgt = prices
+                .iter()
+                .find(|p| p.symbol.eq_ignore_ascii_case(target_sym))?;
+            let cross_rate = src_price / tgt.price;
+            Some(calc::Conversion {
+                from_amount: crypto.amount,
+                from_currency: crypto.symbol.clone(),
+                to_symbol: tgt.symbol.clone(),
+                to_name: tgt.name.clone(),
+                to_amount: crypto.amount * cross_rate,
+                rate: cross_rate,
+                provider: tgt.provider.clone(),
+                timestamp: session.clock.now(),
+            })
+        })
+        .collect())
+}
+
+/// Print conversions; `--quiet` prints bare amounts and fails when a target was
+/// not converted.
+fn print_conversions(
+    session: &Session<'_>,
+    conversions: &[calc::Conversion],
+    target_count: usize,
+) -> Result<i32> {
+    let cli = session.cli;
+    if cli.quiet {
+        let amounts: Vec<f64> = conversions.iter().map(|c| c.to_amount).collect();
+        output::raw::print_values(&amounts);
+        if conversions.len() < target_count {
+            return Ok(EXIT_PARTIAL_RESULTS);
+        }
+    } else if cli.json {
+        output::json::print_conversions_json(conversions, session.json_options)?;
+    } else {
+        output::table::print_conversions_table(conversions, cli.both_rates, &session.table_options);
+    }
+    Ok(0)
+}
+
+/// Convert rows quoted in another fiat currency into `currency` (`--convert`).
+///
+/// All needed rates come from one Frankfurter request; if it fails the rows stay
+/// in their native currency.
+pub async fn convert_to_requested_currency(
+    prices: &mut [provider::CoinPrice],
+    currency: &str,
+    ctx: &ProviderContext,
+) {
+    let sources = calc::foreign_quote_currencies(prices, currency);
+    if sources.is_empty() {
+        return;
+    }
+
+    let fiat_provider = provider::frankfurter::Frankfurter::with_context(ctx.clone());
+    match fiat_provider.get_rates(currency, &sources).await {
+        Ok(rates) => {
+            // Frankfurter answers "1 requested = rate source"; conversion needs the inverse.
+            let rates: HashMap<String, f64> = rates
+                .into_iter()
+                .filter(|(_, rate)| rate.is_finite() && *rate > 0.0)
+                .map(|(code, rate)| (code.to_uppercase(), 1.0 / rate))
+                .collect();
+            calc::convert_prices(prices, currency, &rates);
+        }
+        Err(err) => {
+            warn!(error = %err, "could not fetch ECB rates for --convert; keeping native currencies");
+        }
+    }
+}
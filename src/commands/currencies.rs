@@ -0,0 +1,92 @@
+//! Currency commands: `pricr currencies` and `pricr matrix`.
+
+use std::collections::HashMap;
+
+use pricr::client::first_provider_result;
+use pricr::error::{self, Result};
+use pricr::{calc, output, provider};
+use tracing::{info, warn};
+
+use super::Session;
+
+/// `pricr currencies`: fiat and crypto quote currencies and who serves them.
+///
+/// Either probe may fail on its own; the command only fails when both do.
+pub async fn list(session: &Session<'_>) -> Result<i32> {
+    let fiat_provider =
+        provider::frankfurter::Frankfurter::with_context(session.pricr.context().clone());
+    let (fiat_rates, crypto_quotes) = tokio::join!(
+        fiat_provider.get_currencies(),
+        first_provider_result(
+            session.providers(),
+            &session.provider_indices,
+            "quote currencies",
+            async |prov| prov.get_quote_currencies().await,
+        ),
+    );
+    let (fiat_rates, crypto_quotes) = match (fiat_rates, crypto_quotes) {
+        (Err(err), Err(_)) => return Err(err),
+        (fiat_rates, crypto_quotes) => (
+            fiat_rates
+                .inspect_err(|err| warn!(error = %err, "Frankfurter currency probe failed"))
+                .ok(),
+            crypto_quotes
+                .inspect_err(|err| warn!(error = %err, "quote currency probe failed"))
+                .ok(),
+        ),
+    };
+    let currencies = calc::currency_support(fiat_rates.as_ref(), crypto_quotes.as_deref());
+
+    if session.cli.json {
+        output::json::print_currencies_json(&currencies, session.json_options)?;
+    } else {
+        output::table::print_currencies_table(&currencies, &session.table_options);
+    }
+    Ok(0)
+}
+
+/// `pricr matrix <FIAT>...`: cross rates between every pair of `currencies`.
+///
+/// Rates are fetched once per base currency; bases Frankfurter cannot serve are
+/// left out unless none can be served.
+pub async fn matrix(session: &Session<'_>, currencies: &[String]) -> Result<i32> {
+    let fiat_provider =
+        provider::frankfurter::Frankfurter::with_context(session.pricr.context().clone());
+    info!(currencies = ?currencies, "fetching forex conversion matrix");
+
+    let results = futures::future::join_all(currencies.iter().map(|base| {
+        let targets: Vec<String> = currencies
+            .iter()
+            .filter(|code| *code != base)
+            .cloned()
+            .collect();
+        let fiat_provider = &fiat_provider;
+        async move { (base, fiat_provider.get_rates(base, &targets).await) }
+    }))
+    .await;
+
+    let mut rates = HashMap::new();
+    let mut last_error = None;
+    for (base, result) in results {
+        match result {
+            Ok(found) => {
+                rates.insert(base.clone(), found);
+            }
+            Err(err) => {
+                warn!(base = %base, error = %err, "forex rates unavailable");
+                last_error = Some(err);
+            }
+        }
+    }
+    if rates.is_empty() {
+        return Err(last_error.unwrap_or(error::Error::NoResults));
+    }
+
+    let matrix = calc::conversion_matrix(currencies, &rates);
+    if session.cli.json {
+        output::json::print_conversion_matrix_json(&matrix, session.json_options)?;
+    } else {
+        output::table::print_conversion_matrix(&matrix, &session.table_options);
+    }
+    Ok(0)
+}
@@ -0,0 +1,111 @@
+//! Single-asset profiles: `pricr info` and `--details`.
+
+use pricr::client::{first_provider_result, is_ignorable_price_error};
+use pricr::error::{self, Result};
+use pricr::{output, provider};
+use tracing::info;
+
+use super::Session;
+use super::unresolved::unresolved_symbols;
+use crate::EXIT_NO_RESULTS;
+
+/// Symbols whose `--details` metadata is fetched at the same time.
+const MAX_DETAILS_CONCURRENCY: usize = 4;
+
+/// `pricr info <SYMBOL>`: the first provider's profile of one asset.
+pub async fn info(session: &Session<'_>, symbol: &str) -> Result<i32> {
+    let info = match first_provider_result(
+        session.providers(),
+        &session.provider_indices,
+        "asset info",
+        async |prov| prov.get_info(symbol, &session.currency).await,
+    )
+    .await
+    {
+        Ok(info) => info,
+        Err(error::Error::NoResults) => {
+            let symbol_errors = session.unresolved_symbols(&[symbol.to_string()], &[]).await;
+            output::table::print_symbol_errors(&symbol_errors);
+            return Ok(EXIT_NO_RESULTS);
+        }
+        Err(err) => return Err(err),
+    };
+
+    if session.cli.json {
+        output::json::print_asset_info_json(&info, session.json_options)?;
+    } else {
+        output::table::print_asset_info(&info);
+    }
+    Ok(0)
+}
+
+/// `--details`: CoinGecko metadata for each symbol.
+pub async fn run(session: &Session<'_>, symbols: &[String]) -> Result<i32> {
+    let cli = session.cli;
+    let coingecko = provider::coingecko::CoinGecko::configured(
+        session.config.coingecko.base_url.clone(),
+        session.api_keys.coingecko.clone(),
+        session.pricr.context().clone(),
+    );
+    info!(symbols = ?symbols, "fetching CoinGecko coin details");
+
+    let progress = output::progress::Progress::new(symbols.len(), cli.json);
+    let (details, missing) = fetch_coin_details(&coingecko, symbols, &progress).await?;
+    if details.is_empty() {
+        return Err(error::Error::NoResults);
+    }
+
+    let symbol_errors = unresolved_symbols(&missing, &[], &["coingecko".to_string()]);
+    if cli.json {
+        output::json::print_coin_details_json(&details, session.json_options)?;
+    } else {
+        output::table::print_coin_details(&details, &session.table_options);
+    }
+
+    Ok(session.finish(&symbol_errors))
+}
+
+/// Fetch CoinGecko metadata for each symbol, keeping input order.
+///
+/// Returns the details found and the symbols CoinGecko does not know; a provider
+/// failure is only returned when no symbol could be resolved.
+async fn fetch_coin_details(
+    coingecko: &provider::coingecko::CoinGecko,
+    symbols: &[String],
+    progress: &output::progress::Progress,
+) -> Result<(Vec<provider::CoinDetails>, Vec<String>)> {
+    use futures::StreamExt;
+
+    let results: Vec<(&String, Result<provider::CoinDetails>)> = futures::stream::iter(symbols)
+        .map(|symbol| async move {
+            let result = coingecko.get_coin_details(symbol).await;
+            progress.inc();
+            (symbol, result)
+        })
+        .buffered(MAX_DETAILS_CONCURRENCY)
+        .collect()
+        .await;
+    progress.finish();
+
+    let mut details = Vec::new();
+    let mut missing = Vec::new();
+    let mut last_non_ignorable_error = None;
+    for (symbol, result) in results {
+        match result {
+            Ok(found) => details.push(found),
+            Err(err) => {
+                if !is_ignorable_price_error(&err) {
+                    last_non_ignorable_error = Some(err);
+                }
+                missing.push(symbol.clone());
+            }
+        }
+    }
+
+    if details.is_empty()
+        && let Some(err) = last_non_ignorable_error
+    {
+        return Err(err);
+    }
+    Ok((details, missing))
+}
@@ -0,0 +1,322 @@
+//! Printing fetched or imported price histories, and `--history-import`.
+
+use std::path::Path;
+
+use pricr::error::{self, Result};
+use pricr::{calc, config, output, provider};
+use tracing::info;
+
+use crate::cli::{ChartRange, Cli, HistoryFormatArg, format_chart_range_label};
+
+/// Points per chart series without `--points`: about four per chart column.
+const DEFAULT_CHART_POINTS: usize = 4 * output::table::CHART_WIDTH as usize;
+
+/// `--history-import`: print histories saved by `--history-export`, over the
+/// dates they span.
+pub fn import(path: &Path, interval: ChartRange, history_output: &HistoryOutput) -> Result<i32> {
+    info!(path = %path.display(), "importing price history");
+    let histories = output::json::read_history_json(path)?;
+    let mut dates = histories
+        .iter()
+        .flat_map(|h| h.points.iter().map(|p| p.timestamp.date_naive()));
+    let Some(first) = dates.next() else {
+        return Err(error::Error::NoResults);
+    };
+    let (start, end) = dates.fold((first, first), |(lo, hi), d| (lo.min(d), hi.max(d)));
+
+    let range_label = format_chart_range_label(Some(start), end, interval);
+    emit_histories(
+        &histories,
+        &range_label,
+        infer_history_sampling(&histories),
+        None,
+        history_output,
+    )?;
+    Ok(0)
+}
+
+/// How fetched or imported price histories are printed.
+pub struct HistoryOutput<'a> {
+    pub json: bool,
+    /// Print JSON as aligned columns instead of one series per symbol.
+    pub wide: bool,
+    pub ascii: bool,
+    pub overlay: bool,
+    /// Mark the low and high of each chart.
+    pub annotate: bool,
+    /// Downsample each series to at most this many points before printing.
+    pub max_points: Option<usize>,
+    /// Keep one point per week or month (from `--sampling weekly|monthly`).
+    pub resample: Option<calc::resample::ResamplePeriod>,
+    /// Gap filling before downsampling; `None` picks forward fill for daily series.
+    pub fill: Option<calc::fill::FillMode>,
+    /// Evenly thin each (gap-filled) series to at most this many points when
+    /// `max_points` does not downsample it.
+    pub max_history_points: Option<usize>,
+    pub timestamps: &'a output::chart::TimestampFormat,
+    pub table_options: output::table::TableOptions,
+    pub json_options: &'a output::json::JsonOptions,
+    /// Print descriptive statistics of each series instead of charting it.
+    pub stats: bool,
+}
+
+impl<'a> HistoryOutput<'a> {
+    /// Display settings from the chart flags, with `[defaults].max_history_points`
+    /// as the fallback thinning limit.
+    ///
+    /// Charts are always downsampled to `--points`, so the thinning limit only
+    /// applies to `--json` output without `--points`.
+    pub fn from_cli(
+        cli: &Cli,
+        app_config: &config::AppConfig,
+        timestamps: &'a output::chart::TimestampFormat,
+        table_options: output::table::TableOptions,
+        json_options: &'a output::json::JsonOptions,
+    ) -> Result<Self> {
+        let max_history_points = match cli.max_history_points {
+            Some(points) => Some(points as usize),
+            None => match app_config.defaults.max_history_points {
+                Some(points) if points < 2 => {
+                    return Err(error::Error::Config(
+                        "[defaults].max_history_points must be at least 2".into(),
+                    ));
+                }
+                points => points,
+            },
+        };
+
+        Ok(Self {
+            json: cli.json,
+            wide: cli.history_format == HistoryFormatArg::Wide,
+            ascii: cli.ascii,
+            overlay: cli.overlay,
+            annotate: cli.annotate,
+            max_points: cli
+                .points
+                .map(|points| points as usize)
+                .or((!cli.json).then_some(DEFAULT_CHART_POINTS)),
+            resample: cli.sampling.resample_period(),
+            fill: cli.fill.map(Into::into),
+            max_history_points,
+            timestamps,
+            table_options,
+            json_options,
+            stats: cli.stats,
+        })
+    }
+}
+
+/// Export (when requested) and print fetched or imported price histories.
+///
+/// The export keeps every point as fetched; only the printed series are resampled or
+/// gap-filled, and downsampled to `max_points` (or else thinned to
+/// `max_history_points`).
+pub fn emit_histories(
+    histories: &[provider::PriceHistory],
+    range_label: &str,
+    sampling: provider::HistoryInterval,
+    export_path: Option<&Path>,
+    display: &HistoryOutput,
+) -> Result<()> {
+    if let Some(path) = export_path {
+        output::json::write_history_json(histories, path)?;
+        info!(path = %path.display(), count = histories.len(), "exported price history");
+    }
+
+    if display.stats {
+        let stats: Vec<calc::HistoryStats> =
+            histories.iter().filter_map(calc::history_stats).collect();
+        if display.json {
+            output::json::print_return_stats_json(&stats, display.json_options)?;
+        } else {
+            output::table::print_stats_table(&stats, &display.table_options);
+        }
+        return Ok(());
+    }
+
+    // `--sampling auto` leaves the spacing to the provider, so read it from the points.
+    let spacing = match sampling {
+        provider::HistoryInterval::Auto => infer_history_sampling(histories),
+        requested => requested,
+    };
+    let step = match spacing {
+        provider::HistoryInterval::Hourly => chrono::Duration::hours(1),
+        _ => chrono::Duration::days(1),
+    };
+    let resampled: Vec<provider::PriceHistory>;
+    let histories = match display.resample {
+        Some(period) => {
+            resampled = histories
+                .iter()
+                .map(|history| provider::PriceHistory {
+                    points: calc::resample::resample(&history.points, period),
+                    ..history.clone()
+                })
+                .collect();
+            &resampled
+        }
+        None => histories,
+    };
+
+    // Resampled series have one point per period, so there are no daily gaps to fill.
+    let fill = match display.resample {
+        Some(_) => calc::fill::FillMode::None,
+        None => display.fill.unwrap_or(match spacing {
+            provider::HistoryInterval::Daily => calc::fill::FillMode::Forward,
+            _ => calc::fill::FillMode::None,
+        }),
+    };
+    let gap_filled: Vec<provider::PriceHistory>;
+    let histories = match fill {
+        calc::fill::FillMode::Forward => {
+            gap_filled = histories
+                .iter()
+                .map(|history| provider::PriceHistory {
+                    points: calc::fill::forward_fill(&history.points, step),
+                    ..history.clone()
+                })
+                .collect();
+            &gap_filled
+        }
+        calc::fill::FillMode::None => histories,
+    };
+
+    // LTTB keeps the spikes that even thinning would drop, so the thinning limit
+    // only applies when there is no LTTB pass.
+    let downsampled: Vec<provider::PriceHistory>;
+    let histories = match (display.max_points, display.max_history_points) {
+        (Some(max_points), _) => {
+            downsampled = histories
+                .iter()
+                .map(|history| provider::PriceHistory {
+                    points: calc::downsample::lttb(&history.points, max_points),
+                    ..history.clone()
+                })
+                .collect();
+            &downsampled
+        }
+        (None, Some(max_history_points))
+            if histories
+                .iter()
+                .any(|history| history.points.len() > max_history_points) =>
+        {
+            downsampled = histories
+                .iter()
+                .map(|history| provider::PriceHistory {
+                    points: calc::downsample_uniform(&history.points, max_history_points),
+                    ..history.clone()
+                })
+                .collect();
+            &downsampled
+        }
+        _ => histories,
+    };
+
+    if display.json && display.wide {
+        let interval = match display.resample {
+            Some(calc::resample::ResamplePeriod::Weekly) => chrono::Duration::weeks(1),
+            Some(calc::resample::ResamplePeriod::Monthly) => chrono::Duration::days(28),
+            None => step,
+        };
+        let aligned = calc::align::align_histories(histories, interval);
+        output::json::print_aligned_history_json(&aligned, display.json_options)?;
+    } else if display.json {
+        output::json::print_history_json(histories, display.json_options)?;
+    } else if display.overlay && histories.len() > 1 {
+        if histories.len() > output::chart::MAX_OVERLAY_SERIES {
+            return Err(error::Error::Config(format!(
+                "--overlay supports up to {} series, got {}",
+                output::chart::MAX_OVERLAY_SERIES,
+                histories.len()
+            )));
+        }
+        output::table::print_history_overlay(
+            &cased_histories(histories, display.table_options.symbol_case),
+            range_label,
+            sampling,
+            display.resample,
+            display.timestamps,
+        );
+    } else {
+        output::table::print_history_charts(
+            &cased_histories(histories, display.table_options.symbol_case),
+            range_label,
+            sampling,
+            display.resample,
+            display.timestamps,
+            display.ascii,
+            display.annotate,
+        );
+    }
+    Ok(())
+}
+
+/// Copy `histories` with each symbol rewritten in `case` for the chart titles.
+fn cased_histories(
+    histories: &[provider::PriceHistory],
+    case: output::format::SymbolCase,
+) -> Vec<provider::PriceHistory> {
+    histories
+        .iter()
+        .map(|history| provider::PriceHistory {
+            symbol: output::format::apply_symbol_case(&history.symbol, case),
+            ..history.clone()
+        })
+        .collect()
+}
+
+/// Guess the sampling of imported histories from the spacing of their points.
+pub fn infer_history_sampling(histories: &[provider::PriceHistory]) -> provider::HistoryInterval {
+    let hourly = histories.iter().any(|history| {
+        history
+            .points
+            .windows(2)
+            .any(|pair| pair[1].timestamp - pair[0].timestamp < chrono::Duration::hours(20))
+    });
+    if hourly {
+        provider::HistoryInterval::Hourly
+    } else {
+        provider::HistoryInterval::Daily
+    }
+}
+
+pub fn filter_histories_by_time_window(
+    histories: &mut Vec<provider::PriceHistory>,
+    start: Option<chrono::DateTime<chrono::Utc>>,
+    end: chrono::DateTime<chrono::Utc>,
+) {
+    for history in histories.iter_mut() {
+        history.points.retain(|point| {
+            point.timestamp <= end && start.map(|s| point.timestamp >= s).unwrap_or(true)
+        });
+    }
+
+    histories.retain(|history| !history.points.is_empty());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infer_history_sampling_detects_hourly_spacing() {
+        let history = |step_secs: i64| provider::PriceHistory {
+            symbol: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            currency: "USD".to_string(),
+            provider: "CoinGecko".to_string(),
+            points: (0..3)
+                .map(|i| provider::PricePoint {
+                    timestamp: chrono::DateTime::from_timestamp(1_700_000_000 + i * step_secs, 0)
+                        .unwrap(),
+                    price: 1.0,
+                    filled: false,
+                })
+                .collect(),
+            events: Vec::new(),
+        };
+
+        assert_eq!(infer_history_sampling(&[history(3_600)]).as_str(), "hourly");
+        assert_eq!(infer_history_sampling(&[history(86_400)]).as_str(), "daily");
+    }
+}
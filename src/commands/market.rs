@@ -0,0 +1,350 @@
+//! Market-wide commands (`top`, `trending`, `movers`, `info`, `currencies`, `matrix`)
+//! and `--category` listings.
+
+use pricr::client::first_provider_result;
+use pricr::error::{self, Result};
+use pricr::{calc, output, provider};
+use tracing::info;
+
+use super::{Session, apply_stablecoin_filter, currencies, details};
+
+/// Number of coins listed by `pricr top` without an explicit count.
+const DEFAULT_TOP_LIMIT: usize = 10;
+/// Largest count accepted by `pricr top N` (one CoinGecko markets page).
+const MAX_TOP_LIMIT: usize = 250;
+/// Most currencies in `pricr matrix`; each one costs a Frankfurter request.
+const MAX_MATRIX_CURRENCIES: usize = 8;
+
+/// Run a market-wide command from [`resolve_market_command`].
+pub async fn run(session: &Session<'_>, command: MarketCommand) -> Result<i32> {
+    let cli = session.cli;
+    let currency = session.currency.as_str();
+    let listings = match command {
+        MarketCommand::Currencies => return currencies::list(session).await,
+        MarketCommand::Matrix(codes) => return currencies::matrix(session, &codes).await,
+        MarketCommand::Info(symbol) => return details::info(session, &symbol).await,
+        MarketCommand::Movers => return movers(session).await,
+        MarketCommand::Top(limit) => {
+            first_provider_result(
+                session.providers(),
+                &session.provider_indices,
+                "market listings",
+                async |prov| prov.get_top_listings(limit, currency).await,
+            )
+            .await?
+        }
+        MarketCommand::Trending => {
+            first_provider_result(
+                session.providers(),
+                &session.provider_indices,
+                "trending coins",
+                async |prov| prov.get_trending(currency).await,
+            )
+            .await?
+        }
+    };
+    let mut listings = apply_stablecoin_filter(
+        listings,
+        cli.exclude_stablecoins,
+        cli.include_only_stablecoins,
+    );
+    if let MarketCommand::Top(_) = command {
+        calc::sort_prices(
+            &mut listings,
+            cli.sort_by.map_or(calc::PriceSort::Rank, Into::into),
+        );
+    } else if let Some(sort_by) = cli.sort_by {
+        calc::sort_prices(&mut listings, sort_by.into());
+    }
+
+    if cli.json {
+        output::json::print_json(&listings, session.json_options)?;
+    } else {
+        output::table::print_table(&listings, &session.table_options);
+    }
+
+    Ok(0)
+}
+
+/// `pricr movers`: the biggest 24h gainers and losers among the top listings.
+async fn movers(session: &Session<'_>) -> Result<i32> {
+    let cli = session.cli;
+    let listings = first_provider_result(
+        session.providers(),
+        &session.provider_indices,
+        "market listings",
+        async |prov| {
+            prov.get_top_listings(MAX_TOP_LIMIT, &session.currency)
+                .await
+        },
+    )
+    .await?;
+    let listings = apply_stablecoin_filter(
+        listings,
+        cli.exclude_stablecoins,
+        cli.include_only_stablecoins,
+    );
+    let movers = calc::split_movers(listings, cli.top.into(), cli.min_market_cap);
+
+    if cli.json {
+        output::json::print_movers_json(&movers, session.json_options)?;
+    } else {
+        output::table::print_movers(&movers, &session.table_options);
+    }
+    Ok(0)
+}
+
+/// `--category`: the top coins in a CoinGecko category.
+pub async fn category(session: &Session<'_>, category: &str) -> Result<i32> {
+    let cli = session.cli;
+    if let Some(provider_id) = cli.provider.as_deref().map(str::trim)
+        && !provider_id.eq_ignore_ascii_case("coingecko")
+    {
+        return Err(error::Error::Config(format!(
+            "--category lists CoinGecko categories and cannot use --provider {}",
+            provider_id
+        )));
+    }
+    let coingecko = provider::coingecko::CoinGecko::configured(
+        session.config.coingecko.base_url.clone(),
+        session.api_keys.coingecko.clone(),
+        session.pricr.context().clone(),
+    );
+    info!(category = %category, limit = cli.top, "fetching CoinGecko category listings");
+
+    let listings = coingecko
+        .get_category_listings(category, cli.top.into(), &session.currency)
+        .await?;
+    let mut listings = apply_stablecoin_filter(
+        listings,
+        cli.exclude_stablecoins,
+        cli.include_only_stablecoins,
+    );
+    calc::sort_prices(
+        &mut listings,
+        cli.sort_by.map_or(calc::PriceSort::Rank, Into::into),
+    );
+
+    if cli.json {
+        output::json::print_json(&listings, session.json_options)?;
+    } else {
+        output::table::print_table(&listings, &session.table_options);
+    }
+    Ok(0)
+}
+
+/// Market-wide listing commands given as the first positional argument.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MarketCommand {
+    /// `pricr top [N]`: the N largest coins by market cap.
+    Top(usize),
+    /// `pricr trending`: currently trending coins.
+    Trending,
+    /// `pricr movers`: biggest 24h gainers and losers.
+    Movers,
+    /// `pricr info <SYMBOL>`: profile of a single coin or stock.
+    Info(String),
+    /// `pricr currencies`: quote currencies and which providers serve them.
+    Currencies,
+    /// `pricr matrix <FIAT>...`: cross rates between every pair of fiat currencies.
+    Matrix(Vec<String>),
+}
+
+/// Parse the `top [N]`, `trending`, `movers`, `info <SYMBOL>`, `currencies`, and
+/// `matrix <FIAT>...` shorthands.
+///
+/// Returns `None` when the first positional is not one of them.
+pub fn resolve_market_command(symbols: &[String]) -> Result<Option<MarketCommand>> {
+    let Some(first) = symbols.first() else {
+        return Ok(None);
+    };
+    let args = &symbols[1..];
+
+    if first.eq_ignore_ascii_case("info") {
+        return match args {
+            [symbol] if !symbol.trim().is_empty() => {
+                Ok(Some(MarketCommand::Info(symbol.trim().to_string())))
+            }
+            _ => Err(error::Error::Config(
+                "info takes exactly one symbol -- usage: pricr info btc".into(),
+            )),
+        };
+    }
+
+    if first.eq_ignore_ascii_case("matrix") {
+        let mut currencies: Vec<String> = Vec::new();
+        for code in args {
+            let code = code.trim().to_uppercase();
+            if !calc::is_known_fiat(&code) {
+                return Err(error::Error::Config(format!(
+                    "matrix only supports fiat currency codes, got '{}' -- usage: pricr matrix usd eur gbp",
+                    code
+                )));
+            }
+            if !currencies.contains(&code) {
+                currencies.push(code);
+            }
+        }
+        if !(2..=MAX_MATRIX_CURRENCIES).contains(&currencies.len()) {
+            return Err(error::Error::Config(format!(
+                "matrix takes 2 to {} currencies -- usage: pricr matrix usd eur gbp",
+                MAX_MATRIX_CURRENCIES
+            )));
+        }
+        return Ok(Some(MarketCommand::Matrix(currencies)));
+    }
+
+    if first.eq_ignore_ascii_case("top") {
+        return match args {
+            [] => Ok(Some(MarketCommand::Top(DEFAULT_TOP_LIMIT))),
+            [count] => match count.trim().parse::<usize>() {
+                Ok(limit) if (1..=MAX_TOP_LIMIT).contains(&limit) => {
+                    Ok(Some(MarketCommand::Top(limit)))
+                }
+                _ => Err(error::Error::Config(format!(
+                    "invalid top count '{}' -- usage: pricr top [N] (1-{})",
+                    count, MAX_TOP_LIMIT
+                ))),
+            },
+            _ => Err(error::Error::Config(format!(
+                "top takes a single count -- usage: pricr top [N] (1-{})",
+                MAX_TOP_LIMIT
+            ))),
+        };
+    }
+
+    let (command, usage) = if first.eq_ignore_ascii_case("trending") {
+        (MarketCommand::Trending, "pricr trending")
+    } else if first.eq_ignore_ascii_case("movers") {
+        (MarketCommand::Movers, "pricr movers --top 10")
+    } else if first.eq_ignore_ascii_case("currencies") {
+        (MarketCommand::Currencies, "pricr currencies")
+    } else {
+        return Ok(None);
+    };
+
+    if !args.is_empty() {
+        return Err(error::Error::Config(format!(
+            "{} takes no symbols -- usage: {}",
+            first.to_lowercase(),
+            usage
+        )));
+    }
+
+    Ok(Some(command))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_market_command_parses_shorthands() {
+        let tokens = |raw: &[&str]| -> Vec<String> { raw.iter().map(|s| s.to_string()).collect() };
+        let resolve = |raw: &[&str]| resolve_market_command(&tokens(raw));
+
+        assert_eq!(resolve(&["btc", "eth"]).unwrap(), None);
+        assert_eq!(resolve(&["top"]).unwrap(), Some(MarketCommand::Top(10)));
+        assert_eq!(
+            resolve(&["TOP", "25"]).unwrap(),
+            Some(MarketCommand::Top(25))
+        );
+        assert_eq!(
+            resolve(&["top", "250"]).unwrap(),
+            Some(MarketCommand::Top(250))
+        );
+        assert!(resolve(&["top", "0"]).is_err());
+        assert!(resolve(&["top", "251"]).is_err());
+        assert!(resolve(&["top", "ten"]).is_err());
+        assert!(resolve(&["top", "5", "btc"]).is_err());
+
+        assert_eq!(
+            resolve(&["trending"]).unwrap(),
+            Some(MarketCommand::Trending)
+        );
+        assert_eq!(resolve(&["Movers"]).unwrap(), Some(MarketCommand::Movers));
+        assert_eq!(
+            resolve(&["currencies"]).unwrap(),
+            Some(MarketCommand::Currencies)
+        );
+        assert!(resolve(&["movers", "btc"]).is_err());
+        assert!(resolve(&["currencies", "usd"]).is_err());
+
+        assert_eq!(
+            resolve(&["info", "btc"]).unwrap(),
+            Some(MarketCommand::Info("btc".to_string()))
+        );
+        assert!(resolve(&["info"]).is_err());
+        assert!(resolve(&["info", "btc", "eth"]).is_err());
+
+        assert_eq!(
+            resolve(&["matrix", "usd", "EUR", "usd", "jpy"]).unwrap(),
+            Some(MarketCommand::Matrix(vec![
+                "USD".to_string(),
+                "EUR".to_string(),
+                "JPY".to_string()
+            ]))
+        );
+        assert!(resolve(&["matrix", "usd"]).is_err());
+        assert!(resolve(&["matrix", "usd", "btc"]).is_err());
+        assert!(
+            resolve(&[
+                "matrix", "usd", "eur", "gbp", "jpy", "chf", "cad", "aud", "nzd", "sek"
+            ])
+            .is_err()
+        );
+    }
+
+    #[tokio::test]
+    async fn first_provider_result_falls_back_to_coinmarketcap_for_trending() {
+        use wiremock::matchers::{method, path};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/first-provider/api/v3/search/trending"))
+            .respond_with(ResponseTemplate::new(503).set_body_string("unavailable"))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/first-provider/v1/cryptocurrency/trending/latest"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "data": [{
+                    "name": "Pepe",
+                    "symbol": "PEPE",
+                    "cmc_rank": 40,
+                    "quote": { "USD": { "price": 0.00001, "percent_change_24h": 12.5 } }
+                }]
+            })))
+            .mount(&server)
+            .await;
+
+        let providers: Vec<Box<dyn provider::PriceProvider>> = vec![
+            Box::new(provider::coingecko::CoinGecko::with_base_url(format!(
+                "{}/first-provider/api/v3",
+                server.uri()
+            ))),
+            Box::new(provider::stooq::Stooq::new()),
+            Box::new(provider::coinmarketcap::CoinMarketCap::with_base_url(
+                "test-api-key".to_string(),
+                format!("{}/first-provider/v1", server.uri()),
+            )),
+        ];
+
+        let trending = first_provider_result(&providers, &[0, 1, 2], "trending coins", async |p| {
+            p.get_trending("usd").await
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(trending.len(), 1);
+        assert_eq!(trending[0].provider, "CoinMarketCap");
+
+        let err = first_provider_result(&providers, &[1], "trending coins", async |p| {
+            p.get_trending("usd").await
+        })
+        .await
+        .unwrap_err();
+        assert!(matches!(err, error::Error::NoResults));
+    }
+}
@@ -0,0 +1,183 @@
+//! `--performance` returns and `--period` changes.
+
+use pricr::client::{first_provider_result, is_ignorable_price_error};
+use pricr::clock::Clock;
+use pricr::error::{self, Result};
+use pricr::{calc, output, provider};
+use tracing::{info, warn};
+
+use super::Session;
+use crate::cli::PeriodArg;
+
+/// Days of daily history `--performance` fetches: a year plus margin for weekends
+/// and holidays at the start of the 1Y period.
+const PERFORMANCE_FETCH_DAYS: u32 = 380;
+/// Symbols whose `--performance` history is fetched at the same time.
+const MAX_PERFORMANCE_CONCURRENCY: usize = 4;
+
+/// `--performance`: returns over standard periods from a year of daily history.
+pub async fn run(session: &Session<'_>, symbols: &[String]) -> Result<i32> {
+    let cli = session.cli;
+    info!(
+        providers = ?session.ordered_ids(),
+        symbols = ?symbols,
+        currency = %session.currency,
+        fetch_days = PERFORMANCE_FETCH_DAYS,
+        "fetching performance history"
+    );
+
+    let progress = output::progress::Progress::new(symbols.len(), cli.json);
+    let (histories, missing) = fetch_performance_histories(
+        session.providers(),
+        &session.provider_indices,
+        symbols,
+        &session.currency,
+        &progress,
+    )
+    .await?;
+    let performance: Vec<calc::Performance> =
+        histories.iter().filter_map(calc::performance).collect();
+    if performance.is_empty() {
+        return Err(error::Error::NoResults);
+    }
+
+    let symbol_errors = session.unresolved_symbols(&missing, &[]).await;
+    if cli.json {
+        output::json::print_performance_json(&performance, session.json_options)?;
+    } else {
+        output::table::print_performance_table(&performance, &session.table_options);
+    }
+
+    Ok(session.finish(&symbol_errors))
+}
+
+/// Fetch daily history for `--performance`, a few symbols at a time, trying
+/// providers in order for each symbol.
+///
+/// Returns the histories in `symbols` order together with the symbols no provider
+/// could serve. A provider failure is only returned when no symbol resolved.
+async fn fetch_performance_histories(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    symbols: &[String],
+    currency: &str,
+    progress: &output::progress::Progress,
+) -> Result<(Vec<provider::PriceHistory>, Vec<String>)> {
+    use futures::StreamExt;
+
+    let results: Vec<(&String, Result<Vec<provider::PriceHistory>>)> =
+        futures::stream::iter(symbols)
+            .map(|symbol| async move {
+                let result = first_provider_result(
+                    providers,
+                    provider_indices,
+                    "performance history",
+                    async |prov| {
+                        let histories = prov
+                            .get_price_history(
+                                std::slice::from_ref(symbol),
+                                currency,
+                                PERFORMANCE_FETCH_DAYS,
+                                provider::HistoryInterval::Daily,
+                            )
+                            .await?;
+                        if histories.iter().all(|h| h.points.is_empty()) {
+                            return Err(error::Error::NoResults);
+                        }
+                        Ok(histories)
+                    },
+                )
+                .await;
+                progress.inc();
+                (symbol, result)
+            })
+            .buffered(MAX_PERFORMANCE_CONCURRENCY)
+            .collect()
+            .await;
+    progress.finish();
+
+    let mut histories = Vec::new();
+    let mut missing = Vec::new();
+    let mut last_non_ignorable_error = None;
+    for (symbol, result) in results {
+        match result {
+            Ok(found) => histories.extend(found),
+            Err(err) => {
+                if !is_ignorable_price_error(&err) {
+                    last_non_ignorable_error = Some(err);
+                }
+                missing.push(symbol.clone());
+            }
+        }
+    }
+
+    if histories.is_empty()
+        && let Some(err) = last_non_ignorable_error
+    {
+        return Err(err);
+    }
+    Ok((histories, missing))
+}
+
+/// Days of daily history fetched beyond a `--period`, so the series reaches back
+/// past weekends and holidays at its start.
+const PERIOD_FETCH_MARGIN_DAYS: u32 = 7;
+
+/// Replace each quote's change with its change over `period` (`--period`).
+///
+/// Daily history is fetched per symbol, trying the provider that served the
+/// quote first. Quotes whose history does not reach back far enough are left
+/// without a change rather than keeping a 24h one.
+pub async fn apply_period_changes(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    prices: &mut [provider::CoinPrice],
+    currency: &str,
+    period: PeriodArg,
+    clock: &dyn Clock,
+) {
+    use futures::StreamExt;
+
+    let (Some(days), Some(basis)) = (calc::period_to_days(period.as_str()), period.change_basis())
+    else {
+        return;
+    };
+    let start = clock.today() - chrono::Duration::days(i64::from(days));
+
+    let histories: Vec<Option<provider::PriceHistory>> = futures::stream::iter(prices.iter())
+        .map(|price| async move {
+            let mut order: Vec<usize> = provider_indices.to_vec();
+            order.sort_by_key(|&idx| providers[idx].name() != price.provider);
+            let result = first_provider_result(providers, &order, "period history", async |prov| {
+                let histories = prov
+                    .get_price_history(
+                        std::slice::from_ref(&price.symbol),
+                        currency,
+                        days + PERIOD_FETCH_MARGIN_DAYS,
+                        provider::HistoryInterval::Daily,
+                    )
+                    .await?;
+                histories
+                    .into_iter()
+                    .find(|h| !h.points.is_empty())
+                    .ok_or(error::Error::NoResults)
+            })
+            .await;
+            match result {
+                Ok(history) => Some(history),
+                Err(err) => {
+                    warn!(symbol = %price.symbol, error = %err, "no history for --period change");
+                    None
+                }
+            }
+        })
+        .buffered(MAX_PERFORMANCE_CONCURRENCY)
+        .collect()
+        .await;
+
+    for (price, history) in prices.iter_mut().zip(histories) {
+        price.change_24h =
+            history.and_then(|history| calc::change_since(&history.points, start, price.price));
+        price.change_basis = Some(basis);
+    }
+}
@@ -0,0 +1,41 @@
+//! `--portfolio-file`: value or allocation of a set of holdings.
+
+use std::path::Path;
+
+use pricr::error::Result;
+use pricr::{calc, output, portfolio};
+
+use super::Session;
+
+/// Price the holdings in `path` and print their value, or their allocation with
+/// `--allocation`.
+pub async fn run(session: &Session<'_>, path: &Path) -> Result<i32> {
+    let cli = session.cli;
+    let holdings = portfolio::load_portfolio(path)?;
+    let symbols: Vec<String> = holdings
+        .iter()
+        .map(|h| h.symbol.trim().to_string())
+        .collect();
+
+    let prices = session.fetch_prices(&symbols, false).await?;
+    let symbol_errors = session.unresolved_symbols(&symbols, &prices).await;
+
+    if cli.allocation {
+        let allocations = calc::portfolio_allocation(&holdings, &prices);
+        if cli.json {
+            output::json::print_allocation_json(&allocations, session.json_options)?;
+        } else {
+            output::table::print_allocation_table(&allocations, &session.table_options);
+        }
+    } else {
+        let values = portfolio::calc_portfolio_value(&holdings, &prices);
+        let totals = portfolio::portfolio_totals(&values);
+        if cli.json {
+            output::json::print_portfolio_json(&values, &totals, session.json_options)?;
+        } else {
+            output::table::print_portfolio_table(&values, &totals, &session.table_options);
+        }
+    }
+
+    Ok(session.finish(&symbol_errors))
+}
@@ -0,0 +1,171 @@
+//! The default price lookup.
+
+use pricr::client;
+use pricr::error::Result;
+use pricr::{calc, output};
+
+use super::convert::convert_to_requested_currency;
+use super::performance::apply_period_changes;
+use super::unresolved::prices_in_symbol_order;
+use super::{Session, apply_stablecoin_filter};
+use crate::EXIT_PARTIAL_RESULTS;
+use crate::cli::PeriodArg;
+
+/// Look up `symbols` and print them as a table, JSON, CSV, or bare prices.
+///
+/// `convert` rewrites rows quoted in another fiat currency into the requested one.
+pub async fn run(
+    session: &Session<'_>,
+    symbols: &[String],
+    convert: bool,
+    csv_options: output::csv::CsvOptions,
+) -> Result<i32> {
+    let cli = session.cli;
+    let currency = session.currency.as_str();
+    let prices = if cli.allow_partial {
+        client::fetch_partial_prices(session.primary_provider(), symbols, currency, cli.ohlc)
+            .await?
+    } else {
+        session.fetch_prices(symbols, cli.ohlc).await?
+    };
+    let mut symbol_errors =
+        super::unresolved::unresolved_symbols(symbols, &prices, &session.ordered_ids());
+    let mut prices = apply_stablecoin_filter(
+        prices,
+        cli.exclude_stablecoins,
+        cli.include_only_stablecoins,
+    );
+    if cli.period != PeriodArg::Hours24 && !cli.quiet {
+        apply_period_changes(
+            session.providers(),
+            &session.provider_indices,
+            &mut prices,
+            currency,
+            cli.period,
+            &*session.clock,
+        )
+        .await;
+    }
+    if convert {
+        convert_to_requested_currency(&mut prices, currency, session.pricr.context()).await;
+    }
+    if cli.quiet {
+        output::raw::print_values(&prices_in_symbol_order(symbols, &prices));
+        if !symbol_errors.is_empty() {
+            return Ok(EXIT_PARTIAL_RESULTS);
+        }
+        return Ok(0);
+    }
+    if !cli.no_suggest {
+        session.attach_suggestions(&mut symbol_errors).await;
+    }
+    if let Some(sort_by) = cli.sort_by {
+        calc::sort_prices(&mut prices, sort_by.into());
+    }
+
+    if cli.json_envelope {
+        output::json::print_price_envelope_json(&prices, &symbol_errors, session.json_options)?;
+    } else if cli.json && cli.allow_partial {
+        output::json::print_partial_prices_json(&prices, &symbol_errors, session.json_options)?;
+    } else if cli.json {
+        output::json::print_json(&prices, session.json_options)?;
+    } else if cli.csv {
+        output::csv::print_price_csv(&prices, csv_options);
+    } else if cli.ohlc {
+        output::table::print_ohlc_table(&prices, &session.table_options);
+    } else {
+        output::table::print_table(&prices, &session.table_options);
+    }
+
+    Ok(session.finish(&symbol_errors))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use pricr::client::{fetch_prices, split_symbol_pins};
+    use pricr::provider;
+
+    use crate::commands::test_support::MockProvider;
+
+    #[tokio::test]
+    async fn inline_pins_fetch_from_their_provider_in_input_order() {
+        let registry: provider::registry::ProviderRegistry = [
+            Box::new(MockProvider {
+                id: "alpha",
+                quotes: vec![("BTC", 100.0), ("AAPL", 200.0), ("BRK-B", 400.0)],
+                fail: false,
+            }) as Box<dyn provider::PriceProvider>,
+            Box::new(MockProvider {
+                id: "beta",
+                quotes: vec![("BTC", 102.0), ("BRK-B", 410.0)],
+                fail: false,
+            }),
+        ]
+        .into_iter()
+        .collect();
+        let providers = registry.providers();
+        let tokens = ["beta:brk-b", "btc@beta", "aapl", "BTC"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+
+        let (symbols, pins) = split_symbol_pins(&registry, tokens).unwrap();
+        assert_eq!(symbols, vec!["brk-b", "btc", "aapl"]);
+
+        let prices = fetch_prices(providers, &[0, 1], false, &symbols, "usd", false, &pins)
+            .await
+            .unwrap();
+        let rows: Vec<(&str, &str)> = prices
+            .iter()
+            .map(|p| (p.symbol.as_str(), p.provider.as_str()))
+            .collect();
+        assert_eq!(
+            rows,
+            vec![("BRK-B", "beta"), ("BTC", "beta"), ("AAPL", "alpha")]
+        );
+
+        let err = split_symbol_pins(&registry, vec!["kraken:btc".to_string()]).unwrap_err();
+        assert!(
+            err.to_string().contains("valid providers: alpha, beta"),
+            "{err}"
+        );
+    }
+
+    #[tokio::test]
+    async fn fetch_prices_honors_symbol_provider_overrides() {
+        let providers: Vec<Box<dyn provider::PriceProvider>> = vec![
+            Box::new(MockProvider {
+                id: "alpha",
+                quotes: vec![("BTC", 100.0), ("THIN", 1.0)],
+                fail: false,
+            }),
+            Box::new(MockProvider {
+                id: "beta",
+                quotes: vec![("BTC", 102.0), ("THIN", 2.0)],
+                fail: false,
+            }),
+        ];
+        let symbols = vec!["thin".to_string(), "btc".to_string()];
+        let symbol_providers = HashMap::from([("THIN".to_string(), 1)]);
+
+        let prices = fetch_prices(
+            &providers,
+            &[0, 1],
+            false,
+            &symbols,
+            "usd",
+            false,
+            &symbol_providers,
+        )
+        .await
+        .unwrap();
+
+        let rows: Vec<(&str, &str)> = prices
+            .iter()
+            .map(|p| (p.symbol.as_str(), p.provider.as_str()))
+            .collect();
+        assert_eq!(rows, vec![("THIN", "beta"), ("BTC", "alpha")]);
+    }
+}
@@ -0,0 +1,56 @@
+//! Ticker search (`--search` or `pricr search <query>`).
+
+use pricr::error::{self, Result};
+use pricr::{calc, output};
+
+use super::Session;
+use crate::cli::Cli;
+
+/// Search tickers matching `query` across providers, narrowed by `filter`.
+pub async fn run(session: &Session<'_>, query: &str, filter: &calc::SearchFilter) -> Result<i32> {
+    let cli = session.cli;
+    if query.is_empty() {
+        return Err(error::Error::Config(
+            "search mode requires a query -- usage: pricr --search apple".into(),
+        ));
+    }
+
+    let matches = session
+        .pricr
+        .search_filtered(query, cli.search_limit as usize, filter)
+        .await?;
+
+    if cli.json {
+        output::json::print_ticker_matches_json(&matches, filter, session.json_options)?;
+    } else {
+        output::table::print_ticker_matches_table(&matches, &session.table_options);
+    }
+
+    Ok(0)
+}
+
+pub fn resolve_search_query(cli: &Cli) -> Option<String> {
+    if let Some(query) = cli.search.as_deref() {
+        return Some(query.trim().to_string());
+    }
+
+    if !cli.symbols.is_empty() && cli.symbols[0].eq_ignore_ascii_case("search") {
+        let mut tokens: Vec<String> = cli
+            .symbols
+            .iter()
+            .skip(1)
+            .map(|s| s.trim().to_string())
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        if let Some(first) = tokens.first()
+            && first.eq_ignore_ascii_case("search")
+        {
+            tokens.remove(0);
+        }
+
+        return Some(tokens.join(" ").trim().to_string());
+    }
+
+    None
+}
@@ -0,0 +1,358 @@
+//! Gathering and rewriting the symbols a run looks up.
+
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+use pricr::client::{MAX_SEARCH_LIMIT, is_ignorable_search_error, search_tickers_across_providers};
+use pricr::error::{self, Result};
+use pricr::{calc, output, provider};
+use tracing::{debug, warn};
+
+use super::Session;
+use crate::cli::Cli;
+
+/// The positional symbols with `-` replaced by stdin, `--symbol-file` appended,
+/// and `--normalize-symbols` applied. Watchlists are not expanded yet.
+pub fn collect_symbol_input(cli: &Cli) -> Result<Vec<String>> {
+    let mut symbols = cli.symbols.clone();
+    if let Some(pos) = symbols.iter().position(|symbol| symbol == "-") {
+        let stdin_symbols = read_symbol_file(Path::new("-"))?;
+        symbols.splice(pos..=pos, stdin_symbols);
+    }
+
+    if let Some(path) = cli.symbol_file.as_deref() {
+        symbols.extend(read_symbol_file(path)?);
+    }
+
+    if cli.normalize_symbols {
+        symbols = symbols
+            .iter()
+            .map(|symbol| normalize_symbol_input(symbol))
+            .filter(|symbol| !symbol.is_empty())
+            .collect();
+    }
+    Ok(symbols)
+}
+
+/// `--resolve`: the id each provider would query for each symbol.
+pub fn resolve(session: &Session<'_>, symbols: &[String]) -> Result<i32> {
+    let providers = session.providers();
+    let resolutions: Vec<provider::SymbolResolution> = symbols
+        .iter()
+        .flat_map(|symbol| {
+            session
+                .provider_indices
+                .iter()
+                .map(|&idx| provider::SymbolResolution {
+                    symbol: symbol.clone(),
+                    provider: providers[idx].id().to_string(),
+                    resolved: providers[idx].resolve_symbol(symbol),
+                })
+        })
+        .collect();
+
+    if session.cli.json {
+        output::json::print_symbol_resolutions_json(&resolutions, session.json_options)?;
+    } else {
+        output::table::print_symbol_resolutions_table(&resolutions, &session.table_options);
+    }
+    Ok(0)
+}
+
+/// Read whitespace/newline-delimited symbol tokens from a file, or stdin for `-`.
+///
+/// Text after `#` is a comment. `@watchlist` tokens are returned as-is
+/// for [`expand_symbol_tokens`] to resolve.
+pub fn read_symbol_file(path: &Path) -> Result<Vec<String>> {
+    let raw = if path == Path::new("-") {
+        std::io::read_to_string(std::io::stdin())
+    } else {
+        std::fs::read_to_string(path)
+    }
+    .map_err(|err| {
+        error::Error::Io(format!(
+            "failed to read symbol file '{}': {}",
+            path.display(),
+            err
+        ))
+    })?;
+
+    Ok(parse_symbol_list(&raw))
+}
+
+/// Split whitespace/newline-delimited symbols, dropping blank lines and `#` comments.
+pub fn parse_symbol_list(raw: &str) -> Vec<String> {
+    raw.lines()
+        .map(|line| line.split('#').next().unwrap_or_default())
+        .flat_map(str::split_whitespace)
+        .map(str::to_string)
+        .collect()
+}
+
+/// Uppercase `input` and drop whitespace and hyphens (`--normalize-symbols`).
+///
+/// `=` and `.` are kept, so futures (`GC=F`) and exchange tickers (`CW8.PA`)
+/// survive. Dropping a hyphen changes tickers like `BTC-USD` or `BRK-B`, so it
+/// is warned about.
+pub fn normalize_symbol_input(input: &str) -> String {
+    let normalized: String = input
+        .chars()
+        .filter(|c| !c.is_whitespace() && *c != '-')
+        .collect::<String>()
+        .to_uppercase();
+    if input.contains('-') {
+        warn!(
+            symbol = input,
+            normalized = %normalized,
+            "removed '-' from symbol; omit --normalize-symbols if it is part of the ticker"
+        );
+    }
+    if normalized != input {
+        debug!(symbol = input, normalized = %normalized, "normalized symbol");
+    }
+    normalized
+}
+
+/// Replace plain tickers with their listing on `exchange` (`--exchange`).
+///
+/// Each symbol is looked up with ticker search; symbols that already carry a
+/// suffix (`sap.de`), indices, and futures pass through unchanged. When search
+/// finds no listing, the exchange's Yahoo suffix is appended if it has one.
+/// Several listings are an error naming the candidates.
+pub async fn resolve_exchange_listings(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    symbols: Vec<String>,
+    exchange: &str,
+) -> Result<Vec<String>> {
+    let suffix = provider::yahoo::exchange_suffix(exchange);
+    let mut resolved = Vec::with_capacity(symbols.len());
+
+    for symbol in symbols {
+        let ticker = symbol.trim();
+        if ticker.contains(['.', '^', '=']) {
+            resolved.push(symbol);
+            continue;
+        }
+
+        let matches = match search_tickers_across_providers(
+            providers,
+            provider_indices,
+            ticker,
+            MAX_SEARCH_LIMIT,
+            None,
+            &calc::SearchFilter::default(),
+        )
+        .await
+        {
+            Ok(matches) => matches,
+            Err(err) if suffix.is_some() || is_ignorable_search_error(&err) => {
+                debug!(symbol = %ticker, error = %err, "no search results for --exchange");
+                Vec::new()
+            }
+            Err(err) => return Err(err),
+        };
+
+        match calc::exchange_listings(&matches, ticker, exchange, suffix).as_slice() {
+            [listing] => {
+                debug!(symbol = %ticker, listing = %listing.symbol, exchange, "resolved exchange listing");
+                resolved.push(listing.symbol.clone());
+            }
+            [] => match suffix {
+                Some(suffix) => resolved.push(format!("{}{}", ticker.to_uppercase(), suffix)),
+                None => {
+                    return Err(error::Error::SymbolNotFound {
+                        provider: exchange.to_uppercase(),
+                        symbol: ticker.to_uppercase(),
+                    });
+                }
+            },
+            listings => {
+                let candidates: Vec<String> = listings
+                    .iter()
+                    .map(|listing| format!("{} ({})", listing.symbol, listing.exchange))
+                    .collect();
+                return Err(error::Error::Config(format!(
+                    "'{}' has several listings on {}: {} -- pass one of them as the symbol instead",
+                    ticker.to_uppercase(),
+                    exchange,
+                    candidates.join(", ")
+                )));
+            }
+        }
+    }
+
+    Ok(resolved)
+}
+
+/// Symbols to prefetch when `--warm-cache` is given no positional symbols.
+pub fn watchlist_symbols(watchlists: &HashMap<String, Vec<String>>) -> Vec<String> {
+    let mut names: Vec<&String> = watchlists.keys().collect();
+    names.sort();
+
+    let mut seen = HashSet::new();
+    let mut symbols = Vec::new();
+    for name in names {
+        for symbol in &watchlists[name] {
+            let symbol = symbol.trim();
+            if !symbol.is_empty() && seen.insert(symbol.to_uppercase()) {
+                symbols.push(symbol.to_string());
+            }
+        }
+    }
+    symbols
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use pricr::client::expand_symbol_tokens;
+    use std::io::Write;
+
+    fn watchlists_for_tests() -> HashMap<String, Vec<String>> {
+        HashMap::from([
+            (
+                "commodities".to_string(),
+                vec!["GC=F".to_string(), "SI=F".to_string(), "CL=F".to_string()],
+            ),
+            (
+                "metals".to_string(),
+                vec!["GC=F".to_string(), "SI=F".to_string()],
+            ),
+        ])
+    }
+
+    #[test]
+    fn watchlist_symbols_merges_all_watchlists_without_duplicates() {
+        let symbols = watchlist_symbols(&watchlists_for_tests());
+
+        assert_eq!(symbols, vec!["GC=F", "SI=F", "CL=F"]);
+    }
+
+    #[test]
+    fn normalize_symbol_input_uppercases_and_strips_spaces_and_hyphens() {
+        assert_eq!(normalize_symbol_input("  btc "), "BTC");
+        assert_eq!(normalize_symbol_input("B t C"), "BTC");
+        assert_eq!(normalize_symbol_input("EtH"), "ETH");
+        assert_eq!(normalize_symbol_input("btc-eth"), "BTCETH");
+        assert_eq!(normalize_symbol_input("brk-b"), "BRKB");
+    }
+
+    #[test]
+    fn normalize_symbol_input_keeps_futures_and_exchange_suffixes() {
+        assert_eq!(normalize_symbol_input("gc=f"), "GC=F");
+        assert_eq!(normalize_symbol_input(" cw8.pa"), "CW8.PA");
+        assert_eq!(normalize_symbol_input("vwce .de"), "VWCE.DE");
+        assert_eq!(normalize_symbol_input("@metals"), "@METALS");
+    }
+
+    #[test]
+    fn read_symbol_file_skips_comments_and_expands_watchlists() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(
+            file,
+            "# long-term holdings\nbtc eth\n\n  # metals\n@metals\naapl\tmsft"
+        )
+        .unwrap();
+
+        let tokens = read_symbol_file(file.path()).unwrap();
+        assert_eq!(tokens, vec!["btc", "eth", "@metals", "aapl", "msft"]);
+
+        let mut raw = vec!["sol".to_string()];
+        raw.extend(tokens);
+        let expanded = expand_symbol_tokens(&raw, &watchlists_for_tests()).unwrap();
+        assert_eq!(
+            expanded,
+            vec!["sol", "btc", "eth", "GC=F", "SI=F", "aapl", "msft"]
+        );
+    }
+
+    #[test]
+    fn parse_symbol_list_drops_blank_lines_and_comments() {
+        let raw = "\n# header\nbtc  eth # majors\n\n\t\n^GSPC\n   #indented comment\nGC=F\n";
+
+        assert_eq!(parse_symbol_list(raw), vec!["btc", "eth", "^GSPC", "GC=F"]);
+        assert!(parse_symbol_list("# only comments\n\n").is_empty());
+    }
+
+    #[test]
+    fn read_symbol_file_reports_unreadable_file_as_config_error() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let err = read_symbol_file(&dir.path().join("missing.txt")).unwrap_err();
+
+        match err {
+            error::Error::Io(message) => {
+                assert!(message.contains("failed to read symbol file"));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn exchange_listings_resolve_through_search_or_suffix() {
+        use wiremock::matchers::{method, path, query_param};
+        use wiremock::{Mock, MockServer, ResponseTemplate};
+
+        let server = MockServer::start().await;
+        Mock::given(method("GET"))
+            .and(path("/exchange/v1/finance/search"))
+            .and(query_param("q", "sap"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "quotes": [
+                    { "symbol": "SAP", "shortname": "SAP SE", "exchDisp": "NYSE", "typeDisp": "Equity" },
+                    { "symbol": "SAP.DE", "shortname": "SAP SE", "exchDisp": "XETRA", "typeDisp": "Equity" },
+                    { "symbol": "SAP.F", "shortname": "SAP SE", "exchDisp": "Frankfurt", "typeDisp": "Equity" },
+                    { "symbol": "SAPA.F", "shortname": "SAP SE", "exchDisp": "Frankfurt", "typeDisp": "Equity" }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/exchange/v1/finance/search"))
+            .and(query_param("q", "bas"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "quotes": [
+                    { "symbol": "BAS.DE", "shortname": "BASF SE", "exchDisp": "Germany", "typeDisp": "Equity" },
+                    { "symbol": "BAS.F", "shortname": "BASF SE", "exchDisp": "Germany", "typeDisp": "Equity" }
+                ]
+            })))
+            .mount(&server)
+            .await;
+        Mock::given(method("GET"))
+            .and(path("/exchange/v1/finance/search"))
+            .and(query_param("q", "vod"))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "quotes": []
+            })))
+            .mount(&server)
+            .await;
+
+        let providers: Vec<Box<dyn provider::PriceProvider>> = vec![Box::new(
+            provider::yahoo::YahooFinance::with_base_url(format!("{}/exchange", server.uri())),
+        )];
+        let symbols = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>();
+
+        let resolved =
+            resolve_exchange_listings(&providers, &[0], symbols(&["sap", "bmw.de"]), "XETRA")
+                .await
+                .unwrap();
+        assert_eq!(resolved, vec!["SAP.DE", "bmw.de"]);
+
+        let resolved = resolve_exchange_listings(&providers, &[0], symbols(&["vod"]), "lse")
+            .await
+            .unwrap();
+        assert_eq!(resolved, vec!["VOD.L"]);
+
+        let err = resolve_exchange_listings(&providers, &[0], symbols(&["vod"]), "Nowhere")
+            .await
+            .unwrap_err();
+        assert!(matches!(err, error::Error::SymbolNotFound { .. }));
+
+        let err = resolve_exchange_listings(&providers, &[0], symbols(&["bas"]), "Germany")
+            .await
+            .unwrap_err();
+        let message = err.to_string();
+        assert!(message.contains("BAS.DE (Germany)"), "{message}");
+        assert!(message.contains("BAS.F (Germany)"), "{message}");
+    }
+}
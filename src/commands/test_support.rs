@@ -0,0 +1,76 @@
+//! Fixtures shared by command handler tests.
+
+use pricr::error::Result;
+use pricr::{error, provider};
+
+pub fn price_for_tests(symbol: &str) -> provider::CoinPrice {
+    provider::CoinPrice {
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        price: 1.0,
+        change_24h: None,
+        market_cap: None,
+        open_24h: None,
+        high_24h: None,
+        low_24h: None,
+        market_state: None,
+        change_basis: None,
+        rank: None,
+        fundamentals: None,
+        native_price: None,
+        native_currency: None,
+        currency: "USD".to_string(),
+        provider: "Test".to_string(),
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+/// In-memory provider quoting a fixed price per symbol, or failing every lookup.
+pub struct MockProvider {
+    pub id: &'static str,
+    pub quotes: Vec<(&'static str, f64)>,
+    pub fail: bool,
+}
+
+#[async_trait::async_trait]
+impl provider::PriceProvider for MockProvider {
+    fn name(&self) -> &str {
+        self.id
+    }
+
+    fn id(&self) -> &str {
+        self.id
+    }
+
+    fn capabilities(&self) -> provider::ProviderCapabilities {
+        provider::ProviderCapabilities {
+            api_key: provider::ApiKeyStatus::NotRequired,
+            features: vec![provider::Feature::Prices],
+            asset_classes: vec![provider::AssetClass::Crypto],
+            base_url: String::new(),
+        }
+    }
+
+    async fn get_prices(
+        &self,
+        symbols: &[String],
+        _currency: &str,
+    ) -> Result<Vec<provider::CoinPrice>> {
+        if self.fail {
+            return Err(error::Error::Api(format!("{} is down", self.id)));
+        }
+        Ok(symbols
+            .iter()
+            .filter_map(|symbol| {
+                let (_, value) = self
+                    .quotes
+                    .iter()
+                    .find(|(s, _)| s.eq_ignore_ascii_case(symbol))?;
+                let mut price = price_for_tests(&symbol.to_uppercase());
+                price.price = *value;
+                price.provider = self.id.to_string();
+                Some(price)
+            })
+            .collect())
+    }
+}
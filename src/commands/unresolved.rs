@@ -0,0 +1,154 @@
+//! Reporting symbols no provider could price, with "did you mean" suggestions.
+
+use std::collections::HashMap;
+
+use pricr::client::search_tickers_across_providers;
+use pricr::{calc, provider};
+
+/// Max "did you mean" suggestions shown per unresolved symbol.
+const MAX_SYMBOL_SUGGESTIONS: usize = 3;
+
+/// Only plain alphabetic tokens (e.g. `bitcon`) are worth a search; `GC=F` or `CW8.PA` are not.
+fn is_suggestible_symbol(symbol: &str) -> bool {
+    !symbol.is_empty() && symbol.chars().all(|c| c.is_ascii_alphabetic())
+}
+
+/// Fill in ticker search suggestions for unresolved symbols, one search per symbol.
+pub async fn attach_symbol_suggestions(
+    providers: &[Box<dyn provider::PriceProvider>],
+    provider_indices: &[usize],
+    errors: &mut [provider::SymbolError],
+) {
+    let searches = errors.iter().map(|err| async {
+        if !is_suggestible_symbol(&err.symbol) {
+            return Vec::new();
+        }
+        search_tickers_across_providers(
+            providers,
+            provider_indices,
+            &err.symbol,
+            MAX_SYMBOL_SUGGESTIONS,
+            None,
+            &calc::SearchFilter::default(),
+        )
+        .await
+        .unwrap_or_default()
+    });
+    let results = futures::future::join_all(searches).await;
+
+    for (err, matches) in errors.iter_mut().zip(results) {
+        err.suggestions = matches
+            .into_iter()
+            .filter(|m| !m.symbol.eq_ignore_ascii_case(&err.symbol))
+            .map(|m| provider::SymbolSuggestion {
+                symbol: m.symbol,
+                name: m.name,
+            })
+            .collect();
+    }
+}
+
+pub fn unresolved_symbols(
+    symbols: &[String],
+    prices: &[provider::CoinPrice],
+    tried: &[String],
+) -> Vec<provider::SymbolError> {
+    let mut available: HashMap<String, usize> = HashMap::new();
+    for price in prices {
+        *available
+            .entry(price.symbol.trim().to_uppercase())
+            .or_default() += 1;
+    }
+
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            let key = symbol.trim().to_uppercase();
+            match available.get_mut(&key) {
+                Some(count) if *count > 0 => {
+                    *count -= 1;
+                    None
+                }
+                _ => Some(provider::SymbolError {
+                    symbol: key,
+                    tried: tried.to_vec(),
+                    suggestions: Vec::new(),
+                }),
+            }
+        })
+        .collect()
+}
+
+/// Pick one price per requested symbol, in input order, for `--quiet` output.
+///
+/// Symbols without a price are skipped; repeated symbols use repeated quotes.
+pub fn prices_in_symbol_order(symbols: &[String], prices: &[provider::CoinPrice]) -> Vec<f64> {
+    let mut used = vec![false; prices.len()];
+
+    symbols
+        .iter()
+        .filter_map(|symbol| {
+            let key = symbol.trim();
+            let idx = prices.iter().enumerate().position(|(idx, price)| {
+                !used[idx] && price.symbol.trim().eq_ignore_ascii_case(key)
+            })?;
+            used[idx] = true;
+            Some(prices[idx].price)
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::test_support::price_for_tests;
+
+    #[test]
+    fn unresolved_symbols_reports_missing_inputs_with_tried_providers() {
+        let symbols = vec![
+            "btc".to_string(),
+            "typoedcoin".to_string(),
+            "eth".to_string(),
+        ];
+        let prices = vec![price_for_tests("BTC"), price_for_tests("ETH")];
+        let tried = vec!["coingecko".to_string(), "yahoo".to_string()];
+
+        let errors = unresolved_symbols(&symbols, &prices, &tried);
+
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].symbol, "TYPOEDCOIN");
+        assert_eq!(errors[0].tried, tried);
+    }
+
+    #[test]
+    fn is_suggestible_symbol_accepts_only_alphabetic_tokens() {
+        assert!(is_suggestible_symbol("BITCON"));
+        assert!(!is_suggestible_symbol("GC=F"));
+        assert!(!is_suggestible_symbol("CW8.PA"));
+        assert!(!is_suggestible_symbol(""));
+    }
+
+    #[test]
+    fn prices_in_symbol_order_follows_input_and_skips_missing() {
+        let symbols: Vec<String> = ["eth", "typoedcoin", "btc", "eth"]
+            .into_iter()
+            .map(String::from)
+            .collect();
+        let mut btc = price_for_tests("BTC");
+        btc.price = 97_000.0;
+        let mut eth = price_for_tests("ETH");
+        eth.price = 3_500.0;
+
+        let values = prices_in_symbol_order(&symbols, &[btc, eth.clone(), eth]);
+
+        assert_eq!(values, vec![3_500.0, 97_000.0, 3_500.0]);
+    }
+
+    #[test]
+    fn unresolved_symbols_is_empty_when_everything_resolved() {
+        let symbols = vec!["btc".to_string()];
+        let prices = vec![price_for_tests("BTC")];
+
+        assert!(unresolved_symbols(&symbols, &prices, &[]).is_empty());
+    }
+}
@@ -0,0 +1,129 @@
+//! `--watch`: refresh a price table until the user quits.
+
+use pricr::error::Result;
+use pricr::{output, watch};
+use ratatui::crossterm;
+
+use super::unresolved::unresolved_symbols;
+use super::{Session, apply_stablecoin_filter};
+
+/// Redraw the price table every `interval_secs` until `q`, `Esc`, or Ctrl-C, or
+/// until `--watch-count` refreshes.
+pub async fn run(session: &Session<'_>, symbols: &[String], interval_secs: u64) -> Result<i32> {
+    let cli = session.cli;
+    let quit_listener = spawn_quit_listener();
+    // Raw mode disables the tty's newline translation, so emit explicit carriage returns.
+    let newline = if quit_listener.is_some() {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let (_raw_mode, quit_rx) = match quit_listener {
+        Some((guard, rx)) => (Some(guard), Some(rx)),
+        None => (None, None),
+    };
+    let stop = async move {
+        match quit_rx {
+            Some(rx) => {
+                let _ = rx.await;
+            }
+            None => {
+                let _ = tokio::signal::ctrl_c().await;
+            }
+        }
+    };
+
+    let table_options = &session.table_options;
+    watch::run(
+        std::time::Duration::from_secs(interval_secs),
+        cli.watch_count,
+        stop,
+        |_| async move {
+            let frame = render_watch_frame(session, symbols, table_options).await;
+            print!("{}", frame.replace('\n', newline));
+            let _ = std::io::Write::flush(&mut std::io::stdout());
+        },
+    )
+    .await;
+
+    Ok(0)
+}
+
+/// Restores the terminal's cooked mode when dropped.
+struct RawModeGuard;
+
+impl Drop for RawModeGuard {
+    fn drop(&mut self) {
+        let _ = crossterm::terminal::disable_raw_mode();
+    }
+}
+
+/// Listen for `q`, `Esc`, or Ctrl-C on an interactive terminal.
+///
+/// Returns `None` when stdin is not a terminal or raw mode is unavailable.
+fn spawn_quit_listener() -> Option<(RawModeGuard, tokio::sync::oneshot::Receiver<()>)> {
+    use crossterm::event::{self, Event, KeyCode, KeyEventKind, KeyModifiers};
+    use std::io::IsTerminal;
+
+    if !std::io::stdin().is_terminal() || crossterm::terminal::enable_raw_mode().is_err() {
+        return None;
+    }
+
+    let (tx, rx) = tokio::sync::oneshot::channel();
+    std::thread::spawn(move || {
+        loop {
+            if !event::poll(std::time::Duration::from_millis(100)).unwrap_or(false) {
+                continue;
+            }
+            let Ok(Event::Key(key)) = event::read() else {
+                continue;
+            };
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+            let quit = matches!(key.code, KeyCode::Char('q') | KeyCode::Esc)
+                || (key.code == KeyCode::Char('c')
+                    && key.modifiers.contains(KeyModifiers::CONTROL));
+            if quit {
+                let _ = tx.send(());
+                break;
+            }
+        }
+    });
+
+    Some((RawModeGuard, rx))
+}
+
+/// One refresh: the update time, the price table after the stablecoin filters, and
+/// any unresolved symbols.
+async fn render_watch_frame(
+    session: &Session<'_>,
+    symbols: &[String],
+    table_options: &output::table::TableOptions,
+) -> String {
+    let mut frame = String::from(watch::CLEAR_SCREEN);
+    frame.push_str(&format!(
+        "Updated: {}  (press q to quit)\n",
+        table_options.tz.format(session.clock.now(), "%H:%M:%S")
+    ));
+
+    match session.fetch_prices(symbols, false).await {
+        Ok(prices) => {
+            let symbol_errors = unresolved_symbols(symbols, &prices, &session.ordered_ids());
+            let prices = apply_stablecoin_filter(
+                prices,
+                session.cli.exclude_stablecoins,
+                session.cli.include_only_stablecoins,
+            );
+            frame.push_str(&output::table::render_price_table(&prices, table_options));
+            frame.push('\n');
+            for line in output::table::render_symbol_errors(&symbol_errors) {
+                frame.push_str(&line);
+                frame.push('\n');
+            }
+        }
+        Err(err) => frame.push_str(&format!("Error: {}\n", err)),
+    }
+
+    frame
+}
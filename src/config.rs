@@ -1,11 +1,12 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::ErrorKind;
 use std::path::{Path, PathBuf};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize, Serializer};
 
 use crate::error::{Error, Result};
+use crate::provider::coingecko::ApiTier;
 
 /// Default fiat currency for price display.
 pub const DEFAULT_CURRENCY: &str = "usd";
@@ -13,31 +14,141 @@ pub const DEFAULT_CURRENCY: &str = "usd";
 /// File name used in the XDG config directory.
 pub const CONFIG_FILE_NAME: &str = "pricr.toml";
 
-/// Application configuration loaded from `$XDG_CONFIG_HOME/pricr.toml`
-/// or `~/.config/pricr.toml`.
-#[derive(Debug, Clone, Default, Deserialize)]
+/// System-wide config loaded before the user's file.
+pub const SYSTEM_CONFIG_PATH: &str = "/etc/pricr/pricr.toml";
+
+/// Application configuration loaded from `/etc/pricr/pricr.toml`, then
+/// `$XDG_CONFIG_HOME/pricr.toml` or `~/.config/pricr.toml`.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct AppConfig {
     pub defaults: DefaultsConfig,
     pub coinmarketcap: CoinMarketCapConfig,
+    pub coingecko: CoinGeckoConfig,
+    pub network: NetworkConfig,
+    pub cache: CacheConfig,
+    #[serde(serialize_with = "sorted_map")]
     pub watchlists: HashMap<String, Vec<String>>,
+    /// Symbol to provider id, forcing that symbol's lookups to one provider.
+    #[serde(serialize_with = "sorted_map")]
+    pub symbol_providers: HashMap<String, String>,
 }
 
+/// Placeholder printed instead of API keys by `pricr config show`.
+pub const MASKED_SECRET: &str = "***";
+
 /// General defaults used when CLI flags are not provided.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct DefaultsConfig {
     pub currency: Option<String>,
     pub provider_order: Option<Vec<String>>,
+    /// Chart `--interval` used when the flag is omitted (e.g. `6M` or `90d`).
+    pub chart_interval: Option<String>,
+    /// strftime pattern for chart dates (overridden by `--date-format`).
+    pub date_format: Option<String>,
+    /// `User-Agent` sent to providers.
+    pub user_agent: Option<String>,
+    /// Most points kept per `--json` history series without `--points`
+    /// (overridden by `--max-history-points`).
+    pub max_history_points: Option<usize>,
+    /// Convert quotes in another currency into the requested one (same as `--convert`).
+    pub convert: Option<bool>,
 }
 
 /// CoinMarketCap provider-specific configuration.
-#[derive(Debug, Clone, Default, Deserialize)]
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
 #[serde(default)]
 pub struct CoinMarketCapConfig {
     pub api_key: Option<String>,
 }
 
+/// CoinGecko provider-specific configuration.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CoinGeckoConfig {
+    /// Override the API base URL (e.g. a proxy or a local mock server).
+    pub base_url: Option<String>,
+    /// Demo or pro API key (overridden by `COINGECKO_API_KEY`).
+    pub api_key: Option<String>,
+    /// Plan the key belongs to; `pro` keys also switch to the pro API host.
+    pub api_tier: Option<ApiTier>,
+}
+
+/// HTTP behavior shared by all providers.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct NetworkConfig {
+    /// Longest `Retry-After` delay (seconds) to wait out before retrying a 429 once.
+    pub max_retry_after_secs: Option<u64>,
+}
+
+/// On-disk response cache settings.
+#[derive(Debug, Clone, Default, Deserialize, Serialize)]
+#[serde(default)]
+pub struct CacheConfig {
+    /// Most cache files to keep per provider; older entries are pruned first.
+    pub max_entries: Option<usize>,
+}
+
+impl AppConfig {
+    /// Copy of the config with API keys replaced by [`MASKED_SECRET`], safe to print.
+    pub fn masked(&self) -> AppConfig {
+        let mask = |key: &Option<String>| key.as_ref().map(|_| MASKED_SECRET.to_string());
+        let mut masked = self.clone();
+        masked.coinmarketcap.api_key = mask(&self.coinmarketcap.api_key);
+        masked.coingecko.api_key = mask(&self.coingecko.api_key);
+        masked
+    }
+
+    /// Layer `other` on top of `self`.
+    ///
+    /// Scalars set in `other` win, `provider_order` lists are concatenated without
+    /// repeating a provider, and map entries of `other` replace those of the same name.
+    fn merge(&mut self, other: AppConfig) {
+        fn last_wins<T>(base: &mut Option<T>, overlay: Option<T>) {
+            if overlay.is_some() {
+                *base = overlay;
+            }
+        }
+
+        let defaults = other.defaults;
+        last_wins(&mut self.defaults.currency, defaults.currency);
+        last_wins(&mut self.defaults.chart_interval, defaults.chart_interval);
+        last_wins(&mut self.defaults.date_format, defaults.date_format);
+        last_wins(&mut self.defaults.user_agent, defaults.user_agent);
+        last_wins(
+            &mut self.defaults.max_history_points,
+            defaults.max_history_points,
+        );
+        last_wins(&mut self.defaults.convert, defaults.convert);
+        if let Some(overlay) = defaults.provider_order {
+            let order = self.defaults.provider_order.get_or_insert_with(Vec::new);
+            for provider in overlay {
+                if !order.iter().any(|p| p.eq_ignore_ascii_case(&provider)) {
+                    order.push(provider);
+                }
+            }
+        }
+        last_wins(&mut self.coinmarketcap.api_key, other.coinmarketcap.api_key);
+        last_wins(&mut self.coingecko.base_url, other.coingecko.base_url);
+        last_wins(&mut self.coingecko.api_key, other.coingecko.api_key);
+        last_wins(&mut self.coingecko.api_tier, other.coingecko.api_tier);
+        last_wins(
+            &mut self.network.max_retry_after_secs,
+            other.network.max_retry_after_secs,
+        );
+        last_wins(&mut self.cache.max_entries, other.cache.max_entries);
+        self.watchlists.extend(other.watchlists);
+        self.symbol_providers.extend(other.symbol_providers);
+    }
+}
+
+/// Path of the system-wide config file, on platforms that have one.
+pub fn system_config_path() -> Option<PathBuf> {
+    cfg!(unix).then(|| PathBuf::from(SYSTEM_CONFIG_PATH))
+}
+
 /// Resolve the configuration file path based on XDG conventions.
 pub fn config_path() -> Option<PathBuf> {
     if let Ok(xdg_config_home) = std::env::var("XDG_CONFIG_HOME")
@@ -50,21 +161,33 @@ pub fn config_path() -> Option<PathBuf> {
     Some(PathBuf::from(home).join(".config").join(CONFIG_FILE_NAME))
 }
 
-/// Load config from disk. Returns defaults when the file does not exist.
+/// Load the system config, then the user config on top of it.
+///
+/// Returns defaults when neither file exists.
 pub fn load() -> Result<AppConfig> {
-    let Some(path) = config_path() else {
-        return Ok(AppConfig::default());
-    };
-
-    let raw = match fs::read_to_string(&path) {
-        Ok(raw) => raw,
-        Err(err) if err.kind() == ErrorKind::NotFound => return Ok(AppConfig::default()),
-        Err(err) => {
-            return Err(read_config_error(&path, err));
-        }
-    };
+    let paths: Vec<PathBuf> = [system_config_path(), config_path()]
+        .into_iter()
+        .flatten()
+        .collect();
+    let paths: Vec<&Path> = paths.iter().map(PathBuf::as_path).collect();
+    load_multiple(&paths)
+}
 
-    parse(&raw).map_err(|err| parse_config_error(&path, err))
+/// Load and merge config files in order, later files overriding earlier ones.
+///
+/// Missing files are skipped; unreadable or malformed files are an error. See
+/// [`AppConfig::merge`] for how values combine.
+pub fn load_multiple(paths: &[&Path]) -> Result<AppConfig> {
+    let mut config = AppConfig::default();
+    for path in paths {
+        let raw = match fs::read_to_string(path) {
+            Ok(raw) => raw,
+            Err(err) if err.kind() == ErrorKind::NotFound => continue,
+            Err(err) => return Err(read_config_error(path, err)),
+        };
+        config.merge(parse(&raw).map_err(|err| parse_config_error(path, err))?);
+    }
+    Ok(config)
 }
 
 /// Load config from an explicit path.
@@ -75,10 +198,93 @@ pub fn load_from_path(path: &Path) -> Result<AppConfig> {
     parse(&raw).map_err(|err| parse_config_error(path, err))
 }
 
+/// Override config values with `PRICR_*` environment variables.
+///
+/// Environment values win over the config file, while CLI flags still win over
+/// both. Empty variables are ignored; malformed numbers and user agents that
+/// cannot be sent as a header are a config error.
+///
+/// | Variable | Config field |
+/// |---|---|
+/// | `PRICR_CURRENCY` | `[defaults].currency` |
+/// | `PRICR_PROVIDER_ORDER` (comma-separated) | `[defaults].provider_order` |
+/// | `PRICR_DEFAULT_CHART_INTERVAL` | `[defaults].chart_interval` |
+/// | `PRICR_DATE_FORMAT` | `[defaults].date_format` |
+/// | `PRICR_USER_AGENT` | `[defaults].user_agent` |
+/// | `PRICR_MAX_RETRY_AFTER_SECS` | `[network].max_retry_after_secs` |
+/// | `PRICR_CACHE_MAX_ENTRIES` | `[cache].max_entries` |
+pub fn merge_env(config: &mut AppConfig) -> Result<()> {
+    merge_vars(config, |name| std::env::var(name).ok())
+}
+
+fn merge_vars(config: &mut AppConfig, var: impl Fn(&str) -> Option<String>) -> Result<()> {
+    let var = |name: &str| {
+        var(name)
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty())
+    };
+
+    if let Some(currency) = var("PRICR_CURRENCY") {
+        config.defaults.currency = Some(currency);
+    }
+    if let Some(order) = var("PRICR_PROVIDER_ORDER") {
+        config.defaults.provider_order = Some(
+            order
+                .split(',')
+                .map(str::trim)
+                .filter(|id| !id.is_empty())
+                .map(String::from)
+                .collect(),
+        );
+    }
+    if let Some(interval) = var("PRICR_DEFAULT_CHART_INTERVAL") {
+        config.defaults.chart_interval = Some(interval);
+    }
+    if let Some(date_format) = var("PRICR_DATE_FORMAT") {
+        config.defaults.date_format = Some(date_format);
+    }
+    if let Some(user_agent) = var("PRICR_USER_AGENT") {
+        crate::provider::http::check_user_agent(&user_agent)?;
+        config.defaults.user_agent = Some(user_agent);
+    }
+    if let Some(secs) = var("PRICR_MAX_RETRY_AFTER_SECS") {
+        config.network.max_retry_after_secs =
+            Some(parse_env_number("PRICR_MAX_RETRY_AFTER_SECS", &secs)?);
+    }
+    if let Some(max_entries) = var("PRICR_CACHE_MAX_ENTRIES") {
+        config.cache.max_entries = Some(parse_env_number("PRICR_CACHE_MAX_ENTRIES", &max_entries)?);
+    }
+
+    Ok(())
+}
+
+fn parse_env_number<T: std::str::FromStr>(name: &str, value: &str) -> Result<T> {
+    value.parse().map_err(|_| {
+        Error::Config(format!(
+            "invalid {} '{}', expected a non-negative integer",
+            name, value
+        ))
+    })
+}
+
+/// Serialize a config back to TOML, as printed by `pricr config show`.
+pub fn to_toml(config: &AppConfig) -> Result<String> {
+    toml::to_string_pretty(config)
+        .map_err(|err| Error::Config(format!("failed to serialize config: {}", err)))
+}
+
 fn parse(raw: &str) -> std::result::Result<AppConfig, toml::de::Error> {
     toml::from_str(raw)
 }
 
+/// Serialize a map with its keys in order, so printed configs are stable.
+fn sorted_map<S: Serializer, V: Serialize>(
+    map: &HashMap<String, V>,
+    serializer: S,
+) -> std::result::Result<S::Ok, S::Error> {
+    map.iter().collect::<BTreeMap<_, _>>().serialize(serializer)
+}
+
 fn read_config_error(path: &Path, err: std::io::Error) -> Error {
     Error::Config(format!(
         "failed to read config file '{}': {}",
@@ -105,9 +311,153 @@ mod tests {
         assert!(cfg.defaults.currency.is_none());
         assert!(cfg.defaults.provider_order.is_none());
         assert!(cfg.coinmarketcap.api_key.is_none());
+        assert!(cfg.coingecko.base_url.is_none());
         assert!(cfg.watchlists.is_empty());
     }
 
+    #[test]
+    fn to_toml_round_trips_through_parse() {
+        let cfg = parse(
+            r#"
+            [defaults]
+            currency = "eur"
+            provider_order = ["yahoo", "coingecko"]
+            max_history_points = 500
+            convert = true
+
+            [coinmarketcap]
+            api_key = "cmc-secret"
+
+            [coingecko]
+            base_url = "http://localhost:9000"
+            api_tier = "pro"
+
+            [cache]
+            max_entries = 64
+
+            [watchlists]
+            tech = ["AAPL", "MSFT"]
+            metals = ["GC=F"]
+
+            [symbol_providers]
+            brk-b = "yahoo"
+            "#,
+        )
+        .unwrap();
+
+        let round_tripped = parse(&to_toml(&cfg).unwrap()).unwrap();
+
+        assert_eq!(round_tripped.defaults.currency.as_deref(), Some("eur"));
+        assert_eq!(
+            round_tripped.defaults.provider_order,
+            Some(vec!["yahoo".to_string(), "coingecko".to_string()])
+        );
+        assert_eq!(round_tripped.defaults.max_history_points, Some(500));
+        assert_eq!(round_tripped.defaults.convert, Some(true));
+        assert_eq!(
+            round_tripped.coinmarketcap.api_key.as_deref(),
+            Some("cmc-secret")
+        );
+        assert_eq!(
+            round_tripped.coingecko.base_url.as_deref(),
+            Some("http://localhost:9000")
+        );
+        assert_eq!(round_tripped.coingecko.api_tier, Some(ApiTier::Pro));
+        assert_eq!(round_tripped.cache.max_entries, Some(64));
+        assert_eq!(round_tripped.watchlists, cfg.watchlists);
+        assert_eq!(round_tripped.symbol_providers, cfg.symbol_providers);
+    }
+
+    #[test]
+    fn masked_config_hides_api_keys() {
+        let mut cfg = AppConfig::default();
+        cfg.coinmarketcap.api_key = Some("cmc-secret".to_string());
+        cfg.coingecko.base_url = Some("http://localhost:9000".to_string());
+
+        let shown = to_toml(&cfg.masked()).unwrap();
+
+        assert!(!shown.contains("cmc-secret"), "{shown}");
+        assert!(shown.contains(r#"api_key = "***""#), "{shown}");
+        assert!(shown.contains("http://localhost:9000"), "{shown}");
+        assert!(cfg.masked().coingecko.api_key.is_none());
+    }
+
+    fn write_config(dir: &Path, name: &str, raw: &str) -> PathBuf {
+        let path = dir.join(name);
+        fs::write(&path, raw).unwrap();
+        path
+    }
+
+    #[test]
+    fn load_multiple_layers_user_config_over_system_config() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let system = write_config(
+            dir.path(),
+            "system.toml",
+            r#"
+            [defaults]
+            currency = "usd"
+            provider_order = ["coingecko", "yahoo"]
+            user_agent = "corp-proxy/1.0"
+
+            [coingecko]
+            base_url = "http://proxy.internal/api/v3"
+
+            [watchlists]
+            majors = ["btc", "eth"]
+            indices = ["^gspc"]
+            "#,
+        );
+        let user = write_config(
+            dir.path(),
+            "user.toml",
+            r#"
+            [defaults]
+            currency = "eur"
+            provider_order = ["stooq", "Yahoo"]
+
+            [watchlists]
+            majors = ["btc", "sol"]
+            mine = ["aapl"]
+            "#,
+        );
+
+        let cfg = load_multiple(&[&system, &user]).unwrap();
+
+        assert_eq!(cfg.defaults.currency.as_deref(), Some("eur"));
+        assert_eq!(cfg.defaults.user_agent.as_deref(), Some("corp-proxy/1.0"));
+        assert_eq!(
+            cfg.coingecko.base_url.as_deref(),
+            Some("http://proxy.internal/api/v3")
+        );
+        assert_eq!(
+            cfg.defaults.provider_order,
+            Some(vec![
+                "coingecko".to_string(),
+                "yahoo".to_string(),
+                "stooq".to_string()
+            ])
+        );
+        assert_eq!(cfg.watchlists["majors"], vec!["btc", "sol"]);
+        assert_eq!(cfg.watchlists["indices"], vec!["^gspc"]);
+        assert_eq!(cfg.watchlists["mine"], vec!["aapl"]);
+    }
+
+    #[test]
+    fn load_multiple_skips_missing_files_and_rejects_malformed_ones() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let missing = dir.path().join("missing.toml");
+        let user = write_config(dir.path(), "user.toml", "[defaults]\ncurrency = \"gbp\"\n");
+        let broken = write_config(dir.path(), "broken.toml", "[defaults\ncurrency = 1");
+
+        let cfg = load_multiple(&[&missing, &user]).unwrap();
+        assert_eq!(cfg.defaults.currency.as_deref(), Some("gbp"));
+        assert!(load_multiple(&[]).unwrap().defaults.currency.is_none());
+
+        let err = load_multiple(&[&user, &broken]).unwrap_err();
+        assert!(matches!(err, Error::Config(ref msg) if msg.contains("broken.toml")));
+    }
+
     #[test]
     fn parse_coinmarketcap_api_key() {
         let cfg = parse(
@@ -121,6 +471,63 @@ mod tests {
         assert_eq!(cfg.coinmarketcap.api_key.as_deref(), Some("abc123"));
     }
 
+    #[test]
+    fn parse_coingecko_api_key_and_tier() {
+        let toml_str = r#"
+            [coingecko]
+            api_key = "CG-abc"
+            api_tier = "pro"
+        "#;
+        let cfg: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.coingecko.api_key.as_deref(), Some("CG-abc"));
+        assert_eq!(cfg.coingecko.api_tier, Some(ApiTier::Pro));
+
+        let invalid = "[coingecko]\napi_tier = \"enterprise\"\n";
+        assert!(toml::from_str::<AppConfig>(invalid).is_err());
+    }
+
+    #[test]
+    fn parse_coingecko_base_url() {
+        let cfg = parse(
+            r#"
+            [coingecko]
+            base_url = "http://127.0.0.1:8080/api/v3"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            cfg.coingecko.base_url.as_deref(),
+            Some("http://127.0.0.1:8080/api/v3")
+        );
+    }
+
+    #[test]
+    fn parse_network_max_retry_after() {
+        let cfg = parse(
+            r#"
+            [network]
+            max_retry_after_secs = 3
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.network.max_retry_after_secs, Some(3));
+    }
+
+    #[test]
+    fn parse_cache_max_entries() {
+        let cfg = parse(
+            r#"
+            [cache]
+            max_entries = 200
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.cache.max_entries, Some(200));
+    }
+
     #[test]
     fn parse_default_currency() {
         let cfg = parse(
@@ -135,6 +542,67 @@ mod tests {
         assert!(cfg.defaults.provider_order.is_none());
     }
 
+    #[test]
+    fn parse_date_format() {
+        let cfg = parse(
+            r#"
+            [defaults]
+            date_format = "%d/%m/%Y"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.defaults.date_format.as_deref(), Some("%d/%m/%Y"));
+    }
+
+    #[test]
+    fn parse_symbol_providers() {
+        let cfg = parse(
+            r#"
+            [symbol_providers]
+            thin = "coingecko"
+            "BRK.B" = "yahoo"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.symbol_providers.len(), 2);
+        assert_eq!(cfg.symbol_providers["BRK.B"], "yahoo");
+    }
+
+    #[test]
+    fn parse_max_history_points() {
+        let toml_str = r#"
+            [defaults]
+            max_history_points = 1200
+        "#;
+        let cfg: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.defaults.max_history_points, Some(1200));
+    }
+
+    #[test]
+    fn parse_convert_default() {
+        let toml_str = r#"
+            [defaults]
+            convert = true
+        "#;
+        let cfg: AppConfig = toml::from_str(toml_str).unwrap();
+        assert_eq!(cfg.defaults.convert, Some(true));
+    }
+
+    #[test]
+    fn parse_user_agent() {
+        let cfg = parse(
+            r#"
+            [defaults]
+            user_agent = "my-dashboard/2.0"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(cfg.defaults.user_agent.as_deref(), Some("my-dashboard/2.0"));
+    }
+
     #[test]
     fn parse_provider_order() {
         let cfg = parse(
@@ -179,4 +647,92 @@ mod tests {
             Some(&vec!["GC=F".to_string(), "SI=F".to_string()])
         );
     }
+
+    fn vars(pairs: &[(&str, &str)]) -> impl Fn(&str) -> Option<String> {
+        let map: HashMap<String, String> = pairs
+            .iter()
+            .map(|(k, v)| (k.to_string(), v.to_string()))
+            .collect();
+        move |name| map.get(name).cloned()
+    }
+
+    #[test]
+    fn merge_env_overrides_file_values() {
+        let mut cfg = parse(
+            r#"
+            [defaults]
+            currency = "eur"
+            provider_order = ["coingecko"]
+            date_format = "%d/%m/%Y"
+
+            [network]
+            max_retry_after_secs = 3
+            "#,
+        )
+        .unwrap();
+
+        merge_vars(
+            &mut cfg,
+            vars(&[
+                ("PRICR_CURRENCY", "gbp"),
+                ("PRICR_PROVIDER_ORDER", " yahoo, stooq ,,cmc "),
+                ("PRICR_DEFAULT_CHART_INTERVAL", "6M"),
+                ("PRICR_USER_AGENT", "dashboard/1.0"),
+                ("PRICR_MAX_RETRY_AFTER_SECS", "0"),
+                ("PRICR_CACHE_MAX_ENTRIES", "50"),
+            ]),
+        )
+        .unwrap();
+
+        assert_eq!(cfg.defaults.currency.as_deref(), Some("gbp"));
+        assert_eq!(
+            cfg.defaults.provider_order,
+            Some(vec![
+                "yahoo".to_string(),
+                "stooq".to_string(),
+                "cmc".to_string()
+            ])
+        );
+        assert_eq!(cfg.defaults.chart_interval.as_deref(), Some("6M"));
+        assert_eq!(cfg.defaults.date_format.as_deref(), Some("%d/%m/%Y"));
+        assert_eq!(cfg.defaults.user_agent.as_deref(), Some("dashboard/1.0"));
+        assert_eq!(cfg.network.max_retry_after_secs, Some(0));
+        assert_eq!(cfg.cache.max_entries, Some(50));
+    }
+
+    #[test]
+    fn merge_env_ignores_unset_and_blank_variables() {
+        let mut cfg = parse(
+            r#"
+            [defaults]
+            currency = "eur"
+            "#,
+        )
+        .unwrap();
+
+        merge_vars(&mut cfg, vars(&[("PRICR_CURRENCY", "  ")])).unwrap();
+
+        assert_eq!(cfg.defaults.currency.as_deref(), Some("eur"));
+        assert!(cfg.defaults.provider_order.is_none());
+        assert!(cfg.network.max_retry_after_secs.is_none());
+    }
+
+    #[test]
+    fn merge_env_rejects_malformed_numbers() {
+        let mut cfg = AppConfig::default();
+
+        let err = merge_vars(&mut cfg, vars(&[("PRICR_CACHE_MAX_ENTRIES", "lots")])).unwrap_err();
+
+        assert!(matches!(err, Error::Config(msg) if msg.contains("PRICR_CACHE_MAX_ENTRIES")));
+    }
+
+    #[test]
+    fn merge_env_rejects_user_agents_with_control_characters() {
+        let mut cfg = AppConfig::default();
+
+        let err = merge_vars(&mut cfg, vars(&[("PRICR_USER_AGENT", "bad\x01agent")])).unwrap_err();
+
+        assert!(matches!(err, Error::Config(msg) if msg.contains("user agent")));
+        assert!(cfg.defaults.user_agent.is_none());
+    }
 }
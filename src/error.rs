@@ -4,7 +4,38 @@ use thiserror::Error;
 #[derive(Error, Debug)]
 pub enum Error {
     #[error("HTTP request failed: {0}")]
-    Http(#[from] reqwest::Error),
+    Http(reqwest::Error),
+
+    #[error("request to {0} timed out -- check your network connection and try again")]
+    Timeout(String),
+
+    #[error("HTTP request to {provider} failed: {source}")]
+    Network {
+        provider: String,
+        #[source]
+        source: reqwest::Error,
+    },
+
+    #[error(
+        "{provider} rate limited{} (consider provider_order or an API key)",
+        format_retry_after(*.retry_after)
+    )]
+    RateLimited {
+        provider: String,
+        retry_after: Option<u64>,
+    },
+
+    #[error("{provider} authentication failed: {detail}")]
+    AuthFailed { provider: String, detail: String },
+
+    #[error("Configuration error: {provider} requires an API key -- {hint}")]
+    MissingApiKey { provider: String, hint: String },
+
+    #[error("{provider} has no data for symbol '{symbol}'")]
+    SymbolNotFound { provider: String, symbol: String },
+
+    #[error("Configuration error: provider '{provider}' does not support {operation}")]
+    Unsupported { provider: String, operation: String },
 
     #[error("API error: {0}")]
     Api(String),
@@ -15,8 +46,100 @@ pub enum Error {
     #[error("Configuration error: {0}")]
     Config(String),
 
+    #[error("I/O error: {0}")]
+    Io(String),
+
     #[error("No results returned")]
     NoResults,
 }
 
+impl Error {
+    /// Wrap a transport-level reqwest failure with the provider that issued it.
+    ///
+    /// Timeouts become [`Error::Timeout`] so they can be reported on their own.
+    pub fn network(provider: &str, source: reqwest::Error) -> Self {
+        if source.is_timeout() {
+            return Self::Timeout(provider.to_string());
+        }
+        Self::Network {
+            provider: provider.to_string(),
+            source,
+        }
+    }
+}
+
+impl From<reqwest::Error> for Error {
+    fn from(source: reqwest::Error) -> Self {
+        if source.is_timeout() {
+            let host = source
+                .url()
+                .and_then(|url| url.host_str())
+                .unwrap_or("provider")
+                .to_string();
+            return Self::Timeout(host);
+        }
+        Self::Http(source)
+    }
+}
+
+fn format_retry_after(retry_after: Option<u64>) -> String {
+    match retry_after {
+        Some(secs) => format!(", retry in {}s", secs),
+        None => String::new(),
+    }
+}
+
 pub type Result<T> = std::result::Result<T, Error>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// A real reqwest timeout: connect to a listener that never answers.
+    async fn timed_out_request() -> reqwest::Error {
+        let listener = std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}/slow", listener.local_addr().unwrap());
+        reqwest::Client::new()
+            .get(url)
+            .timeout(std::time::Duration::from_millis(50))
+            .send()
+            .await
+            .expect_err("the listener never responds")
+    }
+
+    #[tokio::test]
+    async fn timeouts_convert_to_timeout_variant() {
+        let err = timed_out_request().await;
+        assert!(err.is_timeout());
+        let err = Error::network("CoinGecko", err);
+        assert!(matches!(&err, Error::Timeout(provider) if provider == "CoinGecko"));
+        assert_eq!(
+            err.to_string(),
+            "request to CoinGecko timed out -- check your network connection and try again"
+        );
+
+        let err: Error = timed_out_request().await.into();
+        assert!(matches!(&err, Error::Timeout(host) if host == "127.0.0.1"));
+    }
+
+    #[test]
+    fn rate_limited_display_includes_retry_after() {
+        let err = Error::RateLimited {
+            provider: "CoinGecko".into(),
+            retry_after: Some(30),
+        };
+        assert_eq!(
+            err.to_string(),
+            "CoinGecko rate limited, retry in 30s (consider provider_order or an API key)"
+        );
+
+        let err = Error::RateLimited {
+            provider: "CoinGecko".into(),
+            retry_after: None,
+        };
+        assert_eq!(
+            err.to_string(),
+            "CoinGecko rate limited (consider provider_order or an API key)"
+        );
+    }
+}
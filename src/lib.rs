@@ -1,5 +1,9 @@
 pub mod calc;
+pub mod client;
+pub mod clock;
 pub mod config;
 pub mod error;
 pub mod output;
+pub mod portfolio;
 pub mod provider;
+pub mod watch;
@@ -4,13 +4,134 @@ use ratatui::symbols;
 use ratatui::text::Line;
 use ratatui::widgets::{Axis, Block, Borders, Chart, Dataset, GraphType, Widget};
 
-use crate::provider::PriceHistory;
+use crate::error::{Error, Result};
+use crate::provider::{HistoryInterval, PriceHistory};
 
 const MIN_WIDTH: u16 = 48;
 const MIN_HEIGHT: u16 = 12;
 
+/// Default strftime pattern for chart dates.
+pub const DEFAULT_DATE_FORMAT: &str = "%Y-%m-%d";
+/// Default strftime pattern for the time of day in intraday charts.
+pub const DEFAULT_TIME_FORMAT: &str = "%H:%M";
+
+/// Time zone that displayed timestamps are converted to (`--tz`).
+///
+/// Only rendering changes; fetched timestamps stay in UTC.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum DisplayTz {
+    #[default]
+    Local,
+    Utc,
+    Named(chrono_tz::Tz),
+}
+
+impl DisplayTz {
+    /// Parse `local`, `utc`, or an IANA zone name such as `Europe/Paris`.
+    pub fn parse(raw: &str) -> Result<Self> {
+        let trimmed = raw.trim();
+        if trimmed.eq_ignore_ascii_case("local") {
+            return Ok(Self::Local);
+        }
+        if trimmed.eq_ignore_ascii_case("utc") {
+            return Ok(Self::Utc);
+        }
+
+        trimmed
+            .parse::<chrono_tz::Tz>()
+            .or_else(|_| chrono_tz::Tz::from_str_insensitive(trimmed))
+            .map(Self::Named)
+            .map_err(|_| {
+                Error::Config(format!(
+                    "unknown time zone '{}' -- use local, utc, or an IANA name such as Europe/Paris, America/New_York, or Asia/Tokyo",
+                    raw
+                ))
+            })
+    }
+
+    /// Render a UTC timestamp in this zone with a strftime `pattern`.
+    pub fn format(self, timestamp: chrono::DateTime<chrono::Utc>, pattern: &str) -> String {
+        match self {
+            Self::Local => timestamp
+                .with_timezone(&chrono::Local)
+                .format(pattern)
+                .to_string(),
+            Self::Utc => timestamp.format(pattern).to_string(),
+            Self::Named(tz) => timestamp.with_timezone(&tz).format(pattern).to_string(),
+        }
+    }
+}
+
+/// strftime patterns and time zone used for chart timestamps
+/// (`--date-format` / `--time-format` / `--tz`).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TimestampFormat {
+    pub date: String,
+    /// Appended after the date when the chart is intraday.
+    pub time: String,
+    pub tz: DisplayTz,
+}
+
+impl Default for TimestampFormat {
+    fn default() -> Self {
+        Self {
+            date: DEFAULT_DATE_FORMAT.to_string(),
+            time: DEFAULT_TIME_FORMAT.to_string(),
+            tz: DisplayTz::default(),
+        }
+    }
+}
+
+impl TimestampFormat {
+    /// Pattern for one history: the date, plus the time for hourly (or hourly-looking) series.
+    pub fn pattern(&self, history: &PriceHistory, sampling: HistoryInterval) -> String {
+        let intraday = match sampling {
+            HistoryInterval::Hourly => true,
+            HistoryInterval::Daily => false,
+            HistoryInterval::Auto => history
+                .points
+                .windows(2)
+                .any(|pair| pair[1].timestamp - pair[0].timestamp < chrono::Duration::hours(20)),
+        };
+        if intraday {
+            format!("{} {}", self.date, self.time)
+        } else {
+            self.date.clone()
+        }
+    }
+}
+
+/// Check a strftime pattern by formatting a known date with it.
+///
+/// Unknown specifiers or patterns that render to nothing are reported as `Error::Config`.
+pub fn validate_strftime(pattern: &str, flag: &str) -> Result<()> {
+    use std::fmt::Write;
+
+    let sample =
+        chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000, 0).expect("valid timestamp");
+    let mut rendered = String::new();
+    if write!(rendered, "{}", sample.format(pattern)).is_err() || rendered.trim().is_empty() {
+        return Err(Error::Config(format!(
+            "invalid {} '{}' -- use strftime specifiers such as %Y-%m-%d or %H:%M",
+            flag, pattern
+        )));
+    }
+    Ok(())
+}
+
 /// Render a static terminal chart for a coin price history series.
-pub fn render_history_chart(history: &PriceHistory, width: u16, height: u16) -> String {
+///
+/// The first and last points are labeled on the X axis using the strftime `label_format`
+/// in time zone `tz`. With `annotate`, the lowest and highest points get an
+/// [`EXTREME_MARKER`] and their price next to it.
+pub fn render_history_chart(
+    history: &PriceHistory,
+    width: u16,
+    height: u16,
+    label_format: &str,
+    tz: DisplayTz,
+    annotate: bool,
+) -> String {
     if history.points.is_empty() {
         return String::new();
     }
@@ -29,12 +150,12 @@ pub fn render_history_chart(history: &PriceHistory, width: u16, height: u16) ->
     let first_label = history
         .points
         .first()
-        .map(|p| p.timestamp.format("%Y-%m-%d").to_string())
+        .map(|p| tz.format(p.timestamp, label_format))
         .unwrap_or_default();
     let last_label = history
         .points
         .last()
-        .map(|p| p.timestamp.format("%Y-%m-%d").to_string())
+        .map(|p| tz.format(p.timestamp, label_format))
         .unwrap_or_default();
 
     let dataset = Dataset::default()
@@ -43,7 +164,20 @@ pub fn render_history_chart(history: &PriceHistory, width: u16, height: u16) ->
         .marker(symbols::Marker::Dot)
         .data(&points);
 
-    let chart = Chart::new(vec![dataset])
+    let extremes = if annotate {
+        extreme_points(&points)
+    } else {
+        Vec::new()
+    };
+    let mut datasets = vec![dataset];
+    datasets.extend(extremes.iter().map(|point| {
+        Dataset::default()
+            .graph_type(GraphType::Scatter)
+            .marker(symbols::Marker::Block)
+            .data(std::slice::from_ref(point))
+    }));
+
+    let chart = Chart::new(datasets)
         .block(
             Block::default()
                 .title(format!("{} Price History", history.symbol))
@@ -67,10 +201,338 @@ pub fn render_history_chart(history: &PriceHistory, width: u16, height: u16) ->
 
     let mut buffer = Buffer::empty(area);
     chart.render(area, &mut buffer);
+    mark_events_on_x_axis(&mut buffer, area, history);
+    label_extremes(&mut buffer, area, &extremes);
     buffer_to_string(&buffer, area)
 }
 
-fn y_bounds(points: &[(f64, f64)]) -> (f64, f64) {
+/// Glyph drawn on the low and high points of an annotated chart.
+pub const EXTREME_MARKER: &str = "█";
+
+/// The lowest and highest points of `points` (first occurrence of each), low first.
+///
+/// A flat series has a single extreme.
+fn extreme_points(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    let by_price = |a: &&(f64, f64), b: &&(f64, f64)| a.1.total_cmp(&b.1);
+    // `max_by` keeps the last of equal elements, so scan backwards for the first high.
+    let (Some(&low), Some(&high)) = (
+        points.iter().min_by(by_price),
+        points.iter().rev().max_by(by_price),
+    ) else {
+        return Vec::new();
+    };
+    if low == high {
+        vec![low]
+    } else {
+        vec![low, high]
+    }
+}
+
+/// Write each extreme's price beside its marker, to the right when it fits and
+/// otherwise to the left.
+///
+/// Markers are found in the rendered buffer; the topmost is the high and the
+/// bottommost the low.
+fn label_extremes(buffer: &mut Buffer, area: Rect, extremes: &[(f64, f64)]) {
+    if extremes.is_empty() {
+        return;
+    }
+    let markers: Vec<(u16, u16)> = (area.y..area.y + area.height)
+        .flat_map(|y| (area.x..area.x + area.width).map(move |x| (x, y)))
+        .filter(|&(x, y)| buffer[(x, y)].symbol() == EXTREME_MARKER)
+        .collect();
+    let (Some(&top), Some(&bottom)) = (markers.first(), markers.last()) else {
+        return;
+    };
+
+    let low = extremes[0].1;
+    let high = extremes[extremes.len() - 1].1;
+    let placements = if top == bottom {
+        vec![(top, high)]
+    } else {
+        vec![(bottom, low), (top, high)]
+    };
+    // Stay inside the block's right border.
+    let right = (area.x + area.width).saturating_sub(1);
+    for ((x, y), price) in placements {
+        let label = format_price_label(price);
+        let len = label.chars().count() as u16;
+        let start = if x + 1 + len <= right {
+            x + 1
+        } else {
+            x.saturating_sub(len)
+        };
+        for (offset, ch) in label.chars().enumerate() {
+            buffer[(start + offset as u16, y)].set_char(ch);
+        }
+    }
+}
+
+/// Point index and marker (`D`/`S`) for each dividend or split in `history`.
+///
+/// Events are placed on the first point at or after them, or the last point for
+/// events after the series ends.
+fn event_markers(history: &PriceHistory) -> Vec<(usize, char)> {
+    let Some(last_idx) = history.points.len().checked_sub(1) else {
+        return Vec::new();
+    };
+    history
+        .events
+        .iter()
+        .map(|event| {
+            let idx = history
+                .points
+                .partition_point(|point| point.timestamp < event.timestamp());
+            (idx.min(last_idx), event.marker())
+        })
+        .collect()
+}
+
+/// Overwrite the rendered X axis line with event markers below their points.
+fn mark_events_on_x_axis(buffer: &mut Buffer, area: Rect, history: &PriceHistory) {
+    let markers = event_markers(history);
+    if markers.is_empty() {
+        return;
+    }
+    // The axis corner is the first `└` inside the block's left border.
+    let Some((corner_x, axis_y)) = (area.y..area.y + area.height)
+        .flat_map(|y| (area.x + 1..area.x + area.width).map(move |x| (x, y)))
+        .find(|&(x, y)| buffer[(x, y)].symbol() == "└")
+    else {
+        return;
+    };
+
+    let left = corner_x + 1;
+    let right = (area.x + area.width).saturating_sub(2);
+    if right <= left {
+        return;
+    }
+    let last_idx = history.points.len().saturating_sub(1).max(1) as f64;
+    for (idx, marker) in markers {
+        let offset = (idx as f64 / last_idx * f64::from(right - left)).round() as u16;
+        buffer[(left + offset, axis_y)].set_char(marker);
+    }
+}
+
+/// Markers that tell overlaid series apart, with the glyph shown for each in the legend.
+const OVERLAY_MARKERS: [(symbols::Marker, &str); 3] = [
+    (symbols::Marker::Dot, "•"),
+    (symbols::Marker::Braille, "⣿"),
+    (symbols::Marker::Block, "█"),
+];
+
+/// Most series [`render_multi_history_chart`] can overlay with distinct markers.
+pub const MAX_OVERLAY_SERIES: usize = OVERLAY_MARKERS.len();
+
+/// Render several price histories on one chart with a shared Y axis.
+///
+/// Series are placed by timestamp, so histories with different lengths still line up,
+/// and the Y axis spans the global min/max across all of them. Each series gets its own
+/// marker (dot, braille, block), listed in a legend line below the chart. The earliest
+/// and latest timestamps are labeled with `label_format` in time zone `tz`.
+pub fn render_multi_history_chart(
+    histories: &[PriceHistory],
+    width: u16,
+    height: u16,
+    label_format: &str,
+    tz: DisplayTz,
+) -> String {
+    let histories: Vec<&PriceHistory> = histories
+        .iter()
+        .filter(|history| !history.points.is_empty())
+        .take(MAX_OVERLAY_SERIES)
+        .collect();
+    let Some(first_at) = histories
+        .iter()
+        .filter_map(|history| history.points.first())
+        .map(|p| p.timestamp)
+        .min()
+    else {
+        return String::new();
+    };
+    let last_at = histories
+        .iter()
+        .filter_map(|history| history.points.last())
+        .map(|p| p.timestamp)
+        .max()
+        .unwrap_or(first_at);
+
+    let area = Rect::new(0, 0, width.max(MIN_WIDTH), height.max(MIN_HEIGHT));
+    let series: Vec<Vec<(f64, f64)>> = histories
+        .iter()
+        .map(|history| {
+            history
+                .points
+                .iter()
+                .map(|p| ((p.timestamp - first_at).num_seconds() as f64, p.price))
+                .collect()
+        })
+        .collect();
+    let all_points: Vec<(f64, f64)> = series.iter().flatten().copied().collect();
+    let (y_min, y_max) = y_bounds(&all_points);
+    let x_max = (last_at - first_at).num_seconds() as f64;
+
+    let datasets: Vec<Dataset> = histories
+        .iter()
+        .zip(&series)
+        .zip(OVERLAY_MARKERS)
+        .map(|((history, points), (marker, _))| {
+            Dataset::default()
+                .name(history.symbol.as_str())
+                .graph_type(GraphType::Line)
+                .marker(marker)
+                .data(points)
+        })
+        .collect();
+
+    let symbols: Vec<&str> = histories.iter().map(|h| h.symbol.as_str()).collect();
+    let mut currencies: Vec<&str> = histories.iter().map(|h| h.currency.as_str()).collect();
+    currencies.dedup();
+
+    let chart = Chart::new(datasets)
+        .block(
+            Block::default()
+                .title(format!("{} Price History", symbols.join(" / ")))
+                .borders(Borders::ALL),
+        )
+        .x_axis(
+            Axis::default()
+                .title(Line::from("Time"))
+                .bounds([0.0, x_max.max(1.0)])
+                .labels(vec![
+                    Line::from(tz.format(first_at, label_format)),
+                    Line::from(tz.format(last_at, label_format)),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .title(Line::from(currencies.join("/")))
+                .bounds([y_min, y_max])
+                .labels(vec![
+                    Line::from(format_price_label(y_min)),
+                    Line::from(format_price_label(y_max)),
+                ]),
+        );
+
+    let mut buffer = Buffer::empty(area);
+    chart.render(area, &mut buffer);
+
+    let legend: Vec<String> = symbols
+        .iter()
+        .zip(OVERLAY_MARKERS)
+        .map(|(symbol, (_, glyph))| format!("{glyph} {symbol}"))
+        .collect();
+    format!(
+        "{}
+Legend: {}",
+        buffer_to_string(&buffer, area),
+        legend.join("   ")
+    )
+}
+
+/// Render the same chart using only ASCII characters (`*` markers, `-`/`|`/`+` borders).
+///
+/// Intended for terminals, fonts, and CI logs that mangle braille or box-drawing glyphs.
+pub fn render_history_chart_ascii(
+    history: &PriceHistory,
+    width: u16,
+    height: u16,
+    label_format: &str,
+    tz: DisplayTz,
+) -> String {
+    if history.points.is_empty() {
+        return String::new();
+    }
+
+    let width = usize::from(width.max(MIN_WIDTH));
+    let height = usize::from(height.max(MIN_HEIGHT));
+    let points: Vec<(f64, f64)> = history
+        .points
+        .iter()
+        .enumerate()
+        .map(|(idx, p)| (idx as f64, p.price))
+        .collect();
+    let (y_min, y_max) = y_bounds(&points);
+
+    let top_label = format_price_label(y_max);
+    let bottom_label = format_price_label(y_min);
+    let label_width = top_label.len().max(bottom_label.len());
+    // Title, top border, bottom border and the date row take four lines.
+    let plot_height = height.saturating_sub(4).max(1);
+    let plot_width = width.saturating_sub(label_width + 3).max(1);
+
+    // Linearly interpolate one marker row per plot column.
+    let last_idx = points.len() - 1;
+    let marker_rows: Vec<usize> = (0..plot_width)
+        .map(|col| {
+            let x = if plot_width > 1 {
+                col as f64 * last_idx as f64 / (plot_width - 1) as f64
+            } else {
+                0.0
+            };
+            let lower = x.floor() as usize;
+            let upper = (lower + 1).min(last_idx);
+            let frac = x - lower as f64;
+            let price = points[lower].1 + (points[upper].1 - points[lower].1) * frac;
+
+            let ratio = (y_max - price) / (y_max - y_min);
+            ((ratio * (plot_height - 1) as f64).round() as usize).min(plot_height - 1)
+        })
+        .collect();
+
+    let first_label = history
+        .points
+        .first()
+        .map(|p| tz.format(p.timestamp, label_format))
+        .unwrap_or_default();
+    let last_label = history
+        .points
+        .last()
+        .map(|p| tz.format(p.timestamp, label_format))
+        .unwrap_or_default();
+
+    let border = format!("+{}+", "-".repeat(plot_width));
+    let mut lines = Vec::with_capacity(plot_height + 4);
+    lines.push(format!(
+        "{} Price History ({})",
+        history.symbol, history.currency
+    ));
+    lines.push(format!("{top_label:>label_width$} {border}"));
+    for row in 0..plot_height {
+        let plot: String = marker_rows
+            .iter()
+            .map(|&marker_row| if marker_row == row { '*' } else { ' ' })
+            .collect();
+        lines.push(format!("{:label_width$} |{plot}|", ""));
+    }
+    let mut bottom_border: Vec<char> = border.chars().collect();
+    for (idx, marker) in event_markers(history) {
+        let col = if plot_width > 1 && last_idx > 0 {
+            (idx as f64 * (plot_width - 1) as f64 / last_idx as f64).round() as usize
+        } else {
+            0
+        };
+        bottom_border[col + 1] = marker;
+    }
+    let bottom_border: String = bottom_border.into_iter().collect();
+    lines.push(format!("{bottom_label:>label_width$} {bottom_border}"));
+    let gap = (plot_width + 2).saturating_sub(first_label.len() + last_label.len());
+    lines.push(format!(
+        "{:label_width$} {}{}{}",
+        "",
+        first_label,
+        " ".repeat(gap),
+        last_label
+    ));
+
+    lines.join("\n")
+}
+
+/// Y-axis bounds with padding around the series.
+///
+/// Flat series get 1% padding (or `±0.01` when every price is zero) so the axis
+/// stays close to the actual price instead of jumping by a fixed amount.
+pub(crate) fn y_bounds(points: &[(f64, f64)]) -> (f64, f64) {
     let min = points.iter().map(|(_, y)| *y).fold(f64::INFINITY, f64::min);
     let max = points
         .iter()
@@ -79,11 +541,10 @@ fn y_bounds(points: &[(f64, f64)]) -> (f64, f64) {
 
     let span = max - min;
     if span <= f64::EPSILON {
-        let padding = if max.abs() <= 1.0 {
-            1.0
-        } else {
-            (max.abs() * 0.01).max(1.0)
-        };
+        if max == 0.0 {
+            return (-0.01, 0.01);
+        }
+        let padding = max.abs() * 0.01;
         (min - padding, max + padding)
     } else {
         let padding = span * 0.08;
@@ -136,18 +597,308 @@ mod tests {
                     timestamp: chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000, 0)
                         .expect("valid timestamp"),
                     price: 40000.0,
+                    filled: false,
                 },
                 PricePoint {
                     timestamp: chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_086_400, 0)
                         .expect("valid timestamp"),
                     price: 42000.0,
+                    filled: false,
                 },
             ],
+            events: Vec::new(),
         };
 
-        let rendered = render_history_chart(&history, 60, 14);
+        let rendered =
+            render_history_chart(&history, 60, 14, DEFAULT_DATE_FORMAT, DisplayTz::Utc, false);
         assert!(!rendered.is_empty());
         assert!(rendered.lines().count() >= 10);
         assert!(rendered.contains("BTC Price History"));
     }
+
+    #[test]
+    fn annotated_chart_marks_and_labels_low_and_high() {
+        let start = chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+        let prices = [41000.0, 39500.0, 40200.0, 43750.0, 42100.0, 42900.0];
+        let history = PriceHistory {
+            symbol: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            currency: "USD".to_string(),
+            provider: "CoinGecko".to_string(),
+            points: prices
+                .iter()
+                .enumerate()
+                .map(|(day, &price)| PricePoint {
+                    timestamp: start + chrono::Duration::days(day as i64),
+                    price,
+                    filled: false,
+                })
+                .collect(),
+            events: Vec::new(),
+        };
+
+        let plain =
+            render_history_chart(&history, 60, 14, DEFAULT_DATE_FORMAT, DisplayTz::Utc, false);
+        assert!(!plain.contains(EXTREME_MARKER));
+
+        let rendered =
+            render_history_chart(&history, 60, 14, DEFAULT_DATE_FORMAT, DisplayTz::Utc, true);
+        assert_eq!(rendered.matches(EXTREME_MARKER).count(), 2);
+        let row_of = |label: &str| rendered.lines().position(|line| line.contains(label));
+        let (high, low) = (row_of("43750").unwrap(), row_of("39500").unwrap());
+        assert!(high < low);
+        assert_eq!(extreme_points(&[(0.0, 5.0), (1.0, 5.0)]), vec![(0.0, 5.0)]);
+    }
+
+    #[test]
+    fn charts_mark_dividends_and_splits_on_the_x_axis() {
+        let at = |secs: i64| chrono::DateTime::<chrono::Utc>::from_timestamp(secs, 0).unwrap();
+        let history = PriceHistory {
+            symbol: "AAPL".to_string(),
+            name: "Apple".to_string(),
+            currency: "USD".to_string(),
+            provider: "Yahoo Finance".to_string(),
+            points: (0..10)
+                .map(|day| PricePoint {
+                    timestamp: at(1_700_000_000 + day * 86_400),
+                    price: 180.0 + day as f64,
+                    filled: false,
+                })
+                .collect(),
+            events: vec![
+                crate::provider::HistoryEvent::Dividend {
+                    timestamp: at(1_700_000_000 + 2 * 86_400),
+                    amount: 0.24,
+                },
+                crate::provider::HistoryEvent::Split {
+                    timestamp: at(1_700_000_000 + 7 * 86_400 - 3600),
+                    numerator: 4.0,
+                    denominator: 1.0,
+                },
+            ],
+        };
+        assert_eq!(event_markers(&history), [(2, 'D'), (7, 'S')]);
+
+        let axis_row = |rendered: &str, border: char| {
+            rendered
+                .lines()
+                .find(|line| line.contains(border) && (line.contains('D') || line.contains('S')))
+                .map(str::to_string)
+                .unwrap_or_default()
+        };
+        let rendered =
+            render_history_chart(&history, 60, 14, DEFAULT_DATE_FORMAT, DisplayTz::Utc, false);
+        let row = axis_row(&rendered, '└');
+        assert!(row.find('D') < row.find('S'), "{rendered}");
+
+        let ascii =
+            render_history_chart_ascii(&history, 60, 14, DEFAULT_DATE_FORMAT, DisplayTz::Utc);
+        let row = axis_row(&ascii, '+');
+        assert!(row.find('D') < row.find('S'), "{ascii}");
+    }
+
+    #[test]
+    fn render_multi_history_chart_overlays_series_with_legend() {
+        let history = |symbol: &str, prices: &[f64]| PriceHistory {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            currency: "USD".to_string(),
+            provider: "CoinGecko".to_string(),
+            points: prices
+                .iter()
+                .enumerate()
+                .map(|(idx, &price)| PricePoint {
+                    timestamp: chrono::DateTime::<chrono::Utc>::from_timestamp(
+                        1_700_000_000 + idx as i64 * 86_400,
+                        0,
+                    )
+                    .expect("valid timestamp"),
+                    price,
+                    filled: false,
+                })
+                .collect(),
+            events: Vec::new(),
+        };
+        let histories = [
+            history("SOL", &[150.0, 160.0, 155.0]),
+            history("AVAX", &[30.0, 28.0]),
+        ];
+
+        let rendered =
+            render_multi_history_chart(&histories, 60, 14, DEFAULT_DATE_FORMAT, DisplayTz::Utc);
+        assert!(rendered.contains("SOL / AVAX Price History"));
+        assert!(rendered.contains("Legend: • SOL   ⣿ AVAX"));
+        // The shared axis spans both series (28 to 160 plus padding).
+        assert!(rendered.contains("17.44"));
+        assert!(rendered.contains("170.56"));
+        assert!(rendered.contains("2023-11-16"));
+
+        assert!(render_multi_history_chart(&[], 60, 14, "%d", DisplayTz::Utc).is_empty());
+    }
+
+    fn series(prices: &[f64]) -> Vec<(f64, f64)> {
+        prices
+            .iter()
+            .enumerate()
+            .map(|(idx, price)| (idx as f64, *price))
+            .collect()
+    }
+
+    fn assert_close(actual: f64, expected: f64) {
+        assert!(
+            (actual - expected).abs() <= expected.abs() * 1e-12 + 1e-12,
+            "{actual} != {expected}"
+        );
+    }
+
+    #[test]
+    fn y_bounds_all_zero_prices() {
+        assert_eq!(y_bounds(&series(&[0.0, 0.0, 0.0])), (-0.01, 0.01));
+    }
+
+    #[test]
+    fn y_bounds_identical_prices_pad_by_one_percent() {
+        let (low, high) = y_bounds(&series(&[250.0, 250.0]));
+        assert_close(low, 247.5);
+        assert_close(high, 252.5);
+
+        let (low, high) = y_bounds(&series(&[0.5]));
+        assert_close(low, 0.495);
+        assert_close(high, 0.505);
+    }
+
+    #[test]
+    fn y_bounds_normal_spread_pads_by_eight_percent_of_span() {
+        let (low, high) = y_bounds(&series(&[100.0, 150.0, 200.0]));
+        assert_close(low, 92.0);
+        assert_close(high, 208.0);
+    }
+
+    #[test]
+    fn y_bounds_very_large_prices() {
+        let (low, high) = y_bounds(&series(&[2e12, 2e12]));
+        assert_close(low, 1.98e12);
+        assert_close(high, 2.02e12);
+
+        let (low, high) = y_bounds(&series(&[1e13, 2e13]));
+        assert_close(low, 1e13 - 8e11);
+        assert_close(high, 2e13 + 8e11);
+        assert!(low < 1e13 && high > 2e13);
+    }
+
+    #[test]
+    fn render_history_chart_ascii_uses_only_ascii() {
+        let points = (0..30)
+            .map(|day| PricePoint {
+                timestamp: chrono::DateTime::<chrono::Utc>::from_timestamp(
+                    1_700_000_000 + day * 86_400,
+                    0,
+                )
+                .expect("valid timestamp"),
+                price: 40000.0 + (day as f64 * 0.7).sin() * 1500.0,
+                filled: false,
+            })
+            .collect();
+        let history = PriceHistory {
+            symbol: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            currency: "EUR".to_string(),
+            provider: "CoinGecko".to_string(),
+            points,
+            events: Vec::new(),
+        };
+
+        let rendered =
+            render_history_chart_ascii(&history, 60, 14, DEFAULT_DATE_FORMAT, DisplayTz::Utc);
+
+        assert!(rendered.is_ascii(), "non-ASCII output:\n{rendered}");
+        assert!(rendered.contains("BTC Price History (EUR)"));
+        assert!(rendered.contains('*'));
+        assert!(rendered.contains("2023-11-14"));
+        assert_eq!(rendered.lines().count(), 14);
+
+        let width = rendered.lines().nth(2).unwrap().len();
+        for line in rendered.lines().skip(1).take(12) {
+            assert_eq!(line.len(), width, "misaligned line: {line}");
+        }
+    }
+
+    #[test]
+    fn display_tz_parses_keywords_and_iana_names() {
+        assert_eq!(DisplayTz::parse("local").unwrap(), DisplayTz::Local);
+        assert_eq!(DisplayTz::parse("UTC").unwrap(), DisplayTz::Utc);
+        assert_eq!(
+            DisplayTz::parse("europe/paris").unwrap(),
+            DisplayTz::Named(chrono_tz::Europe::Paris)
+        );
+
+        let err = DisplayTz::parse("Mars/Olympus").unwrap_err();
+        assert!(matches!(err, Error::Config(msg) if msg.contains("America/New_York")));
+    }
+
+    #[test]
+    fn display_tz_converts_only_the_rendered_timestamp() {
+        // 2023-11-14 22:13:20 UTC
+        let at = chrono::DateTime::<chrono::Utc>::from_timestamp(1_700_000_000, 0).unwrap();
+
+        assert_eq!(
+            DisplayTz::Utc.format(at, "%Y-%m-%d %H:%M"),
+            "2023-11-14 22:13"
+        );
+        assert_eq!(
+            DisplayTz::Named(chrono_tz::Asia::Tokyo).format(at, "%Y-%m-%d %H:%M"),
+            "2023-11-15 07:13"
+        );
+        assert_eq!(
+            DisplayTz::Named(chrono_tz::America::New_York).format(at, "%H:%M %Z"),
+            "17:13 EST"
+        );
+    }
+
+    #[test]
+    fn validate_strftime_accepts_known_specifiers() {
+        assert!(validate_strftime("%d/%m/%Y", "--date-format").is_ok());
+        assert!(validate_strftime("%b %d", "--date-format").is_ok());
+        assert!(validate_strftime("%H:%M:%S", "--time-format").is_ok());
+    }
+
+    #[test]
+    fn validate_strftime_rejects_invalid_or_empty_patterns() {
+        for pattern in ["%Q", "", "%"] {
+            let err = validate_strftime(pattern, "--date-format").unwrap_err();
+            assert!(matches!(err, Error::Config(_)), "{pattern}: {err}");
+        }
+    }
+
+    #[test]
+    fn timestamp_pattern_adds_time_for_intraday_series() {
+        let history = PriceHistory {
+            symbol: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            currency: "USD".to_string(),
+            provider: "CoinGecko".to_string(),
+            points: [1_700_000_000, 1_700_003_600]
+                .into_iter()
+                .map(|secs| PricePoint {
+                    timestamp: chrono::DateTime::<chrono::Utc>::from_timestamp(secs, 0)
+                        .expect("valid timestamp"),
+                    price: 1.0,
+                    filled: false,
+                })
+                .collect(),
+            events: Vec::new(),
+        };
+        let format = TimestampFormat {
+            date: "%d/%m".to_string(),
+            time: "%Hh".to_string(),
+            tz: DisplayTz::Utc,
+        };
+
+        assert_eq!(
+            format.pattern(&history, HistoryInterval::Hourly),
+            "%d/%m %Hh"
+        );
+        assert_eq!(format.pattern(&history, HistoryInterval::Auto), "%d/%m %Hh");
+        assert_eq!(format.pattern(&history, HistoryInterval::Daily), "%d/%m");
+    }
 }
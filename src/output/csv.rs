@@ -0,0 +1,232 @@
+use std::borrow::Cow;
+use std::io::{self, Write};
+
+use crate::error::{Error, Result};
+use crate::output::format::{SymbolCase, apply_symbol_case};
+use crate::provider::CoinPrice;
+
+/// Field separator, quote character, and symbol case for `--csv` output.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CsvOptions {
+    pub delimiter: char,
+    pub quote_char: char,
+    pub symbol_case: SymbolCase,
+}
+
+impl CsvOptions {
+    /// Options for `--csv-delimiter` and `--csv-quote-char`.
+    ///
+    /// Returns [`Error::Config`] when the two characters are the same or either is
+    /// a line break, since the output could not be parsed back.
+    pub fn new(delimiter: char, quote_char: char) -> Result<Self> {
+        if delimiter == quote_char {
+            return Err(Error::Config(format!(
+                "--csv-delimiter and --csv-quote-char must differ (both are {:?})",
+                delimiter
+            )));
+        }
+        if let Some(c) = [delimiter, quote_char]
+            .into_iter()
+            .find(|c| matches!(c, '\n' | '\r'))
+        {
+            return Err(Error::Config(format!(
+                "--csv-delimiter and --csv-quote-char cannot be a line break ({:?})",
+                c
+            )));
+        }
+        Ok(Self {
+            delimiter,
+            quote_char,
+            ..Self::default()
+        })
+    }
+}
+
+impl Default for CsvOptions {
+    fn default() -> Self {
+        Self {
+            delimiter: ',',
+            quote_char: '"',
+            symbol_case: SymbolCase::default(),
+        }
+    }
+}
+
+/// Writes CSV records to `W`, quoting fields that contain the delimiter, the
+/// quote character, or a line break, and doubling quote characters inside them.
+pub struct CsvWriter<W: Write> {
+    inner: W,
+    options: CsvOptions,
+}
+
+impl<W: Write> CsvWriter<W> {
+    pub fn new(inner: W, options: CsvOptions) -> Self {
+        Self { inner, options }
+    }
+
+    /// Write one record terminated by `\n`.
+    pub fn write_record<I, S>(&mut self, fields: I) -> io::Result<()>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut delimiter = [0; 4];
+        let delimiter = self.options.delimiter.encode_utf8(&mut delimiter);
+        for (idx, field) in fields.into_iter().enumerate() {
+            if idx > 0 {
+                self.inner.write_all(delimiter.as_bytes())?;
+            }
+            self.inner
+                .write_all(escape_field(field.as_ref(), self.options).as_bytes())?;
+        }
+        self.inner.write_all(b"\n")
+    }
+
+    pub fn into_inner(self) -> W {
+        self.inner
+    }
+}
+
+fn escape_field(field: &str, options: CsvOptions) -> Cow<'_, str> {
+    let needs_quotes = field
+        .chars()
+        .any(|c| c == options.delimiter || c == options.quote_char || c == '\n' || c == '\r');
+    if !needs_quotes {
+        return Cow::Borrowed(field);
+    }
+
+    let quote = options.quote_char.to_string();
+    let doubled = quote.repeat(2);
+    Cow::Owned(format!(
+        "{quote}{}{quote}",
+        field.replace(options.quote_char, &doubled)
+    ))
+}
+
+/// Print prices as CSV with a header row.
+pub fn print_price_csv(prices: &[CoinPrice], options: CsvOptions) {
+    print!("{}", render_price_csv(prices, options));
+}
+
+/// Render prices as CSV: plain numbers, full names, and empty fields for missing values.
+pub fn render_price_csv(prices: &[CoinPrice], options: CsvOptions) -> String {
+    let optional = |value: Option<f64>| value.map(|v| v.to_string()).unwrap_or_default();
+
+    let mut writer = CsvWriter::new(Vec::new(), options);
+    let mut write = |fields: &[String]| {
+        writer
+            .write_record(fields)
+            .expect("writing CSV to memory cannot fail");
+    };
+    write(
+        &[
+            "symbol",
+            "name",
+            "price",
+            "currency",
+            "change_24h",
+            "market_cap",
+            "provider",
+            "timestamp",
+        ]
+        .map(String::from),
+    );
+    for p in prices {
+        write(&[
+            apply_symbol_case(&p.symbol, options.symbol_case),
+            p.name.clone(),
+            p.price.to_string(),
+            p.currency.clone(),
+            optional(p.change_24h),
+            optional(p.market_cap),
+            p.provider.clone(),
+            p.timestamp.to_rfc3339(),
+        ]);
+    }
+
+    String::from_utf8(writer.into_inner()).expect("CSV fields are UTF-8")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_price;
+
+    fn record(fields: &[&str], options: CsvOptions) -> String {
+        let mut writer = CsvWriter::new(Vec::new(), options);
+        writer.write_record(fields).unwrap();
+        String::from_utf8(writer.into_inner()).unwrap()
+    }
+
+    #[test]
+    fn new_rejects_ambiguous_delimiter_and_quote_chars() {
+        assert_eq!(CsvOptions::new(';', '\'').unwrap().delimiter, ';');
+        for (delimiter, quote_char) in [('"', '"'), ('\n', '"'), (',', '\r')] {
+            assert!(
+                matches!(
+                    CsvOptions::new(delimiter, quote_char),
+                    Err(Error::Config(_))
+                ),
+                "{delimiter:?} {quote_char:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn names_with_the_delimiter_are_quoted() {
+        assert_eq!(
+            record(
+                &["BRK-B", "Berkshire Hathaway, Inc."],
+                CsvOptions::default()
+            ),
+            "BRK-B,\"Berkshire Hathaway, Inc.\"\n"
+        );
+    }
+
+    #[test]
+    fn locale_formatted_prices_with_the_delimiter_are_quoted() {
+        assert_eq!(
+            record(&["BTC", "97,423.12"], CsvOptions::default()),
+            "BTC,\"97,423.12\"\n"
+        );
+        let semicolon = CsvOptions {
+            delimiter: ';',
+            ..CsvOptions::default()
+        };
+        assert_eq!(record(&["BTC", "97.423,12"], semicolon), "BTC;97.423,12\n");
+    }
+
+    #[test]
+    fn quote_chars_in_names_are_doubled() {
+        assert_eq!(
+            record(&["X", "The \"Best\" Fund"], CsvOptions::default()),
+            "X,\"The \"\"Best\"\" Fund\"\n"
+        );
+        let single = CsvOptions {
+            quote_char: '\'',
+            ..CsvOptions::default()
+        };
+        assert_eq!(record(&["X", "Moody's"], single), "X,'Moody''s'\n");
+    }
+
+    #[test]
+    fn pipe_delimited_price_output() {
+        let price = CoinPrice {
+            name: "Bitcoin | Digital Gold".to_string(),
+            change_24h: Some(-1.5),
+            provider: "CoinGecko".to_string(),
+            timestamp: chrono::DateTime::from_timestamp(1_717_243_200, 0).unwrap(),
+            ..test_price("BTC", 97_423.12)
+        };
+        let pipe = CsvOptions {
+            delimiter: '|',
+            ..CsvOptions::default()
+        };
+
+        assert_eq!(
+            render_price_csv(&[price], pipe),
+            "symbol|name|price|currency|change_24h|market_cap|provider|timestamp\n\
+             BTC|\"Bitcoin | Digital Gold\"|97423.12|USD|-1.5||CoinGecko|2024-06-01T12:00:00+00:00\n"
+        );
+    }
+}
@@ -0,0 +1,195 @@
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+/// Significant figures shown for prices too small for eight decimals.
+const SUB_CENT_SIG_FIGS: usize = 4;
+/// Smallest price eight decimals still show with [`SUB_CENT_SIG_FIGS`] digits.
+const MIN_FIXED_DECIMALS_PRICE: f64 = 1e-5;
+
+/// How ticker symbols are written in table, JSON, and CSV output (`--symbol-case`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum SymbolCase {
+    #[default]
+    Upper,
+    Lower,
+    /// Keep the symbol exactly as the provider returned it.
+    Preserve,
+}
+
+/// Rewrite `symbol` in the requested case.
+pub fn apply_symbol_case(symbol: &str, case: SymbolCase) -> String {
+    match case {
+        SymbolCase::Upper => symbol.to_uppercase(),
+        SymbolCase::Lower => symbol.to_lowercase(),
+        SymbolCase::Preserve => symbol.to_string(),
+    }
+}
+
+/// Rewrite a conversion side in the requested case, leaving fiat codes as given.
+pub fn apply_asset_case(code: &str, case: SymbolCase) -> String {
+    if crate::calc::is_known_fiat(code) {
+        code.to_string()
+    } else {
+        apply_symbol_case(code, case)
+    }
+}
+
+/// Shorten `text` to at most `max_width` terminal cells, ending it with `…` when cut.
+///
+/// Widths are measured in display cells, so a CJK character counts as two and is
+/// never split; text that already fits is returned unchanged.
+pub fn truncate_to_width(text: &str, max_width: usize) -> String {
+    if UnicodeWidthStr::width(text) <= max_width {
+        return text.to_string();
+    }
+
+    let budget = max_width.saturating_sub(1);
+    let mut width = 0;
+    let mut truncated = String::new();
+    for ch in text.chars() {
+        let ch_width = ch.width().unwrap_or(0);
+        if width + ch_width > budget {
+            break;
+        }
+        width += ch_width;
+        truncated.push(ch);
+    }
+    let mut truncated = truncated.trim_end().to_string();
+    if max_width > 0 {
+        truncated.push('…');
+    }
+    truncated
+}
+
+/// Price with its currency symbol, keeping more decimals for sub-dollar prices.
+pub(crate) fn format_price(price: f64, currency: &str) -> String {
+    let sym = currency_symbol(currency);
+    if price >= 1.0 {
+        format!("{}{}", sym, format_with_commas(price, 2))
+    } else if price >= 0.01 {
+        format!("{}{:.4}", sym, price)
+    } else if price >= MIN_FIXED_DECIMALS_PRICE || price <= 0.0 {
+        format!("{}{:.8}", sym, price)
+    } else {
+        format!(
+            "{}{}",
+            sym,
+            significant_figures_format(price, SUB_CENT_SIG_FIGS)
+        )
+    }
+}
+
+/// `value` rounded to `sig` significant figures in plain decimal notation,
+/// e.g. `0.000000001235` rather than `1.235e-9`.
+pub(crate) fn significant_figures_format(value: f64, sig: usize) -> String {
+    if value == 0.0 || !value.is_finite() {
+        return value.to_string();
+    }
+    let magnitude = value.abs().log10().floor() as i32;
+    let decimals = (sig as i32 - 1 - magnitude).max(0) as usize;
+    format!("{value:.decimals$}")
+}
+
+pub(crate) fn format_with_commas(value: f64, decimals: usize) -> String {
+    let formatted = format!("{value:.decimals$}");
+    let parts: Vec<&str> = formatted.split('.').collect();
+    let whole = parts[0];
+
+    let mut result = String::new();
+    for (i, ch) in whole.chars().rev().enumerate() {
+        if i > 0 && i % 3 == 0 {
+            result.push(',');
+        }
+        result.push(ch);
+    }
+    let whole_formatted: String = result.chars().rev().collect();
+
+    if parts.len() > 1 {
+        format!("{}.{}", whole_formatted, parts[1])
+    } else {
+        whole_formatted
+    }
+}
+
+pub(crate) fn currency_symbol(currency: &str) -> &str {
+    match currency.to_uppercase().as_str() {
+        "USD" => "$",
+        "EUR" => "\u{20ac}",
+        "GBP" => "\u{00a3}",
+        "JPY" | "CNY" => "\u{00a5}",
+        "CAD" => "CA$",
+        "AUD" => "A$",
+        "CHF" => "CHF ",
+        "BTC" => "\u{20bf}",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn apply_symbol_case_upper() {
+        assert_eq!(apply_symbol_case("btc", SymbolCase::Upper), "BTC");
+        assert_eq!(apply_symbol_case("brk-b", SymbolCase::Upper), "BRK-B");
+    }
+
+    #[test]
+    fn apply_symbol_case_lower() {
+        assert_eq!(apply_symbol_case("BTC", SymbolCase::Lower), "btc");
+        assert_eq!(apply_symbol_case("CW8.PA", SymbolCase::Lower), "cw8.pa");
+    }
+
+    #[test]
+    fn apply_symbol_case_preserve() {
+        assert_eq!(apply_symbol_case("BTC", SymbolCase::Preserve), "BTC");
+        assert_eq!(apply_symbol_case("^gspc", SymbolCase::Preserve), "^gspc");
+    }
+
+    #[test]
+    fn tiny_prices_keep_their_significant_digits() {
+        assert_eq!(format_price(0.00123456, "usd"), "$0.00123456");
+        assert_eq!(format_price(0.00001234, "usd"), "$0.00001234");
+        assert_eq!(format_price(0.00000123456, "usd"), "$0.000001235");
+        assert_eq!(format_price(1.2e-9, "eur"), "\u{20ac}0.000000001200");
+        assert_eq!(format_price(9.87654e-13, "usd"), "$0.0000000000009877");
+        assert_eq!(format_price(0.0, "usd"), "$0.00000000");
+    }
+
+    #[test]
+    fn significant_figures_format_rounds_by_magnitude() {
+        assert_eq!(significant_figures_format(0.000123456, 3), "0.000123");
+        assert_eq!(significant_figures_format(-0.0000045678, 2), "-0.0000046");
+        assert_eq!(significant_figures_format(0.0, 4), "0");
+    }
+
+    #[test]
+    fn truncate_to_width_leaves_short_names_unchanged() {
+        assert_eq!(truncate_to_width("Bitcoin", 10), "Bitcoin");
+        assert_eq!(truncate_to_width("Bitcoin", 7), "Bitcoin");
+        assert_eq!(truncate_to_width("", 3), "");
+    }
+
+    #[test]
+    fn truncate_to_width_cuts_long_names_with_an_ellipsis() {
+        assert_eq!(
+            truncate_to_width("iShares Core MSCI World UCITS ETF", 12),
+            "iShares Cor…"
+        );
+        // Trailing spaces before the ellipsis are dropped.
+        assert_eq!(truncate_to_width("Amundi MSCI World", 8), "Amundi…");
+        assert_eq!(truncate_to_width("Bitcoin", 1), "…");
+    }
+
+    #[test]
+    fn truncate_to_width_counts_display_cells_for_multibyte_names() {
+        // Each CJK character is two cells wide and is never split.
+        let name = "日本株式インデックス";
+        assert_eq!(truncate_to_width(name, 7), "日本株…");
+        assert_eq!(
+            UnicodeWidthStr::width(truncate_to_width(name, 8).as_str()),
+            7
+        );
+        assert_eq!(truncate_to_width("Société Générale", 8), "Société…");
+    }
+}
@@ -1,35 +1,537 @@
-use crate::calc::Conversion;
+use std::path::Path;
+
+use serde::Serialize;
+use serde_json::Value;
+use serde_json_path::JsonPath;
+
+use crate::calc::{
+    AlertResult, Allocation, Conversion, ConversionMatrix, CurrencySupport, HistoryStats, Movers,
+    Performance, ProviderComparison, SearchFilter, TwoDateComparison, align::AlignedHistories,
+};
 use crate::error::Result;
-use crate::provider::{CoinPrice, PriceHistory, TickerMatch};
+use crate::output::format::{SymbolCase, apply_asset_case, apply_symbol_case};
+use crate::portfolio::{HoldingValue, PortfolioTotals};
+use crate::provider::{
+    AssetInfo, CoinDetails, CoinPrice, PriceHistory, ProviderInfo, SymbolError, SymbolResolution,
+    TickerMatch,
+};
+
+/// How JSON printed to stdout is shaped.
+#[derive(Debug, Clone, Default)]
+pub struct JsonOptions {
+    /// Print only the nodes matched by this JSONPath expression (`--json-path`).
+    pub path: Option<JsonPath>,
+    /// Print on a single line instead of pretty-printing (`--json-compact`).
+    pub compact: bool,
+    /// Letter case of ticker symbols (`--symbol-case`).
+    pub symbol_case: SymbolCase,
+}
+
+/// Parse a `--json-path` expression.
+pub fn parse_json_path(expr: &str) -> Result<JsonPath> {
+    JsonPath::parse(expr)
+        .map_err(|e| crate::error::Error::Config(format!("invalid --json-path '{}': {}", expr, e)))
+}
+
+/// Collect the nodes of `value` matched by `path` into an array (`[]` when none match).
+pub fn apply_json_path(value: &Value, path: &JsonPath) -> Value {
+    Value::Array(path.query(value).all().into_iter().cloned().collect())
+}
+
+/// Serialize `value` as pretty JSON, or on a single line when `compact`.
+fn to_json<T: Serialize + ?Sized>(value: &T, compact: bool) -> Result<String> {
+    let output = if compact {
+        serde_json::to_string(value)
+    } else {
+        serde_json::to_string_pretty(value)
+    };
+    output.map_err(|e| crate::error::Error::Io(format!("JSON serialize: {}", e)))
+}
+
+/// Serialize `value` for stdout, applying `--json-compact` and the `--json-path` filter when set.
+fn render<T: Serialize + ?Sized>(value: &T, options: &JsonOptions) -> Result<String> {
+    match &options.path {
+        Some(path) => {
+            let value = serde_json::to_value(value)
+                .map_err(|e| crate::error::Error::Io(format!("JSON serialize: {}", e)))?;
+            to_json(&apply_json_path(&value, path), options.compact)
+        }
+        None => to_json(value, options.compact),
+    }
+}
+
+/// Envelope pairing resolved prices with per-symbol lookup failures.
+#[derive(Serialize)]
+struct PriceEnvelope<'a> {
+    prices: &'a [CoinPrice],
+    errors: &'a [SymbolError],
+}
+
+/// Prices kept under `--allow-partial` with the symbols the provider failed on.
+#[derive(Serialize)]
+struct PartialPrices<'a> {
+    prices: &'a [CoinPrice],
+    missing: Vec<String>,
+}
+
+/// Filtered ticker search results together with the filters that produced them.
+#[derive(Serialize)]
+struct FilteredMatches<'a> {
+    filters: &'a SearchFilter,
+    matches: &'a [TickerMatch],
+}
+
+/// Machine-readable build and capability summary for `--version --json`.
+#[derive(Serialize)]
+struct VersionInfo<'a> {
+    name: &'a str,
+    version: &'a str,
+    providers: &'a [String],
+}
+
+/// Print the program name, version, and available provider ids as formatted JSON to stdout.
+pub fn print_version_json(
+    name: &str,
+    version: &str,
+    providers: &[String],
+    options: &JsonOptions,
+) -> Result<()> {
+    let output = render(
+        &VersionInfo {
+            name,
+            version,
+            providers,
+        },
+        options,
+    )?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Copy `items` with the symbol picked by `symbol` rewritten in `case`.
+fn cased<T: Clone>(
+    items: &[T],
+    case: SymbolCase,
+    symbol: impl Fn(&mut T) -> &mut String,
+) -> Vec<T> {
+    items
+        .iter()
+        .map(|item| {
+            let mut item = item.clone();
+            let symbol = symbol(&mut item);
+            *symbol = apply_symbol_case(symbol, case);
+            item
+        })
+        .collect()
+}
+
+/// Copy `prices` with each symbol rewritten in `case`.
+fn cased_prices(prices: &[CoinPrice], case: SymbolCase) -> Vec<CoinPrice> {
+    cased(prices, case, |price| &mut price.symbol)
+}
+
+/// Copy `errors` with each requested symbol rewritten in `case`.
+fn cased_errors(errors: &[SymbolError], case: SymbolCase) -> Vec<SymbolError> {
+    cased(errors, case, |error| &mut error.symbol)
+}
 
 /// Print prices as formatted JSON to stdout.
-pub fn print_json(prices: &[CoinPrice]) -> Result<()> {
-    let output = serde_json::to_string_pretty(prices)
-        .map_err(|e| crate::error::Error::Parse(format!("JSON serialize: {}", e)))?;
+pub fn print_json(prices: &[CoinPrice], options: &JsonOptions) -> Result<()> {
+    let output = render(&cased_prices(prices, options.symbol_case), options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print prices and unresolved symbols as a formatted JSON object to stdout.
+pub fn print_price_envelope_json(
+    prices: &[CoinPrice],
+    errors: &[SymbolError],
+    options: &JsonOptions,
+) -> Result<()> {
+    let prices = cased_prices(prices, options.symbol_case);
+    let errors = cased_errors(errors, options.symbol_case);
+    let envelope = PriceEnvelope {
+        prices: &prices,
+        errors: &errors,
+    };
+    let output = render(&envelope, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print `--allow-partial` prices and the missing symbols as a formatted JSON object to stdout.
+pub fn print_partial_prices_json(
+    prices: &[CoinPrice],
+    errors: &[SymbolError],
+    options: &JsonOptions,
+) -> Result<()> {
+    let output = render_partial_prices_json(prices, errors, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+fn render_partial_prices_json(
+    prices: &[CoinPrice],
+    errors: &[SymbolError],
+    options: &JsonOptions,
+) -> Result<String> {
+    let prices = cased_prices(prices, options.symbol_case);
+    render(
+        &PartialPrices {
+            prices: &prices,
+            missing: errors
+                .iter()
+                .map(|error| apply_symbol_case(&error.symbol, options.symbol_case))
+                .collect(),
+        },
+        options,
+    )
+}
+
+/// Print per-provider quotes with median and spread as formatted JSON to stdout.
+pub fn print_provider_comparisons_json(
+    comparisons: &[ProviderComparison],
+    options: &JsonOptions,
+) -> Result<()> {
+    let comparisons = cased(comparisons, options.symbol_case, |c| &mut c.symbol);
+    let output = render(&comparisons, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print an asset profile as a formatted JSON object to stdout.
+pub fn print_asset_info_json(info: &AssetInfo, options: &JsonOptions) -> Result<()> {
+    let output = render(info, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print coin metadata as a formatted JSON array to stdout.
+pub fn print_coin_details_json(details: &[CoinDetails], options: &JsonOptions) -> Result<()> {
+    let details = cased(details, options.symbol_case, |d| &mut d.symbol);
+    let output = render(&details, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print a forex conversion matrix as a formatted JSON object to stdout.
+pub fn print_conversion_matrix_json(
+    matrix: &ConversionMatrix,
+    options: &JsonOptions,
+) -> Result<()> {
+    let output = render(matrix, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print quote currency support as a formatted JSON array to stdout.
+pub fn print_currencies_json(currencies: &[CurrencySupport], options: &JsonOptions) -> Result<()> {
+    let output = render(currencies, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print 24h gainers and losers as a formatted JSON object to stdout.
+pub fn print_movers_json(movers: &Movers, options: &JsonOptions) -> Result<()> {
+    let movers = Movers {
+        gainers: cased_prices(&movers.gainers, options.symbol_case),
+        losers: cased_prices(&movers.losers, options.symbol_case),
+    };
+    let output = render(&movers, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print trailing period returns as a formatted JSON array to stdout.
+pub fn print_performance_json(performance: &[Performance], options: &JsonOptions) -> Result<()> {
+    let performance = cased(performance, options.symbol_case, |p| &mut p.symbol);
+    let output = render(&performance, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print two-date price comparisons as a formatted JSON array to stdout.
+pub fn print_two_date_json(comparisons: &[TwoDateComparison], options: &JsonOptions) -> Result<()> {
+    let comparisons = cased(comparisons, options.symbol_case, |c| &mut c.symbol);
+    let output = render(&comparisons, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print `--stats` summaries as a formatted JSON array to stdout.
+pub fn print_return_stats_json(stats: &[HistoryStats], options: &JsonOptions) -> Result<()> {
+    let stats = cased(stats, options.symbol_case, |s| &mut s.symbol);
+    let output = render(&stats, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print a `--price-alert` verdict as formatted JSON to stdout.
+pub fn print_alert_json(result: &AlertResult, options: &JsonOptions) -> Result<()> {
+    let output = render(result, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print portfolio allocation shares as formatted JSON to stdout.
+pub fn print_allocation_json(allocations: &[Allocation], options: &JsonOptions) -> Result<()> {
+    let allocations = cased(allocations, options.symbol_case, |a| &mut a.symbol);
+    let output = render(&allocations, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Portfolio holdings together with their aggregate totals.
+#[derive(Serialize)]
+struct PortfolioReport<'a> {
+    holdings: &'a [HoldingValue],
+    total: &'a PortfolioTotals,
+}
+
+/// Print portfolio valuation as formatted JSON to stdout.
+pub fn print_portfolio_json(
+    holdings: &[HoldingValue],
+    total: &PortfolioTotals,
+    options: &JsonOptions,
+) -> Result<()> {
+    let holdings = cased(holdings, options.symbol_case, |h| &mut h.symbol);
+    let report = PortfolioReport {
+        holdings: &holdings,
+        total,
+    };
+    let output = render(&report, options)?;
     println!("{}", output);
     Ok(())
 }
 
 /// Print fiat-to-crypto conversions as formatted JSON to stdout.
-pub fn print_conversions_json(conversions: &[Conversion]) -> Result<()> {
-    let output = serde_json::to_string_pretty(conversions)
-        .map_err(|e| crate::error::Error::Parse(format!("JSON serialize: {}", e)))?;
+pub fn print_conversions_json(conversions: &[Conversion], options: &JsonOptions) -> Result<()> {
+    let conversions: Vec<Conversion> = conversions
+        .iter()
+        .map(|c| Conversion {
+            from_currency: apply_asset_case(&c.from_currency, options.symbol_case),
+            to_symbol: apply_asset_case(&c.to_symbol, options.symbol_case),
+            ..c.clone()
+        })
+        .collect();
+    let output = render(&conversions, options)?;
     println!("{}", output);
     Ok(())
 }
 
 /// Print historical prices as formatted JSON to stdout.
-pub fn print_history_json(histories: &[PriceHistory]) -> Result<()> {
+pub fn print_history_json(histories: &[PriceHistory], options: &JsonOptions) -> Result<()> {
+    let histories = cased(histories, options.symbol_case, |h| &mut h.symbol);
+    let output = render(&histories, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print histories aligned onto shared timestamps (`--history-format wide`) to stdout.
+pub fn print_aligned_history_json(aligned: &AlignedHistories, options: &JsonOptions) -> Result<()> {
+    let aligned = AlignedHistories {
+        timestamps: aligned.timestamps.clone(),
+        columns: cased(&aligned.columns, options.symbol_case, |c| &mut c.symbol),
+    };
+    let output = render(&aligned, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Print provider capabilities as formatted JSON to stdout.
+pub fn print_providers_json(providers: &[ProviderInfo], options: &JsonOptions) -> Result<()> {
+    let output = render(providers, options)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Write historical prices to `path` in the same format as `--chart --json`.
+pub fn write_history_json(histories: &[PriceHistory], path: &Path) -> Result<()> {
     let output = serde_json::to_string_pretty(histories)
-        .map_err(|e| crate::error::Error::Parse(format!("JSON serialize: {}", e)))?;
+        .map_err(|e| crate::error::Error::Io(format!("JSON serialize: {}", e)))?;
+    std::fs::write(path, output + "\n").map_err(|e| {
+        crate::error::Error::Io(format!(
+            "failed to write history file '{}': {}",
+            path.display(),
+            e
+        ))
+    })
+}
+
+/// Read historical prices previously written by [`write_history_json`].
+pub fn read_history_json(path: &Path) -> Result<Vec<PriceHistory>> {
+    let raw = std::fs::read_to_string(path).map_err(|e| {
+        crate::error::Error::Io(format!(
+            "failed to read history file '{}': {}",
+            path.display(),
+            e
+        ))
+    })?;
+    serde_json::from_str(&raw)
+        .map_err(|e| crate::error::Error::Io(format!("history file '{}': {}", path.display(), e)))
+}
+
+/// Print per-provider symbol resolutions as formatted JSON to stdout.
+pub fn print_symbol_resolutions_json(
+    resolutions: &[SymbolResolution],
+    options: &JsonOptions,
+) -> Result<()> {
+    let output = render(resolutions, options)?;
     println!("{}", output);
     Ok(())
 }
 
 /// Print ticker search matches as formatted JSON to stdout.
-pub fn print_ticker_matches_json(matches: &[TickerMatch]) -> Result<()> {
-    let output = serde_json::to_string_pretty(matches)
-        .map_err(|e| crate::error::Error::Parse(format!("JSON serialize: {}", e)))?;
+///
+/// With `--type`/`--exchange` set, prints `{"filters": {...}, "matches": [...]}` instead.
+pub fn print_ticker_matches_json(
+    matches: &[TickerMatch],
+    filter: &SearchFilter,
+    options: &JsonOptions,
+) -> Result<()> {
+    let output = if filter.is_empty() {
+        render(matches, options)?
+    } else {
+        render(
+            &FilteredMatches {
+                filters: filter,
+                matches,
+            },
+            options,
+        )?
+    };
     println!("{}", output);
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::PricePoint;
+    use crate::provider::test_price;
+
+    #[test]
+    fn apply_json_path_extracts_matching_nodes() {
+        let value =
+            serde_json::to_value([test_price("BTC", 50000.0), test_price("ETH", 2500.5)]).unwrap();
+
+        let prices = apply_json_path(&value, &JsonPath::parse("$[*].price").unwrap());
+        assert_eq!(prices, serde_json::json!([50000.0, 2500.5]));
+
+        let eth = apply_json_path(
+            &value,
+            &JsonPath::parse("$[?@.symbol == 'ETH'].symbol").unwrap(),
+        );
+        assert_eq!(eth, serde_json::json!(["ETH"]));
+    }
+
+    #[test]
+    fn compact_json_stays_on_one_line() {
+        let prices = [test_price("BTC", 50000.0), test_price("ETH", 2500.5)];
+
+        let options = JsonOptions {
+            compact: true,
+            ..JsonOptions::default()
+        };
+        let compact = render(&prices[..], &options).unwrap();
+        assert!(!compact.contains('\n'));
+        assert_eq!(
+            serde_json::from_str::<Value>(&compact).unwrap(),
+            serde_json::to_value(&prices[..]).unwrap()
+        );
+        assert!(
+            render(&prices[..], &JsonOptions::default())
+                .unwrap()
+                .contains('\n')
+        );
+    }
+
+    #[test]
+    fn apply_json_path_returns_empty_array_without_matches() {
+        let value = serde_json::to_value([test_price("BTC", 50000.0)]).unwrap();
+
+        let none = apply_json_path(&value, &JsonPath::parse("$[*].volume").unwrap());
+        assert_eq!(none, serde_json::json!([]));
+    }
+
+    fn history() -> PriceHistory {
+        PriceHistory {
+            symbol: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            currency: "USD".to_string(),
+            provider: "CoinGecko".to_string(),
+            points: vec![
+                PricePoint {
+                    timestamp: chrono::DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+                    price: 40000.0,
+                    filled: false,
+                },
+                PricePoint {
+                    timestamp: chrono::DateTime::from_timestamp(1_700_086_400, 0).unwrap(),
+                    price: 41234.56,
+                    filled: false,
+                },
+            ],
+            events: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn history_export_round_trips_through_import() {
+        let dir = tempfile::TempDir::new().unwrap();
+        let path = dir.path().join("btc.json");
+        let histories = vec![history()];
+
+        write_history_json(&histories, &path).unwrap();
+        let imported = read_history_json(&path).unwrap();
+
+        assert_eq!(imported.len(), 1);
+        assert_eq!(imported[0].symbol, "BTC");
+        assert_eq!(imported[0].provider, "CoinGecko");
+        assert_eq!(imported[0].points.len(), 2);
+        assert_eq!(
+            imported[0].points[1].timestamp,
+            histories[0].points[1].timestamp
+        );
+        assert_eq!(imported[0].points[1].price, 41234.56);
+
+        let stdout_format = serde_json::to_string_pretty(&histories).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(&path).unwrap().trim_end(),
+            stdout_format
+        );
+    }
+
+    #[test]
+    fn history_import_reports_missing_and_malformed_files() {
+        let dir = tempfile::TempDir::new().unwrap();
+
+        let missing = read_history_json(&dir.path().join("missing.json")).unwrap_err();
+        assert!(matches!(missing, crate::error::Error::Io(_)));
+
+        let malformed = dir.path().join("bad.json");
+        std::fs::write(&malformed, "{not json").unwrap();
+        let err = read_history_json(&malformed).unwrap_err();
+        assert!(matches!(err, crate::error::Error::Io(_)));
+    }
+
+    #[test]
+    fn partial_prices_list_missing_symbols_next_to_prices() {
+        let errors = [SymbolError {
+            symbol: "AAPL".to_string(),
+            tried: vec!["mexc".to_string()],
+            suggestions: Vec::new(),
+        }];
+
+        let output = render_partial_prices_json(
+            &[test_price("BTC", 50000.0)],
+            &errors,
+            &JsonOptions::default(),
+        )
+        .unwrap();
+        let value: Value = serde_json::from_str(&output).unwrap();
+
+        assert_eq!(value["prices"][0]["symbol"], "BTC");
+        assert_eq!(value["missing"], serde_json::json!(["AAPL"]));
+    }
+}
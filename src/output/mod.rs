@@ -1,3 +1,11 @@
+#[cfg(feature = "cli")]
 pub mod chart;
+pub mod csv;
+pub mod format;
+#[cfg(feature = "cli")]
 pub mod json;
+#[cfg(feature = "cli")]
+pub mod progress;
+pub mod raw;
+#[cfg(feature = "cli")]
 pub mod table;
@@ -0,0 +1,69 @@
+use std::io::IsTerminal;
+use std::time::Duration;
+
+use indicatif::{ProgressBar, ProgressStyle};
+
+/// Fetch progress shown on stderr as `Fetching 12/30…` while concurrent requests run.
+///
+/// The bar is cleared when finished or dropped, so it never mixes with results.
+pub struct Progress {
+    bar: Option<ProgressBar>,
+}
+
+impl Progress {
+    /// Show progress for `total` requests unless `quiet`, stderr is not a terminal,
+    /// or there is only one request to wait on.
+    pub fn new(total: usize, quiet: bool) -> Self {
+        if quiet || total < 2 || !std::io::stderr().is_terminal() {
+            return Self::hidden();
+        }
+
+        let bar = ProgressBar::new(total as u64);
+        bar.set_style(
+            ProgressStyle::with_template("{spinner} Fetching {pos}/{len}…")
+                .unwrap_or_else(|_| ProgressStyle::default_spinner()),
+        );
+        bar.enable_steady_tick(Duration::from_millis(100));
+        Self { bar: Some(bar) }
+    }
+
+    /// A progress indicator that never draws anything.
+    pub fn hidden() -> Self {
+        Self { bar: None }
+    }
+
+    /// Record one completed request.
+    pub fn inc(&self) {
+        if let Some(bar) = &self.bar {
+            bar.inc(1);
+        }
+    }
+
+    /// Clear the indicator from the terminal.
+    pub fn finish(&self) {
+        if let Some(bar) = &self.bar {
+            bar.finish_and_clear();
+        }
+    }
+}
+
+impl Drop for Progress {
+    fn drop(&mut self) {
+        self.finish();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quiet_or_single_request_progress_stays_hidden() {
+        assert!(Progress::new(30, true).bar.is_none());
+        assert!(Progress::new(1, false).bar.is_none());
+
+        let progress = Progress::hidden();
+        progress.inc();
+        progress.finish();
+    }
+}
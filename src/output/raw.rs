@@ -0,0 +1,26 @@
+/// Print each value on its own line with no symbol, currency, or separators.
+pub fn print_values(values: &[f64]) {
+    print!("{}", render_values(values));
+}
+
+/// Render values as plain decimal floats, one per line.
+///
+/// `f64`'s `Display` never switches to exponent notation, so every line parses
+/// as a number in `awk`, `bc`, or shell arithmetic tools.
+pub fn render_values(values: &[f64]) -> String {
+    values.iter().map(|value| format!("{value}\n")).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn render_values_prints_one_plain_float_per_line() {
+        assert_eq!(
+            render_values(&[97_000.5, 0.000_012_3, 3.0]),
+            "97000.5\n0.0000123\n3\n"
+        );
+        assert_eq!(render_values(&[]), "");
+    }
+}
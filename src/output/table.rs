@@ -1,13 +1,31 @@
 use colored::Colorize;
-use tabled::settings::Style;
+use tabled::builder::Builder;
+use tabled::settings::location::ByColumnName;
+use tabled::settings::object::Rows;
+use tabled::settings::themes::Theme;
+use tabled::settings::{Format, Modify, Remove, Style};
 use tabled::{Table, Tabled};
 
-use crate::calc::{self, Conversion};
+use crate::calc::{
+    self, Allocation, Conversion, ConversionMatrix, CurrencySupport, HistoryStats, Movers,
+    Performance, PerformancePeriod, ProviderComparison, TwoDateComparison,
+    resample::ResamplePeriod,
+};
 use crate::output::chart;
-use crate::provider::{CoinPrice, HistoryInterval, PriceHistory, TickerMatch};
+use crate::output::format::{
+    SymbolCase, apply_asset_case, apply_symbol_case, currency_symbol, format_price,
+    format_with_commas, truncate_to_width,
+};
+use crate::portfolio::{HoldingValue, PortfolioTotals};
+use crate::provider::{
+    AssetInfo, ChangeBasis, CoinDetails, CoinPrice, Fundamentals, HistoryEvent, HistoryInterval,
+    MarketState, PriceHistory, ProviderInfo, SymbolError, SymbolResolution, TickerMatch,
+};
 
 #[derive(Tabled)]
 struct PriceRow {
+    #[tabled(rename = "#")]
+    rank: String,
     #[tabled(rename = "Symbol")]
     symbol: String,
     #[tabled(rename = "Name")]
@@ -18,37 +36,330 @@ struct PriceRow {
     change_24h: String,
     #[tabled(rename = "Market Cap")]
     market_cap: String,
+    #[tabled(rename = "24h High")]
+    high_24h: String,
+    #[tabled(rename = "24h Low")]
+    low_24h: String,
+    #[tabled(rename = "P/E")]
+    trailing_pe: String,
+    #[tabled(rename = "Div Yield")]
+    dividend_yield: String,
+    #[tabled(rename = "52W Range")]
+    week_52_range: String,
     #[tabled(rename = "Provider")]
     provider: String,
 }
 
+#[derive(Tabled)]
+struct OhlcRow {
+    #[tabled(rename = "Symbol")]
+    symbol: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Open")]
+    open: String,
+    #[tabled(rename = "High")]
+    high: String,
+    #[tabled(rename = "Low")]
+    low: String,
+    #[tabled(rename = "Close")]
+    close: String,
+    #[tabled(rename = "24h Change")]
+    change_24h: String,
+    #[tabled(rename = "Provider")]
+    provider: String,
+}
+
+/// Width, in terminal columns, of the charts printed by [`print_history_charts`].
+pub const CHART_WIDTH: u16 = 96;
+const CHART_HEIGHT: u16 = 18;
+
+/// How human-readable tables are drawn.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputStyle {
+    /// Rounded box-drawing borders.
+    #[default]
+    Table,
+    /// Space-aligned columns without borders, for pipes and files.
+    Plain,
+}
+
+impl OutputStyle {
+    fn theme(self) -> Theme {
+        match self {
+            Self::Table => Theme::from_style(Style::rounded()),
+            Self::Plain => Theme::from_style(Style::blank()),
+        }
+    }
+}
+
+/// How tables are drawn and which optional price table columns they show.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct TableOptions {
+    /// Borders for terminals, or plain aligned columns for pipes.
+    pub style: OutputStyle,
+    /// Letter case of ticker symbols (`--symbol-case`).
+    pub symbol_case: SymbolCase,
+    /// Truncate names to this many display cells (`--max-name-width`).
+    pub max_name_width: Option<usize>,
+    /// Show `24h High` / `24h Low` when at least one price carries them.
+    pub show_high_low: bool,
+    /// Append the market state (e.g. `[OPEN]`, `[24/7]`) to each symbol.
+    pub show_market_status: bool,
+    /// Prefix 24h changes with a direction arrow, treating changes smaller than
+    /// this many percent as flat.
+    pub change_arrow_threshold: Option<f64>,
+    /// Time zone for displayed timestamps (`--tz`).
+    pub tz: chart::DisplayTz,
+}
+
 /// Print prices as a styled table to stdout.
-pub fn print_table(prices: &[CoinPrice]) {
+pub fn print_table(prices: &[CoinPrice], options: &TableOptions) {
+    println!("{}", render_price_table(prices, options));
+    if let Some(footer) = render_freshness_footer(prices, options.tz) {
+        println!("{}", footer);
+    }
+}
+
+/// Footer stating how recent the quotes are, e.g. `As of 2024-06-01 12:00 UTC`.
+///
+/// Rows fetched at different minutes show the oldest and newest timestamp.
+pub fn render_freshness_footer(prices: &[CoinPrice], tz: chart::DisplayTz) -> Option<String> {
+    const PATTERN: &str = "%Y-%m-%d %H:%M %Z";
+
+    let oldest = prices.iter().map(|p| p.timestamp).min()?;
+    let newest = prices.iter().map(|p| p.timestamp).max()?;
+    let oldest = tz.format(oldest, PATTERN);
+    let newest = tz.format(newest, PATTERN);
+    if oldest == newest {
+        Some(format!("As of {}", oldest))
+    } else {
+        Some(format!("As of {} to {}", oldest, newest))
+    }
+}
+
+/// Render prices as a styled table.
+///
+/// Column widths are measured in terminal display cells (not chars), so
+/// names with CJK characters or emoji stay aligned with their neighbours.
+pub fn render_price_table(prices: &[CoinPrice], options: &TableOptions) -> String {
     let rows: Vec<PriceRow> = prices
         .iter()
-        .map(|p| {
-            let change_str = match p.change_24h {
-                Some(c) if c >= 0.0 => format!("+{:.2}%", c).green().to_string(),
-                Some(c) => format!("{:.2}%", c).red().to_string(),
-                None => "-".dimmed().to_string(),
-            };
+        .map(|p| PriceRow {
+            rank: p
+                .rank
+                .map_or_else(|| "-".to_string(), |rank| rank.to_string()),
+            symbol: format_symbol(p, options),
+            name: format_name(&p.name, options),
+            price: format_price(p.price, &p.currency),
+            change_24h: format_price_change(p.change_24h, options),
+            market_cap: match p.market_cap {
+                Some(cap) => format_market_cap(cap, &p.currency),
+                None => "-".to_string(),
+            },
+            high_24h: format_optional_price(p.high_24h, &p.currency),
+            low_24h: format_optional_price(p.low_24h, &p.currency),
+            trailing_pe: fundamental(p, |f| f.trailing_pe.map(|pe| format!("{:.2}", pe))),
+            dividend_yield: fundamental(p, |f| f.dividend_yield.map(|pct| format!("{:.2}%", pct))),
+            week_52_range: fundamental(p, |f| {
+                (f.week_52_low.is_some() || f.week_52_high.is_some()).then(|| {
+                    format!(
+                        "{} - {}",
+                        format_optional_price(f.week_52_low, &p.currency),
+                        format_optional_price(f.week_52_high, &p.currency)
+                    )
+                })
+            }),
+            provider: p.provider.clone().dimmed().to_string(),
+        })
+        .collect();
 
-            PriceRow {
-                symbol: p.symbol.clone().bold().to_string(),
-                name: p.name.clone(),
-                price: format_price(p.price, &p.currency),
-                change_24h: change_str,
-                market_cap: match p.market_cap {
-                    Some(cap) => format_market_cap(cap, &p.currency),
-                    None => "-".to_string(),
-                },
-                provider: p.provider.clone().dimmed().to_string(),
-            }
+    let has_high_low = prices
+        .iter()
+        .any(|p| p.high_24h.is_some() || p.low_24h.is_some());
+
+    let mut table = Table::new(rows);
+    table.with(options.style.theme());
+    label_change_column(&mut table, prices);
+    if !(options.show_high_low && has_high_low) {
+        table
+            .with(Remove::column(ByColumnName::new("24h High")))
+            .with(Remove::column(ByColumnName::new("24h Low")));
+    }
+    if prices.iter().all(|p| p.fundamentals.is_none()) {
+        table
+            .with(Remove::column(ByColumnName::new("P/E")))
+            .with(Remove::column(ByColumnName::new("Div Yield")))
+            .with(Remove::column(ByColumnName::new("52W Range")));
+    }
+    if prices.iter().all(|p| p.rank.is_none()) {
+        table.with(Remove::column(ByColumnName::new("#")));
+    }
+    table.to_string()
+}
+
+/// Format one fundamentals figure of `price`, or `-` when it is unknown.
+fn fundamental(price: &CoinPrice, format: impl Fn(&Fundamentals) -> Option<String>) -> String {
+    price
+        .fundamentals
+        .as_ref()
+        .and_then(format)
+        .unwrap_or_else(|| "-".to_string())
+}
+
+/// Print prices as an open/high/low/close table to stdout.
+pub fn print_ohlc_table(prices: &[CoinPrice], options: &TableOptions) {
+    println!("{}", render_ohlc_table(prices, options));
+}
+
+/// Render prices as `Open | High | Low | Close` rows; the latest price is the close.
+pub fn render_ohlc_table(prices: &[CoinPrice], options: &TableOptions) -> String {
+    let rows: Vec<OhlcRow> = prices
+        .iter()
+        .map(|p| OhlcRow {
+            symbol: format_symbol(p, options),
+            name: format_name(&p.name, options),
+            open: format_optional_price(p.open_24h, &p.currency),
+            high: format_optional_price(p.high_24h, &p.currency),
+            low: format_optional_price(p.low_24h, &p.currency),
+            close: format_price(p.price, &p.currency),
+            change_24h: format_price_change(p.change_24h, options),
+            provider: p.provider.clone().dimmed().to_string(),
         })
         .collect();
 
-    let table = Table::new(rows).with(Style::rounded()).to_string();
-    println!("{}", table);
+    let mut table = Table::new(rows);
+    table.with(options.style.theme());
+    label_change_column(&mut table, prices);
+    table.to_string()
+}
+
+/// Header for the change column, named after what the shown changes are measured against.
+///
+/// Stock quotes compare with the previous session's close, which is Friday's on a
+/// Monday, so calling that a 24h change would be misleading. Mixed bases get a
+/// neutral `Change`.
+pub fn change_column_label(prices: &[CoinPrice]) -> &'static str {
+    let mut bases = prices
+        .iter()
+        .filter(|p| p.change_24h.is_some())
+        .map(|p| p.change_basis.unwrap_or(ChangeBasis::Rolling24h));
+    let Some(first) = bases.next() else {
+        return "24h Change";
+    };
+    if bases.any(|basis| basis != first) {
+        return "Change";
+    }
+    match first {
+        ChangeBasis::Rolling24h => "24h Change",
+        ChangeBasis::PreviousClose => "Change (prev close)",
+        ChangeBasis::SessionOpen => "Change (since open)",
+        ChangeBasis::Days7 => "7d Change",
+        ChangeBasis::Days30 => "30d Change",
+        ChangeBasis::Days90 => "90d Change",
+        ChangeBasis::Year1 => "1y Change",
+    }
+}
+
+fn label_change_column(table: &mut Table, prices: &[CoinPrice]) {
+    let label = change_column_label(prices);
+    table.with(Modify::new(Rows::first()).with(Format::content(|header| {
+        if header == "24h Change" {
+            label.to_string()
+        } else {
+            header.to_string()
+        }
+    })));
+}
+
+fn format_symbol(price: &CoinPrice, options: &TableOptions) -> String {
+    let symbol = apply_symbol_case(&price.symbol, options.symbol_case)
+        .bold()
+        .to_string();
+    match price.market_state {
+        Some(state) if options.show_market_status => {
+            format!("{} {}", symbol, format_market_state(state))
+        }
+        _ => symbol,
+    }
+}
+
+/// A `Name` cell, truncated to `--max-name-width` when one was given.
+fn format_name(name: &str, options: &TableOptions) -> String {
+    match options.max_name_width {
+        Some(width) => truncate_to_width(name, width),
+        None => name.to_string(),
+    }
+}
+
+fn format_market_state(state: MarketState) -> String {
+    let label = format!("[{}]", state.label());
+    match state {
+        MarketState::Open => label.green().to_string(),
+        MarketState::Closed => label.red().to_string(),
+        MarketState::PreMarket | MarketState::PostMarket => label.yellow().to_string(),
+        MarketState::Always => label.dimmed().to_string(),
+    }
+}
+
+fn format_optional_price(value: Option<f64>, currency: &str) -> String {
+    match value {
+        Some(v) => format_price(v, currency),
+        None => "-".to_string(),
+    }
+}
+
+/// Print gainers and losers as two titled price tables to stdout.
+pub fn print_movers(movers: &Movers, options: &TableOptions) {
+    println!("{}", render_movers(movers, options));
+}
+
+/// Render a `Gainers` section followed by a `Losers` section.
+pub fn render_movers(movers: &Movers, options: &TableOptions) -> String {
+    let section = |title: String, prices: &[CoinPrice]| {
+        let body = if prices.is_empty() {
+            "none".dimmed().to_string()
+        } else {
+            render_price_table(prices, options)
+        };
+        format!("{}\n{}", title, body)
+    };
+
+    format!(
+        "{}\n\n{}",
+        section("Gainers".green().bold().to_string(), &movers.gainers),
+        section("Losers".red().bold().to_string(), &movers.losers)
+    )
+}
+
+/// Print a warning to stderr for each symbol no provider could resolve.
+pub fn print_symbol_errors(errors: &[SymbolError]) {
+    for line in render_symbol_errors(errors) {
+        eprintln!("{}", line);
+    }
+}
+
+/// Render one highlighted warning line per unresolved symbol.
+pub fn render_symbol_errors(errors: &[SymbolError]) -> Vec<String> {
+    errors
+        .iter()
+        .map(|err| {
+            let mut line = format!(
+                "No data for: {} (tried {})",
+                err.symbol,
+                err.tried.join(", ")
+            );
+            if !err.suggestions.is_empty() {
+                let suggestions: Vec<String> = err
+                    .suggestions
+                    .iter()
+                    .map(|s| format!("{} ({})", s.symbol, s.name))
+                    .collect();
+                line.push_str(&format!(". Did you mean: {}?", suggestions.join(", ")));
+            }
+            line.yellow().to_string()
+        })
+        .collect()
 }
 
 #[derive(Tabled)]
@@ -66,10 +377,33 @@ struct ConversionRow {
 }
 
 /// Print fiat-to-crypto conversions as a styled table to stdout.
-pub fn print_conversions_table(conversions: &[Conversion]) {
+pub fn print_conversions_table(
+    conversions: &[Conversion],
+    both_rates: bool,
+    options: &TableOptions,
+) {
+    println!(
+        "{}",
+        render_conversions_table(conversions, both_rates, options)
+    );
+}
+
+/// Render conversions as a styled table.
+///
+/// With `both_rates`, the rate cell also shows the inverse direction on a second line.
+pub fn render_conversions_table(
+    conversions: &[Conversion],
+    both_rates: bool,
+    options: &TableOptions,
+) -> String {
     let rows: Vec<ConversionRow> = conversions
         .iter()
         .map(|c| {
+            let c = &Conversion {
+                from_currency: apply_asset_case(&c.from_currency, options.symbol_case),
+                to_symbol: apply_asset_case(&c.to_symbol, options.symbol_case),
+                ..c.clone()
+            };
             let from_is_fiat = calc::is_known_fiat(&c.from_currency);
             let to_is_fiat = calc::is_known_fiat(&c.to_symbol);
 
@@ -87,42 +421,11 @@ pub fn print_conversions_table(conversions: &[Conversion]) {
                 format_crypto_amount(c.to_amount, &c.to_symbol)
             };
 
-            let rate = if from_is_fiat && !to_is_fiat {
-                // fiat->crypto: "1 XMR = €294.52"
-                let from_sym = currency_symbol(&c.from_currency);
-                format!(
-                    "1 {} = {}{}",
-                    c.to_symbol.to_uppercase(),
-                    from_sym,
-                    format_with_commas(c.rate, 2)
-                )
-            } else if !from_is_fiat && to_is_fiat {
-                // crypto->fiat: "1 XMR = €294.52"
-                let to_sym = currency_symbol(&c.to_symbol);
-                format!(
-                    "1 {} = {}{}",
-                    c.from_currency.to_uppercase(),
-                    to_sym,
-                    format_with_commas(c.rate, 2)
-                )
-            } else if from_is_fiat && to_is_fiat {
-                // fiat->fiat: "1 EUR = $1.08"
-                let from_sym = currency_symbol(&c.from_currency);
-                format!(
-                    "1 {} = {}{}",
-                    c.to_symbol.to_uppercase(),
-                    from_sym,
-                    format_with_commas(c.rate, 2)
-                )
-            } else {
-                // crypto->crypto: "1 BTC = 15.23 ETH"
-                format!(
-                    "1 {} = {} {}",
-                    c.from_currency.to_uppercase(),
-                    format_with_commas(c.rate, 6),
-                    c.to_symbol.to_uppercase()
-                )
-            };
+            let mut rate = format_conversion_rate(c, from_is_fiat, to_is_fiat);
+            if both_rates && c.rate.is_finite() && c.rate > 0.0 {
+                rate.push('\n');
+                rate.push_str(&format_inverse_rate(c, from_is_fiat, to_is_fiat));
+            }
 
             ConversionRow {
                 amount,
@@ -134,20 +437,108 @@ pub fn print_conversions_table(conversions: &[Conversion]) {
         })
         .collect();
 
-    let table = Table::new(rows).with(Style::rounded()).to_string();
-    println!("{}", table);
+    Table::new(rows).with(options.style.theme()).to_string()
+}
+
+fn format_conversion_rate(c: &Conversion, from_is_fiat: bool, to_is_fiat: bool) -> String {
+    if from_is_fiat && !to_is_fiat {
+        // fiat->crypto: "1 XMR = €294.52"
+        let from_sym = currency_symbol(&c.from_currency);
+        format!(
+            "1 {} = {}{}",
+            c.to_symbol.to_uppercase(),
+            from_sym,
+            format_with_commas(c.rate, 2)
+        )
+    } else if !from_is_fiat && to_is_fiat {
+        // crypto->fiat: "1 XMR = €294.52"
+        let to_sym = currency_symbol(&c.to_symbol);
+        format!(
+            "1 {} = {}{}",
+            c.from_currency.to_uppercase(),
+            to_sym,
+            format_with_commas(c.rate, 2)
+        )
+    } else if from_is_fiat && to_is_fiat {
+        // fiat->fiat: "1 EUR = $1.08"
+        let from_sym = currency_symbol(&c.from_currency);
+        format!(
+            "1 {} = {}{}",
+            c.to_symbol.to_uppercase(),
+            from_sym,
+            format_with_commas(c.rate, 2)
+        )
+    } else {
+        // crypto->crypto: "1 BTC = 15.23 ETH"
+        format!(
+            "1 {} = {} {}",
+            c.from_currency.to_uppercase(),
+            format_with_commas(c.rate, 6),
+            c.to_symbol.to_uppercase()
+        )
+    }
+}
+
+/// The opposite direction of [`format_conversion_rate`], e.g. "1 EUR = 0.003395 XMR".
+fn format_inverse_rate(c: &Conversion, from_is_fiat: bool, to_is_fiat: bool) -> String {
+    let inverse = 1.0 / c.rate;
+    if from_is_fiat && !to_is_fiat {
+        // fiat->crypto: "1 EUR = 0.003395 XMR"
+        format!(
+            "1 {} = {}",
+            c.from_currency.to_uppercase(),
+            format_crypto_amount(inverse, &c.to_symbol)
+        )
+    } else if !from_is_fiat && to_is_fiat {
+        // crypto->fiat: "1 EUR = 0.003395 XMR"
+        format!(
+            "1 {} = {}",
+            c.to_symbol.to_uppercase(),
+            format_crypto_amount(inverse, &c.from_currency)
+        )
+    } else if from_is_fiat && to_is_fiat {
+        // fiat->fiat: "1 USD = €0.9259"
+        format!(
+            "1 {} = {}",
+            c.from_currency.to_uppercase(),
+            format_price(inverse, &c.to_symbol)
+        )
+    } else {
+        // crypto->crypto: "1 ETH = 0.065660 BTC"
+        format!(
+            "1 {} = {}",
+            c.to_symbol.to_uppercase(),
+            format_crypto_amount(inverse, &c.from_currency)
+        )
+    }
 }
 
-/// Print ASCII charts for historical price series.
+/// Print terminal charts for historical price series.
+///
+/// Start/end dates and axis labels use the patterns and time zone in `timestamps`. With `ascii`, charts use the
+/// pure-ASCII renderer instead of unicode markers and borders. `resampled` names the weekly or monthly period the
+/// series were reduced to, if any. With `annotate`, the low and high of each unicode chart are marked and labeled.
 pub fn print_history_charts(
     histories: &[PriceHistory],
     range_label: &str,
     sampling: HistoryInterval,
+    resampled: Option<ResamplePeriod>,
+    timestamps: &chart::TimestampFormat,
+    ascii: bool,
+    annotate: bool,
 ) {
     for history in histories {
         if history.points.is_empty() {
             continue;
         }
+        let label_format = timestamps.pattern(history, sampling);
+        let first_at = timestamps
+            .tz
+            .format(history.points[0].timestamp, &label_format);
+        let last_at = timestamps.tz.format(
+            history.points[history.points.len() - 1].timestamp,
+            &label_format,
+        );
 
         let prices: Vec<f64> = history.points.iter().map(|p| p.price).collect();
         let start = prices[0];
@@ -173,11 +564,16 @@ pub fn print_history_charts(
             history.currency,
             range_label
         );
-        println!("Sampling: {}", sampling.as_str());
         println!(
-            "Start: {}  End: {}  Change: {}",
+            "Sampling: {}",
+            resampled.map_or(sampling.as_str(), ResamplePeriod::as_str)
+        );
+        println!(
+            "Start: {} ({})  End: {} ({})  Change: {}",
             format_price(start, &history.currency),
+            first_at,
             format_price(end, &history.currency),
+            last_at,
             trend
         );
         println!(
@@ -185,109 +581,1599 @@ pub fn print_history_charts(
             format_price(low, &history.currency),
             format_price(high, &history.currency)
         );
-        println!("{}", chart::render_history_chart(history, 96, 18));
+        let rendered = if ascii {
+            chart::render_history_chart_ascii(
+                history,
+                CHART_WIDTH,
+                CHART_HEIGHT,
+                &label_format,
+                timestamps.tz,
+            )
+        } else {
+            chart::render_history_chart(
+                history,
+                CHART_WIDTH,
+                CHART_HEIGHT,
+                &label_format,
+                timestamps.tz,
+                annotate,
+            )
+        };
+        println!("{}", rendered);
+        if !history.events.is_empty() {
+            println!("Events: {}", format_history_events(history, timestamps));
+        }
         println!("Provider: {}", history.provider.dimmed());
         println!();
     }
 }
 
+/// Legend for the `D`/`S` chart markers, e.g. `D 2024-08-12 $0.25, S 2020-08-31 4:1`.
+fn format_history_events(history: &PriceHistory, timestamps: &chart::TimestampFormat) -> String {
+    history
+        .events
+        .iter()
+        .map(|event| {
+            let date = timestamps.tz.format(event.timestamp(), &timestamps.date);
+            match event {
+                HistoryEvent::Dividend { amount, .. } => {
+                    format!("D {} {}", date, format_price(*amount, &history.currency))
+                }
+                HistoryEvent::Split {
+                    numerator,
+                    denominator,
+                    ..
+                } => format!("S {} {}:{}", date, numerator, denominator),
+            }
+        })
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+/// Print several price histories overlaid on one chart with a shared Y axis.
+///
+/// Each series gets a `Start`/`End`/`Change` summary line above the chart; the
+/// chart's legend tells the series apart by marker.
+pub fn print_history_overlay(
+    histories: &[PriceHistory],
+    range_label: &str,
+    sampling: HistoryInterval,
+    resampled: Option<ResamplePeriod>,
+    timestamps: &chart::TimestampFormat,
+) {
+    let Some(first) = histories.iter().find(|history| !history.points.is_empty()) else {
+        return;
+    };
+    let label_format = timestamps.pattern(first, sampling);
+
+    let symbols: Vec<String> = histories
+        .iter()
+        .map(|history| history.symbol.bold().to_string())
+        .collect();
+    println!(
+        "{}  [{} {}]",
+        symbols.join(", "),
+        first.currency,
+        range_label
+    );
+    println!(
+        "Sampling: {}",
+        resampled.map_or(sampling.as_str(), ResamplePeriod::as_str)
+    );
+    for history in histories {
+        let (Some(start), Some(end)) = (history.points.first(), history.points.last()) else {
+            continue;
+        };
+        let change_pct = if start.price.abs() > f64::EPSILON {
+            ((end.price - start.price) / start.price) * 100.0
+        } else {
+            0.0
+        };
+        let trend = if change_pct >= 0.0 {
+            format!("+{change_pct:.2}%").green().to_string()
+        } else {
+            format!("{change_pct:.2}%").red().to_string()
+        };
+        println!(
+            "{}  Start: {} ({})  End: {} ({})  Change: {}",
+            history.symbol,
+            format_price(start.price, &history.currency),
+            timestamps.tz.format(start.timestamp, &label_format),
+            format_price(end.price, &history.currency),
+            timestamps.tz.format(end.timestamp, &label_format),
+            trend
+        );
+    }
+    println!(
+        "{}",
+        chart::render_multi_history_chart(
+            histories,
+            CHART_WIDTH,
+            CHART_HEIGHT,
+            &label_format,
+            timestamps.tz,
+        )
+    );
+    let mut providers: Vec<&str> = histories.iter().map(|h| h.provider.as_str()).collect();
+    providers.dedup();
+    println!("Provider: {}", providers.join(", ").dimmed());
+    println!();
+}
+
 #[derive(Tabled)]
-struct TickerMatchRow {
+struct HoldingRow {
     #[tabled(rename = "Symbol")]
     symbol: String,
     #[tabled(rename = "Name")]
     name: String,
-    #[tabled(rename = "Exchange")]
-    exchange: String,
-    #[tabled(rename = "Type")]
-    asset_type: String,
+    #[tabled(rename = "Quantity")]
+    quantity: String,
+    #[tabled(rename = "Price")]
+    price: String,
+    #[tabled(rename = "Value")]
+    value: String,
+    #[tabled(rename = "Cost")]
+    cost: String,
+    #[tabled(rename = "Gain/Loss")]
+    gain_loss: String,
     #[tabled(rename = "Provider")]
     provider: String,
 }
 
-/// Print ticker search matches as a styled table to stdout.
-pub fn print_ticker_matches_table(matches: &[TickerMatch]) {
-    let rows: Vec<TickerMatchRow> = matches
+/// Print portfolio holdings with a total row as a styled table to stdout.
+pub fn print_portfolio_table(
+    holdings: &[HoldingValue],
+    total: &PortfolioTotals,
+    options: &TableOptions,
+) {
+    let mut rows: Vec<HoldingRow> = holdings
         .iter()
-        .map(|m| TickerMatchRow {
-            symbol: m.symbol.clone().bold().to_string(),
-            name: m.name.clone(),
-            exchange: m.exchange.clone(),
-            asset_type: m.asset_type.clone(),
-            provider: m.provider.clone().dimmed().to_string(),
+        .map(|h| HoldingRow {
+            symbol: apply_symbol_case(&h.symbol, options.symbol_case)
+                .bold()
+                .to_string(),
+            name: format_name(&h.name, options),
+            quantity: format_quantity(h.quantity),
+            price: format_price(h.current_price, &h.currency),
+            value: format_price(h.current_value, &h.currency),
+            cost: format_price(h.cost_basis, &h.currency),
+            gain_loss: format_gain_loss(h.gain_loss, h.gain_loss_pct, &h.currency),
+            provider: h.provider.clone().dimmed().to_string(),
         })
         .collect();
 
-    let table = Table::new(rows).with(Style::rounded()).to_string();
+    let total_currency = holdings.first().map(|h| h.currency.as_str()).unwrap_or("");
+    rows.push(HoldingRow {
+        symbol: "TOTAL".bold().to_string(),
+        name: String::new(),
+        quantity: String::new(),
+        price: String::new(),
+        value: format_price(total.current_value, total_currency)
+            .bold()
+            .to_string(),
+        cost: format_price(total.cost_basis, total_currency),
+        gain_loss: format_gain_loss(total.gain_loss, total.gain_loss_pct, total_currency),
+        provider: String::new(),
+    });
+
+    let table = Table::new(rows).with(options.style.theme()).to_string();
     println!("{}", table);
 }
 
-fn format_crypto_amount(amount: f64, symbol: &str) -> String {
-    let upper = symbol.to_uppercase();
-    if amount >= 0.0001 {
-        format!("{:.6} {}", amount, upper)
-    } else {
-        format!("{:.10} {}", amount, upper)
-    }
+#[derive(Tabled)]
+struct AllocationRow {
+    #[tabled(rename = "Symbol")]
+    symbol: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Value")]
+    value: String,
+    #[tabled(rename = "Allocation")]
+    percent: String,
 }
 
-fn format_price(price: f64, currency: &str) -> String {
-    let sym = currency_symbol(currency);
-    if price >= 1.0 {
-        format!("{}{}", sym, format_with_commas(price, 2))
-    } else if price >= 0.01 {
-        format!("{}{:.4}", sym, price)
-    } else {
-        format!("{}{:.8}", sym, price)
-    }
+/// Print portfolio allocation shares as a styled table to stdout.
+pub fn print_allocation_table(allocations: &[Allocation], options: &TableOptions) {
+    let rows: Vec<AllocationRow> = allocations
+        .iter()
+        .map(|a| AllocationRow {
+            symbol: apply_symbol_case(&a.symbol, options.symbol_case)
+                .bold()
+                .to_string(),
+            name: format_name(&a.name, options),
+            value: format_price(a.value, &a.currency),
+            percent: format!("{:.2}%", a.percent),
+        })
+        .collect();
+
+    let table = Table::new(rows).with(options.style.theme()).to_string();
+    println!("{}", table);
+}
+
+#[derive(Tabled)]
+struct CurrencyRow {
+    #[tabled(rename = "Code")]
+    code: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Fiat rates")]
+    fiat_rates: String,
+    #[tabled(rename = "Crypto quotes")]
+    crypto_quotes: String,
 }
 
-fn format_with_commas(value: f64, decimals: usize) -> String {
-    let formatted = format!("{value:.decimals$}");
-    let parts: Vec<&str> = formatted.split('.').collect();
-    let whole = parts[0];
+/// Print quote currency support as a styled table to stdout.
+pub fn print_currencies_table(currencies: &[CurrencySupport], options: &TableOptions) {
+    println!("{}", render_currencies_table(currencies, options));
+}
 
-    let mut result = String::new();
-    for (i, ch) in whole.chars().rev().enumerate() {
-        if i > 0 && i % 3 == 0 {
-            result.push(',');
-        }
-        result.push(ch);
+/// Render quote currency support as a styled table.
+///
+/// Support columns show `yes`, `no`, or `?` when the provider could not be asked.
+pub fn render_currencies_table(currencies: &[CurrencySupport], options: &TableOptions) -> String {
+    let rows: Vec<CurrencyRow> = currencies
+        .iter()
+        .map(|c| CurrencyRow {
+            code: c.code.clone().bold().to_string(),
+            name: c.name.clone(),
+            fiat_rates: format_support(c.fiat_rates),
+            crypto_quotes: format_support(c.crypto_quotes),
+        })
+        .collect();
+
+    Table::new(rows).with(options.style.theme()).to_string()
+}
+
+/// Print a forex conversion matrix as a styled table.
+pub fn print_conversion_matrix(matrix: &ConversionMatrix, options: &TableOptions) {
+    println!("{}", render_conversion_matrix(matrix, options));
+}
+
+/// Render a conversion matrix with one row per base currency and one column per
+/// target; the diagonal is dimmed and missing rates show `-`.
+pub fn render_conversion_matrix(matrix: &ConversionMatrix, options: &TableOptions) -> String {
+    let mut builder = Builder::default();
+    builder.push_record(std::iter::once(String::new()).chain(matrix.currencies.iter().cloned()));
+    for (base_idx, (base, rates)) in matrix.currencies.iter().zip(&matrix.rates).enumerate() {
+        let cells = rates.iter().enumerate().map(|(idx, rate)| match rate {
+            Some(rate) if idx == base_idx => format_matrix_rate(*rate).dimmed().to_string(),
+            Some(rate) => format_matrix_rate(*rate),
+            None => "-".to_string(),
+        });
+        builder.push_record(std::iter::once(base.clone().bold().to_string()).chain(cells));
     }
-    let whole_formatted: String = result.chars().rev().collect();
 
-    if parts.len() > 1 {
-        format!("{}.{}", whole_formatted, parts[1])
-    } else {
-        whole_formatted
+    builder.build().with(options.style.theme()).to_string()
+}
+
+/// Four decimals for rates of at least 1, four significant digits below that.
+fn format_matrix_rate(rate: f64) -> String {
+    if rate >= 1.0 || rate <= 0.0 {
+        return format_with_commas(rate, 4);
     }
+    let decimals = (3 - rate.log10().floor() as i32).max(4) as usize;
+    format!("{rate:.decimals$}")
 }
 
-fn format_market_cap(cap: f64, currency: &str) -> String {
-    let sym = currency_symbol(currency);
-    if cap >= 1_000_000_000_000.0 {
-        format!("{}{:.2}T", sym, cap / 1_000_000_000_000.0)
-    } else if cap >= 1_000_000_000.0 {
-        format!("{}{:.2}B", sym, cap / 1_000_000_000.0)
-    } else if cap >= 1_000_000.0 {
-        format!("{}{:.2}M", sym, cap / 1_000_000.0)
-    } else if cap >= 1_000.0 {
-        format!("{}{:.2}K", sym, cap / 1_000.0)
-    } else {
-        format!("{}{:.2}", sym, cap)
+fn format_support(supported: Option<bool>) -> String {
+    match supported {
+        Some(true) => "yes".green().to_string(),
+        Some(false) => "no".red().to_string(),
+        None => "?".dimmed().to_string(),
+    }
+}
+
+#[derive(Tabled)]
+struct PerformanceRow {
+    #[tabled(rename = "Symbol")]
+    symbol: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Price")]
+    price: String,
+    #[tabled(rename = "1M")]
+    one_month: String,
+    #[tabled(rename = "3M")]
+    three_months: String,
+    #[tabled(rename = "6M")]
+    six_months: String,
+    #[tabled(rename = "YTD")]
+    ytd: String,
+    #[tabled(rename = "1Y")]
+    one_year: String,
+}
+
+/// Print trailing returns as a styled table to stdout.
+pub fn print_performance_table(performance: &[Performance], options: &TableOptions) {
+    println!("{}", render_performance_table(performance, options));
+}
+
+/// Render trailing returns with one row per symbol and one column per period.
+///
+/// Periods the fetched history does not reach back to show `-`.
+pub fn render_performance_table(performance: &[Performance], options: &TableOptions) -> String {
+    let rows: Vec<PerformanceRow> = performance
+        .iter()
+        .map(|p| PerformanceRow {
+            symbol: apply_symbol_case(&p.symbol, options.symbol_case)
+                .bold()
+                .to_string(),
+            name: format_name(&p.name, options),
+            price: format_price(p.price, &p.currency),
+            one_month: format_change(p.get(PerformancePeriod::OneMonth)),
+            three_months: format_change(p.get(PerformancePeriod::ThreeMonths)),
+            six_months: format_change(p.get(PerformancePeriod::SixMonths)),
+            ytd: format_change(p.get(PerformancePeriod::Ytd)),
+            one_year: format_change(p.get(PerformancePeriod::OneYear)),
+        })
+        .collect();
+
+    Table::new(rows).with(options.style.theme()).to_string()
+}
+
+#[derive(Tabled)]
+struct StatsRow {
+    #[tabled(rename = "Symbol")]
+    symbol: String,
+    #[tabled(rename = "Points")]
+    points: usize,
+    #[tabled(rename = "Mean")]
+    mean: String,
+    #[tabled(rename = "Std Dev")]
+    std_dev: String,
+    #[tabled(rename = "Min")]
+    min: String,
+    #[tabled(rename = "Max")]
+    max: String,
+    #[tabled(rename = "Volatility (ann.)")]
+    volatility: String,
+}
+
+/// Print `--stats` summaries as a styled table to stdout.
+pub fn print_stats_table(stats: &[HistoryStats], options: &TableOptions) {
+    println!("{}", render_stats_table(stats, options));
+}
+
+/// Render one row of price statistics per symbol.
+///
+/// Series with fewer than 3 points show `n/a (needs 3+ points)` for volatility.
+pub fn render_stats_table(stats: &[HistoryStats], options: &TableOptions) -> String {
+    let rows: Vec<StatsRow> = stats
+        .iter()
+        .map(|s| StatsRow {
+            symbol: apply_symbol_case(&s.symbol, options.symbol_case)
+                .bold()
+                .to_string(),
+            points: s.stats.points,
+            mean: format_price(s.stats.mean, &s.currency),
+            std_dev: format_price(s.stats.std_dev, &s.currency),
+            min: format_price(s.stats.min, &s.currency),
+            max: format_price(s.stats.max, &s.currency),
+            volatility: s.stats.volatility.map_or_else(
+                || "n/a (needs 3+ points)".dimmed().to_string(),
+                |pct| format!("{:.2}%", pct),
+            ),
+        })
+        .collect();
+
+    Table::new(rows).with(options.style.theme()).to_string()
+}
+
+/// Print two-date price comparisons as a styled table to stdout.
+pub fn print_two_date_table(comparisons: &[TwoDateComparison], options: &TableOptions) {
+    println!("{}", render_two_date_table(comparisons, options));
+}
+
+/// Render `Symbol | Price on DATE1 | Price on DATE2 | Change` rows.
+///
+/// The change cell shows the absolute and percent change, colored by direction.
+pub fn render_two_date_table(comparisons: &[TwoDateComparison], options: &TableOptions) -> String {
+    let Some(first) = comparisons.first() else {
+        return String::new();
+    };
+
+    let mut builder = Builder::default();
+    builder.push_record([
+        "Symbol".to_string(),
+        format!("Price on {}", first.date1),
+        format!("Price on {}", first.date2),
+        "Change".to_string(),
+    ]);
+    for c in comparisons {
+        let change = format!(
+            "{}{}",
+            if c.change >= 0.0 { "+" } else { "-" },
+            format_price(c.change.abs(), &c.currency)
+        );
+        let change = match c.change_pct {
+            Some(pct) => format!("{} ({:+.2}%)", change, pct),
+            None => change,
+        };
+        builder.push_record([
+            apply_symbol_case(&c.symbol, options.symbol_case)
+                .bold()
+                .to_string(),
+            format_price(c.price1, &c.currency),
+            format_price(c.price2, &c.currency),
+            if c.change >= 0.0 {
+                change.green().to_string()
+            } else {
+                change.red().to_string()
+            },
+        ]);
     }
+
+    builder.build().with(options.style.theme()).to_string()
+}
+
+#[derive(Tabled)]
+struct ProviderComparisonRow {
+    #[tabled(rename = "Symbol")]
+    symbol: String,
+    #[tabled(rename = "Provider")]
+    provider: String,
+    #[tabled(rename = "Price")]
+    price: String,
+    #[tabled(rename = "24h Change")]
+    change_24h: String,
+    #[tabled(rename = "Spread")]
+    spread: String,
 }
 
-fn currency_symbol(currency: &str) -> &str {
-    match currency.to_uppercase().as_str() {
-        "USD" => "$",
-        "EUR" => "\u{20ac}",
-        "GBP" => "\u{00a3}",
-        "JPY" | "CNY" => "\u{00a5}",
-        "CAD" => "CA$",
-        "AUD" => "A$",
-        "CHF" => "CHF ",
-        "BTC" => "\u{20bf}",
-        _ => "",
+#[derive(Tabled)]
+struct SymbolResolutionRow {
+    #[tabled(rename = "Symbol")]
+    symbol: String,
+    #[tabled(rename = "Provider")]
+    provider: String,
+    #[tabled(rename = "Resolves To")]
+    resolved: String,
+}
+
+/// Print how each provider maps each input symbol.
+pub fn print_symbol_resolutions_table(resolutions: &[SymbolResolution], options: &TableOptions) {
+    println!("{}", render_symbol_resolutions_table(resolutions, options));
+}
+
+/// Render symbol resolutions, showing each symbol only on its first row.
+pub fn render_symbol_resolutions_table(
+    resolutions: &[SymbolResolution],
+    options: &TableOptions,
+) -> String {
+    let rows: Vec<SymbolResolutionRow> = resolutions
+        .iter()
+        .enumerate()
+        .map(|(idx, r)| {
+            let first = idx == 0 || resolutions[idx - 1].symbol != r.symbol;
+            SymbolResolutionRow {
+                symbol: if first {
+                    r.symbol.clone().bold().to_string()
+                } else {
+                    String::new()
+                },
+                provider: r.provider.clone(),
+                resolved: r.resolved.clone(),
+            }
+        })
+        .collect();
+
+    Table::new(rows).with(options.style.theme()).to_string()
+}
+
+/// Print the provider list with API key status and supported features.
+pub fn print_providers(providers: &[ProviderInfo]) {
+    for line in render_providers(providers) {
+        println!("{}", line);
+    }
+}
+
+/// Render one aligned line per provider under a column header.
+pub fn render_providers(providers: &[ProviderInfo]) -> Vec<String> {
+    let mut lines = vec![
+        "Available providers:".to_string(),
+        format!("  {:12} {:16} {:4} {}", "ID", "NAME", "KEY", "FEATURES"),
+    ];
+    for p in providers {
+        let features: Vec<&str> = p.capabilities.features.iter().map(|f| f.as_str()).collect();
+        lines.push(format!(
+            "  {:12} {:16} {:4} {}",
+            p.id,
+            p.name,
+            p.capabilities.api_key.label(),
+            features.join(", ")
+        ));
+    }
+    lines
+}
+
+/// Print an asset profile as an aligned key-value block.
+pub fn print_asset_info(info: &AssetInfo) {
+    for line in render_asset_info(info) {
+        println!("{}", line);
+    }
+}
+
+/// Render the known profile fields as `Label  value` lines, followed by the description.
+pub fn render_asset_info(info: &AssetInfo) -> Vec<String> {
+    let mut fields: Vec<(&str, String)> = vec![(
+        "Name",
+        format!("{} ({})", info.name, info.symbol.clone().bold()),
+    )];
+    if let Some(rank) = info.rank {
+        fields.push(("Rank", format!("#{}", rank)));
+    }
+    if !info.categories.is_empty() {
+        fields.push(("Categories", info.categories.join(", ")));
+    }
+    if let Some(date) = info.genesis_date {
+        fields.push(("Genesis date", date.to_string()));
+    }
+    for (label, value) in [
+        ("Sector", &info.sector),
+        ("Industry", &info.industry),
+        ("Exchange", &info.exchange),
+        ("Homepage", &info.homepage),
+    ] {
+        if let Some(value) = value {
+            fields.push((label, value.clone()));
+        }
+    }
+    for (idx, explorer) in info.explorers.iter().enumerate() {
+        fields.push((if idx == 0 { "Explorers" } else { "" }, explorer.clone()));
+    }
+    if let Some(supply) = info.circulating_supply {
+        fields.push(("Circulating", format_with_commas(supply, 0)));
+    }
+    if let Some(supply) = info.total_supply {
+        fields.push(("Total supply", format_with_commas(supply, 0)));
+    }
+    for (label, value, date) in [
+        ("All-time high", info.ath, info.ath_date),
+        ("All-time low", info.atl, info.atl_date),
+    ] {
+        if let Some(value) = value {
+            let mut text = format_price(value, &info.currency);
+            if let Some(date) = date {
+                text.push_str(&format!(" ({})", date.format("%Y-%m-%d")));
+            }
+            fields.push((label, text));
+        }
+    }
+    if let (Some(low), Some(high)) = (info.week_52_low, info.week_52_high) {
+        fields.push((
+            "52-week range",
+            format!(
+                "{} - {}",
+                format_price(low, &info.currency),
+                format_price(high, &info.currency)
+            ),
+        ));
+    }
+    fields.push(("Provider", info.provider.clone().dimmed().to_string()));
+
+    let mut lines = render_fields(fields);
+    if let Some(description) = &info.description {
+        lines.push(String::new());
+        lines.push(description.clone());
+    }
+    lines
+}
+
+/// Render `(label, value)` pairs as lines with the labels padded to a common width.
+fn render_fields(fields: Vec<(&str, String)>) -> Vec<String> {
+    let width = fields
+        .iter()
+        .map(|(label, _)| label.len())
+        .max()
+        .unwrap_or(0);
+    fields
+        .into_iter()
+        .map(|(label, value)| format!("{}  {}", format!("{:width$}", label).bold(), value))
+        .collect()
+}
+
+/// Print coin metadata summaries, separated by blank lines.
+pub fn print_coin_details(details: &[CoinDetails], options: &TableOptions) {
+    for (idx, detail) in details.iter().enumerate() {
+        if idx > 0 {
+            println!();
+        }
+        for line in render_coin_details(detail, options) {
+            println!("{}", line);
+        }
+    }
+}
+
+/// Render coin metadata as `Label  value` lines, followed by the description.
+pub fn render_coin_details(details: &CoinDetails, options: &TableOptions) -> Vec<String> {
+    let mut fields: Vec<(&str, String)> = vec![(
+        "Name",
+        format!(
+            "{} ({})",
+            details.name,
+            apply_symbol_case(&details.symbol, options.symbol_case).bold()
+        ),
+    )];
+    if let Some(rank) = details.market_cap_rank {
+        fields.push(("Rank", format!("#{}", rank)));
+    }
+    if !details.categories.is_empty() {
+        fields.push(("Categories", details.categories.join(", ")));
+    }
+    if let Some(date) = details.genesis_date {
+        fields.push(("Genesis date", date.to_string()));
+    }
+    for (label, links) in [("Homepage", &details.homepage), ("GitHub", &details.github)] {
+        for (idx, link) in links.iter().enumerate() {
+            fields.push((if idx == 0 { label } else { "" }, link.clone()));
+        }
+    }
+
+    let mut lines = render_fields(fields);
+    if !details.description.is_empty() {
+        lines.push(String::new());
+        lines.push(details.description.clone());
+    }
+    lines
+}
+
+/// Print per-provider quotes grouped by symbol, each group ending in a median row.
+pub fn print_provider_comparison_table(comparisons: &[ProviderComparison], options: &TableOptions) {
+    println!("{}", render_provider_comparison_table(comparisons, options));
+}
+
+/// Give each built-in provider its own color so rows from the same source line up visually.
+fn format_provider_name(name: &str) -> String {
+    match name {
+        "CoinGecko" => name.green().to_string(),
+        "CoinMarketCap" => name.blue().to_string(),
+        "Yahoo Finance" => name.magenta().to_string(),
+        "Stooq" => name.cyan().to_string(),
+        "MEXC" => name.yellow().to_string(),
+        _ => name.to_string(),
+    }
+}
+
+/// Render per-provider quotes grouped by symbol with a separator between groups.
+pub fn render_provider_comparison_table(
+    comparisons: &[ProviderComparison],
+    options: &TableOptions,
+) -> String {
+    let mut rows = Vec::new();
+    let mut group_starts = Vec::new();
+
+    for comparison in comparisons {
+        group_starts.push(rows.len() + 1);
+
+        for (idx, quote) in comparison.quotes.iter().enumerate() {
+            let symbol = if idx == 0 {
+                apply_symbol_case(&comparison.symbol, options.symbol_case)
+                    .bold()
+                    .to_string()
+            } else {
+                String::new()
+            };
+            let row = match (quote.price, quote.currency.as_deref()) {
+                (Some(price), Some(currency)) => ProviderComparisonRow {
+                    symbol,
+                    provider: format_provider_name(&quote.provider),
+                    price: format_price(price, currency),
+                    change_24h: format_change(quote.change_24h),
+                    spread: String::new(),
+                },
+                _ => ProviderComparisonRow {
+                    symbol,
+                    provider: quote.provider.clone().dimmed().to_string(),
+                    price: "unavailable".dimmed().to_string(),
+                    change_24h: "-".dimmed().to_string(),
+                    spread: String::new(),
+                },
+            };
+            rows.push(row);
+        }
+
+        rows.push(ProviderComparisonRow {
+            symbol: String::new(),
+            provider: "median".bold().to_string(),
+            price: match comparison.median {
+                Some(median) => format_price(median, &comparison.currency)
+                    .bold()
+                    .to_string(),
+                None => "-".to_string(),
+            },
+            change_24h: String::new(),
+            spread: match comparison.spread_pct {
+                Some(spread) => format!("{:.2}%", spread),
+                None => "-".to_string(),
+            },
+        });
+    }
+
+    let mut theme = options.style.theme();
+    if let Some(line) = theme.get_horizontal_line(1).cloned() {
+        for &row in group_starts.iter().skip(1) {
+            theme.insert_horizontal_line(row, line);
+        }
+    }
+
+    let mut table = Table::new(rows);
+    table.with(theme);
+    table.to_string()
+}
+
+fn format_change(change: Option<f64>) -> String {
+    match change {
+        Some(c) if c >= 0.0 => format!("+{:.2}%", c).green().to_string(),
+        Some(c) => format!("{:.2}%", c).red().to_string(),
+        None => "-".dimmed().to_string(),
+    }
+}
+
+/// Direction of a percent change: `↑`, `↓`, `→` when its magnitude is below
+/// `threshold` percent, or `-` when unknown.
+pub fn change_arrow(change: Option<f64>, threshold: f64) -> &'static str {
+    match change.filter(|c| !c.is_nan()) {
+        None => "-",
+        Some(c) if c == 0.0 || c.abs() < threshold => "→",
+        Some(c) if c > 0.0 => "↑",
+        Some(_) => "↓",
+    }
+}
+
+/// Format a 24h change, with its direction arrow when the options ask for one.
+fn format_price_change(change: Option<f64>, options: &TableOptions) -> String {
+    let (Some(threshold), Some(c)) = (options.change_arrow_threshold, change) else {
+        return format_change(change);
+    };
+
+    let text = format!("{} {:+.2}%", change_arrow(change, threshold), c);
+    if c == 0.0 || c.abs() < threshold {
+        text
+    } else if c > 0.0 {
+        text.green().to_string()
+    } else {
+        text.red().to_string()
+    }
+}
+
+fn format_gain_loss(amount: f64, pct: Option<f64>, currency: &str) -> String {
+    let sign = if amount >= 0.0 { "+" } else { "-" };
+    let text = match pct {
+        Some(p) => format!(
+            "{}{} ({}{:.2}%)",
+            sign,
+            format_price(amount.abs(), currency),
+            sign,
+            p.abs()
+        ),
+        None => format!("{}{}", sign, format_price(amount.abs(), currency)),
+    };
+
+    if amount >= 0.0 {
+        text.green().to_string()
+    } else {
+        text.red().to_string()
+    }
+}
+
+fn format_quantity(quantity: f64) -> String {
+    let formatted = format!("{quantity:.8}");
+    let trimmed = formatted.trim_end_matches('0').trim_end_matches('.');
+    trimmed.to_string()
+}
+
+#[derive(Tabled)]
+struct TickerMatchRow {
+    #[tabled(rename = "Symbol")]
+    symbol: String,
+    #[tabled(rename = "Name")]
+    name: String,
+    #[tabled(rename = "Exchange")]
+    exchange: String,
+    #[tabled(rename = "Type")]
+    asset_type: String,
+    #[tabled(rename = "Provider")]
+    provider: String,
+}
+
+/// Print ticker search matches as a styled table to stdout.
+pub fn print_ticker_matches_table(matches: &[TickerMatch], options: &TableOptions) {
+    let rows: Vec<TickerMatchRow> = matches
+        .iter()
+        .map(|m| TickerMatchRow {
+            symbol: m.symbol.clone().bold().to_string(),
+            name: format_name(&m.name, options),
+            exchange: m.exchange.clone(),
+            asset_type: m.asset_type.clone(),
+            provider: m.provider.clone().dimmed().to_string(),
+        })
+        .collect();
+
+    let table = Table::new(rows).with(options.style.theme()).to_string();
+    println!("{}", table);
+}
+
+fn format_crypto_amount(amount: f64, symbol: &str) -> String {
+    let upper = symbol.to_uppercase();
+    if amount >= 0.0001 {
+        format!("{:.6} {}", amount, upper)
+    } else {
+        format!("{:.10} {}", amount, upper)
+    }
+}
+
+fn format_market_cap(cap: f64, currency: &str) -> String {
+    let sym = currency_symbol(currency);
+    if cap >= 1_000_000_000_000.0 {
+        format!("{}{:.2}T", sym, cap / 1_000_000_000_000.0)
+    } else if cap >= 1_000_000_000.0 {
+        format!("{}{:.2}B", sym, cap / 1_000_000_000.0)
+    } else if cap >= 1_000_000.0 {
+        format!("{}{:.2}M", sym, cap / 1_000_000.0)
+    } else if cap >= 1_000.0 {
+        format!("{}{:.2}K", sym, cap / 1_000.0)
+    } else {
+        format!("{}{:.2}", sym, cap)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_price;
+    use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+    fn strip_ansi(s: &str) -> String {
+        let mut out = String::with_capacity(s.len());
+        let mut chars = s.chars();
+        while let Some(c) = chars.next() {
+            if c == '\u{1b}' {
+                for next in chars.by_ref() {
+                    if next == 'm' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            out.push(c);
+        }
+        out
+    }
+
+    fn column_boundaries(line: &str) -> Vec<usize> {
+        let mut offsets = Vec::new();
+        let mut col = 0;
+        for ch in line.chars() {
+            if ch == '\u{2502}' {
+                offsets.push(col);
+            }
+            col += ch.width().unwrap_or(0);
+        }
+        offsets
+    }
+
+    fn price(symbol: &str, name: &str) -> CoinPrice {
+        CoinPrice {
+            name: name.to_string(),
+            change_24h: Some(1.25),
+            currency: "JPY".to_string(),
+            provider: "Yahoo Finance".to_string(),
+            ..test_price(symbol, 1234.5)
+        }
+    }
+
+    #[test]
+    fn price_table_aligns_wide_character_names() {
+        let prices = vec![
+            price(
+                "8035.T",
+                "\u{6771}\u{4eac}\u{30a8}\u{30ec}\u{30af}\u{30c8}\u{30ed}\u{30f3}",
+            ),
+            price("ROCKET", "Rocket \u{1f680}"),
+            price("AAPL", "Apple Inc."),
+        ];
+
+        let rendered = strip_ansi(&render_price_table(&prices, &TableOptions::default()));
+        let lines: Vec<&str> = rendered.lines().collect();
+        assert!(lines.len() >= 5);
+
+        let width = UnicodeWidthStr::width(lines[0]);
+        for line in &lines {
+            assert_eq!(
+                UnicodeWidthStr::width(*line),
+                width,
+                "misaligned line: {line}"
+            );
+        }
+
+        let header = column_boundaries(lines[1]);
+        assert_eq!(header.len(), 7);
+        for line in lines.iter().filter(|l| l.starts_with('\u{2502}')) {
+            assert_eq!(column_boundaries(line), header, "misaligned row: {line}");
+        }
+    }
+
+    #[test]
+    fn stats_table_explains_missing_volatility() {
+        let stats = |symbol: &str, points: usize, volatility: Option<f64>| HistoryStats {
+            symbol: symbol.to_string(),
+            name: symbol.to_string(),
+            currency: "USD".to_string(),
+            provider: "Test".to_string(),
+            stats: calc::ReturnStats {
+                points,
+                mean: 100.0,
+                std_dev: 5.0,
+                min: 90.0,
+                max: 110.0,
+                volatility,
+            },
+        };
+
+        let rendered = strip_ansi(&render_stats_table(
+            &[stats("BTC", 30, Some(48.123)), stats("NEW", 1, None)],
+            &TableOptions::default(),
+        ));
+
+        assert!(rendered.contains("Volatility (ann.)"), "{rendered}");
+        assert!(rendered.contains("48.12%"), "{rendered}");
+        assert!(rendered.contains("n/a (needs 3+ points)"), "{rendered}");
+    }
+
+    #[test]
+    fn freshness_footer_spans_oldest_to_newest_quote() {
+        let at = |hour: u32, minute: u32, second: u32| {
+            chrono::NaiveDate::from_ymd_opt(2024, 6, 1)
+                .unwrap()
+                .and_hms_opt(hour, minute, second)
+                .unwrap()
+                .and_utc()
+        };
+        let stamped = |symbol: &str, timestamp| CoinPrice {
+            timestamp,
+            ..price(symbol, symbol)
+        };
+
+        let same_minute = vec![stamped("BTC", at(12, 0, 5)), stamped("ETH", at(12, 0, 40))];
+        assert_eq!(
+            render_freshness_footer(&same_minute, chart::DisplayTz::Utc).as_deref(),
+            Some("As of 2024-06-01 12:00 UTC")
+        );
+
+        let mixed = vec![
+            stamped("BTC", at(12, 0, 0)),
+            stamped("AAPL", at(11, 45, 0)),
+            stamped("ETH", at(11, 59, 0)),
+        ];
+        assert_eq!(
+            render_freshness_footer(&mixed, chart::DisplayTz::Utc).as_deref(),
+            Some("As of 2024-06-01 11:45 UTC to 2024-06-01 12:00 UTC")
+        );
+        assert_eq!(
+            render_freshness_footer(&mixed, chart::DisplayTz::Named(chrono_tz::Europe::Paris))
+                .as_deref(),
+            Some("As of 2024-06-01 13:45 CEST to 2024-06-01 14:00 CEST")
+        );
+
+        assert_eq!(render_freshness_footer(&[], chart::DisplayTz::Utc), None);
+    }
+
+    #[test]
+    fn price_table_shows_high_low_only_when_enabled_and_available() {
+        let enabled = TableOptions {
+            show_high_low: true,
+            ..TableOptions::default()
+        };
+        let without_data = vec![price("AAPL", "Apple Inc.")];
+
+        let mut with_data = without_data.clone();
+        with_data[0].high_24h = Some(1300.0);
+        with_data[0].low_24h = Some(1200.0);
+
+        let shown = strip_ansi(&render_price_table(&with_data, &enabled));
+        assert!(shown.contains("24h High"));
+        assert!(shown.contains("24h Low"));
+
+        let no_data = strip_ansi(&render_price_table(&without_data, &enabled));
+        assert!(!no_data.contains("24h High"));
+        assert!(!no_data.contains("24h Low"));
+
+        let disabled = strip_ansi(&render_price_table(&with_data, &TableOptions::default()));
+        assert!(!disabled.contains("24h High"));
+    }
+
+    #[test]
+    fn price_table_shows_fundamentals_columns_only_when_present() {
+        let mut aapl = price("AAPL", "Apple Inc.");
+        aapl.currency = "USD".to_string();
+        aapl.fundamentals = Some(Fundamentals {
+            trailing_pe: Some(32.456),
+            dividend_yield: Some(0.44),
+            week_52_high: Some(260.1),
+            week_52_low: Some(164.08),
+        });
+        let btc = price("BTC", "Bitcoin");
+
+        let shown = strip_ansi(&render_price_table(
+            &[aapl, btc.clone()],
+            &TableOptions::default(),
+        ));
+        assert!(
+            shown.contains("P/E") && shown.contains("Div Yield") && shown.contains("52W Range")
+        );
+        let aapl_row = shown.lines().find(|line| line.contains("AAPL")).unwrap();
+        assert!(aapl_row.contains("32.46"), "{aapl_row}");
+        assert!(aapl_row.contains("0.44%"), "{aapl_row}");
+        assert!(aapl_row.contains("$164.08 - $260.10"), "{aapl_row}");
+
+        let hidden = strip_ansi(&render_price_table(&[btc], &TableOptions::default()));
+        assert!(!hidden.contains("P/E") && !hidden.contains("52W Range"));
+    }
+
+    #[test]
+    fn coin_details_render_links_under_one_label() {
+        let details = CoinDetails {
+            symbol: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            description: "The first cryptocurrency.".to_string(),
+            homepage: vec!["http://www.bitcoin.org".to_string()],
+            github: vec![
+                "https://github.com/bitcoin/bitcoin".to_string(),
+                "https://github.com/bitcoin/bips".to_string(),
+            ],
+            market_cap_rank: Some(1),
+            ..CoinDetails::default()
+        };
+
+        let lines: Vec<String> = render_coin_details(&details, &TableOptions::default())
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+
+        assert_eq!(lines[0], "Name      Bitcoin (BTC)");
+        assert!(lines.contains(&"Rank      #1".to_string()));
+        assert!(lines.contains(&"Homepage  http://www.bitcoin.org".to_string()));
+        assert!(lines.contains(&"GitHub    https://github.com/bitcoin/bitcoin".to_string()));
+        assert!(lines.contains(&"          https://github.com/bitcoin/bips".to_string()));
+        assert!(!lines.iter().any(|l| l.starts_with("Categories")));
+        assert_eq!(lines.last().unwrap(), "The first cryptocurrency.");
+    }
+
+    #[test]
+    fn conversion_matrix_renders_one_row_per_base() {
+        let matrix = ConversionMatrix {
+            currencies: vec!["USD".to_string(), "JPY".to_string()],
+            rates: vec![vec![Some(1.0), Some(1498.5)], vec![None, Some(1.0)]],
+        };
+
+        let rendered = strip_ansi(&render_conversion_matrix(&matrix, &TableOptions::default()));
+        let rows: Vec<Vec<&str>> = rendered
+            .lines()
+            .filter(|line| line.contains('\u{2502}'))
+            .map(|line| {
+                line.split('\u{2502}')
+                    .map(str::trim)
+                    .filter(|c| !c.is_empty())
+                    .collect()
+            })
+            .collect();
+
+        assert_eq!(rows[0], vec!["USD", "JPY"]);
+        assert_eq!(rows[1], vec!["USD", "1.0000", "1,498.5000"]);
+        assert_eq!(rows[2], vec!["JPY", "-", "1.0000"]);
+        assert_eq!(format_matrix_rate(0.006673), "0.006673");
+        assert_eq!(format_matrix_rate(0.92), "0.9200");
+    }
+
+    #[test]
+    fn asset_info_renders_aligned_fields_and_description() {
+        let info = AssetInfo {
+            symbol: "BTC".to_string(),
+            name: "Bitcoin".to_string(),
+            rank: Some(1),
+            explorers: vec![
+                "https://mempool.space/".to_string(),
+                "https://blockchair.com/".to_string(),
+            ],
+            circulating_supply: Some(19_700_000.0),
+            ath: Some(73738.0),
+            ath_date: "2024-03-14T07:10:36Z".parse().ok(),
+            description: Some("The first cryptocurrency.".to_string()),
+            currency: "USD".to_string(),
+            provider: "CoinGecko".to_string(),
+            ..AssetInfo::default()
+        };
+
+        let lines: Vec<String> = render_asset_info(&info)
+            .iter()
+            .map(|l| strip_ansi(l))
+            .collect();
+
+        assert_eq!(lines[0], "Name           Bitcoin (BTC)");
+        assert!(lines.contains(&"Rank           #1".to_string()));
+        assert!(lines.contains(&"Explorers      https://mempool.space/".to_string()));
+        assert!(lines.contains(&"               https://blockchair.com/".to_string()));
+        assert!(lines.contains(&"Circulating    19,700,000".to_string()));
+        assert!(lines.contains(&"All-time high  $73,738.00 (2024-03-14)".to_string()));
+        assert!(!lines.iter().any(|l| l.starts_with("Sector")));
+        assert_eq!(lines.last().unwrap(), "The first cryptocurrency.");
+    }
+
+    #[test]
+    fn change_column_label_follows_the_change_basis() {
+        let with_basis = |basis: Option<ChangeBasis>| {
+            let mut p = price("AAPL", "Apple Inc.");
+            p.change_24h = Some(1.0);
+            p.change_basis = basis;
+            p
+        };
+        let crypto = with_basis(None);
+        let stock = with_basis(Some(ChangeBasis::PreviousClose));
+        let stooq = with_basis(Some(ChangeBasis::SessionOpen));
+        let mut no_change = with_basis(Some(ChangeBasis::SessionOpen));
+        no_change.change_24h = None;
+
+        assert_eq!(change_column_label(&[]), "24h Change");
+        assert_eq!(
+            change_column_label(std::slice::from_ref(&crypto)),
+            "24h Change"
+        );
+        assert_eq!(
+            change_column_label(&[stock.clone(), stock.clone()]),
+            "Change (prev close)"
+        );
+        assert_eq!(change_column_label(&[stooq]), "Change (since open)");
+        assert_eq!(
+            change_column_label(&[stock.clone(), no_change]),
+            "Change (prev close)"
+        );
+        assert_eq!(change_column_label(&[crypto, stock.clone()]), "Change");
+
+        let rendered = strip_ansi(&render_price_table(&[stock], &TableOptions::default()));
+        let header = rendered.lines().nth(1).unwrap();
+        assert!(header.contains("Change (prev close)"));
+        assert!(!header.contains("24h Change"));
+    }
+
+    #[test]
+    fn ohlc_table_shows_open_high_low_close_columns() {
+        let mut aapl = price("AAPL", "Apple Inc.");
+        aapl.open_24h = Some(1200.0);
+        aapl.high_24h = Some(1300.0);
+
+        let rendered = strip_ansi(&render_ohlc_table(&[aapl], &TableOptions::default()));
+        let header = rendered.lines().nth(1).unwrap();
+        let columns: Vec<&str> = header
+            .split('\u{2502}')
+            .map(str::trim)
+            .filter(|c| !c.is_empty())
+            .collect();
+        assert_eq!(
+            columns,
+            vec![
+                "Symbol",
+                "Name",
+                "Open",
+                "High",
+                "Low",
+                "Close",
+                "24h Change",
+                "Provider"
+            ]
+        );
+
+        let row = rendered.lines().nth(3).unwrap();
+        assert!(row.contains("1,200"));
+        assert!(row.contains("1,300"));
+        assert!(row.contains("1,234.5"));
+        assert!(row.contains(" - "));
+    }
+
+    #[test]
+    fn price_table_writes_symbols_in_the_requested_case() {
+        let prices = vec![price("cw8.pa", "Amundi MSCI World")];
+
+        let upper = strip_ansi(&render_price_table(&prices, &TableOptions::default()));
+        assert!(upper.contains("CW8.PA"), "{upper}");
+
+        let options = TableOptions {
+            symbol_case: SymbolCase::Preserve,
+            ..TableOptions::default()
+        };
+        let preserved = strip_ansi(&render_price_table(&prices, &options));
+        assert!(preserved.contains("cw8.pa"), "{preserved}");
+    }
+
+    #[test]
+    fn price_table_truncates_names_to_the_max_name_width() {
+        let prices = vec![price("IWDA.AS", "iShares Core MSCI World UCITS ETF")];
+
+        let full = strip_ansi(&render_price_table(&prices, &TableOptions::default()));
+        assert!(full.contains("iShares Core MSCI World UCITS ETF"), "{full}");
+
+        let options = TableOptions {
+            max_name_width: Some(12),
+            ..TableOptions::default()
+        };
+        let truncated = strip_ansi(&render_price_table(&prices, &options));
+        assert!(truncated.contains("iShares Cor…"), "{truncated}");
+        assert!(!truncated.contains("UCITS"), "{truncated}");
+    }
+
+    #[test]
+    fn price_table_shows_rank_column_only_for_ranked_prices() {
+        let mut btc = price("BTC", "Bitcoin");
+        btc.rank = Some(1);
+        let mut eth = price("ETH", "Ethereum");
+        eth.rank = Some(2);
+
+        let ranked = strip_ansi(&render_price_table(&[btc, eth], &TableOptions::default()));
+        let header = ranked.lines().nth(1).unwrap();
+        assert!(header.starts_with("\u{2502} # "));
+        assert!(ranked.lines().nth(3).unwrap().starts_with("\u{2502} 1 "));
+
+        let unranked = strip_ansi(&render_price_table(
+            &[price("AAPL", "Apple Inc.")],
+            &TableOptions::default(),
+        ));
+        assert!(
+            unranked
+                .lines()
+                .nth(1)
+                .unwrap()
+                .starts_with("\u{2502} Symbol")
+        );
+    }
+
+    #[test]
+    fn movers_render_gainers_then_losers() {
+        let mut sol = price("SOL", "Solana");
+        sol.change_24h = Some(9.0);
+        let mut doge = price("DOGE", "Dogecoin");
+        doge.change_24h = Some(-7.5);
+        let movers = Movers {
+            gainers: vec![sol],
+            losers: vec![doge],
+        };
+
+        let rendered = strip_ansi(&render_movers(&movers, &TableOptions::default()));
+        let gainers = rendered.find("Gainers").unwrap();
+        let losers = rendered.find("Losers").unwrap();
+        assert!(gainers < rendered.find("SOL").unwrap());
+        assert!(rendered.find("SOL").unwrap() < losers);
+        assert!(losers < rendered.find("DOGE").unwrap());
+
+        let empty = Movers {
+            gainers: Vec::new(),
+            losers: Vec::new(),
+        };
+        let rendered = strip_ansi(&render_movers(&empty, &TableOptions::default()));
+        assert_eq!(rendered, "Gainers\nnone\n\nLosers\nnone");
+    }
+
+    #[test]
+    fn price_table_shows_market_state_only_when_enabled() {
+        let mut stock = price("AAPL", "Apple Inc.");
+        stock.market_state = Some(MarketState::PostMarket);
+        let mut coin = price("BTC", "Bitcoin");
+        coin.market_state = Some(MarketState::Always);
+        let unknown = price("SPY", "SPDR S&P 500");
+        let prices = vec![stock, coin, unknown];
+
+        let enabled = TableOptions {
+            show_market_status: true,
+            ..TableOptions::default()
+        };
+        let shown = strip_ansi(&render_price_table(&prices, &enabled));
+        assert!(shown.contains("AAPL [POST]"));
+        assert!(shown.contains("BTC [24/7]"));
+        assert!(!shown.contains("SPY ["));
+
+        let hidden = strip_ansi(&render_price_table(&prices, &TableOptions::default()));
+        assert!(!hidden.contains('['));
+    }
+
+    #[test]
+    fn provider_comparison_table_groups_rows_by_symbol() {
+        let quote = |provider: &str, price: Option<f64>| calc::ProviderQuote {
+            provider: provider.to_string(),
+            price,
+            change_24h: None,
+            currency: price.map(|_| "USD".to_string()),
+            error: price.is_none().then(|| "timed out".to_string()),
+        };
+        let comparisons = vec![
+            calc::compare_provider_quotes(
+                "BTC",
+                "Bitcoin",
+                vec![
+                    quote("CoinGecko", Some(100.0)),
+                    quote("CoinMarketCap", Some(102.0)),
+                    quote("Yahoo Finance", None),
+                ],
+            ),
+            calc::compare_provider_quotes("ETH", "Ethereum", vec![quote("CoinGecko", Some(5.0))]),
+        ];
+
+        let rendered = strip_ansi(&render_provider_comparison_table(
+            &comparisons,
+            &TableOptions::default(),
+        ));
+        let lines: Vec<&str> = rendered.lines().collect();
+
+        let row = |needle: &str| {
+            lines
+                .iter()
+                .position(|l| l.contains(needle))
+                .unwrap_or_else(|| panic!("missing {needle}: {rendered}"))
+        };
+        assert!(lines[row("Yahoo Finance")].contains("unavailable"));
+        assert!(lines[row("$101.00")].contains("median"));
+        assert!(lines[row("$101.00")].contains("2.00%"));
+        // Separator line between the BTC median row and the ETH group.
+        assert!(lines[row("ETH") - 1].starts_with('\u{251c}'));
+        assert_eq!(lines.iter().filter(|l| l.contains("median")).count(), 2);
+    }
+
+    #[test]
+    fn symbol_resolutions_table_groups_rows_by_symbol() {
+        let resolution = |symbol: &str, provider: &str, resolved: &str| SymbolResolution {
+            symbol: symbol.to_string(),
+            provider: provider.to_string(),
+            resolved: resolved.to_string(),
+        };
+        let resolutions = vec![
+            resolution("btc", "coingecko", "bitcoin (Bitcoin)"),
+            resolution("btc", "stooq", "btc.us"),
+            resolution("aapl", "coingecko", "aapl (Aapl)"),
+        ];
+
+        let rendered = strip_ansi(&render_symbol_resolutions_table(
+            &resolutions,
+            &TableOptions::default(),
+        ));
+
+        assert!(rendered.contains("Resolves To"));
+        assert_eq!(rendered.matches("btc ").count(), 1);
+        let stooq_row = rendered.lines().find(|l| l.contains("stooq")).unwrap();
+        assert!(stooq_row.starts_with("\u{2502}  "));
+        assert!(stooq_row.contains("btc.us"));
+    }
+
+    #[test]
+    fn providers_list_shows_key_status_and_features() {
+        use crate::provider::coinmarketcap::CoinMarketCap;
+        use crate::provider::yahoo::YahooFinance;
+
+        let infos = vec![
+            ProviderInfo::from_provider(&CoinMarketCap::without_key()),
+            ProviderInfo::from_provider(&YahooFinance::new()),
+        ];
+
+        let lines = render_providers(&infos);
+
+        assert_eq!(
+            lines[1].split_whitespace().collect::<Vec<_>>(),
+            ["ID", "NAME", "KEY", "FEATURES"]
+        );
+        let cmc = lines
+            .iter()
+            .find(|l| l.trim_start().starts_with("cmc"))
+            .unwrap();
+        assert!(cmc.contains(" no "));
+        assert!(cmc.contains("prices, history"));
+        let yahoo = lines
+            .iter()
+            .find(|l| l.trim_start().starts_with("yahoo"))
+            .unwrap();
+        assert!(yahoo.contains(" n-a "));
+        assert!(yahoo.ends_with("prices, history, history_window, search, info"));
+    }
+
+    #[test]
+    fn symbol_errors_include_did_you_mean_suggestions() {
+        let errors = vec![SymbolError {
+            symbol: "BITCON".to_string(),
+            tried: vec!["coingecko".to_string(), "yahoo".to_string()],
+            suggestions: vec![
+                crate::provider::SymbolSuggestion {
+                    symbol: "BTC".to_string(),
+                    name: "Bitcoin".to_string(),
+                },
+                crate::provider::SymbolSuggestion {
+                    symbol: "BTCB".to_string(),
+                    name: "Bitcoin BEP2".to_string(),
+                },
+            ],
+        }];
+
+        let lines = render_symbol_errors(&errors);
+
+        assert_eq!(
+            strip_ansi(&lines[0]),
+            "No data for: BITCON (tried coingecko, yahoo). Did you mean: BTC (Bitcoin), BTCB (Bitcoin BEP2)?"
+        );
+    }
+
+    fn conversion(from: &str, to: &str, rate: f64) -> Conversion {
+        Conversion {
+            from_amount: 100.0,
+            from_currency: from.to_string(),
+            to_symbol: to.to_string(),
+            to_name: to.to_string(),
+            to_amount: 100.0 / rate,
+            rate,
+            provider: "Frankfurter".to_string(),
+            timestamp: chrono::Utc::now(),
+        }
+    }
+
+    #[test]
+    fn conversions_table_shows_both_rate_directions_when_enabled() {
+        let conversions = vec![conversion("USD", "EUR", 1.08)];
+
+        let single = strip_ansi(&render_conversions_table(
+            &conversions,
+            false,
+            &TableOptions::default(),
+        ));
+        assert!(single.contains("1 EUR = $1.08"));
+        assert!(!single.contains("1 USD ="));
+
+        let both = strip_ansi(&render_conversions_table(
+            &conversions,
+            true,
+            &TableOptions::default(),
+        ));
+        assert!(both.contains("1 EUR = $1.08"));
+        assert!(both.contains("1 USD = \u{20ac}0.9259"));
+    }
+
+    #[test]
+    fn plain_output_style_aligns_columns_without_borders() {
+        let currencies = vec![CurrencySupport {
+            code: "EUR".to_string(),
+            name: "Euro".to_string(),
+            fiat_rates: Some(true),
+            crypto_quotes: Some(false),
+        }];
+        let options = TableOptions {
+            style: OutputStyle::Plain,
+            ..TableOptions::default()
+        };
+
+        let bordered = render_currencies_table(&currencies, &TableOptions::default());
+        assert!(bordered.contains('╭'));
+
+        let plain = strip_ansi(&render_currencies_table(&currencies, &options));
+        assert!(!plain.contains(['╭', '│', '─', '╰']));
+        let lines: Vec<Vec<&str>> = plain
+            .lines()
+            .map(|l| l.split_whitespace().collect())
+            .collect();
+        assert_eq!(
+            lines[0],
+            vec!["Code", "Name", "Fiat", "rates", "Crypto", "quotes"]
+        );
+        assert_eq!(lines[1], vec!["EUR", "Euro", "yes", "no"]);
+    }
+
+    #[test]
+    fn change_arrow_reflects_direction_and_flat_threshold() {
+        assert_eq!(change_arrow(Some(1.23), 0.01), "↑");
+        assert_eq!(change_arrow(Some(-0.45), 0.01), "↓");
+        assert_eq!(change_arrow(Some(0.0), 0.01), "→");
+        assert_eq!(change_arrow(Some(0.009), 0.01), "→");
+        assert_eq!(change_arrow(Some(-0.009), 0.01), "→");
+        assert_eq!(change_arrow(Some(0.01), 0.01), "↑");
+        assert_eq!(change_arrow(Some(0.4), 0.5), "→");
+        assert_eq!(change_arrow(Some(0.0), 0.0), "→");
+        assert_eq!(change_arrow(None, 0.01), "-");
+        assert_eq!(change_arrow(Some(f64::NAN), 0.01), "-");
+    }
+
+    #[test]
+    fn price_table_prefixes_changes_with_arrows_when_enabled() {
+        let mut up = price("BTC", "Bitcoin");
+        up.change_24h = Some(1.23);
+        let mut down = price("ETH", "Ethereum");
+        down.change_24h = Some(-0.45);
+        let mut flat = price("USDT", "Tether");
+        flat.change_24h = Some(0.004);
+        let prices = vec![up, down, flat];
+
+        let plain = strip_ansi(&render_price_table(&prices, &TableOptions::default()));
+        assert!(plain.contains("+1.23%"));
+        assert!(!plain.contains('↑'));
+
+        let options = TableOptions {
+            change_arrow_threshold: Some(0.01),
+            ..TableOptions::default()
+        };
+        let arrows = strip_ansi(&render_price_table(&prices, &options));
+        assert!(arrows.contains("↑ +1.23%"));
+        assert!(arrows.contains("↓ -0.45%"));
+        assert!(arrows.contains("→ +0.00%"));
+    }
+
+    #[test]
+    fn performance_table_shows_dash_for_periods_without_history() {
+        let performance = vec![Performance {
+            symbol: "AAPL".to_string(),
+            name: "Apple Inc.".to_string(),
+            currency: "USD".to_string(),
+            provider: "Yahoo Finance".to_string(),
+            price: 210.0,
+            as_of: chrono::NaiveDate::from_ymd_opt(2026, 3, 31).unwrap(),
+            one_month: Some(2.5),
+            three_months: Some(-4.25),
+            six_months: None,
+            ytd: Some(1.0),
+            one_year: None,
+        }];
+
+        let table = strip_ansi(&render_performance_table(
+            &performance,
+            &TableOptions::default(),
+        ));
+        let header = table.lines().find(|line| line.contains("Symbol")).unwrap();
+        let row = table.lines().find(|line| line.contains("AAPL")).unwrap();
+
+        assert!(header.contains("1M") && header.contains("YTD") && header.contains("1Y"));
+        assert!(row.contains("+2.50%"));
+        assert!(row.contains("-4.25%"));
+        assert_eq!(row.matches(" - ").count(), 2);
+    }
+
+    #[test]
+    fn two_date_table_names_both_dates_and_signs_the_change() {
+        let comparison = |symbol: &str, price1: f64, price2: f64| {
+            let (change, pct) = calc::compute_two_date_change(price1, price2);
+            TwoDateComparison {
+                symbol: symbol.to_string(),
+                name: symbol.to_string(),
+                currency: "USD".to_string(),
+                provider: "CoinGecko".to_string(),
+                date1: chrono::NaiveDate::from_ymd_opt(2024, 5, 1).unwrap(),
+                price1,
+                date2: chrono::NaiveDate::from_ymd_opt(2025, 5, 1).unwrap(),
+                price2,
+                change,
+                change_pct: Some(pct).filter(|pct| pct.is_finite()),
+            }
+        };
+        let table = strip_ansi(&render_two_date_table(
+            &[
+                comparison("BTC", 60_000.0, 96_000.0),
+                comparison("ETH", 3_000.0, 1_800.0),
+            ],
+            &TableOptions::default(),
+        ));
+
+        let header = table.lines().find(|line| line.contains("Symbol")).unwrap();
+        assert!(header.contains("Price on 2024-05-01") && header.contains("Price on 2025-05-01"));
+        let btc = table.lines().find(|line| line.contains("BTC")).unwrap();
+        assert!(btc.contains("+$36,000.00 (+60.00%)"), "{btc}");
+        let eth = table.lines().find(|line| line.contains("ETH")).unwrap();
+        assert!(eth.contains("-$1,200.00 (-40.00%)"), "{eth}");
+
+        assert!(render_two_date_table(&[], &TableOptions::default()).is_empty());
+    }
+
+    #[test]
+    fn conversions_table_inverts_crypto_rates() {
+        let conversions = vec![
+            conversion("EUR", "XMR", 250.0),
+            conversion("BTC", "ETH", 20.0),
+        ];
+
+        let rendered = strip_ansi(&render_conversions_table(
+            &conversions,
+            true,
+            &TableOptions::default(),
+        ));
+
+        assert!(rendered.contains("1 EUR = 0.004000 XMR"));
+        assert!(rendered.contains("1 ETH = 0.050000 BTC"));
     }
 }
@@ -0,0 +1,268 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{Error, Result};
+use crate::provider::CoinPrice;
+
+/// Portfolio file layout: a list of `[[holdings]]` tables.
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+struct PortfolioFile {
+    holdings: Vec<Holding>,
+}
+
+/// A single position read from a portfolio file.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Holding {
+    pub symbol: String,
+    pub quantity: f64,
+    pub purchase_price: f64,
+    #[serde(default)]
+    pub purchase_date: Option<NaiveDate>,
+}
+
+/// Current valuation of one holding.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HoldingValue {
+    pub symbol: String,
+    pub name: String,
+    pub quantity: f64,
+    pub purchase_price: f64,
+    pub purchase_date: Option<NaiveDate>,
+    pub current_price: f64,
+    pub cost_basis: f64,
+    pub current_value: f64,
+    pub gain_loss: f64,
+    pub gain_loss_pct: Option<f64>,
+    pub currency: String,
+    pub provider: String,
+}
+
+/// Aggregate totals across all valued holdings.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct PortfolioTotals {
+    pub cost_basis: f64,
+    pub current_value: f64,
+    pub gain_loss: f64,
+    pub gain_loss_pct: Option<f64>,
+}
+
+/// Load holdings from a TOML portfolio file.
+pub fn load_portfolio(path: &Path) -> Result<Vec<Holding>> {
+    let raw = fs::read_to_string(path).map_err(|err| {
+        Error::Io(format!(
+            "failed to read portfolio file '{}': {}",
+            path.display(),
+            err
+        ))
+    })?;
+
+    parse(&raw).map_err(|err| {
+        Error::Config(format!(
+            "invalid portfolio file '{}': {}",
+            path.display(),
+            err
+        ))
+    })
+}
+
+fn parse(raw: &str) -> std::result::Result<Vec<Holding>, String> {
+    let file: PortfolioFile = toml::from_str(raw).map_err(|err| err.to_string())?;
+
+    for holding in &file.holdings {
+        if holding.symbol.trim().is_empty() {
+            return Err("holding symbol cannot be empty".into());
+        }
+        if !holding.quantity.is_finite() || holding.quantity <= 0.0 {
+            return Err(format!(
+                "holding '{}' must have a positive quantity",
+                holding.symbol
+            ));
+        }
+        if !holding.purchase_price.is_finite() || holding.purchase_price < 0.0 {
+            return Err(format!(
+                "holding '{}' must have a non-negative purchase_price",
+                holding.symbol
+            ));
+        }
+    }
+
+    if file.holdings.is_empty() {
+        return Err("no [[holdings]] entries found".into());
+    }
+
+    Ok(file.holdings)
+}
+
+/// Value each holding against the fetched prices.
+///
+/// Holdings without a matching price (by case-insensitive symbol) are skipped.
+pub fn calc_portfolio_value(holdings: &[Holding], prices: &[CoinPrice]) -> Vec<HoldingValue> {
+    let by_symbol: HashMap<String, &CoinPrice> = prices
+        .iter()
+        .map(|p| (p.symbol.trim().to_uppercase(), p))
+        .collect();
+
+    holdings
+        .iter()
+        .filter_map(|holding| {
+            let price = by_symbol.get(&holding.symbol.trim().to_uppercase())?;
+            let cost_basis = holding.quantity * holding.purchase_price;
+            let current_value = holding.quantity * price.price;
+            let gain_loss = current_value - cost_basis;
+            let gain_loss_pct = if cost_basis.abs() > f64::EPSILON {
+                Some((gain_loss / cost_basis) * 100.0)
+            } else {
+                None
+            };
+
+            Some(HoldingValue {
+                symbol: price.symbol.clone(),
+                name: price.name.clone(),
+                quantity: holding.quantity,
+                purchase_price: holding.purchase_price,
+                purchase_date: holding.purchase_date,
+                current_price: price.price,
+                cost_basis,
+                current_value,
+                gain_loss,
+                gain_loss_pct,
+                currency: price.currency.clone(),
+                provider: price.provider.clone(),
+            })
+        })
+        .collect()
+}
+
+/// Sum cost basis, value and gain/loss across holdings.
+pub fn portfolio_totals(values: &[HoldingValue]) -> PortfolioTotals {
+    let cost_basis: f64 = values.iter().map(|v| v.cost_basis).sum();
+    let current_value: f64 = values.iter().map(|v| v.current_value).sum();
+    let gain_loss = current_value - cost_basis;
+    let gain_loss_pct = if cost_basis.abs() > f64::EPSILON {
+        Some((gain_loss / cost_basis) * 100.0)
+    } else {
+        None
+    };
+
+    PortfolioTotals {
+        cost_basis,
+        current_value,
+        gain_loss,
+        gain_loss_pct,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::provider::test_price;
+
+    fn holding(symbol: &str, quantity: f64, purchase_price: f64) -> Holding {
+        Holding {
+            symbol: symbol.to_string(),
+            quantity,
+            purchase_price,
+            purchase_date: None,
+        }
+    }
+
+    #[test]
+    fn parse_holdings() {
+        let holdings = parse(
+            r#"
+            [[holdings]]
+            symbol = "BTC"
+            quantity = 0.5
+            purchase_price = 40000
+            purchase_date = "2023-01-01"
+
+            [[holdings]]
+            symbol = "AAPL"
+            quantity = 10
+            purchase_price = 150.25
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(holdings.len(), 2);
+        assert_eq!(holdings[0].symbol, "BTC");
+        assert_eq!(
+            holdings[0].purchase_date,
+            NaiveDate::from_ymd_opt(2023, 1, 1)
+        );
+        assert!(holdings[1].purchase_date.is_none());
+    }
+
+    #[test]
+    fn parse_rejects_empty_and_invalid_quantity() {
+        assert!(parse("").is_err());
+        assert!(
+            parse(
+                r#"
+                [[holdings]]
+                symbol = "BTC"
+                quantity = 0
+                purchase_price = 1
+                "#
+            )
+            .is_err()
+        );
+    }
+
+    #[test]
+    fn calc_portfolio_value_computes_gain_and_loss() {
+        let holdings = vec![holding("btc", 0.5, 40000.0), holding("ETH", 2.0, 3000.0)];
+        let prices = vec![test_price("BTC", 60000.0), test_price("ETH", 2500.0)];
+
+        let values = calc_portfolio_value(&holdings, &prices);
+
+        assert_eq!(values.len(), 2);
+        assert_eq!(values[0].symbol, "BTC");
+        assert!((values[0].cost_basis - 20000.0).abs() < 1e-9);
+        assert!((values[0].current_value - 30000.0).abs() < 1e-9);
+        assert!((values[0].gain_loss - 10000.0).abs() < 1e-9);
+        assert!((values[0].gain_loss_pct.unwrap() - 50.0).abs() < 1e-9);
+
+        assert!((values[1].current_value - 5000.0).abs() < 1e-9);
+        assert!((values[1].gain_loss + 1000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn portfolio_totals_sums_values() {
+        let holdings = vec![holding("BTC", 0.5, 40000.0), holding("ETH", 2.0, 3000.0)];
+        let prices = vec![test_price("BTC", 60000.0), test_price("ETH", 2500.0)];
+
+        let totals = portfolio_totals(&calc_portfolio_value(&holdings, &prices));
+
+        assert!((totals.cost_basis - 26000.0).abs() < 1e-9);
+        assert!((totals.current_value - 35000.0).abs() < 1e-9);
+        assert!((totals.gain_loss - 9000.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn calc_portfolio_value_skips_holdings_without_prices() {
+        let holdings = vec![holding("BTC", 1.0, 10.0), holding("NOPE", 1.0, 10.0)];
+        let prices = vec![test_price("BTC", 20.0)];
+
+        let values = calc_portfolio_value(&holdings, &prices);
+
+        assert_eq!(values.len(), 1);
+        assert_eq!(values[0].symbol, "BTC");
+    }
+
+    #[test]
+    fn calc_portfolio_value_handles_zero_cost_basis() {
+        let holdings = vec![holding("AIRDROP", 100.0, 0.0)];
+        let prices = vec![test_price("AIRDROP", 0.5)];
+
+        let values = calc_portfolio_value(&holdings, &prices);
+
+        assert!((values[0].gain_loss - 50.0).abs() < 1e-9);
+        assert!(values[0].gain_loss_pct.is_none());
+    }
+}
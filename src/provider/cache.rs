@@ -1,30 +1,72 @@
+use chrono::{DateTime, Utc};
 use serde::Serialize;
 use serde::de::DeserializeOwned;
-use std::collections::hash_map::DefaultHasher;
-use std::hash::{Hash, Hasher};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tracing::debug;
 
+use super::context::ProviderContext;
+use crate::clock::Clock;
+
+/// File written to the cache root once old-scheme entries have been removed.
+const LEGACY_CLEANUP_MARKER: &str = ".legacy-keys-removed";
+/// Length of the file stems written by the old `DefaultHasher` key scheme.
+const LEGACY_STEM_LEN: usize = 16;
+
 #[derive(Debug, Serialize, serde::Deserialize)]
 struct CacheEnvelope<T> {
     fetched_at_unix: i64,
     value: T,
 }
 
-pub async fn read_json<T: DeserializeOwned>(provider: &str, key: &str, ttl_secs: i64) -> Option<T> {
+/// A cache entry's value and the time it was fetched from the provider.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Cached<T> {
+    pub value: T,
+    pub fetched_at: DateTime<Utc>,
+}
+
+/// Read an entry at most `ttl_secs` old by `clock`.
+pub async fn read_json<T: DeserializeOwned>(
+    provider: &str,
+    key: &str,
+    ttl_secs: i64,
+    clock: &dyn Clock,
+) -> Option<Cached<T>> {
+    read_json_with(provider, key, |fetched_at| {
+        is_fresh(fetched_at.timestamp(), ttl_secs, clock)
+    })
+    .await
+}
+
+/// Read an entry that `is_fresh` accepts, given the time it was fetched.
+///
+/// For freshness rules a fixed TTL cannot express, such as expiring at market close.
+pub async fn read_json_with<T: DeserializeOwned>(
+    provider: &str,
+    key: &str,
+    is_fresh: impl FnOnce(DateTime<Utc>) -> bool,
+) -> Option<Cached<T>> {
     let path = cache_path(provider, key)?;
     let raw = tokio::fs::read_to_string(&path).await.ok()?;
     let envelope: CacheEnvelope<T> = serde_json::from_str(&raw).ok()?;
+    let fetched_at = DateTime::from_timestamp(envelope.fetched_at_unix, 0)?;
 
-    let age_secs = chrono::Utc::now().timestamp() - envelope.fetched_at_unix;
-    if age_secs < 0 || age_secs > ttl_secs {
-        return None;
-    }
+    is_fresh(fetched_at).then_some(Cached {
+        value: envelope.value,
+        fetched_at,
+    })
+}
 
-    Some(envelope.value)
+/// Whether an entry fetched at `fetched_at_unix` is at most `ttl_secs` old.
+///
+/// Entries from the future (a clock that moved backwards) count as stale.
+fn is_fresh(fetched_at_unix: i64, ttl_secs: i64, clock: &dyn Clock) -> bool {
+    let age_secs = clock.now().timestamp() - fetched_at_unix;
+    (0..=ttl_secs).contains(&age_secs)
 }
 
-pub async fn write_json<T: Serialize>(provider: &str, key: &str, value: &T) {
+/// Write `value` stamped with `ctx`'s clock, then prune to `ctx.cache_max_entries`.
+pub async fn write_json<T: Serialize>(provider: &str, key: &str, value: &T, ctx: &ProviderContext) {
     let Some(path) = cache_path(provider, key) else {
         return;
     };
@@ -39,7 +81,7 @@ pub async fn write_json<T: Serialize>(provider: &str, key: &str, value: &T) {
     }
 
     let envelope = CacheEnvelope {
-        fetched_at_unix: chrono::Utc::now().timestamp(),
+        fetched_at_unix: ctx.clock.now().timestamp(),
         value,
     };
 
@@ -51,9 +93,93 @@ pub async fn write_json<T: Serialize>(provider: &str, key: &str, value: &T) {
         }
     };
 
-    if let Err(err) = tokio::fs::write(&path, serialized).await {
-        debug!(path = %path.display(), error = %err, "failed to write cache file");
+    match tokio::fs::write(&path, serialized).await {
+        Ok(()) => {
+            ctx.record_cache_write();
+            if ctx.cache_max_entries > 0 {
+                prune_dir(parent, ctx.cache_max_entries).await;
+            }
+        }
+        Err(err) => {
+            debug!(path = %path.display(), error = %err, "failed to write cache file");
+        }
+    }
+}
+
+/// Delete cache files left by the pre-SHA-256 key scheme, once per cache directory.
+///
+/// Those entries can never be read again. Returns the number of files removed.
+pub async fn remove_legacy_entries() -> usize {
+    let Some(root) = cache_root() else {
+        return 0;
+    };
+    remove_legacy_in(&root.join("pricr")).await
+}
+
+/// Delete the oldest cache files for `provider` beyond `max_entries`.
+///
+/// Returns the number of files removed.
+pub async fn prune(provider: &str, max_entries: usize) -> usize {
+    let Some(root) = cache_root() else {
+        return 0;
+    };
+    prune_dir(
+        &root.join("pricr").join(sanitize_component(provider)),
+        max_entries,
+    )
+    .await
+}
+
+/// Prune every provider directory under the cache root to `max_entries`.
+pub async fn prune_all(max_entries: usize) -> usize {
+    let Some(root) = cache_root() else {
+        return 0;
+    };
+    let Ok(mut dirs) = tokio::fs::read_dir(root.join("pricr")).await else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    while let Ok(Some(entry)) = dirs.next_entry().await {
+        if entry.file_type().await.is_ok_and(|t| t.is_dir()) {
+            removed += prune(&entry.file_name().to_string_lossy(), max_entries).await;
+        }
+    }
+    removed
+}
+
+async fn prune_dir(dir: &Path, max_entries: usize) -> usize {
+    let Ok(mut entries) = tokio::fs::read_dir(dir).await else {
+        return 0;
+    };
+
+    let mut files = Vec::new();
+    while let Ok(Some(entry)) = entries.next_entry().await {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "json") {
+            continue;
+        }
+        if let Ok(modified) = entry.metadata().await.and_then(|m| m.modified()) {
+            files.push((modified, path));
+        }
+    }
+
+    if files.len() <= max_entries {
+        return 0;
+    }
+
+    files.sort();
+    let excess = files.len() - max_entries;
+    let mut removed = 0;
+    for (_, path) in files.into_iter().take(excess) {
+        match tokio::fs::remove_file(&path).await {
+            Ok(()) => removed += 1,
+            Err(err) => {
+                debug!(path = %path.display(), error = %err, "failed to prune cache file");
+            }
+        }
     }
+    removed
 }
 
 fn cache_path(provider: &str, key: &str) -> Option<PathBuf> {
@@ -86,8 +212,112 @@ fn sanitize_component(s: &str) -> String {
         .collect()
 }
 
+/// File stem for a cache key: the hex SHA-256 digest, stable across Rust versions.
 fn hash_key(key: &str) -> String {
-    let mut hasher = DefaultHasher::new();
-    key.hash(&mut hasher);
-    format!("{:016x}", hasher.finish())
+    use sha2::Digest;
+    format!("{:x}", sha2::Sha256::digest(key.as_bytes()))
+}
+
+/// Whether `path` was written by the old 16-hex-digit `DefaultHasher` key scheme.
+fn is_legacy_entry(path: &Path) -> bool {
+    path.extension().is_some_and(|ext| ext == "json")
+        && path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .is_some_and(|stem| {
+                stem.len() == LEGACY_STEM_LEN && stem.chars().all(|c| c.is_ascii_hexdigit())
+            })
+}
+
+async fn remove_legacy_in(pricr_dir: &Path) -> usize {
+    let marker = pricr_dir.join(LEGACY_CLEANUP_MARKER);
+    if tokio::fs::try_exists(&marker).await.unwrap_or(true) {
+        return 0;
+    }
+    let Ok(mut dirs) = tokio::fs::read_dir(pricr_dir).await else {
+        return 0;
+    };
+
+    let mut removed = 0;
+    while let Ok(Some(dir)) = dirs.next_entry().await {
+        if !dir.file_type().await.is_ok_and(|t| t.is_dir()) {
+            continue;
+        }
+        let Ok(mut entries) = tokio::fs::read_dir(dir.path()).await else {
+            continue;
+        };
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if is_legacy_entry(&path) && tokio::fs::remove_file(&path).await.is_ok() {
+                removed += 1;
+            }
+        }
+    }
+
+    if let Err(err) = tokio::fs::write(&marker, b"").await {
+        debug!(path = %marker.display(), error = %err, "failed to write cache cleanup marker");
+    }
+    if removed > 0 {
+        debug!(removed, "removed cache files from the old key scheme");
+    }
+    removed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::clock::FixedClock;
+
+    #[test]
+    fn hash_key_is_a_stable_sha256_hex_digest() {
+        let hash = hash_key("simple_price:https://api.coingecko.com:bitcoin:usd");
+
+        assert_eq!(hash.len(), 64);
+        assert!(hash.chars().all(|c| c.is_ascii_hexdigit()));
+        assert_eq!(
+            hash,
+            hash_key("simple_price:https://api.coingecko.com:bitcoin:usd")
+        );
+        assert_eq!(
+            hash_key(""),
+            "e3b0c44298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855"
+        );
+    }
+
+    #[test]
+    fn entries_expire_once_older_than_the_ttl() {
+        let fetched = FixedClock::at(2024, 6, 1, 12, 0, 0);
+        let fetched_at = fetched.now().timestamp();
+        let later = |secs: i64| FixedClock(fetched.now() + chrono::Duration::seconds(secs));
+
+        assert!(is_fresh(fetched_at, 60, &fetched));
+        assert!(is_fresh(fetched_at, 60, &later(60)));
+        assert!(!is_fresh(fetched_at, 60, &later(61)));
+        assert!(!is_fresh(fetched_at, 60, &later(-1)));
+    }
+
+    #[test]
+    fn hash_key_differs_for_different_keys() {
+        assert_ne!(hash_key("ticker:BTCUSDT"), hash_key("ticker:ETHUSDT"));
+        assert_ne!(hash_key("a:b"), hash_key("a:c"));
+    }
+
+    #[tokio::test]
+    async fn remove_legacy_in_deletes_old_entries_once() {
+        let root = tempfile::tempdir().unwrap();
+        let provider_dir = root.path().join("coingecko");
+        std::fs::create_dir_all(&provider_dir).unwrap();
+        let legacy = provider_dir.join("0123456789abcdef.json");
+        let current = provider_dir.join(format!("{}.json", hash_key("key")));
+        std::fs::write(&legacy, "{}").unwrap();
+        std::fs::write(&current, "{}").unwrap();
+
+        assert_eq!(remove_legacy_in(root.path()).await, 1);
+        assert!(!legacy.exists());
+        assert!(current.exists());
+
+        std::fs::write(&legacy, "{}").unwrap();
+        assert_eq!(remove_legacy_in(root.path()).await, 0);
+        assert!(legacy.exists());
+    }
 }
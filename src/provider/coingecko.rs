@@ -1,40 +1,103 @@
 use async_trait::async_trait;
 use futures::future::join_all;
-use reqwest::Client;
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
 use tracing::{debug, trace};
 
-use super::cache;
-use super::{CoinPrice, HistoryInterval, PriceHistory, PricePoint, PriceProvider};
+use super::context::ProviderContext;
+use super::{
+    ApiKeyStatus, AssetClass, AssetInfo, CoinDetails, CoinPrice, Feature, HistoryInterval,
+    MarketState, PriceHistory, PricePoint, PriceProvider, ProviderCapabilities, TickerMatch,
+};
+use super::{HttpResponse, cache, send_with_retry, short_description, status_error};
+use crate::clock::Clock;
 use crate::error::{Error, Result};
 
 const BASE_URL: &str = "https://api.coingecko.com/api/v3";
+const PRO_BASE_URL: &str = "https://pro-api.coingecko.com/api/v3";
 const PRICE_CACHE_TTL_SECS: i64 = 30;
 const HOURLY_HISTORY_CACHE_TTL_SECS: i64 = 60 * 60;
 const DAILY_HISTORY_CACHE_TTL_SECS: i64 = 12 * 60 * 60;
+const MARKETS_CACHE_TTL_SECS: i64 = 5 * 60;
+const SEARCH_CACHE_TTL_SECS: i64 = 10 * 60;
+const TRENDING_CACHE_TTL_SECS: i64 = 10 * 60;
+const INFO_CACHE_TTL_SECS: i64 = 60 * 60;
+const VS_CURRENCIES_CACHE_TTL_SECS: i64 = 24 * 60 * 60;
 
-/// CoinGecko price provider -- free public API, no key required.
+/// CoinGecko API plan an API key belongs to.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ApiTier {
+    /// Free registered key, sent to the public host.
+    #[default]
+    Demo,
+    /// Paid key, sent to the `pro-api` host.
+    Pro,
+}
+
+impl ApiTier {
+    fn header(self) -> &'static str {
+        match self {
+            Self::Demo => "x-cg-demo-api-key",
+            Self::Pro => "x-cg-pro-api-key",
+        }
+    }
+}
+
+/// CoinGecko API key and the plan it belongs to.
+#[derive(Debug, Clone)]
+pub struct ApiKey {
+    pub key: String,
+    pub tier: ApiTier,
+}
+
+/// CoinGecko price provider -- free public API; an optional key raises rate limits.
 pub struct CoinGecko {
-    client: Client,
+    ctx: ProviderContext,
     base_url: String,
+    api_key: Option<ApiKey>,
 }
 
 impl CoinGecko {
     /// Create a CoinGecko provider using the default production API URL.
     pub fn new() -> Self {
-        Self::with_base_url(BASE_URL)
+        Self::configured(None, None, ProviderContext::new())
     }
 
     /// Create a CoinGecko provider with a custom base URL.
     pub fn with_base_url(base_url: impl Into<String>) -> Self {
-        let client = Client::builder()
-            .user_agent("pricr/0.1.0")
-            .build()
-            .expect("failed to build HTTP client");
+        Self::configured(Some(base_url.into()), None, ProviderContext::new())
+    }
+
+    /// Create a CoinGecko provider from the optional `[coingecko]` overrides,
+    /// sharing `ctx`'s client and clock.
+    ///
+    /// A pro key switches the default host to `pro-api.coingecko.com`; an explicit
+    /// `base_url` always wins.
+    pub fn configured(
+        base_url: Option<String>,
+        api_key: Option<ApiKey>,
+        ctx: ProviderContext,
+    ) -> Self {
+        let base_url = base_url.unwrap_or_else(|| match &api_key {
+            Some(ApiKey {
+                tier: ApiTier::Pro, ..
+            }) => PRO_BASE_URL.to_string(),
+            _ => BASE_URL.to_string(),
+        });
         Self {
-            client,
-            base_url: base_url.into(),
+            ctx,
+            base_url,
+            api_key,
+        }
+    }
+
+    /// GET request carrying the API key header when one is configured.
+    fn get(&self, url: &str) -> reqwest::RequestBuilder {
+        let request = self.ctx.client.get(url);
+        match &self.api_key {
+            Some(api_key) => request.header(api_key.tier.header(), &api_key.key),
+            None => request,
         }
     }
 
@@ -72,6 +135,42 @@ impl CoinGecko {
         };
         (id.to_string(), name.to_string())
     }
+
+    /// Fetch descriptive metadata (description, links, categories) for one coin.
+    pub async fn get_coin_details(&self, symbol: &str) -> Result<CoinDetails> {
+        let detail = self.fetch_coin_detail(symbol).await?;
+
+        Ok(CoinDetails {
+            symbol: detail.symbol.to_uppercase(),
+            name: detail.name,
+            description: detail
+                .description
+                .get("en")
+                .and_then(|text| short_description(text.as_deref()?))
+                .unwrap_or_default(),
+            homepage: non_empty_links(detail.links.homepage),
+            github: non_empty_links(detail.links.repos_url.github),
+            categories: detail.categories.into_iter().flatten().collect(),
+            genesis_date: detail.genesis_date,
+            market_cap_rank: detail.market_cap_rank,
+        })
+    }
+
+    /// Top `limit` coins by market cap in a CoinGecko category such as `layer-1`
+    /// or `meme-token`.
+    pub async fn get_category_listings(
+        &self,
+        category: &str,
+        limit: usize,
+        currency: &str,
+    ) -> Result<Vec<CoinPrice>> {
+        let query = format!(
+            "category={}&order=market_cap_desc&per_page={}&page=1",
+            category.trim().to_lowercase(),
+            limit
+        );
+        self.ranked_listings(&query, limit, currency).await
+    }
 }
 
 impl Default for CoinGecko {
@@ -84,6 +183,100 @@ impl Default for CoinGecko {
 /// Example: `{ "bitcoin": { "usd": 50000, "usd_24h_change": 2.5, "usd_market_cap": 9.5e11 } }`
 type SimplePrice = HashMap<String, HashMap<String, f64>>;
 
+/// One entry of the CoinGecko `/coins/markets` response.
+#[derive(Debug, Clone, Deserialize)]
+struct MarketListing {
+    id: String,
+    symbol: String,
+    name: String,
+    current_price: Option<f64>,
+    market_cap: Option<f64>,
+    market_cap_rank: Option<u32>,
+    price_change_24h: Option<f64>,
+    price_change_percentage_24h: Option<f64>,
+    high_24h: Option<f64>,
+    low_24h: Option<f64>,
+}
+
+/// CoinGecko `/search/trending` response; only the coin ids are used.
+#[derive(Debug, Deserialize)]
+struct TrendingResponse {
+    #[serde(default)]
+    coins: Vec<TrendingCoin>,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrendingCoin {
+    item: TrendingItem,
+}
+
+#[derive(Debug, Deserialize)]
+struct TrendingItem {
+    id: String,
+}
+
+/// CoinGecko `/search` response; only the `coins` section is used.
+#[derive(Debug, Deserialize)]
+struct SearchResponse {
+    #[serde(default)]
+    coins: Vec<SearchCoin>,
+}
+
+#[derive(Debug, Deserialize)]
+struct SearchCoin {
+    symbol: String,
+    name: String,
+    market_cap_rank: Option<u32>,
+}
+
+/// CoinGecko `/coins/{id}` response; only the fields shown by `pricr info` and `--details` are read.
+#[derive(Debug, Deserialize)]
+struct CoinDetail {
+    symbol: String,
+    name: String,
+    #[serde(default)]
+    categories: Vec<Option<String>>,
+    genesis_date: Option<chrono::NaiveDate>,
+    market_cap_rank: Option<u32>,
+    #[serde(default)]
+    description: HashMap<String, Option<String>>,
+    #[serde(default)]
+    links: CoinLinks,
+    #[serde(default)]
+    market_data: Option<CoinMarketData>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CoinLinks {
+    #[serde(default)]
+    homepage: Vec<String>,
+    #[serde(default)]
+    blockchain_site: Vec<String>,
+    #[serde(default)]
+    repos_url: CoinRepos,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct CoinRepos {
+    #[serde(default)]
+    github: Vec<String>,
+}
+
+/// Per-currency maps are keyed by lowercase vs-currency (e.g. `usd`).
+#[derive(Debug, Default, Deserialize)]
+struct CoinMarketData {
+    #[serde(default)]
+    ath: HashMap<String, Option<f64>>,
+    #[serde(default)]
+    ath_date: HashMap<String, Option<chrono::DateTime<chrono::Utc>>>,
+    #[serde(default)]
+    atl: HashMap<String, Option<f64>>,
+    #[serde(default)]
+    atl_date: HashMap<String, Option<chrono::DateTime<chrono::Utc>>>,
+    circulating_supply: Option<f64>,
+    total_supply: Option<f64>,
+}
+
 #[derive(Debug, Deserialize)]
 struct MarketChartResponse {
     prices: Vec<[f64; 2]>,
@@ -99,6 +292,36 @@ impl PriceProvider for CoinGecko {
         "coingecko"
     }
 
+    fn clock(&self) -> &dyn Clock {
+        self.ctx.clock.as_ref()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            api_key: if self.api_key.is_some() {
+                ApiKeyStatus::Configured
+            } else {
+                ApiKeyStatus::NotRequired
+            },
+            features: vec![
+                Feature::Prices,
+                Feature::History,
+                Feature::Search,
+                Feature::Listings,
+                Feature::Trending,
+                Feature::CryptoQuote,
+                Feature::Info,
+            ],
+            asset_classes: vec![AssetClass::Crypto],
+            base_url: self.base_url.clone(),
+        }
+    }
+
+    fn resolve_symbol(&self, symbol: &str) -> String {
+        let (id, name) = Self::resolve(symbol.trim());
+        format!("{} ({})", id, name)
+    }
+
     async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
         let resolved: Vec<(String, String)> = symbols.iter().map(|s| Self::resolve(s)).collect();
         let ids_param: String = resolved
@@ -116,28 +339,43 @@ impl PriceProvider for CoinGecko {
 
         debug!(url = %url, "fetching prices from CoinGecko");
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("coingecko", &cache_key, PRICE_CACHE_TTL_SECS).await
+        let (body, fetched_at) = if let Some(cached_body) = cache::read_json::<String>(
+            "coingecko",
+            &cache_key,
+            PRICE_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(ids = %ids_param, currency = %cur, "using cached CoinGecko prices");
-            cached_body
+            (cached_body.value, cached_body.fetched_at)
         } else {
-            let resp = self.client.get(&url).send().await?;
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(self.name(), self.get(&url), &self.ctx).await?;
 
             debug!(status = %status, body_len = body.len(), "CoinGecko response");
             trace!(body = %body, "CoinGecko response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "CoinGecko returned {}: {}",
-                    status, body
-                )));
+                let symbol = match symbols {
+                    [only] => Some(only.as_str()),
+                    _ => None,
+                };
+                return Err(status_error(
+                    self.name(),
+                    "CoinGecko",
+                    status,
+                    retry_after,
+                    symbol,
+                    &body,
+                ));
             }
 
-            cache::write_json("coingecko", &cache_key, &body).await;
-            body
+            cache::write_json("coingecko", &cache_key, &body, &self.ctx).await;
+            (body, self.ctx.clock.now())
         };
 
         let data: SimplePrice = serde_json::from_str(&body)
@@ -148,17 +386,28 @@ impl PriceProvider for CoinGecko {
 
         let mut results = Vec::new();
         for (i, (cg_id, display_name)) in resolved.iter().enumerate() {
-            if let Some(coin_data) = data.get(cg_id.as_str()) {
-                let price = coin_data.get(&cur).copied().unwrap_or(0.0);
+            // A coin without a price in `cur` is left out so fallback can try other providers.
+            if let Some(coin_data) = data.get(cg_id.as_str())
+                && let Some(price) = coin_data.get(&cur).copied()
+            {
                 results.push(CoinPrice {
                     symbol: symbols[i].to_uppercase(),
                     name: display_name.clone(),
                     price,
                     change_24h: coin_data.get(&change_key).copied(),
                     market_cap: coin_data.get(&cap_key).copied(),
+                    open_24h: None,
+                    high_24h: None,
+                    low_24h: None,
+                    market_state: Some(MarketState::Always),
+                    change_basis: None,
+                    rank: None,
+                    fundamentals: None,
+                    native_price: None,
+                    native_currency: None,
                     currency: cur.to_uppercase(),
                     provider: self.name().to_string(),
-                    timestamp: chrono::Utc::now(),
+                    timestamp: fetched_at,
                 });
             }
         }
@@ -170,6 +419,47 @@ impl PriceProvider for CoinGecko {
         Ok(results)
     }
 
+    async fn get_ohlc(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
+        let resolved: Vec<(String, String)> = symbols.iter().map(|s| Self::resolve(s)).collect();
+        let ids_param: String = resolved
+            .iter()
+            .map(|(id, _)| id.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+        let cur = currency.to_lowercase();
+
+        // /simple/price has no intraday range, so read OHLC from /coins/markets.
+        let cache::Cached {
+            value: listings,
+            fetched_at,
+        } = self
+            .fetch_markets(&cur, &format!("ids={}", ids_param))
+            .await?;
+        let by_id: HashMap<String, MarketListing> = listings
+            .into_iter()
+            .map(|listing| (listing.id.clone(), listing))
+            .collect();
+
+        let results: Vec<CoinPrice> = resolved
+            .iter()
+            .enumerate()
+            .filter_map(|(i, (cg_id, display_name))| {
+                let listing = by_id.get(cg_id)?.clone();
+                let mut price = self.listing_price(listing, &cur, fetched_at)?;
+                price.symbol = symbols[i].to_uppercase();
+                price.name = display_name.clone();
+                price.rank = None;
+                Some(price)
+            })
+            .collect();
+
+        if results.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(results)
+    }
+
     async fn get_price_history(
         &self,
         symbols: &[String],
@@ -193,9 +483,407 @@ impl PriceProvider for CoinGecko {
 
         Ok(histories)
     }
+
+    async fn search_tickers(&self, query: &str, limit: usize) -> Result<Vec<TickerMatch>> {
+        let trimmed = query.trim();
+        if trimmed.is_empty() {
+            return Err(Error::Config("ticker search query cannot be empty".into()));
+        }
+
+        let endpoint = format!("{}/search", self.base_url);
+        let cache_key = format!("search:{}:{}", self.base_url, trimmed.to_lowercase());
+
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "coingecko",
+            &cache_key,
+            SEARCH_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            cached_body.value
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.get(&endpoint).query(&[("query", trimmed)]),
+                &self.ctx,
+            )
+            .await?;
+
+            debug!(status = %status, body_len = body.len(), "CoinGecko search response");
+            trace!(body = %body, "CoinGecko search response body");
+
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    "CoinGecko search",
+                    status,
+                    retry_after,
+                    None,
+                    &body,
+                ));
+            }
+
+            cache::write_json("coingecko", &cache_key, &body, &self.ctx).await;
+            body
+        };
+
+        let payload: SearchResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("CoinGecko search JSON: {}", e)))?;
+
+        let matches: Vec<TickerMatch> = payload
+            .coins
+            .into_iter()
+            .filter(|coin| !coin.symbol.trim().is_empty())
+            .map(|coin| TickerMatch {
+                symbol: coin.symbol.trim().to_uppercase(),
+                name: coin.name,
+                exchange: "Crypto".to_string(),
+                asset_type: "Cryptocurrency".to_string(),
+                provider: self.name().to_string(),
+                rank: coin.market_cap_rank,
+            })
+            .take(limit)
+            .collect();
+
+        if matches.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(matches)
+    }
+
+    async fn get_info(&self, symbol: &str, currency: &str) -> Result<AssetInfo> {
+        let cur = currency.to_lowercase();
+        let detail = self.fetch_coin_detail(symbol).await?;
+        let market = detail.market_data.unwrap_or_default();
+
+        Ok(AssetInfo {
+            symbol: detail.symbol.to_uppercase(),
+            name: detail.name,
+            rank: detail.market_cap_rank,
+            categories: detail.categories.into_iter().flatten().collect(),
+            genesis_date: detail.genesis_date,
+            homepage: non_empty_links(detail.links.homepage).into_iter().next(),
+            explorers: non_empty_links(detail.links.blockchain_site),
+            circulating_supply: market.circulating_supply,
+            total_supply: market.total_supply,
+            ath: market.ath.get(&cur).copied().flatten(),
+            ath_date: market.ath_date.get(&cur).copied().flatten(),
+            atl: market.atl.get(&cur).copied().flatten(),
+            atl_date: market.atl_date.get(&cur).copied().flatten(),
+            description: detail
+                .description
+                .get("en")
+                .and_then(|text| short_description(text.as_deref()?)),
+            currency: cur.to_uppercase(),
+            provider: self.name().to_string(),
+            ..AssetInfo::default()
+        })
+    }
+
+    async fn get_quote_currencies(&self) -> Result<Vec<String>> {
+        let url = format!("{}/simple/supported_vs_currencies", self.base_url);
+        let cache_key = format!("supported_vs_currencies:{}", self.base_url);
+
+        debug!(url = %url, "fetching supported vs_currencies from CoinGecko");
+
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "coingecko",
+            &cache_key,
+            VS_CURRENCIES_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            debug!("using cached CoinGecko vs_currencies");
+            cached_body.value
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(self.name(), self.get(&url), &self.ctx).await?;
+
+            debug!(status = %status, body_len = body.len(), "CoinGecko vs_currencies response");
+            trace!(body = %body, "CoinGecko vs_currencies response body");
+
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    "CoinGecko vs_currencies",
+                    status,
+                    retry_after,
+                    None,
+                    &body,
+                ));
+            }
+
+            cache::write_json("coingecko", &cache_key, &body, &self.ctx).await;
+            body
+        };
+
+        let codes: Vec<String> = serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("CoinGecko vs_currencies JSON: {}", e)))?;
+        if codes.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(codes.into_iter().map(|code| code.to_lowercase()).collect())
+    }
+
+    async fn get_top_listings(&self, limit: usize, currency: &str) -> Result<Vec<CoinPrice>> {
+        let query = format!("order=market_cap_desc&per_page={}&page=1", limit);
+        self.ranked_listings(&query, limit, currency).await
+    }
+
+    async fn get_trending(&self, currency: &str) -> Result<Vec<CoinPrice>> {
+        let cur = currency.to_lowercase();
+        let url = format!("{}/search/trending", self.base_url);
+        let cache_key = format!("search_trending:{}", self.base_url);
+
+        debug!(url = %url, "fetching trending coins from CoinGecko");
+
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "coingecko",
+            &cache_key,
+            TRENDING_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            debug!("using cached CoinGecko trending coins");
+            cached_body.value
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(self.name(), self.get(&url), &self.ctx).await?;
+
+            debug!(status = %status, body_len = body.len(), "CoinGecko trending response");
+            trace!(body = %body, "CoinGecko trending response body");
+
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    "CoinGecko trending",
+                    status,
+                    retry_after,
+                    None,
+                    &body,
+                ));
+            }
+
+            cache::write_json("coingecko", &cache_key, &body, &self.ctx).await;
+            body
+        };
+
+        let trending: TrendingResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("CoinGecko trending JSON: {}", e)))?;
+        let ids: Vec<String> = trending.coins.into_iter().map(|c| c.item.id).collect();
+        if ids.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        // Trending entries only carry USD figures, so quote them through /coins/markets.
+        let cache::Cached {
+            value: mut listings,
+            fetched_at,
+        } = self
+            .fetch_markets(&cur, &format!("ids={}", ids.join(",")))
+            .await?;
+        listings.sort_by_key(|listing| ids.iter().position(|id| *id == listing.id));
+
+        let results: Vec<CoinPrice> = listings
+            .into_iter()
+            .filter_map(|listing| self.listing_price(listing, &cur, fetched_at))
+            .collect();
+
+        if results.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(results)
+    }
 }
 
 impl CoinGecko {
+    /// Fetch the `/coins/{id}` profile for `symbol` (cached for an hour).
+    async fn fetch_coin_detail(&self, symbol: &str) -> Result<CoinDetail> {
+        let (cg_id, _) = Self::resolve(symbol.trim());
+        let url = format!(
+            "{}/coins/{}?localization=false&tickers=false&market_data=true&community_data=false&developer_data=false&sparkline=false",
+            self.base_url, cg_id
+        );
+        let cache_key = format!("coin_detail:{}:{}", self.base_url, cg_id);
+
+        debug!(url = %url, "fetching coin info from CoinGecko");
+
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "coingecko",
+            &cache_key,
+            INFO_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            debug!(id = %cg_id, "using cached CoinGecko coin info");
+            cached_body.value
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(self.name(), self.get(&url), &self.ctx).await?;
+
+            debug!(status = %status, body_len = body.len(), "CoinGecko coin info response");
+            trace!(body = %body, "CoinGecko coin info response body");
+
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    "CoinGecko coin info",
+                    status,
+                    retry_after,
+                    Some(symbol),
+                    &body,
+                ));
+            }
+
+            cache::write_json("coingecko", &cache_key, &body, &self.ctx).await;
+            body
+        };
+
+        serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("CoinGecko coin info JSON: {}", e)))
+    }
+
+    /// Fetch `/coins/markets` rows in `currency` for the extra `query` parameters.
+    async fn fetch_markets(
+        &self,
+        currency: &str,
+        query: &str,
+    ) -> Result<cache::Cached<Vec<MarketListing>>> {
+        let url = format!(
+            "{}/coins/markets?vs_currency={}&{}&price_change_percentage=24h",
+            self.base_url, currency, query
+        );
+        let cache_key = format!("coins_markets:{}:{}:{}", self.base_url, currency, query);
+
+        debug!(url = %url, "fetching market listings from CoinGecko");
+
+        let (body, fetched_at) = if let Some(cached_body) = cache::read_json::<String>(
+            "coingecko",
+            &cache_key,
+            MARKETS_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            debug!(currency = %currency, "using cached CoinGecko market listings");
+            (cached_body.value, cached_body.fetched_at)
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(self.name(), self.get(&url), &self.ctx).await?;
+
+            debug!(status = %status, body_len = body.len(), "CoinGecko markets response");
+            trace!(body = %body, "CoinGecko markets response body");
+
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    "CoinGecko markets",
+                    status,
+                    retry_after,
+                    None,
+                    &body,
+                ));
+            }
+
+            cache::write_json("coingecko", &cache_key, &body, &self.ctx).await;
+            (body, self.ctx.clock.now())
+        };
+
+        let listings = serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("CoinGecko markets JSON: {}", e)))?;
+        Ok(cache::Cached {
+            value: listings,
+            fetched_at,
+        })
+    }
+
+    /// Up to `limit` `/coins/markets` prices for `query`, ranked by position when
+    /// CoinGecko has no market-cap rank.
+    async fn ranked_listings(
+        &self,
+        query: &str,
+        limit: usize,
+        currency: &str,
+    ) -> Result<Vec<CoinPrice>> {
+        let cur = currency.to_lowercase();
+        let cache::Cached {
+            value: listings,
+            fetched_at,
+        } = self.fetch_markets(&cur, query).await?;
+
+        let results: Vec<CoinPrice> = listings
+            .into_iter()
+            .take(limit)
+            .enumerate()
+            .filter_map(|(i, listing)| {
+                let fallback_rank = i as u32 + 1;
+                let mut price = self.listing_price(listing, &cur, fetched_at)?;
+                price.rank = price.rank.or(Some(fallback_rank));
+                Some(price)
+            })
+            .collect();
+
+        if results.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(results)
+    }
+
+    /// Convert a `/coins/markets` row fetched at `fetched_at` into a price; rows
+    /// without a price are dropped.
+    fn listing_price(
+        &self,
+        listing: MarketListing,
+        currency: &str,
+        fetched_at: chrono::DateTime<chrono::Utc>,
+    ) -> Option<CoinPrice> {
+        Some(CoinPrice {
+            symbol: listing.symbol.to_uppercase(),
+            name: listing.name,
+            price: listing.current_price?,
+            change_24h: listing.price_change_percentage_24h,
+            market_cap: listing.market_cap,
+            open_24h: listing
+                .price_change_24h
+                .map(|change| listing.current_price.unwrap_or_default() - change),
+            high_24h: listing.high_24h,
+            low_24h: listing.low_24h,
+            market_state: Some(MarketState::Always),
+            change_basis: None,
+            rank: listing.market_cap_rank,
+            fundamentals: None,
+            native_price: None,
+            native_currency: None,
+            currency: currency.to_uppercase(),
+            provider: self.name().to_string(),
+            timestamp: fetched_at,
+        })
+    }
+
     async fn fetch_history_for_symbol(
         &self,
         symbol: &str,
@@ -232,14 +920,17 @@ impl CoinGecko {
         );
 
         let body = if let Some(cached_body) =
-            cache::read_json::<String>("coingecko", &cache_key, cache_ttl).await
+            cache::read_json::<String>("coingecko", &cache_key, cache_ttl, self.ctx.clock.as_ref())
+                .await
         {
             debug!(symbol = %symbol, currency = %currency, "using cached CoinGecko chart data");
-            cached_body
+            cached_body.value
         } else {
-            let resp = self.client.get(&url).send().await?;
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(self.name(), self.get(&url), &self.ctx).await?;
 
             debug!(
                 status = %status,
@@ -250,13 +941,17 @@ impl CoinGecko {
             trace!(body = %body, symbol = %symbol, "CoinGecko chart response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "CoinGecko returned {} for chart data: {}",
-                    status, body
-                )));
+                return Err(status_error(
+                    self.name(),
+                    "CoinGecko chart data",
+                    status,
+                    retry_after,
+                    Some(symbol),
+                    &body,
+                ));
             }
 
-            cache::write_json("coingecko", &cache_key, &body).await;
+            cache::write_json("coingecko", &cache_key, &body, &self.ctx).await;
             body
         };
 
@@ -273,7 +968,11 @@ impl CoinGecko {
             }
 
             if let Some(timestamp) = chrono::DateTime::<chrono::Utc>::from_timestamp_millis(ts_ms) {
-                points.push(PricePoint { timestamp, price });
+                points.push(PricePoint {
+                    timestamp,
+                    price,
+                    filled: false,
+                });
             }
         }
 
@@ -287,10 +986,20 @@ impl CoinGecko {
             currency: currency.to_uppercase(),
             provider: self.name().to_string(),
             points,
+            events: Vec::new(),
         })
     }
 }
 
+/// Trim link lists, dropping the empty slots CoinGecko pads them with.
+fn non_empty_links(links: Vec<String>) -> Vec<String> {
+    links
+        .into_iter()
+        .map(|link| link.trim().to_string())
+        .filter(|link| !link.is_empty())
+        .collect()
+}
+
 fn capitalize(s: &str) -> String {
     let mut chars = s.chars();
     match chars.next() {
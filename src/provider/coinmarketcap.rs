@@ -1,13 +1,17 @@
 use async_trait::async_trait;
 use futures::future::join_all;
-use reqwest::Client;
 use serde::Deserialize;
 use std::collections::HashMap;
 use tokio::sync::RwLock;
 use tracing::{debug, trace};
 
-use super::cache;
-use super::{CoinPrice, HistoryInterval, PriceHistory, PricePoint, PriceProvider};
+use super::context::ProviderContext;
+use super::{
+    ApiKeyStatus, AssetClass, CoinPrice, Feature, HistoryInterval, MarketState, PriceHistory,
+    PricePoint, PriceProvider, ProviderCapabilities,
+};
+use super::{HttpResponse, cache, send_with_retry, status_error};
+use crate::clock::Clock;
 use crate::error::{Error, Result};
 
 const BASE_URL: &str = "https://pro-api.coinmarketcap.com/v1";
@@ -17,10 +21,12 @@ const CATALOG_CACHE_TTL_SECS: i64 = 24 * 60 * 60;
 const DAILY_CHART_CACHE_TTL_SECS: i64 = 12 * 60 * 60;
 const PRICE_CACHE_TTL_SECS: i64 = 30;
 const HOURLY_CHART_CACHE_TTL_SECS: i64 = 60 * 60;
+const LISTINGS_CACHE_TTL_SECS: i64 = 5 * 60;
+const TRENDING_LIMIT: usize = 15;
 
 /// CoinMarketCap price provider -- requires an API key.
 pub struct CoinMarketCap {
-    client: Client,
+    ctx: ProviderContext,
     api_key: Option<String>,
     base_url: String,
     chart_base_url: String,
@@ -31,25 +37,38 @@ pub struct CoinMarketCap {
 impl CoinMarketCap {
     /// Create a CoinMarketCap provider using the default production API URL.
     pub fn new(api_key: String) -> Self {
+        Self::configured(Some(api_key), ProviderContext::new())
+    }
+
+    /// Create a CoinMarketCap provider without an API key.
+    pub fn without_key() -> Self {
+        Self::configured(None, ProviderContext::new())
+    }
+
+    /// Create a CoinMarketCap provider with an optional API key, sharing `ctx`'s
+    /// client and clock.
+    pub fn configured(api_key: Option<String>, ctx: ProviderContext) -> Self {
         Self::with_optional_key(
-            Some(api_key),
+            api_key,
             BASE_URL,
             WEB_CHART_BASE_URL,
             COIN_SUMMARIES_URL,
+            ctx,
         )
     }
 
-    /// Create a CoinMarketCap provider without an API key.
-    pub fn without_key() -> Self {
-        Self::with_optional_key(None, BASE_URL, WEB_CHART_BASE_URL, COIN_SUMMARIES_URL)
-    }
-
     /// Create a CoinMarketCap provider with a custom base URL.
     pub fn with_base_url(api_key: String, base_url: impl Into<String>) -> Self {
         let base_url = base_url.into();
         let chart_base_url = derive_chart_base_url(&base_url);
         let coin_summaries_url = derive_coin_summaries_url(&chart_base_url);
-        Self::with_optional_key(Some(api_key), base_url, chart_base_url, coin_summaries_url)
+        Self::with_optional_key(
+            Some(api_key),
+            base_url,
+            chart_base_url,
+            coin_summaries_url,
+            ProviderContext::new(),
+        )
     }
 
     fn with_optional_key(
@@ -57,13 +76,10 @@ impl CoinMarketCap {
         base_url: impl Into<String>,
         chart_base_url: impl Into<String>,
         coin_summaries_url: impl Into<String>,
+        ctx: ProviderContext,
     ) -> Self {
-        let client = Client::builder()
-            .user_agent("pricr/0.1.0")
-            .build()
-            .expect("failed to build HTTP client");
         Self {
-            client,
+            ctx,
             api_key,
             base_url: base_url.into(),
             chart_base_url: chart_base_url.into(),
@@ -73,10 +89,9 @@ impl CoinMarketCap {
     }
 
     fn required_api_key(&self) -> Result<&str> {
-        self.api_key.as_deref().ok_or_else(|| {
-            Error::Config(
-                "CoinMarketCap price lookup requires --api-key or COINMARKETCAP_API_KEY".into(),
-            )
+        self.api_key.as_deref().ok_or_else(|| Error::MissingApiKey {
+            provider: self.name().to_string(),
+            hint: "pass --api-key or set COINMARKETCAP_API_KEY".into(),
         })
     }
 
@@ -109,6 +124,21 @@ struct CmcCoin {
     quote: HashMap<String, CmcQuote>,
 }
 
+#[derive(Debug, Deserialize)]
+struct CmcListing {
+    name: String,
+    symbol: String,
+    cmc_rank: Option<u32>,
+    quote: HashMap<String, CmcQuote>,
+}
+
+#[derive(Debug, Deserialize)]
+struct CmcListingsResponse {
+    #[serde(default)]
+    data: Vec<CmcListing>,
+    status: Option<CmcStatus>,
+}
+
 #[derive(Debug, Deserialize)]
 struct CmcQuote {
     price: Option<f64>,
@@ -179,6 +209,37 @@ impl PriceProvider for CoinMarketCap {
         "cmc"
     }
 
+    fn clock(&self) -> &dyn Clock {
+        self.ctx.clock.as_ref()
+    }
+
+    fn requires_key(&self) -> bool {
+        true
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            api_key: if self.api_key.is_some() {
+                ApiKeyStatus::Configured
+            } else {
+                ApiKeyStatus::Missing
+            },
+            features: if self.api_key.is_some() {
+                vec![
+                    Feature::Prices,
+                    Feature::History,
+                    Feature::Listings,
+                    Feature::Trending,
+                    Feature::CryptoQuote,
+                ]
+            } else {
+                vec![Feature::Prices, Feature::History, Feature::CryptoQuote]
+            },
+            asset_classes: vec![AssetClass::Crypto],
+            base_url: self.base_url.clone(),
+        }
+    }
+
     async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
         let api_key = self.required_api_key()?;
         let symbols_upper: Vec<String> = symbols.iter().map(|s| s.to_uppercase()).collect();
@@ -196,34 +257,51 @@ impl PriceProvider for CoinMarketCap {
 
         debug!(url = %url, "fetching prices from CoinMarketCap");
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("coinmarketcap", &cache_key, PRICE_CACHE_TTL_SECS).await
+        let (body, fetched_at) = if let Some(cached_body) = cache::read_json::<String>(
+            "coinmarketcap",
+            &cache_key,
+            PRICE_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(symbols = %symbols_joined, currency = %convert, "using cached CoinMarketCap quotes");
-            cached_body
+            (cached_body.value, cached_body.fetched_at)
         } else {
-            let resp = self
-                .client
-                .get(&url)
-                .header("X-CMC_PRO_API_KEY", api_key)
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&url)
+                    .header("X-CMC_PRO_API_KEY", api_key),
+                &self.ctx,
+            )
+            .await?;
 
             debug!(status = %status, body_len = body.len(), "CoinMarketCap response");
             trace!(body = %body, "CoinMarketCap response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "CoinMarketCap returned {}: {}",
-                    status, body
-                )));
+                let symbol = match symbols_upper.as_slice() {
+                    [only] => Some(only.as_str()),
+                    _ => None,
+                };
+                return Err(status_error(
+                    self.name(),
+                    "CoinMarketCap",
+                    status,
+                    retry_after,
+                    symbol,
+                    &body,
+                ));
             }
 
-            cache::write_json("coinmarketcap", &cache_key, &body).await;
-            body
+            cache::write_json("coinmarketcap", &cache_key, &body, &self.ctx).await;
+            (body, self.ctx.clock.now())
         };
 
         let raw: CmcRawResponse =
@@ -259,9 +337,18 @@ impl PriceProvider for CoinMarketCap {
                         price: quote.price.unwrap_or(0.0),
                         change_24h: quote.percent_change_24h,
                         market_cap: quote.market_cap,
+                        open_24h: None,
+                        high_24h: None,
+                        low_24h: None,
+                        market_state: Some(MarketState::Always),
+                        change_basis: None,
+                        rank: None,
+                        fundamentals: None,
+                        native_price: None,
+                        native_currency: None,
                         currency: convert.clone(),
                         provider: self.name().to_string(),
-                        timestamp: chrono::Utc::now(),
+                        timestamp: fetched_at,
                     });
                 }
             }
@@ -309,9 +396,135 @@ impl PriceProvider for CoinMarketCap {
 
         Ok(histories)
     }
+
+    async fn get_top_listings(&self, limit: usize, currency: &str) -> Result<Vec<CoinPrice>> {
+        let listings = self
+            .fetch_listings("listings/latest", limit, currency, "listings")
+            .await?;
+
+        Ok(listings
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut price)| {
+                price.rank = price.rank.or(Some(i as u32 + 1));
+                price
+            })
+            .collect())
+    }
+
+    async fn get_trending(&self, currency: &str) -> Result<Vec<CoinPrice>> {
+        self.fetch_listings("trending/latest", TRENDING_LIMIT, currency, "trending")
+            .await
+    }
 }
 
 impl CoinMarketCap {
+    /// Fetch a `/cryptocurrency/<endpoint>` list of coins with quotes in `currency`.
+    async fn fetch_listings(
+        &self,
+        endpoint: &str,
+        limit: usize,
+        currency: &str,
+        label: &str,
+    ) -> Result<Vec<CoinPrice>> {
+        let api_key = self.required_api_key()?;
+        let convert = currency.to_uppercase();
+        let url = format!(
+            "{}/cryptocurrency/{}?limit={}&convert={}",
+            self.base_url, endpoint, limit, convert
+        );
+        let cache_key = format!("{}:{}:{}:{}", endpoint, self.base_url, limit, convert);
+
+        debug!(url = %url, "fetching {} from CoinMarketCap", label);
+
+        let (body, fetched_at) = if let Some(cached_body) = cache::read_json::<String>(
+            "coinmarketcap",
+            &cache_key,
+            LISTINGS_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            debug!(limit, currency = %convert, "using cached CoinMarketCap {}", label);
+            (cached_body.value, cached_body.fetched_at)
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&url)
+                    .header("X-CMC_PRO_API_KEY", api_key),
+                &self.ctx,
+            )
+            .await?;
+
+            debug!(status = %status, body_len = body.len(), "CoinMarketCap {} response", label);
+            trace!(body = %body, "CoinMarketCap {} response body", label);
+
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    &format!("CoinMarketCap {}", label),
+                    status,
+                    retry_after,
+                    None,
+                    &body,
+                ));
+            }
+
+            cache::write_json("coinmarketcap", &cache_key, &body, &self.ctx).await;
+            (body, self.ctx.clock.now())
+        };
+
+        let raw: CmcListingsResponse = serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("CMC {} JSON: {}", label, e)))?;
+
+        if let Some(ref st) = raw.status
+            && let Some(ref msg) = st.error_message
+            && !msg.is_empty()
+        {
+            return Err(Error::Api(format!("CoinMarketCap: {}", msg)));
+        }
+
+        let results: Vec<CoinPrice> = raw
+            .data
+            .into_iter()
+            .take(limit)
+            .filter_map(|listing| {
+                let quote = listing.quote.get(&convert)?;
+                Some(CoinPrice {
+                    symbol: listing.symbol,
+                    name: listing.name,
+                    price: quote.price?,
+                    change_24h: quote.percent_change_24h,
+                    market_cap: quote.market_cap,
+                    open_24h: None,
+                    high_24h: None,
+                    low_24h: None,
+                    market_state: Some(MarketState::Always),
+                    change_basis: None,
+                    rank: listing.cmc_rank,
+                    fundamentals: None,
+                    native_price: None,
+                    native_currency: None,
+                    currency: convert.clone(),
+                    provider: self.name().to_string(),
+                    timestamp: fetched_at,
+                })
+            })
+            .collect();
+
+        if results.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(results)
+    }
+
     async fn fetch_history_for_symbol(
         &self,
         symbol: &str,
@@ -399,22 +612,33 @@ impl CoinMarketCap {
     async fn fetch_coin_catalog(&self) -> Result<HashMap<String, (u64, String)>> {
         let catalog_cache_key = self.coin_catalog_cache_key();
 
-        if let Some(cached_body) =
-            cache::read_json::<String>("coinmarketcap", &catalog_cache_key, CATALOG_CACHE_TTL_SECS)
-                .await
+        if let Some(cached_body) = cache::read_json::<String>(
+            "coinmarketcap",
+            &catalog_cache_key,
+            CATALOG_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!("using cached CoinMarketCap coin catalog");
 
-            if let Ok(catalog) = parse_coin_catalog(&cached_body) {
+            if let Ok(catalog) = parse_coin_catalog(&cached_body.value) {
                 return Ok(catalog);
             }
 
             debug!("cached CoinMarketCap coin catalog is invalid; refetching");
         }
 
-        let resp = self.client.get(&self.coin_summaries_url).send().await?;
-        let status = resp.status();
-        let body = resp.text().await?;
+        let HttpResponse {
+            status,
+            retry_after,
+            body,
+        } = send_with_retry(
+            self.name(),
+            self.ctx.client.get(&self.coin_summaries_url),
+            &self.ctx,
+        )
+        .await?;
 
         debug!(
             url = %self.coin_summaries_url,
@@ -424,13 +648,17 @@ impl CoinMarketCap {
         );
 
         if !status.is_success() {
-            return Err(Error::Api(format!(
-                "CoinMarketCap coin catalog returned {}: {}",
-                status, body
-            )));
+            return Err(status_error(
+                self.name(),
+                "CoinMarketCap coin catalog",
+                status,
+                retry_after,
+                None,
+                &body,
+            ));
         }
 
-        cache::write_json("coinmarketcap", &catalog_cache_key, &body).await;
+        cache::write_json("coinmarketcap", &catalog_cache_key, &body, &self.ctx).await;
 
         parse_coin_catalog(&body)
     }
@@ -453,14 +681,19 @@ impl CoinMarketCap {
         let cache_key = self.chart_cache_key(req.coin_id, req.convert_id, req.interval, req.range);
         let cache_ttl = chart_ttl(req.interval);
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("coinmarketcap", &cache_key, cache_ttl).await
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "coinmarketcap",
+            &cache_key,
+            cache_ttl,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(symbol = %req.symbol_upper, interval = req.interval, "using cached CoinMarketCap web chart response");
-            cached_body
+            cached_body.value
         } else {
             let fetched = self.fetch_web_chart_body(&url, req.symbol_upper).await?;
-            cache::write_json("coinmarketcap", &cache_key, &fetched).await;
+            cache::write_json("coinmarketcap", &cache_key, &fetched, &self.ctx).await;
             fetched
         };
 
@@ -484,7 +717,11 @@ impl CoinMarketCap {
                 continue;
             };
 
-            points.push(PricePoint { timestamp, price });
+            points.push(PricePoint {
+                timestamp,
+                price,
+                filled: false,
+            });
         }
 
         points.sort_by_key(|p| p.timestamp);
@@ -501,20 +738,25 @@ impl CoinMarketCap {
             currency: req.convert.to_uppercase(),
             provider: "CoinMarketCap".to_string(),
             points,
+            events: Vec::new(),
         })
     }
 
     async fn fetch_web_chart_body(&self, url: &str, symbol_upper: &str) -> Result<String> {
-        let resp = self
-            .client
-            .get(url)
-            .header("accept", "application/json, text/plain, */*")
-            .header("platform", "web")
-            .send()
-            .await?;
-
-        let status = resp.status();
-        let body = resp.text().await?;
+        let HttpResponse {
+            status,
+            retry_after,
+            body,
+        } = send_with_retry(
+            self.name(),
+            self.ctx
+                .client
+                .get(url)
+                .header("accept", "application/json, text/plain, */*")
+                .header("platform", "web"),
+            &self.ctx,
+        )
+        .await?;
 
         debug!(
             status = %status,
@@ -525,10 +767,14 @@ impl CoinMarketCap {
         trace!(body = %body, symbol = %symbol_upper, "CoinMarketCap web chart response body");
 
         if !status.is_success() {
-            return Err(Error::Api(format!(
-                "CoinMarketCap web chart returned {}: {}",
-                status, body
-            )));
+            return Err(status_error(
+                self.name(),
+                "CoinMarketCap web chart",
+                status,
+                retry_after,
+                Some(symbol_upper),
+                &body,
+            ));
         }
 
         Ok(body)
@@ -542,7 +788,7 @@ impl CoinMarketCap {
         interval_param: &str,
     ) -> Result<PriceHistory> {
         let api_key = self.required_api_key()?;
-        let time_end = chrono::Utc::now();
+        let time_end = self.ctx.clock.now();
         let time_start = time_end - chrono::Duration::days(days as i64);
         let url = format!(
             "{}/cryptocurrency/quotes/historical?symbol={}&convert={}&time_start={}&time_end={}&interval={}",
@@ -568,21 +814,30 @@ impl CoinMarketCap {
             "fetching chart data from CoinMarketCap"
         );
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("coinmarketcap", &cache_key, history_ttl).await
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "coinmarketcap",
+            &cache_key,
+            history_ttl,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(symbol = %symbol_upper, currency = %convert, "using cached CoinMarketCap pro history");
-            cached_body
+            cached_body.value
         } else {
-            let resp = self
-                .client
-                .get(&url)
-                .header("X-CMC_PRO_API_KEY", api_key)
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&url)
+                    .header("X-CMC_PRO_API_KEY", api_key),
+                &self.ctx,
+            )
+            .await?;
 
             debug!(
                 status = %status,
@@ -593,13 +848,17 @@ impl CoinMarketCap {
             trace!(body = %body, symbol = %symbol_upper, "CoinMarketCap chart response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "CoinMarketCap returned {} for chart data: {}",
-                    status, body
-                )));
+                return Err(status_error(
+                    self.name(),
+                    "CoinMarketCap chart data",
+                    status,
+                    retry_after,
+                    Some(symbol_upper),
+                    &body,
+                ));
             }
 
-            cache::write_json("coinmarketcap", &cache_key, &body).await;
+            cache::write_json("coinmarketcap", &cache_key, &body, &self.ctx).await;
             body
         };
 
@@ -784,7 +1043,11 @@ fn parse_history_data(
             continue;
         }
 
-        points.push(PricePoint { timestamp, price });
+        points.push(PricePoint {
+            timestamp,
+            price,
+            filled: false,
+        });
     }
 
     points.sort_by_key(|p| p.timestamp);
@@ -799,6 +1062,7 @@ fn parse_history_data(
         currency: convert.to_uppercase(),
         provider: "CoinMarketCap".to_string(),
         points,
+        events: Vec::new(),
     })
 }
 
@@ -0,0 +1,90 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use reqwest::Client;
+
+use super::http;
+use crate::clock::{self, SharedClock};
+use crate::error::Result;
+
+/// Default ceiling for honoring a `Retry-After` delay before giving up.
+pub const DEFAULT_MAX_RETRY_AFTER_SECS: u64 = 10;
+
+/// What every built-in provider shares besides its endpoint: the HTTP client,
+/// the clock, and the retry and cache limits.
+///
+/// Clones are cheap handles to the same connection pool, clock, and cache write
+/// counter, so build one context per run and clone it into each provider.
+#[derive(Debug, Clone)]
+pub struct ProviderContext {
+    pub client: Client,
+    /// Time source for cache ages and history windows.
+    pub clock: SharedClock,
+    /// Longest `Retry-After` delay to wait out before retrying once; `0` fails
+    /// rate-limited requests immediately.
+    pub max_retry_after_secs: u64,
+    /// Cache files each provider keeps, pruned oldest-first after each write;
+    /// `0` disables pruning.
+    pub cache_max_entries: usize,
+    cache_writes: Arc<AtomicUsize>,
+}
+
+impl ProviderContext {
+    /// A context with a fresh client from [`http::client`] and the system clock.
+    pub fn new() -> Self {
+        Self::from_client(http::client())
+    }
+
+    /// Like [`new`](Self::new), but the client sends `user_agent` instead of
+    /// [`http::DEFAULT_USER_AGENT`]; see [`http::client_with_user_agent`].
+    pub fn with_user_agent(user_agent: &str) -> Result<Self> {
+        http::client_with_user_agent(user_agent).map(Self::from_client)
+    }
+
+    fn from_client(client: Client) -> Self {
+        Self {
+            client,
+            clock: clock::system(),
+            max_retry_after_secs: DEFAULT_MAX_RETRY_AFTER_SECS,
+            cache_max_entries: 0,
+            cache_writes: Arc::default(),
+        }
+    }
+
+    /// Read "now" from `clock` instead of the wall clock.
+    pub fn with_clock(mut self, clock: SharedClock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Send requests through `client`.
+    pub fn with_client(mut self, client: Client) -> Self {
+        self.client = client;
+        self
+    }
+
+    pub fn with_max_retry_after_secs(mut self, secs: u64) -> Self {
+        self.max_retry_after_secs = secs;
+        self
+    }
+
+    pub fn with_cache_max_entries(mut self, max_entries: usize) -> Self {
+        self.cache_max_entries = max_entries;
+        self
+    }
+
+    /// Number of cache entries written by providers sharing this context.
+    pub fn cache_write_count(&self) -> usize {
+        self.cache_writes.load(Ordering::Relaxed)
+    }
+
+    pub(crate) fn record_cache_write(&self) {
+        self.cache_writes.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+impl Default for ProviderContext {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,7 +3,8 @@ use std::collections::HashMap;
 use serde::{Deserialize, Serialize};
 use tracing::debug;
 
-use super::cache;
+use super::context::ProviderContext;
+use super::{HttpResponse, cache, send_with_retry, status_error};
 use super::{PriceHistory, PricePoint};
 use crate::calc;
 use crate::error::{Error, Result};
@@ -11,25 +12,71 @@ use crate::error::{Error, Result};
 const BASE_URL: &str = "https://api.frankfurter.dev/v1";
 const LATEST_RATES_CACHE_TTL_SECS: i64 = 10 * 60;
 const HISTORY_CACHE_TTL_SECS: i64 = 12 * 60 * 60;
+const CURRENCIES_CACHE_TTL_SECS: i64 = 24 * 60 * 60;
+const PROVIDER_NAME: &str = "Frankfurter";
 
 /// Frankfurter forex provider backed by ECB reference rates.
 pub struct Frankfurter {
-    client: reqwest::Client,
+    ctx: ProviderContext,
     base_url: String,
+    fill_weekends: bool,
 }
 
 impl Frankfurter {
     /// Create a Frankfurter provider using the default production API URL.
     pub fn new() -> Self {
-        Self::with_base_url(BASE_URL)
+        Self::with_context(ProviderContext::new())
+    }
+
+    /// Create a Frankfurter provider that shares `ctx`'s client and clock.
+    pub fn with_context(ctx: ProviderContext) -> Self {
+        Self {
+            ctx,
+            base_url: BASE_URL.to_string(),
+            fill_weekends: false,
+        }
     }
 
     /// Create a Frankfurter provider with a custom base URL.
     pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self::with_base_url_and_context(base_url, ProviderContext::new())
+    }
+
+    /// Create a Frankfurter provider with a custom base URL that shares `ctx`'s client
+    /// and clock.
+    pub fn with_base_url_and_context(base_url: impl Into<String>, ctx: ProviderContext) -> Self {
         Self {
-            client: reqwest::Client::new(),
             base_url: base_url.into(),
+            ..Self::with_context(ctx)
+        }
+    }
+
+    /// Give histories a point for every calendar day, carrying the last published
+    /// rate over weekends and ECB holidays instead of returning business days only.
+    pub fn with_weekend_fill(mut self, enabled: bool) -> Self {
+        self.fill_weekends = enabled;
+        self
+    }
+
+    async fn fetch_json<T: serde::de::DeserializeOwned>(&self, url: &str) -> Result<T> {
+        let HttpResponse {
+            status,
+            retry_after,
+            body,
+        } = send_with_retry(PROVIDER_NAME, self.ctx.client.get(url), &self.ctx).await?;
+
+        if !status.is_success() {
+            return Err(status_error(
+                PROVIDER_NAME,
+                "Frankfurter",
+                status,
+                retry_after,
+                None,
+                &body,
+            ));
         }
+
+        serde_json::from_str(&body).map_err(|e| Error::Parse(format!("Frankfurter JSON: {}", e)))
     }
 
     /// Fetch forex rates from Frankfurter.
@@ -47,15 +94,19 @@ impl Frankfurter {
 
         debug!(url = %url, "fetching forex rates from Frankfurter");
 
-        let body: FrankfurterResponse = if let Some(cached) =
-            cache::read_json("frankfurter", &cache_key, LATEST_RATES_CACHE_TTL_SECS).await
+        let body: FrankfurterResponse = if let Some(cached) = cache::read_json(
+            "frankfurter",
+            &cache_key,
+            LATEST_RATES_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(from = %from_upper, to = %to_param, "using cached Frankfurter rates");
-            cached
+            cached.value
         } else {
-            let resp = self.client.get(&url).send().await?.error_for_status()?;
-            let fetched: FrankfurterResponse = resp.json().await?;
-            cache::write_json("frankfurter", &cache_key, &fetched).await;
+            let fetched: FrankfurterResponse = self.fetch_json(&url).await?;
+            cache::write_json("frankfurter", &cache_key, &fetched, &self.ctx).await;
             fetched
         };
 
@@ -68,6 +119,58 @@ impl Frankfurter {
         Ok(body.rates)
     }
 
+    /// Fetch a single forex rate expressed as "1 `from` = rate `to`".
+    ///
+    /// Non-EUR pairs are crossed through EUR by Frankfurter itself, so this is
+    /// a thin wrapper over [`Self::get_rates`]. Identical codes return `1.0`
+    /// without a request.
+    pub async fn get_rate(&self, from: &str, to: &str) -> Result<f64> {
+        let to_upper = to.to_uppercase();
+        if from.eq_ignore_ascii_case(&to_upper) {
+            return Ok(1.0);
+        }
+
+        let rates = self
+            .get_rates(from, std::slice::from_ref(&to_upper))
+            .await?;
+        rates.get(&to_upper).copied().ok_or(Error::NoResults)
+    }
+
+    /// Fetch the currencies Frankfurter publishes rates for.
+    ///
+    /// Returns a map of uppercase currency code to its English name.
+    pub async fn get_currencies(&self) -> Result<HashMap<String, String>> {
+        let url = format!("{}/currencies", self.base_url);
+        let cache_key = format!("currencies:{}", self.base_url);
+
+        debug!(url = %url, "fetching supported currencies from Frankfurter");
+
+        let currencies: HashMap<String, String> = if let Some(cached) = cache::read_json(
+            "frankfurter",
+            &cache_key,
+            CURRENCIES_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            debug!("using cached Frankfurter currencies");
+            cached.value
+        } else {
+            let fetched: HashMap<String, String> = self.fetch_json(&url).await?;
+            cache::write_json("frankfurter", &cache_key, &fetched, &self.ctx).await;
+            fetched
+        };
+
+        if currencies.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(currencies
+            .into_iter()
+            .map(|(code, name)| (code.to_uppercase(), name))
+            .collect())
+    }
+
     /// Fetch historical forex rates from Frankfurter.
     ///
     /// Returns one history series per target code where each point is
@@ -82,7 +185,7 @@ impl Frankfurter {
         let to_upper: Vec<String> = to.iter().map(|s| s.to_uppercase()).collect();
         let to_param = to_upper.join(",");
 
-        let end = chrono::Utc::now().date_naive();
+        let end = self.ctx.clock.today();
         let start = end - chrono::Duration::days(days as i64);
         let url = format!(
             "{}/{}..{}?from={}&to={}",
@@ -99,15 +202,19 @@ impl Frankfurter {
 
         debug!(url = %url, "fetching historical forex rates from Frankfurter");
 
-        let body: FrankfurterHistoryResponse = if let Some(cached) =
-            cache::read_json("frankfurter", &cache_key, HISTORY_CACHE_TTL_SECS).await
+        let body: FrankfurterHistoryResponse = if let Some(cached) = cache::read_json(
+            "frankfurter",
+            &cache_key,
+            HISTORY_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(from = %from_upper, to = %to_param, days, "using cached Frankfurter history");
-            cached
+            cached.value
         } else {
-            let resp = self.client.get(&url).send().await?.error_for_status()?;
-            let fetched: FrankfurterHistoryResponse = resp.json().await?;
-            cache::write_json("frankfurter", &cache_key, &fetched).await;
+            let fetched: FrankfurterHistoryResponse = self.fetch_json(&url).await?;
+            cache::write_json("frankfurter", &cache_key, &fetched, &self.ctx).await;
             fetched
         };
 
@@ -135,6 +242,7 @@ impl Frankfurter {
                 points.push(PricePoint {
                     timestamp: naive_dt.and_utc(),
                     price: rate,
+                    filled: false,
                 });
             }
 
@@ -143,6 +251,9 @@ impl Frankfurter {
             if points.is_empty() {
                 continue;
             }
+            if self.fill_weekends {
+                points = fill_calendar_days(&points, end);
+            }
 
             histories.push(PriceHistory {
                 symbol: target.clone(),
@@ -150,6 +261,7 @@ impl Frankfurter {
                 currency: from_upper.clone(),
                 provider: "Frankfurter/ECB".to_string(),
                 points,
+                events: Vec::new(),
             });
         }
 
@@ -161,6 +273,37 @@ impl Frankfurter {
     }
 }
 
+/// Expand business-day `points` (at midnight UTC) to every calendar day from the first
+/// point through `end`, carrying the prior rate into the missing days.
+fn fill_calendar_days(points: &[PricePoint], end: chrono::NaiveDate) -> Vec<PricePoint> {
+    let (Some(first), Some(last)) = (points.first(), points.last()) else {
+        return Vec::new();
+    };
+    let end = end.max(last.timestamp.date_naive());
+
+    let mut filled = Vec::new();
+    let mut published = points.iter().peekable();
+    let mut rate = first.price;
+    for day in first.timestamp.date_naive().iter_days() {
+        if day > end {
+            break;
+        }
+        let timestamp = day.and_time(chrono::NaiveTime::MIN).and_utc();
+        match published.next_if(|p| p.timestamp.date_naive() == day) {
+            Some(point) => {
+                rate = point.price;
+                filled.push(point.clone());
+            }
+            None => filled.push(PricePoint {
+                timestamp,
+                price: rate,
+                filled: true,
+            }),
+        }
+    }
+    filled
+}
+
 impl Default for Frankfurter {
     fn default() -> Self {
         Self::new()
@@ -209,4 +352,37 @@ mod tests {
         assert!((resp.rates["2026-02-20"]["EUR"] - 0.92).abs() < 1e-6);
         assert!((resp.rates["2026-02-21"]["GBP"] - 0.80).abs() < 1e-6);
     }
+
+    #[test]
+    fn weekend_fill_carries_friday_rate_through_end_of_range() {
+        let day = |date: &str| chrono::NaiveDate::parse_from_str(date, "%Y-%m-%d").unwrap();
+        let point = |date: &str, price: f64| PricePoint {
+            timestamp: day(date).and_hms_opt(0, 0, 0).unwrap().and_utc(),
+            price,
+            filled: false,
+        };
+        let points = [
+            point("2026-02-19", 0.91),
+            point("2026-02-20", 0.92),
+            point("2026-02-23", 0.93),
+        ];
+
+        let filled = fill_calendar_days(&points, day("2026-02-25"));
+        let summary: Vec<_> = filled
+            .iter()
+            .map(|p| (p.timestamp.format("%m-%d").to_string(), p.price, p.filled))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                ("02-19".to_string(), 0.91, false),
+                ("02-20".to_string(), 0.92, false),
+                ("02-21".to_string(), 0.92, true),
+                ("02-22".to_string(), 0.92, true),
+                ("02-23".to_string(), 0.93, false),
+                ("02-24".to_string(), 0.93, true),
+                ("02-25".to_string(), 0.93, true),
+            ]
+        );
+    }
 }
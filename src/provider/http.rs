@@ -0,0 +1,78 @@
+use reqwest::ClientBuilder;
+use reqwest::header::HeaderValue;
+
+use crate::error::{Error, Result};
+
+/// User agent sent when neither `PRICR_USER_AGENT` nor `[defaults].user_agent` is set.
+pub const DEFAULT_USER_AGENT: &str = concat!("pricr/", env!("PRICR_VERSION"));
+
+/// Longest a single provider request may take before it fails with `Error::Timeout`.
+pub const REQUEST_TIMEOUT_SECS: u64 = 30;
+
+/// Start an HTTP client builder with the default user agent, request timeout, and
+/// gzip response decompression applied.
+pub fn client_builder() -> ClientBuilder {
+    reqwest::Client::builder()
+        .user_agent(DEFAULT_USER_AGENT)
+        .timeout(std::time::Duration::from_secs(REQUEST_TIMEOUT_SECS))
+        .gzip(true)
+}
+
+/// Build a client from [`client_builder`].
+///
+/// `reqwest::Client` is a handle to a connection pool, so clones of one client
+/// share connections; [`available_providers`](super::available_providers)
+/// hands one to every provider.
+///
+/// # Panics
+///
+/// Like `reqwest::Client::new`, if the TLS backend cannot be initialized.
+pub fn client() -> reqwest::Client {
+    client_builder()
+        .build()
+        .expect("failed to initialize the HTTP client")
+}
+
+/// Build a client from [`client_builder`] that sends `user_agent`, as set by
+/// `PRICR_USER_AGENT` or `[defaults].user_agent`.
+///
+/// A user agent that is not a valid header value is an [`Error::Config`].
+pub fn client_with_user_agent(user_agent: &str) -> Result<reqwest::Client> {
+    check_user_agent(user_agent)?;
+    client_builder()
+        .user_agent(user_agent)
+        .build()
+        .map_err(|err| Error::Config(format!("failed to build HTTP client: {}", err)))
+}
+
+/// Reject user agents that cannot be sent as a header, such as ones with
+/// control characters.
+pub fn check_user_agent(user_agent: &str) -> Result<()> {
+    HeaderValue::from_str(user_agent)
+        .map(|_| ())
+        .map_err(|_| Error::Config(format!("invalid user agent {:?}", user_agent)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn default_user_agent_carries_version() {
+        assert!(DEFAULT_USER_AGENT.starts_with("pricr/"));
+        assert!(DEFAULT_USER_AGENT.len() > "pricr/".len());
+    }
+
+    #[test]
+    fn control_characters_in_the_user_agent_are_a_config_error() {
+        assert!(check_user_agent("dashboard/1.0 (+https://example.com)").is_ok());
+        assert!(matches!(
+            check_user_agent("bad\x01agent"),
+            Err(Error::Config(_))
+        ));
+        assert!(matches!(
+            client_with_user_agent("bad\nagent"),
+            Err(Error::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,266 @@
+use async_trait::async_trait;
+use futures::future::join_all;
+use serde::Deserialize;
+use tracing::{debug, trace};
+
+use super::context::ProviderContext;
+use super::{
+    ApiKeyStatus, AssetClass, CoinPrice, Feature, MarketState, PriceProvider, ProviderCapabilities,
+};
+use super::{HttpResponse, cache, send_with_retry, status_error};
+use crate::clock::Clock;
+use crate::error::{Error, Result};
+
+const BASE_URL: &str = "https://api.mexc.com";
+const PRICE_CACHE_TTL_SECS: i64 = 30;
+/// MEXC error code for an unknown trading pair.
+const INVALID_SYMBOL_CODE: i64 = -1121;
+
+/// MEXC spot exchange provider for long-tail crypto tokens.
+///
+/// Quotes come from the 24h ticker of each `<SYMBOL><QUOTE>` pair, so coverage
+/// follows MEXC's listings rather than a coin catalog.
+pub struct Mexc {
+    ctx: ProviderContext,
+    base_url: String,
+}
+
+impl Mexc {
+    /// Create a MEXC provider using the default production API URL.
+    pub fn new() -> Self {
+        Self::with_context(ProviderContext::new())
+    }
+
+    /// Create a MEXC provider that shares `ctx`'s client and clock.
+    pub fn with_context(ctx: ProviderContext) -> Self {
+        Self {
+            ctx,
+            base_url: BASE_URL.to_string(),
+        }
+    }
+
+    /// Create a MEXC provider with a custom base URL.
+    pub fn with_base_url(base_url: impl Into<String>) -> Self {
+        Self::with_base_url_and_context(base_url, ProviderContext::new())
+    }
+
+    /// Create a MEXC provider with a custom base URL that shares `ctx`'s client
+    /// and clock.
+    pub fn with_base_url_and_context(base_url: impl Into<String>, ctx: ProviderContext) -> Self {
+        Self {
+            base_url: base_url.into(),
+            ..Self::with_context(ctx)
+        }
+    }
+}
+
+impl Default for Mexc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `/api/v3/ticker/24hr` response; MEXC sends numbers as strings.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct MexcTicker {
+    last_price: String,
+    /// Rolling 24h change as a fraction (`0.0123` is +1.23%).
+    price_change_percent: Option<String>,
+    open_price: Option<String>,
+    high_price: Option<String>,
+    low_price: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct MexcError {
+    code: Option<i64>,
+}
+
+#[async_trait]
+impl PriceProvider for Mexc {
+    fn name(&self) -> &str {
+        "MEXC"
+    }
+
+    fn id(&self) -> &str {
+        "mexc"
+    }
+
+    fn clock(&self) -> &dyn Clock {
+        self.ctx.clock.as_ref()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            api_key: ApiKeyStatus::NotRequired,
+            features: vec![Feature::Prices],
+            asset_classes: vec![AssetClass::Crypto],
+            base_url: self.base_url.clone(),
+        }
+    }
+
+    fn resolve_symbol(&self, symbol: &str) -> String {
+        pair_symbol(symbol, "USDT")
+    }
+
+    async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
+        let Some(quote) = quote_asset(currency) else {
+            return Err(Error::Unsupported {
+                provider: self.id().to_string(),
+                operation: format!("prices in {}", currency.to_uppercase()),
+            });
+        };
+
+        let requested_currency = currency.to_uppercase();
+        let futures = symbols
+            .iter()
+            .map(|symbol| self.fetch_ticker_for_symbol(symbol, quote, &requested_currency));
+
+        let mut results = Vec::new();
+        for result in join_all(futures).await {
+            match result {
+                Ok(Some(price)) => results.push(price),
+                Ok(None) => {}
+                Err(Error::SymbolNotFound { symbol, .. }) => {
+                    debug!(symbol = %symbol, "MEXC has no pair for symbol");
+                }
+                Err(err) => return Err(err),
+            }
+        }
+
+        if results.is_empty() {
+            return Err(Error::NoResults);
+        }
+
+        Ok(results)
+    }
+}
+
+impl Mexc {
+    async fn fetch_ticker_for_symbol(
+        &self,
+        symbol: &str,
+        quote: &str,
+        requested_currency: &str,
+    ) -> Result<Option<CoinPrice>> {
+        let display_symbol = symbol.trim().to_uppercase();
+        let pair = pair_symbol(symbol, quote);
+        let endpoint = format!("{}/api/v3/ticker/24hr", self.base_url);
+        let cache_key = format!("ticker:{}:{}", self.base_url, pair);
+
+        debug!(pair = %pair, "fetching 24h ticker from MEXC");
+
+        let (body, fetched_at) = if let Some(cached_body) = cache::read_json::<String>(
+            "mexc",
+            &cache_key,
+            PRICE_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            debug!(pair = %pair, "using cached MEXC ticker response");
+            (cached_body.value, cached_body.fetched_at)
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&endpoint)
+                    .query(&[("symbol", pair.as_str())]),
+                &self.ctx,
+            )
+            .await?;
+
+            debug!(status = %status, pair = %pair, body_len = body.len(), "MEXC ticker response");
+            trace!(body = %body, pair = %pair, "MEXC ticker response body");
+
+            if status == reqwest::StatusCode::BAD_REQUEST
+                && serde_json::from_str::<MexcError>(&body)
+                    .is_ok_and(|err| err.code == Some(INVALID_SYMBOL_CODE))
+            {
+                return Err(Error::SymbolNotFound {
+                    provider: self.name().to_string(),
+                    symbol: display_symbol,
+                });
+            }
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    "MEXC",
+                    status,
+                    retry_after,
+                    Some(&display_symbol),
+                    &body,
+                ));
+            }
+
+            cache::write_json("mexc", &cache_key, &body, &self.ctx).await;
+            (body, self.ctx.clock.now())
+        };
+
+        let ticker: MexcTicker = serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("MEXC ticker JSON: {}", e)))?;
+
+        let number = |raw: Option<&str>| {
+            raw.and_then(|value| value.trim().parse::<f64>().ok())
+                .filter(|value| value.is_finite())
+        };
+        let Some(price) = number(Some(&ticker.last_price)).filter(|price| *price > 0.0) else {
+            return Ok(None);
+        };
+
+        Ok(Some(CoinPrice {
+            name: display_symbol.clone(),
+            symbol: display_symbol,
+            price,
+            change_24h: number(ticker.price_change_percent.as_deref())
+                .map(|fraction| fraction * 100.0),
+            market_cap: None,
+            open_24h: number(ticker.open_price.as_deref()),
+            high_24h: number(ticker.high_price.as_deref()),
+            low_24h: number(ticker.low_price.as_deref()),
+            market_state: Some(MarketState::Always),
+            change_basis: None,
+            rank: None,
+            fundamentals: None,
+            native_price: None,
+            native_currency: None,
+            currency: requested_currency.to_string(),
+            provider: self.name().to_string(),
+            timestamp: fetched_at,
+        }))
+    }
+}
+
+/// MEXC quote asset used for a requested currency; US dollars are quoted in USDT.
+fn quote_asset(currency: &str) -> Option<&'static str> {
+    match currency.trim().to_uppercase().as_str() {
+        "USD" | "USDT" => Some("USDT"),
+        "USDC" => Some("USDC"),
+        _ => None,
+    }
+}
+
+/// Trading pair for `symbol` against `quote`, e.g. `pepe` -> `PEPEUSDT`.
+fn pair_symbol(symbol: &str, quote: &str) -> String {
+    format!("{}{}", symbol.trim().to_uppercase(), quote)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn quote_asset_maps_usd_to_usdt_and_rejects_other_fiat() {
+        assert_eq!(quote_asset("usd"), Some("USDT"));
+        assert_eq!(quote_asset("USDT"), Some("USDT"));
+        assert_eq!(quote_asset("usdc"), Some("USDC"));
+        assert_eq!(quote_asset("eur"), None);
+        assert_eq!(pair_symbol(" pepe ", "USDT"), "PEPEUSDT");
+    }
+}
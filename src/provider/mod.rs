@@ -1,14 +1,21 @@
 mod cache;
 pub mod coingecko;
 pub mod coinmarketcap;
+pub mod context;
 pub mod frankfurter;
+pub mod http;
+pub mod mexc;
+pub mod registry;
 pub mod stooq;
 pub mod yahoo;
 
 use async_trait::async_trait;
 use serde::{Deserialize, Serialize};
+use tracing::info;
 
+use crate::clock::{Clock, SystemClock};
 use crate::error::{Error, Result};
+use context::ProviderContext;
 
 /// A single coin's price data returned by a provider.
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -18,16 +25,154 @@ pub struct CoinPrice {
     pub price: f64,
     pub change_24h: Option<f64>,
     pub market_cap: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub open_24h: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub high_24h: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub low_24h: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub market_state: Option<MarketState>,
+    /// What `change_24h` is measured against; `None` means a rolling 24 hours.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub change_basis: Option<ChangeBasis>,
+    /// Market-cap rank, set by top-listings lookups.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<u32>,
+    /// Stock valuation figures, set by Yahoo Finance lookups with `--fundamentals`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub fundamentals: Option<Fundamentals>,
+    /// Price as quoted by the provider, kept when `--convert` rewrote `price`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_price: Option<f64>,
+    /// Currency of `native_price`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub native_currency: Option<String>,
     pub currency: String,
     pub provider: String,
     pub timestamp: chrono::DateTime<chrono::Utc>,
 }
 
+impl CoinPrice {
+    /// One-line plain-text summary, e.g. `BTC: $97,423.12 (+1.23%) [CoinGecko]`.
+    ///
+    /// The 24h change is left out when the provider did not report one.
+    pub fn to_display_string(&self) -> String {
+        let price = crate::output::format::format_price(self.price, &self.currency);
+        match self.change_24h {
+            Some(change) => format!(
+                "{}: {} ({:+.2}%) [{}]",
+                self.symbol, price, change, self.provider
+            ),
+            None => format!("{}: {} [{}]", self.symbol, price, self.provider),
+        }
+    }
+}
+
+/// A bare USD quote from a `Test` provider, for unit tests to adjust with struct
+/// update syntax.
+#[cfg(test)]
+pub(crate) fn test_price(symbol: &str, price: f64) -> CoinPrice {
+    CoinPrice {
+        symbol: symbol.to_string(),
+        name: symbol.to_string(),
+        price,
+        change_24h: None,
+        market_cap: None,
+        open_24h: None,
+        high_24h: None,
+        low_24h: None,
+        market_state: None,
+        change_basis: None,
+        rank: None,
+        fundamentals: None,
+        native_price: None,
+        native_currency: None,
+        currency: "USD".to_string(),
+        provider: "Test".to_string(),
+        timestamp: chrono::Utc::now(),
+    }
+}
+
+/// Valuation figures for a stock or fund quote.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Fundamentals {
+    /// Trailing twelve-month price/earnings ratio.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trailing_pe: Option<f64>,
+    /// Forward annual dividend yield, in percent.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dividend_yield: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub week_52_high: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub week_52_low: Option<f64>,
+}
+
+impl Fundamentals {
+    /// Whether no figure is known.
+    pub fn is_empty(&self) -> bool {
+        *self == Self::default()
+    }
+}
+
+/// Reference point of a quote's `change_24h`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeBasis {
+    /// Price 24 hours ago, as reported by crypto providers.
+    Rolling24h,
+    /// Close of the previous trading session, which may be days ago over weekends and holidays.
+    PreviousClose,
+    /// Open of the current trading session.
+    SessionOpen,
+    /// Close 7 days ago (`--period 7d`).
+    #[serde(rename = "7d")]
+    Days7,
+    /// Close 30 days ago (`--period 30d`).
+    #[serde(rename = "30d")]
+    Days30,
+    /// Close 90 days ago (`--period 90d`).
+    #[serde(rename = "90d")]
+    Days90,
+    /// Close one year ago (`--period 1y`).
+    #[serde(rename = "1y")]
+    Year1,
+}
+
+/// Trading session state of the market a quote comes from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum MarketState {
+    Open,
+    Closed,
+    PreMarket,
+    PostMarket,
+    /// Markets that never close, such as crypto.
+    Always,
+}
+
+impl MarketState {
+    /// Short label shown next to the symbol in the price table.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Open => "OPEN",
+            Self::Closed => "CLOSED",
+            Self::PreMarket => "PRE",
+            Self::PostMarket => "POST",
+            Self::Always => "24/7",
+        }
+    }
+}
+
 /// A single historical price point for a coin.
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PricePoint {
     pub timestamp: chrono::DateTime<chrono::Utc>,
     pub price: f64,
+    /// Synthetic point carrying the last known price across a gap (see `--fill`).
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub filled: bool,
 }
 
 /// A single ticker search match returned by a provider.
@@ -38,6 +183,92 @@ pub struct TickerMatch {
     pub exchange: String,
     pub asset_type: String,
     pub provider: String,
+    /// Provider popularity rank (e.g. CoinGecko market-cap rank), used to order merged results.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<u32>,
+}
+
+/// Descriptive profile of a coin or stock (see `pricr info`).
+///
+/// Crypto providers fill the supply, ATH/ATL, and link fields; stock providers fill
+/// sector, industry, exchange, and the 52-week range.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct AssetInfo {
+    pub symbol: String,
+    pub name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub rank: Option<u32>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub categories: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub genesis_date: Option<chrono::NaiveDate>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub homepage: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub explorers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub circulating_supply: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub total_supply: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ath: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub ath_date: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub atl: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub atl_date: Option<chrono::DateTime<chrono::Utc>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub sector: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub industry: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub exchange: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub week_52_high: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub week_52_low: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub currency: String,
+    pub provider: String,
+}
+
+/// CoinGecko coin metadata shown by `--details`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CoinDetails {
+    pub symbol: String,
+    pub name: String,
+    pub description: String,
+    pub homepage: Vec<String>,
+    pub github: Vec<String>,
+    pub categories: Vec<String>,
+    pub genesis_date: Option<chrono::NaiveDate>,
+    pub market_cap_rank: Option<u32>,
+}
+
+/// How one provider maps a user-supplied symbol (see `--resolve`).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolResolution {
+    pub symbol: String,
+    pub provider: String,
+    pub resolved: String,
+}
+
+/// A requested symbol that no provider could resolve.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolError {
+    pub symbol: String,
+    pub tried: Vec<String>,
+    #[serde(default)]
+    pub suggestions: Vec<SymbolSuggestion>,
+}
+
+/// A ticker search match offered as a "did you mean" alternative.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SymbolSuggestion {
+    pub symbol: String,
+    pub name: String,
 }
 
 /// Sampling interval used when fetching historical chart data.
@@ -67,6 +298,162 @@ pub struct PriceHistory {
     pub currency: String,
     pub provider: String,
     pub points: Vec<PricePoint>,
+    /// Dividends and splits in the series' range (Yahoo Finance with `--events`).
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub events: Vec<HistoryEvent>,
+}
+
+/// Corporate action reported alongside a stock's price history.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum HistoryEvent {
+    /// Cash dividend of `amount` per share, in the history's currency.
+    Dividend {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        amount: f64,
+    },
+    /// Stock split of `numerator` new shares for every `denominator` old ones.
+    Split {
+        timestamp: chrono::DateTime<chrono::Utc>,
+        numerator: f64,
+        denominator: f64,
+    },
+}
+
+impl HistoryEvent {
+    /// When the event took effect.
+    pub fn timestamp(&self) -> chrono::DateTime<chrono::Utc> {
+        match self {
+            Self::Dividend { timestamp, .. } | Self::Split { timestamp, .. } => *timestamp,
+        }
+    }
+
+    /// One-letter chart marker: `D` for dividends, `S` for splits.
+    pub fn marker(&self) -> char {
+        match self {
+            Self::Dividend { .. } => 'D',
+            Self::Split { .. } => 'S',
+        }
+    }
+}
+
+impl PriceHistory {
+    /// One-line plain-text summary, e.g. `BTC: 30 points, $85,000.00 - $102,000.00, CoinGecko`.
+    ///
+    /// The low-high range is left out when the series has no points.
+    pub fn summary(&self) -> String {
+        let range = self.points.iter().map(|point| point.price).fold(
+            None,
+            |range: Option<(f64, f64)>, price| match range {
+                Some((low, high)) => Some((low.min(price), high.max(price))),
+                None => Some((price, price)),
+            },
+        );
+
+        match range {
+            Some((low, high)) => format!(
+                "{}: {} points, {} - {}, {}",
+                self.symbol,
+                self.points.len(),
+                crate::output::format::format_price(low, &self.currency),
+                crate::output::format::format_price(high, &self.currency),
+                self.provider
+            ),
+            None => format!("{}: 0 points, {}", self.symbol, self.provider),
+        }
+    }
+}
+
+/// An operation a provider can serve.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Feature {
+    Prices,
+    History,
+    HistoryWindow,
+    Search,
+    Listings,
+    Trending,
+    /// Quotes prices in a crypto asset such as BTC instead of fiat.
+    CryptoQuote,
+    Info,
+}
+
+impl Feature {
+    /// Render the feature as its CLI-facing snake_case name.
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Prices => "prices",
+            Self::History => "history",
+            Self::HistoryWindow => "history_window",
+            Self::Search => "search",
+            Self::Listings => "listings",
+            Self::Trending => "trending",
+            Self::CryptoQuote => "crypto_quote",
+            Self::Info => "info",
+        }
+    }
+}
+
+/// A kind of instrument a provider can quote.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AssetClass {
+    Crypto,
+    Stock,
+    Etf,
+    Index,
+    Commodity,
+    Fx,
+}
+
+/// Whether a provider needs an API key, and if so whether one is configured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApiKeyStatus {
+    Configured,
+    Missing,
+    NotRequired,
+}
+
+impl ApiKeyStatus {
+    /// Short `yes`/`no`/`n-a` label for the provider list.
+    pub fn label(self) -> &'static str {
+        match self {
+            Self::Configured => "yes",
+            Self::Missing => "no",
+            Self::NotRequired => "n-a",
+        }
+    }
+}
+
+/// Capability metadata a provider reports about itself.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderCapabilities {
+    pub api_key: ApiKeyStatus,
+    pub features: Vec<Feature>,
+    pub asset_classes: Vec<AssetClass>,
+    pub base_url: String,
+}
+
+/// A provider's identity and capabilities, as shown by `--list-providers`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProviderInfo {
+    pub id: String,
+    pub name: String,
+    #[serde(flatten)]
+    pub capabilities: ProviderCapabilities,
+}
+
+impl ProviderInfo {
+    /// Describe a configured provider.
+    pub fn from_provider(provider: &dyn PriceProvider) -> Self {
+        Self {
+            id: provider.id().to_string(),
+            name: provider.name().to_string(),
+            capabilities: provider.capabilities(),
+        }
+    }
 }
 
 /// Trait implemented by all price data providers.
@@ -78,12 +465,39 @@ pub trait PriceProvider: Send + Sync {
     /// Short identifier used in CLI flags.
     fn id(&self) -> &str;
 
+    /// Features, asset classes, API key status, and base URL of this provider.
+    fn capabilities(&self) -> ProviderCapabilities;
+
+    /// Clock used for cache ages, history windows, and "today".
+    fn clock(&self) -> &dyn Clock {
+        &SystemClock
+    }
+
+    /// Whether the provider only works with an API key, configured or not.
+    ///
+    /// Providers with an optional key (CoinGecko) return `false`.
+    fn requires_key(&self) -> bool {
+        false
+    }
+
+    /// Show how a user-supplied symbol maps to this provider's identifier, without fetching.
+    fn resolve_symbol(&self, symbol: &str) -> String {
+        symbol.trim().to_uppercase()
+    }
+
     /// Fetch prices for the given coin symbols in the specified fiat currency.
     async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>>;
 
+    /// Fetch prices with the session open, high, and low filled in where available.
+    ///
+    /// Defaults to `get_prices`, for providers whose quotes already carry OHLC data.
+    async fn get_ohlc(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
+        self.get_prices(symbols, currency).await
+    }
+
     /// Fetch price history for the given coin symbols.
     ///
-    /// Providers that do not support historical data may return a configuration error.
+    /// Providers that do not support historical data return `Error::Unsupported`.
     async fn get_price_history(
         &self,
         _symbols: &[String],
@@ -91,15 +505,15 @@ pub trait PriceProvider: Send + Sync {
         _days: u32,
         _interval: HistoryInterval,
     ) -> Result<Vec<PriceHistory>> {
-        Err(Error::Config(format!(
-            "provider '{}' does not support chart mode",
-            self.id()
-        )))
+        Err(Error::Unsupported {
+            provider: self.id().to_string(),
+            operation: "chart mode".into(),
+        })
     }
 
     /// Fetch price history within an explicit time window.
     ///
-    /// Providers that do not support explicit windows may return a configuration error.
+    /// Providers that do not support explicit windows return `Error::Unsupported`.
     async fn get_price_history_window(
         &self,
         _symbols: &[String],
@@ -108,43 +522,507 @@ pub trait PriceProvider: Send + Sync {
         _end: chrono::DateTime<chrono::Utc>,
         _interval: HistoryInterval,
     ) -> Result<Vec<PriceHistory>> {
-        Err(Error::Config(format!(
-            "provider '{}' does not support explicit chart date windows",
-            self.id()
-        )))
+        Err(Error::Unsupported {
+            provider: self.id().to_string(),
+            operation: "explicit chart date windows".into(),
+        })
+    }
+
+    /// Fetch each symbol's daily close on `date` (UTC), or the last close before it.
+    ///
+    /// Built on [`get_price_history`](Self::get_price_history), so providers without
+    /// history return `Error::Unsupported`. Closes more than a week before `date` are
+    /// ignored, and the returned prices carry the close's timestamp.
+    async fn get_price_at_date(
+        &self,
+        symbols: &[String],
+        currency: &str,
+        date: chrono::NaiveDate,
+    ) -> Result<Vec<CoinPrice>> {
+        const MAX_GAP_DAYS: i64 = 7;
+
+        let age_days = (self.clock().today() - date).num_days().max(0);
+        let days = u32::try_from(age_days + MAX_GAP_DAYS + 1).unwrap_or(u32::MAX);
+        let histories = self
+            .get_price_history(symbols, currency, days, HistoryInterval::Daily)
+            .await?;
+
+        let prices: Vec<CoinPrice> = histories
+            .into_iter()
+            .filter_map(|history| {
+                let point = history
+                    .points
+                    .iter()
+                    .filter(|p| p.timestamp.date_naive() <= date && p.price.is_finite())
+                    .max_by_key(|p| p.timestamp)
+                    .filter(|p| (date - p.timestamp.date_naive()).num_days() <= MAX_GAP_DAYS)?;
+                Some(CoinPrice {
+                    symbol: history.symbol,
+                    name: history.name,
+                    price: point.price,
+                    change_24h: None,
+                    market_cap: None,
+                    open_24h: None,
+                    high_24h: None,
+                    low_24h: None,
+                    market_state: None,
+                    change_basis: None,
+                    rank: None,
+                    fundamentals: None,
+                    native_price: None,
+                    native_currency: None,
+                    currency: history.currency,
+                    provider: history.provider,
+                    timestamp: point.timestamp,
+                })
+            })
+            .collect();
+
+        if prices.is_empty() {
+            return Err(Error::NoResults);
+        }
+        Ok(prices)
     }
 
     /// Search provider instruments by symbol/name query.
     ///
-    /// Providers that do not support search may return a configuration error.
+    /// Providers that do not support search return `Error::Unsupported`.
     async fn search_tickers(&self, _query: &str, _limit: usize) -> Result<Vec<TickerMatch>> {
-        Err(Error::Config(format!(
-            "provider '{}' does not support ticker search",
-            self.id()
-        )))
+        Err(Error::Unsupported {
+            provider: self.id().to_string(),
+            operation: "ticker search".into(),
+        })
+    }
+
+    /// Fetch the `limit` largest assets by market cap, ranked from 1.
+    ///
+    /// Providers without market-cap listings return `Error::Unsupported`.
+    async fn get_top_listings(&self, _limit: usize, _currency: &str) -> Result<Vec<CoinPrice>> {
+        Err(Error::Unsupported {
+            provider: self.id().to_string(),
+            operation: "top market cap listings".into(),
+        })
+    }
+
+    /// Fetch currently trending assets, most popular first.
+    ///
+    /// Providers without a trending feed return `Error::Unsupported`.
+    async fn get_trending(&self, _currency: &str) -> Result<Vec<CoinPrice>> {
+        Err(Error::Unsupported {
+            provider: self.id().to_string(),
+            operation: "trending coins".into(),
+        })
     }
+
+    /// List the quote currency codes (lowercase) this provider prices assets in.
+    ///
+    /// Providers without such a listing return `Error::Unsupported`.
+    async fn get_quote_currencies(&self) -> Result<Vec<String>> {
+        Err(Error::Unsupported {
+            provider: self.id().to_string(),
+            operation: "quote currency listing".into(),
+        })
+    }
+
+    /// Fetch a descriptive profile of one asset, with prices in `currency`.
+    ///
+    /// Providers without asset metadata return `Error::Unsupported`.
+    async fn get_info(&self, _symbol: &str, _currency: &str) -> Result<AssetInfo> {
+        Err(Error::Unsupported {
+            provider: self.id().to_string(),
+            operation: "asset info".into(),
+        })
+    }
+}
+
+/// Settings [`available_providers`] applies to the built-in providers.
+#[derive(Debug, Clone, Default)]
+pub struct ProviderOptions {
+    /// Client and clock every built-in provider shares.
+    pub context: ProviderContext,
+    pub yahoo: yahoo::YahooOptions,
 }
 
 /// Build the list of available providers based on configuration.
-pub fn available_providers(api_key: Option<String>) -> Vec<Box<dyn PriceProvider>> {
+///
+/// `coingecko_base_url` overrides the CoinGecko API endpoint when set,
+/// `coingecko_api_key` is sent with every CoinGecko request, and `options`
+/// configures every provider.
+pub fn available_providers(
+    api_key: Option<String>,
+    coingecko_base_url: Option<String>,
+    coingecko_api_key: Option<coingecko::ApiKey>,
+    options: &ProviderOptions,
+) -> Vec<Box<dyn PriceProvider>> {
+    let ctx = &options.context;
     let cmc_key = api_key.or_else(|| std::env::var("COINMARKETCAP_API_KEY").ok());
 
-    let mut providers: Vec<Box<dyn PriceProvider>> = vec![
-        Box::new(coingecko::CoinGecko::new()),
-        Box::new(stooq::Stooq::new()),
-        Box::new(yahoo::YahooFinance::new()),
-    ];
-    match cmc_key {
-        Some(key) => providers.push(Box::new(coinmarketcap::CoinMarketCap::new(key))),
-        None => providers.push(Box::new(coinmarketcap::CoinMarketCap::without_key())),
+    // Every provider clones the one context, so fallback reuses pooled connections.
+    vec![
+        Box::new(coingecko::CoinGecko::configured(
+            coingecko_base_url,
+            coingecko_api_key,
+            ctx.clone(),
+        )),
+        Box::new(stooq::Stooq::with_context(ctx.clone())),
+        Box::new(yahoo::YahooFinance::with_context(ctx.clone()).with_options(options.yahoo)),
+        Box::new(coinmarketcap::CoinMarketCap::configured(
+            cmc_key,
+            ctx.clone(),
+        )),
+        // Exchange tickers last: they only help with tokens the others miss.
+        Box::new(mexc::Mexc::with_context(ctx.clone())),
+    ]
+}
+
+/// Response status, `Retry-After` hint, and body text of a provider request.
+pub(crate) struct HttpResponse {
+    pub status: reqwest::StatusCode,
+    pub retry_after: Option<u64>,
+    pub body: String,
+}
+
+/// Send a provider request, retrying once after a short `Retry-After` on HTTP 429.
+///
+/// `Retry-After` dates are measured from the response's `Date` header, or from
+/// the wall clock when it is missing, not from the context's clock: one-shot runs
+/// freeze that clock at startup, which would overstate the wait.
+pub(crate) async fn send_with_retry(
+    provider: &str,
+    request: reqwest::RequestBuilder,
+    ctx: &ProviderContext,
+) -> Result<HttpResponse> {
+    let retry = request.try_clone();
+    let mut resp = request
+        .send()
+        .await
+        .map_err(|e| Error::network(provider, e))?;
+
+    if resp.status() == reqwest::StatusCode::TOO_MANY_REQUESTS
+        && let Some(retry) = retry
+        && let Some(delay) = retry_after_secs(resp.headers(), response_time(resp.headers()))
+        && delay > 0
+        && delay <= ctx.max_retry_after_secs
+    {
+        info!(
+            provider,
+            delay, "rate limited, retrying once after Retry-After"
+        );
+        tokio::time::sleep(std::time::Duration::from_secs(delay)).await;
+        resp = retry
+            .send()
+            .await
+            .map_err(|e| Error::network(provider, e))?;
     }
 
-    providers
+    let status = resp.status();
+    let retry_after = retry_after_secs(resp.headers(), response_time(resp.headers()));
+    let body = resp.text().await.map_err(|e| Error::network(provider, e))?;
+
+    Ok(HttpResponse {
+        status,
+        retry_after,
+        body,
+    })
+}
+
+/// When the server sent the response, from its `Date` header or else the wall clock.
+fn response_time(headers: &reqwest::header::HeaderMap) -> chrono::DateTime<chrono::Utc> {
+    headers
+        .get(reqwest::header::DATE)
+        .and_then(|date| date.to_str().ok())
+        .and_then(|date| chrono::DateTime::parse_from_rfc2822(date.trim()).ok())
+        .map_or_else(chrono::Utc::now, |date| date.with_timezone(&chrono::Utc))
+}
+
+/// Read a `Retry-After` header given either as delay seconds or as an HTTP date.
+fn retry_after_secs(
+    headers: &reqwest::header::HeaderMap,
+    now: chrono::DateTime<chrono::Utc>,
+) -> Option<u64> {
+    let raw = headers
+        .get(reqwest::header::RETRY_AFTER)?
+        .to_str()
+        .ok()?
+        .trim();
+
+    if let Ok(secs) = raw.parse::<u64>() {
+        return Some(secs);
+    }
+
+    let at = chrono::DateTime::parse_from_rfc2822(raw).ok()?;
+    Some((at.with_timezone(&chrono::Utc) - now).num_seconds().max(0) as u64)
+}
+
+/// Map a non-success HTTP response to a structured error.
+///
+/// `context` names the endpoint (e.g. "CoinGecko") for the generic `Api` fallback;
+/// `symbol` is reported on 404 when the request targeted a single instrument.
+pub(crate) fn status_error(
+    provider: &str,
+    context: &str,
+    status: reqwest::StatusCode,
+    retry_after: Option<u64>,
+    symbol: Option<&str>,
+    body: &str,
+) -> Error {
+    match (status.as_u16(), symbol) {
+        (429, _) => Error::RateLimited {
+            provider: provider.to_string(),
+            retry_after,
+        },
+        (401 | 403, _) => Error::AuthFailed {
+            provider: provider.to_string(),
+            detail: format!("{} returned {}: {}", context, status, body),
+        },
+        (404, Some(symbol)) => Error::SymbolNotFound {
+            provider: provider.to_string(),
+            symbol: symbol.to_string(),
+        },
+        _ => Error::Api(format!("{} returned {}: {}", context, status, body)),
+    }
 }
 
-/// Look up a provider index by its short id.
-pub fn get_provider(providers: &[Box<dyn PriceProvider>], id: &str) -> Option<usize> {
-    providers
-        .iter()
-        .position(|p| p.id().eq_ignore_ascii_case(id))
+/// Reduce a provider's long-form description to its first paragraph, without HTML tags.
+///
+/// Returns `None` when nothing but whitespace remains.
+pub(crate) fn short_description(raw: &str) -> Option<String> {
+    let mut text = String::with_capacity(raw.len());
+    let mut in_tag = false;
+    for ch in raw.chars() {
+        match ch {
+            '<' => in_tag = true,
+            '>' if in_tag => in_tag = false,
+            _ if !in_tag => text.push(ch),
+            _ => {}
+        }
+    }
+
+    let paragraph = text
+        .replace("\r\n", "\n")
+        .split("\n\n")
+        .map(str::trim)
+        .find(|p| !p.is_empty())?
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ");
+    Some(paragraph)
+}
+
+/// Currency code as shown to users: trimmed and uppercase.
+///
+/// Yahoo marks quotes in minor units with a lowercase last letter (`GBp` pence,
+/// `ZAc` cents), so those map to `GBX` / `ZAC` instead of the major-unit code.
+pub(crate) fn normalize_currency(code: &str) -> String {
+    match code.trim() {
+        "GBp" => "GBX".to_string(),
+        "ZAc" => "ZAC".to_string(),
+        code => code.to_uppercase(),
+    }
+}
+
+/// Prune every provider's cache directory down to `max_entries` files.
+///
+/// Returns the number of files removed.
+pub async fn prune_cache(max_entries: usize) -> usize {
+    cache::prune_all(max_entries).await
+}
+
+/// Remove cache files written before keys were hashed with SHA-256.
+///
+/// Runs once per cache directory; returns the number of files removed.
+pub async fn remove_legacy_cache_entries() -> usize {
+    cache::remove_legacy_entries().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::{DATE, HeaderMap, HeaderValue, RETRY_AFTER};
+
+    fn price(change_24h: Option<f64>) -> CoinPrice {
+        CoinPrice {
+            name: "Bitcoin".into(),
+            change_24h,
+            provider: "CoinGecko".into(),
+            ..test_price("BTC", 97_423.12)
+        }
+    }
+
+    #[test]
+    fn coin_price_display_string_includes_change_when_known() {
+        assert_eq!(
+            price(Some(1.234)).to_display_string(),
+            "BTC: $97,423.12 (+1.23%) [CoinGecko]"
+        );
+        assert_eq!(
+            price(Some(-0.5)).to_display_string(),
+            "BTC: $97,423.12 (-0.50%) [CoinGecko]"
+        );
+        assert_eq!(
+            price(None).to_display_string(),
+            "BTC: $97,423.12 [CoinGecko]"
+        );
+    }
+
+    #[test]
+    fn price_history_summary_reports_range_or_empty_series() {
+        let mut history = PriceHistory {
+            symbol: "BTC".into(),
+            name: "Bitcoin".into(),
+            currency: "USD".into(),
+            provider: "CoinGecko".into(),
+            points: [95_000.0, 85_000.0, 102_000.0]
+                .into_iter()
+                .map(|price| PricePoint {
+                    timestamp: chrono::Utc::now(),
+                    price,
+                    filled: false,
+                })
+                .collect(),
+            events: Vec::new(),
+        };
+
+        assert_eq!(
+            history.summary(),
+            "BTC: 3 points, $85,000.00 - $102,000.00, CoinGecko"
+        );
+
+        history.points.clear();
+        assert_eq!(history.summary(), "BTC: 0 points, CoinGecko");
+    }
+
+    #[test]
+    fn resolve_symbol_shows_each_provider_mapping() {
+        let providers =
+            registry::ProviderRegistry::builtin(None, None, None, &ProviderOptions::default());
+        let resolved = |id: &str, symbol: &str| providers.get(id).unwrap().resolve_symbol(symbol);
+
+        assert_eq!(resolved("coingecko", "btc"), "bitcoin (Bitcoin)");
+        assert_eq!(resolved("stooq", "aapl"), "aapl.us");
+        assert_eq!(resolved("stooq", "^GSPC"), "^spx");
+        assert_eq!(resolved("yahoo", "cw8.pa"), "CW8.PA");
+        assert_eq!(resolved("cmc", " eth "), "ETH");
+        assert_eq!(resolved("mexc", "pepe"), "PEPEUSDT");
+    }
+
+    #[test]
+    fn coingecko_pro_key_switches_host_and_reports_configured_key() {
+        let pro_key = || {
+            Some(coingecko::ApiKey {
+                key: "CG-pro".into(),
+                tier: coingecko::ApiTier::Pro,
+            })
+        };
+
+        let pro = coingecko::CoinGecko::configured(None, pro_key(), ProviderContext::new())
+            .capabilities();
+        assert_eq!(pro.api_key, ApiKeyStatus::Configured);
+        assert_eq!(pro.base_url, "https://pro-api.coingecko.com/api/v3");
+
+        let proxied = coingecko::CoinGecko::configured(
+            Some("http://localhost:1/api/v3".into()),
+            pro_key(),
+            ProviderContext::new(),
+        );
+        assert_eq!(proxied.capabilities().base_url, "http://localhost:1/api/v3");
+    }
+
+    #[test]
+    fn provider_capabilities_reflect_api_key_and_features() {
+        let providers = available_providers(
+            None,
+            Some("http://localhost:1/api/v3".into()),
+            None,
+            &ProviderOptions::default(),
+        );
+        let infos: Vec<ProviderInfo> = providers
+            .iter()
+            .map(|p| ProviderInfo::from_provider(p.as_ref()))
+            .collect();
+
+        let coingecko = infos.iter().find(|i| i.id == "coingecko").unwrap();
+        assert_eq!(coingecko.capabilities.api_key, ApiKeyStatus::NotRequired);
+        assert_eq!(coingecko.capabilities.base_url, "http://localhost:1/api/v3");
+
+        let yahoo = infos.iter().find(|i| i.id == "yahoo").unwrap();
+        assert!(
+            yahoo
+                .capabilities
+                .features
+                .contains(&Feature::HistoryWindow)
+        );
+        assert!(yahoo.capabilities.features.contains(&Feature::Search));
+
+        let json = serde_json::to_value(yahoo).unwrap();
+        assert_eq!(json["api_key"], "not_required");
+        assert_eq!(json["features"][0], "prices");
+        assert!(json["asset_classes"].is_array());
+
+        let with_key = coinmarketcap::CoinMarketCap::new("key".into());
+        assert_eq!(with_key.capabilities().api_key, ApiKeyStatus::Configured);
+        let without_key = coinmarketcap::CoinMarketCap::without_key();
+        assert_eq!(without_key.capabilities().api_key, ApiKeyStatus::Missing);
+    }
+
+    #[test]
+    fn short_description_keeps_first_paragraph_without_tags() {
+        let raw = "Bitcoin is the first <a href=\"https://x\">decentralized</a>\r\n  cryptocurrency.\r\n\r\nSecond paragraph.";
+        assert_eq!(
+            short_description(raw).as_deref(),
+            Some("Bitcoin is the first decentralized cryptocurrency.")
+        );
+        assert_eq!(short_description(" \n\n <p></p> "), None);
+    }
+
+    fn headers(value: &str) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_str(value).unwrap());
+        headers
+    }
+
+    #[test]
+    fn retry_after_secs_parses_delay_seconds_and_http_dates() {
+        let now = chrono::DateTime::parse_from_rfc2822("Wed, 21 Oct 2015 07:28:00 GMT")
+            .unwrap()
+            .with_timezone(&chrono::Utc);
+
+        assert_eq!(retry_after_secs(&headers("37"), now), Some(37));
+        assert_eq!(
+            retry_after_secs(&headers("Wed, 21 Oct 2015 07:28:45 GMT"), now),
+            Some(45)
+        );
+        assert_eq!(
+            retry_after_secs(&headers("Wed, 21 Oct 2015 07:27:00 GMT"), now),
+            Some(0)
+        );
+        assert_eq!(retry_after_secs(&headers("soon"), now), None);
+        assert_eq!(retry_after_secs(&HeaderMap::new(), now), None);
+    }
+
+    #[test]
+    fn response_time_reads_the_date_header() {
+        let mut with_date = HeaderMap::new();
+        with_date.insert(
+            DATE,
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        assert_eq!(
+            response_time(&with_date).to_rfc3339(),
+            "2015-10-21T07:28:00+00:00"
+        );
+
+        let before = chrono::Utc::now();
+        assert!(response_time(&headers("37")) >= before);
+    }
+
+    #[test]
+    fn normalize_currency_uppercases_and_keeps_minor_units_apart() {
+        assert_eq!(normalize_currency(" usd "), "USD");
+        assert_eq!(normalize_currency("eur"), "EUR");
+        assert_eq!(normalize_currency("GBp"), "GBX");
+        assert_eq!(normalize_currency("ZAc"), "ZAC");
+        assert_eq!(normalize_currency("GBP"), "GBP");
+        assert_eq!(normalize_currency("ILA"), "ILA");
+    }
 }
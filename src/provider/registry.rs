@@ -0,0 +1,325 @@
+use std::collections::HashSet;
+use std::fmt;
+
+use super::{PriceProvider, ProviderOptions, available_providers, coingecko};
+use crate::error::{Error, Result};
+
+/// Providers known to a lookup, in default fallback order.
+///
+/// Starts empty or with the built-in providers; downstream crates [`register`]
+/// their own [`PriceProvider`] implementations so they take part in fallback,
+/// `--provider`-style pinning, and `provider_order` like any built-in one.
+///
+/// [`register`]: ProviderRegistry::register
+#[derive(Default)]
+pub struct ProviderRegistry {
+    providers: Vec<Box<dyn PriceProvider>>,
+}
+
+impl ProviderRegistry {
+    /// An empty registry.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The built-in providers, configured like [`available_providers`].
+    pub fn builtin(
+        cmc_api_key: Option<String>,
+        coingecko_base_url: Option<String>,
+        coingecko_api_key: Option<coingecko::ApiKey>,
+        options: &ProviderOptions,
+    ) -> Self {
+        Self {
+            providers: available_providers(
+                cmc_api_key,
+                coingecko_base_url,
+                coingecko_api_key,
+                options,
+            ),
+        }
+    }
+
+    /// Add `provider` at the end of the fallback order.
+    ///
+    /// A provider with the same id is replaced in place instead, so a custom
+    /// implementation can stand in for a built-in one.
+    pub fn register(&mut self, provider: Box<dyn PriceProvider>) -> &mut Self {
+        match self.index_of(provider.id()) {
+            Some(idx) => self.providers[idx] = provider,
+            None => self.providers.push(provider),
+        }
+        self
+    }
+
+    /// Add `provider` at the front of the fallback order, replacing any provider
+    /// with the same id.
+    pub fn register_first(&mut self, provider: Box<dyn PriceProvider>) -> &mut Self {
+        if let Some(idx) = self.index_of(provider.id()) {
+            self.providers.remove(idx);
+        }
+        self.providers.insert(0, provider);
+        self
+    }
+
+    /// Drop providers that only work with an API key (`--free-only`).
+    ///
+    /// Indices into [`providers`](Self::providers) change, so call this before
+    /// resolving a lookup order.
+    pub fn retain_free(&mut self) -> &mut Self {
+        self.providers.retain(|p| !p.requires_key());
+        self
+    }
+
+    /// Index of the provider with this id, ignoring case.
+    pub fn index_of(&self, id: &str) -> Option<usize> {
+        self.providers
+            .iter()
+            .position(|p| p.id().eq_ignore_ascii_case(id))
+    }
+
+    /// The provider with this id, ignoring case.
+    pub fn get(&self, id: &str) -> Option<&dyn PriceProvider> {
+        self.index_of(id).map(|idx| self.providers[idx].as_ref())
+    }
+
+    /// Every registered provider, in registration order.
+    pub fn providers(&self) -> &[Box<dyn PriceProvider>] {
+        &self.providers
+    }
+
+    /// Ids of every registered provider, in registration order.
+    pub fn ids(&self) -> Vec<&str> {
+        self.providers.iter().map(|p| p.id()).collect()
+    }
+
+    pub fn len(&self) -> usize {
+        self.providers.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.providers.is_empty()
+    }
+
+    /// Provider indices to try in order: just `explicit_provider` when set, otherwise
+    /// `configured_order` followed by every remaining provider.
+    pub fn lookup_order(
+        &self,
+        explicit_provider: Option<&str>,
+        configured_order: Option<&[String]>,
+    ) -> Result<Vec<usize>> {
+        if let Some(provider_id) = explicit_provider {
+            let requested = provider_id.trim();
+            if requested.is_empty() {
+                return Err(Error::Config(
+                    "provider cannot be empty -- use --list-providers to see options".into(),
+                ));
+            }
+
+            let idx = self.index_of(requested).ok_or_else(|| {
+                Error::Config(format!(
+                    "unknown provider '{}' -- use --list-providers to see options",
+                    provider_id
+                ))
+            })?;
+            return Ok(vec![idx]);
+        }
+
+        let mut ordered = Vec::new();
+        let mut seen_ids = HashSet::new();
+
+        if let Some(config_ids) = configured_order {
+            for configured_id in config_ids {
+                let raw = configured_id.trim();
+                if raw.is_empty() {
+                    continue;
+                }
+
+                let normalized = raw.to_ascii_lowercase();
+                if !seen_ids.insert(normalized.clone()) {
+                    continue;
+                }
+
+                let idx = self.index_of(&normalized).ok_or_else(|| {
+                    Error::Config(format!(
+                        "unknown provider '{}' in [defaults].provider_order -- use --list-providers to see options",
+                        configured_id
+                    ))
+                })?;
+                ordered.push(idx);
+            }
+        }
+
+        for (idx, prov) in self.providers.iter().enumerate() {
+            if seen_ids.insert(prov.id().to_ascii_lowercase()) {
+                ordered.push(idx);
+            }
+        }
+
+        if ordered.is_empty() {
+            return Err(Error::Config(
+                "no providers available -- use --list-providers to verify installation".into(),
+            ));
+        }
+
+        Ok(ordered)
+    }
+}
+
+impl fmt::Debug for ProviderRegistry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_list().entries(self.ids()).finish()
+    }
+}
+
+impl Extend<Box<dyn PriceProvider>> for ProviderRegistry {
+    /// [`register`](ProviderRegistry::register) each provider in turn.
+    fn extend<I: IntoIterator<Item = Box<dyn PriceProvider>>>(&mut self, providers: I) {
+        for provider in providers {
+            self.register(provider);
+        }
+    }
+}
+
+impl FromIterator<Box<dyn PriceProvider>> for ProviderRegistry {
+    fn from_iter<I: IntoIterator<Item = Box<dyn PriceProvider>>>(providers: I) -> Self {
+        let mut registry = Self::new();
+        registry.extend(providers);
+        registry
+    }
+}
+
+impl IntoIterator for ProviderRegistry {
+    type Item = Box<dyn PriceProvider>;
+    type IntoIter = std::vec::IntoIter<Box<dyn PriceProvider>>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.providers.into_iter()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use async_trait::async_trait;
+
+    use super::*;
+    use crate::provider::{ApiKeyStatus, AssetClass, CoinPrice, Feature, ProviderCapabilities};
+
+    struct Named {
+        id: &'static str,
+        name: &'static str,
+    }
+
+    #[async_trait]
+    impl PriceProvider for Named {
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn id(&self) -> &str {
+            self.id
+        }
+
+        fn capabilities(&self) -> ProviderCapabilities {
+            ProviderCapabilities {
+                api_key: ApiKeyStatus::NotRequired,
+                features: vec![Feature::Prices],
+                asset_classes: vec![AssetClass::Crypto],
+                base_url: String::new(),
+            }
+        }
+
+        async fn get_prices(&self, _symbols: &[String], _currency: &str) -> Result<Vec<CoinPrice>> {
+            Err(Error::NoResults)
+        }
+    }
+
+    fn named(id: &'static str, name: &'static str) -> Box<dyn PriceProvider> {
+        Box::new(Named { id, name })
+    }
+
+    #[test]
+    fn lookup_order_uses_configured_order_then_remaining() {
+        let registry = ProviderRegistry::builtin(None, None, None, &ProviderOptions::default());
+        let configured = vec!["yahoo".to_string(), "coingecko".to_string()];
+
+        let indices = registry.lookup_order(None, Some(&configured)).unwrap();
+        let ids: Vec<&str> = indices.iter().map(|&idx| registry.ids()[idx]).collect();
+
+        assert_eq!(ids.len(), registry.len());
+        assert_eq!(ids[0], "yahoo");
+        assert_eq!(ids[1], "coingecko");
+        assert!(ids.contains(&"stooq"));
+        assert!(ids.contains(&"cmc"));
+    }
+
+    #[test]
+    fn lookup_order_rejects_unknown_configured_provider() {
+        let registry = ProviderRegistry::builtin(None, None, None, &ProviderOptions::default());
+        let configured = vec!["not-a-provider".to_string()];
+
+        let err = registry.lookup_order(None, Some(&configured)).unwrap_err();
+        match err {
+            Error::Config(message) => {
+                assert!(message.contains("[defaults].provider_order"));
+            }
+            other => panic!("unexpected error: {other}"),
+        }
+        assert!(matches!(
+            ProviderRegistry::new().lookup_order(None, None),
+            Err(Error::Config(_))
+        ));
+    }
+
+    #[test]
+    fn runtime_providers_are_ordered_and_found_like_built_in_ones() {
+        let mut registry = ProviderRegistry::builtin(None, None, None, &ProviderOptions::default());
+        let builtin = registry.len();
+        registry.register(named("kraken", "Kraken"));
+        registry.register_first(named("local", "Local"));
+
+        assert_eq!(registry.len(), builtin + 2);
+        assert_eq!(registry.ids()[0], "local");
+        assert_eq!(registry.ids()[builtin + 1], "kraken");
+        assert_eq!(registry.get("KRAKEN").unwrap().name(), "Kraken");
+        assert_eq!(
+            registry.lookup_order(Some("kraken"), None).unwrap(),
+            [builtin + 1]
+        );
+
+        let configured = vec!["kraken".to_string()];
+        let order = registry.lookup_order(None, Some(&configured)).unwrap();
+        assert_eq!(order[..2], [builtin + 1, 0]);
+    }
+
+    #[test]
+    fn retain_free_drops_keyed_coinmarketcap() {
+        let mut registry = ProviderRegistry::builtin(
+            Some("cmc-key".into()),
+            None,
+            None,
+            &ProviderOptions::default(),
+        );
+        assert!(registry.get("cmc").unwrap().requires_key());
+        registry.retain_free();
+
+        assert!(registry.get("cmc").is_none());
+        for id in ["coingecko", "yahoo", "stooq", "mexc"] {
+            assert!(registry.get(id).is_some(), "{id} should stay");
+        }
+    }
+
+    #[test]
+    fn registering_an_existing_id_replaces_it_in_place() {
+        let mut registry: ProviderRegistry = [named("alpha", "Alpha"), named("yahoo", "Yahoo")]
+            .into_iter()
+            .collect();
+        registry.register(named("alpha", "Alpha v2"));
+
+        assert_eq!(registry.ids(), ["alpha", "yahoo"]);
+        assert_eq!(registry.get("alpha").unwrap().name(), "Alpha v2");
+
+        registry.register_first(named("yahoo", "My Yahoo"));
+        assert_eq!(registry.ids(), ["yahoo", "alpha"]);
+        assert_eq!(format!("{registry:?}"), r#"["yahoo", "alpha"]"#);
+    }
+}
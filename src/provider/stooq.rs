@@ -1,22 +1,31 @@
 use async_trait::async_trait;
+use chrono::{DateTime, Datelike, TimeZone, Timelike, Utc, Weekday};
+use chrono_tz::America::New_York;
 use futures::future::join_all;
-use reqwest::Client;
 use serde::Deserialize;
 use tracing::{debug, trace};
 
-use super::cache;
-use super::{CoinPrice, HistoryInterval, PriceHistory, PricePoint, PriceProvider, TickerMatch};
+use super::context::ProviderContext;
+use super::{
+    ApiKeyStatus, AssetClass, ChangeBasis, CoinPrice, Feature, HistoryInterval, PriceHistory,
+    PricePoint, PriceProvider, ProviderCapabilities, TickerMatch,
+};
+use super::{HttpResponse, cache, send_with_retry, status_error};
+use crate::clock::Clock;
 use crate::error::{Error, Result};
 
 const BASE_URL: &str = "https://stooq.com";
 const SEARCH_BASE_URL: &str = "https://query2.finance.yahoo.com";
 const HISTORY_CACHE_TTL_SECS: i64 = 12 * 60 * 60;
+const TRADING_HOURS_HISTORY_CACHE_TTL_SECS: i64 = 30 * 60;
+/// US Eastern hour the daily bar closes.
+const MARKET_CLOSE_HOUR: u32 = 17;
 const PRICE_CACHE_TTL_SECS: i64 = 30;
 const SEARCH_CACHE_TTL_SECS: i64 = 10 * 60;
 
 /// Stooq price provider for stock and ETF symbols.
 pub struct Stooq {
-    client: Client,
+    ctx: ProviderContext,
     base_url: String,
     search_base_url: String,
 }
@@ -24,7 +33,16 @@ pub struct Stooq {
 impl Stooq {
     /// Create a Stooq provider using the default production API URL.
     pub fn new() -> Self {
-        Self::with_base_urls(BASE_URL, SEARCH_BASE_URL)
+        Self::with_context(ProviderContext::new())
+    }
+
+    /// Create a Stooq provider that shares `ctx`'s client and clock.
+    pub fn with_context(ctx: ProviderContext) -> Self {
+        Self {
+            ctx,
+            base_url: BASE_URL.to_string(),
+            search_base_url: SEARCH_BASE_URL.to_string(),
+        }
     }
 
     /// Create a Stooq provider with a custom base URL.
@@ -34,14 +52,10 @@ impl Stooq {
 
     /// Create a Stooq provider with custom quote/history and search base URLs.
     pub fn with_base_urls(base_url: impl Into<String>, search_base_url: impl Into<String>) -> Self {
-        let client = Client::builder()
-            .user_agent("pricr/0.1.0")
-            .build()
-            .expect("failed to build HTTP client");
         Self {
-            client,
             base_url: base_url.into(),
             search_base_url: search_base_url.into(),
+            ..Self::new()
         }
     }
 }
@@ -78,6 +92,23 @@ impl PriceProvider for Stooq {
         "stooq"
     }
 
+    fn clock(&self) -> &dyn Clock {
+        self.ctx.clock.as_ref()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            api_key: ApiKeyStatus::NotRequired,
+            features: vec![Feature::Prices, Feature::History, Feature::Search],
+            asset_classes: vec![AssetClass::Stock, AssetClass::Etf, AssetClass::Index],
+            base_url: self.base_url.clone(),
+        }
+    }
+
+    fn resolve_symbol(&self, symbol: &str) -> String {
+        normalize_symbol(symbol)
+    }
+
     async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
         let requested_currency = currency.to_uppercase();
         let requested: Vec<(String, String)> = symbols
@@ -91,8 +122,13 @@ impl PriceProvider for Stooq {
         });
 
         for result in join_all(futures).await {
-            if let Some(price) = result? {
-                results.push(price);
+            match result {
+                Ok(Some(price)) => results.push(price),
+                Ok(None) => {}
+                Err(Error::SymbolNotFound { symbol, .. }) => {
+                    debug!(symbol = %symbol, "Stooq has no quote for symbol");
+                }
+                Err(err) => return Err(err),
             }
         }
 
@@ -149,37 +185,47 @@ impl PriceProvider for Stooq {
 
         debug!(query = %trimmed, limit, "searching tickers via Yahoo Finance search API");
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("stooq", &cache_key, SEARCH_CACHE_TTL_SECS).await
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "stooq",
+            &cache_key,
+            SEARCH_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(query = %trimmed, limit, "using cached ticker search response");
-            cached_body
+            cached_body.value
         } else {
-            let resp = self
-                .client
-                .get(&endpoint)
-                .query(&[
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx.client.get(&endpoint).query(&[
                     ("q", trimmed),
                     ("quotesCount", limit_string.as_str()),
                     ("newsCount", "0"),
-                ])
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+                ]),
+                &self.ctx,
+            )
+            .await?;
 
             debug!(status = %status, body_len = body.len(), "ticker search response");
             trace!(body = %body, query = %trimmed, "ticker search response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "ticker search returned {}: {}",
-                    status, body
-                )));
+                return Err(status_error(
+                    self.name(),
+                    "Stooq ticker search",
+                    status,
+                    retry_after,
+                    None,
+                    &body,
+                ));
             }
 
-            cache::write_json("stooq", &cache_key, &body).await;
+            cache::write_json("stooq", &cache_key, &body, &self.ctx).await;
             body
         };
 
@@ -206,6 +252,7 @@ impl PriceProvider for Stooq {
                     exchange: quote.exch_disp.unwrap_or_else(|| "Unknown".to_string()),
                     asset_type: quote.type_disp.unwrap_or_else(|| "Unknown".to_string()),
                     provider: self.name().to_string(),
+                    rank: None,
                 })
             })
             .take(limit)
@@ -231,21 +278,30 @@ impl Stooq {
 
         debug!(symbol = %normalized, "fetching quote from Stooq");
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("stooq", &cache_key, PRICE_CACHE_TTL_SECS).await
+        let (body, fetched_at) = if let Some(cached_body) = cache::read_json::<String>(
+            "stooq",
+            &cache_key,
+            PRICE_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
             debug!(symbol = %normalized, "using cached Stooq quote response");
-            cached_body
+            (cached_body.value, cached_body.fetched_at)
         } else {
-            let resp = self
-                .client
-                .get(&endpoint)
-                .query(&[("s", normalized), ("i", "d")])
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&endpoint)
+                    .query(&[("s", normalized), ("i", "d")]),
+                &self.ctx,
+            )
+            .await?;
 
             debug!(
                 status = %status,
@@ -256,11 +312,18 @@ impl Stooq {
             trace!(body = %body, symbol = %normalized, "Stooq quote response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!("Stooq returned {}: {}", status, body)));
+                return Err(status_error(
+                    self.name(),
+                    "Stooq",
+                    status,
+                    retry_after,
+                    Some(display_symbol),
+                    &body,
+                ));
             }
 
-            cache::write_json("stooq", &cache_key, &body).await;
-            body
+            cache::write_json("stooq", &cache_key, &body, &self.ctx).await;
+            (body, self.ctx.clock.now())
         };
 
         let key = normalized.to_uppercase();
@@ -282,9 +345,18 @@ impl Stooq {
                 .and_then(|open| percent_change(open, row.close))
                 .filter(|v| v.is_finite()),
             market_cap: None,
+            open_24h: row.open,
+            high_24h: row.high,
+            low_24h: row.low,
+            market_state: None,
+            change_basis: Some(ChangeBasis::SessionOpen),
+            rank: None,
+            fundamentals: None,
+            native_price: None,
+            native_currency: None,
             currency: currency_for_symbol(normalized, requested_currency),
             provider: self.name().to_string(),
-            timestamp: chrono::Utc::now(),
+            timestamp: fetched_at,
         }))
     }
 
@@ -305,21 +377,29 @@ impl Stooq {
             "fetching chart data from Stooq"
         );
 
+        let now = self.ctx.clock.now();
         let body = if let Some(cached_body) =
-            cache::read_json::<String>("stooq", &cache_key, HISTORY_CACHE_TTL_SECS).await
+            cache::read_json_with::<String>("stooq", &cache_key, |fetched_at| {
+                stooq_history_is_fresh(fetched_at, now)
+            })
+            .await
         {
             debug!(symbol = %normalized, "using cached Stooq history response");
-            cached_body
+            cached_body.value
         } else {
-            let resp = self
-                .client
-                .get(&endpoint)
-                .query(&[("s", normalized.as_str()), ("i", "d")])
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&endpoint)
+                    .query(&[("s", normalized.as_str()), ("i", "d")]),
+                &self.ctx,
+            )
+            .await?;
 
             debug!(
                 status = %status,
@@ -330,13 +410,17 @@ impl Stooq {
             trace!(body = %body, symbol = %normalized, "Stooq history response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "Stooq returned {} for chart data: {}",
-                    status, body
-                )));
+                return Err(status_error(
+                    self.name(),
+                    "Stooq chart data",
+                    status,
+                    retry_after,
+                    Some(&display_symbol),
+                    &body,
+                ));
             }
 
-            cache::write_json("stooq", &cache_key, &body).await;
+            cache::write_json("stooq", &cache_key, &body, &self.ctx).await;
             body
         };
 
@@ -366,6 +450,7 @@ impl Stooq {
             points.push(PricePoint {
                 timestamp: naive_dt.and_utc(),
                 price: close,
+                filled: false,
             });
         }
 
@@ -382,6 +467,7 @@ impl Stooq {
             currency: currency_for_symbol(&normalized, requested_currency),
             provider: self.name().to_string(),
             points,
+            events: Vec::new(),
         })
     }
 }
@@ -389,6 +475,8 @@ impl Stooq {
 struct QuoteRow {
     symbol: String,
     open: Option<f64>,
+    high: Option<f64>,
+    low: Option<f64>,
     close: f64,
 }
 
@@ -405,10 +493,14 @@ fn parse_quote_row(line: &str) -> Option<QuoteRow> {
     let symbol = cols.first()?.trim().to_uppercase();
     let close = parse_decimal(cols[6])?;
     let open = parse_decimal(cols[3]);
+    let high = parse_decimal(cols[4]);
+    let low = parse_decimal(cols[5]);
 
     Some(QuoteRow {
         symbol,
         open,
+        high,
+        low,
         close,
     })
 }
@@ -430,8 +522,70 @@ fn percent_change(open: f64, close: f64) -> Option<f64> {
     Some(((close - open) / open) * 100.0)
 }
 
+/// Index symbols as (Yahoo-style alias, Stooq code, quote currency).
+///
+/// Stooq codes are also accepted directly with the `^` prefix.
+const INDEX_SYMBOLS: &[(&str, &str, &str)] = &[
+    ("^gspc", "^spx", "USD"),
+    ("^dji", "^dji", "USD"),
+    ("^ixic", "^ndq", "USD"),
+    ("^ndx", "^ndx", "USD"),
+    ("^gdaxi", "^dax", "EUR"),
+    ("^fchi", "^cac", "EUR"),
+    ("^ftse", "^ukx", "GBP"),
+    ("^n225", "^nkx", "JPY"),
+    ("^hsi", "^hsi", "HKD"),
+];
+
+/// Index codes recognized without a `^` prefix (none of these are US equity tickers).
+const BARE_INDEX_CODES: &[&str] = &["spx", "ndx", "ndq"];
+
+fn index_entry(symbol: &str) -> Option<&'static (&'static str, &'static str, &'static str)> {
+    let lower = symbol.trim().to_lowercase();
+    let prefixed = if lower.starts_with('^') {
+        lower
+    } else if BARE_INDEX_CODES.contains(&lower.as_str()) {
+        format!("^{}", lower)
+    } else {
+        return None;
+    };
+
+    INDEX_SYMBOLS
+        .iter()
+        .find(|(alias, code, _)| *alias == prefixed || *code == prefixed)
+}
+
+/// Market suffixes as (Yahoo-style suffix, Stooq suffix, quote currency).
+///
+/// Stooq suffixes are also accepted directly; London listings are quoted in pence.
+const MARKET_SUFFIXES: &[(&str, &str, &str)] = &[
+    (".us", ".us", "USD"),
+    (".de", ".de", "EUR"),
+    (".l", ".uk", "GBX"),
+    (".t", ".jp", "JPY"),
+    (".hk", ".hk", "HKD"),
+];
+
+fn market_entry(symbol: &str) -> Option<&'static (&'static str, &'static str, &'static str)> {
+    MARKET_SUFFIXES
+        .iter()
+        .find(|(alias, code, _)| symbol.ends_with(alias) || symbol.ends_with(code))
+}
+
 fn normalize_symbol(symbol: &str) -> String {
+    if let Some((_, code, _)) = index_entry(symbol) {
+        return code.to_string();
+    }
+
     let trimmed = symbol.trim().to_lowercase();
+    if trimmed.starts_with('^') {
+        return trimmed;
+    }
+    if let Some((alias, code, _)) = market_entry(&trimmed) {
+        let base = trimmed.strip_suffix(alias).unwrap_or(&trimmed);
+        let base = base.strip_suffix(code).unwrap_or(base);
+        return format!("{}{}", base, code);
+    }
     if trimmed.contains('.') {
         trimmed
     } else {
@@ -440,10 +594,13 @@ fn normalize_symbol(symbol: &str) -> String {
 }
 
 fn currency_for_symbol(normalized_symbol: &str, fallback: &str) -> String {
-    if normalized_symbol.ends_with(".us") {
-        "USD".to_string()
-    } else {
-        fallback.to_string()
+    if let Some((_, _, currency)) = market_entry(normalized_symbol) {
+        return currency.to_string();
+    }
+
+    match index_entry(normalized_symbol) {
+        Some((_, _, currency)) => currency.to_string(),
+        _ => fallback.to_string(),
     }
 }
 
@@ -458,3 +615,174 @@ fn trim_points_to_days(points: &mut Vec<PricePoint>, days: u32) {
     let cutoff = last - chrono::Duration::days(days as i64);
     points.retain(|p| p.timestamp >= cutoff);
 }
+
+/// History cache TTL for a fetch at `now`.
+///
+/// Between 9 AM and 5 PM US Eastern time on weekdays the latest daily bar is
+/// still moving, so entries expire after 30 minutes; otherwise they last 12 hours.
+fn stooq_market_close_aware_ttl(now: DateTime<Utc>) -> i64 {
+    let eastern = now.with_timezone(&New_York);
+    let weekend = matches!(eastern.weekday(), Weekday::Sat | Weekday::Sun);
+    if !weekend && (9..MARKET_CLOSE_HOUR).contains(&eastern.hour()) {
+        TRADING_HOURS_HISTORY_CACHE_TTL_SECS
+    } else {
+        HISTORY_CACHE_TTL_SECS
+    }
+}
+
+/// Whether a history entry fetched at `fetched_at` can still be served at `now`.
+///
+/// The TTL follows the fetch time, and an entry fetched before the latest 5 PM
+/// Eastern close is stale even within it: it lacks that day's closing bar.
+fn stooq_history_is_fresh(fetched_at: DateTime<Utc>, now: DateTime<Utc>) -> bool {
+    let age_secs = (now - fetched_at).num_seconds();
+    (0..=stooq_market_close_aware_ttl(fetched_at)).contains(&age_secs)
+        && latest_market_close(now).is_none_or(|close| fetched_at >= close)
+}
+
+/// The most recent weekday 5 PM US Eastern at or before `now`.
+fn latest_market_close(now: DateTime<Utc>) -> Option<DateTime<Utc>> {
+    let eastern = now.with_timezone(&New_York);
+    let mut date = eastern.date_naive();
+    if eastern.hour() < MARKET_CLOSE_HOUR {
+        date = date.pred_opt()?;
+    }
+    while matches!(date.weekday(), Weekday::Sat | Weekday::Sun) {
+        date = date.pred_opt()?;
+    }
+    let close = date.and_hms_opt(MARKET_CLOSE_HOUR, 0, 0)?;
+    New_York
+        .from_local_datetime(&close)
+        .single()
+        .map(|close| close.with_timezone(&Utc))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn utc(y: i32, m: u32, d: u32, h: u32, min: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, h, min, 0).unwrap()
+    }
+
+    #[test]
+    fn history_ttl_is_short_during_eastern_trading_hours() {
+        // Wednesday 2024-01-10, EST (UTC-5): 09:00 and 16:59 local.
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 1, 10, 14, 0)),
+            30 * 60
+        );
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 1, 10, 21, 59)),
+            30 * 60
+        );
+    }
+
+    #[test]
+    fn history_ttl_is_long_outside_eastern_trading_hours() {
+        // 08:59 and 17:00 EST, then 21:00 EST.
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 1, 10, 13, 59)),
+            12 * 60 * 60
+        );
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 1, 10, 22, 0)),
+            12 * 60 * 60
+        );
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 1, 11, 2, 0)),
+            12 * 60 * 60
+        );
+    }
+
+    #[test]
+    fn history_ttl_follows_daylight_saving_time() {
+        // Wednesday 2024-07-10, EDT (UTC-4): 13:30 UTC is 09:30 local, 21:30 UTC is 17:30.
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 7, 10, 13, 30)),
+            30 * 60
+        );
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 7, 10, 21, 30)),
+            12 * 60 * 60
+        );
+        // The same UTC time in January is 08:30 EST, before the open.
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 1, 10, 13, 30)),
+            12 * 60 * 60
+        );
+    }
+
+    #[test]
+    fn history_fetched_before_the_close_is_stale_after_it() {
+        // Wednesday 2024-01-10 EST: fetched at 10:00, read at 21:00.
+        assert!(!stooq_history_is_fresh(
+            utc(2024, 1, 10, 15, 0),
+            utc(2024, 1, 11, 2, 0)
+        ));
+        // Fetched at 16:50 and read at 17:05, inside the 30 minute TTL.
+        assert!(!stooq_history_is_fresh(
+            utc(2024, 1, 10, 21, 50),
+            utc(2024, 1, 10, 22, 5)
+        ));
+        // Fetched after the close: fresh for the evening, and Friday's bar over the weekend.
+        assert!(stooq_history_is_fresh(
+            utc(2024, 1, 10, 22, 30),
+            utc(2024, 1, 11, 2, 0)
+        ));
+        assert!(stooq_history_is_fresh(
+            utc(2024, 1, 12, 22, 30),
+            utc(2024, 1, 13, 10, 0)
+        ));
+        assert_eq!(
+            latest_market_close(utc(2024, 1, 14, 12, 0)),
+            Some(utc(2024, 1, 12, 22, 0))
+        );
+    }
+
+    #[test]
+    fn history_ttl_is_long_on_weekends() {
+        // Saturday 2024-01-13, 12:00 EST.
+        assert_eq!(
+            stooq_market_close_aware_ttl(utc(2024, 1, 13, 17, 0)),
+            12 * 60 * 60
+        );
+    }
+
+    #[test]
+    fn normalize_symbol_appends_us_suffix_to_plain_tickers() {
+        assert_eq!(normalize_symbol("AAPL"), "aapl.us");
+        assert_eq!(normalize_symbol("cw8.pa"), "cw8.pa");
+    }
+
+    #[test]
+    fn normalize_symbol_maps_yahoo_market_suffixes_to_stooq() {
+        assert_eq!(normalize_symbol("SAP.DE"), "sap.de");
+        assert_eq!(normalize_symbol("VOD.L"), "vod.uk");
+        assert_eq!(normalize_symbol("vod.uk"), "vod.uk");
+        assert_eq!(normalize_symbol("7203.T"), "7203.jp");
+        assert_eq!(normalize_symbol("0700.HK"), "0700.hk");
+        assert_eq!(currency_for_symbol("sap.de", "USD"), "EUR");
+        assert_eq!(currency_for_symbol("vod.uk", "USD"), "GBX");
+        assert_eq!(currency_for_symbol("7203.jp", "USD"), "JPY");
+    }
+
+    #[test]
+    fn normalize_symbol_maps_index_symbols() {
+        assert_eq!(normalize_symbol("^GSPC"), "^spx");
+        assert_eq!(normalize_symbol("^spx"), "^spx");
+        assert_eq!(normalize_symbol("^DJI"), "^dji");
+        assert_eq!(normalize_symbol("^IXIC"), "^ndq");
+        assert_eq!(normalize_symbol("^FTSE"), "^ukx");
+        assert_eq!(normalize_symbol("spx"), "^spx");
+        assert_eq!(normalize_symbol("^UNKNOWN"), "^unknown");
+    }
+
+    #[test]
+    fn currency_for_symbol_uses_index_currency() {
+        assert_eq!(currency_for_symbol("^spx", "EUR"), "USD");
+        assert_eq!(currency_for_symbol("^dax", "USD"), "EUR");
+        assert_eq!(currency_for_symbol("^unknown", "CHF"), "CHF");
+        assert_eq!(currency_for_symbol("aapl.us", "EUR"), "USD");
+    }
+}
@@ -1,43 +1,131 @@
+use std::collections::HashMap;
+
 use async_trait::async_trait;
 use futures::future::join_all;
-use reqwest::Client;
 use serde::Deserialize;
 use tracing::{debug, trace};
 
-use super::cache;
-use super::{CoinPrice, HistoryInterval, PriceHistory, PricePoint, PriceProvider, TickerMatch};
+use super::context::ProviderContext;
+use super::{
+    ApiKeyStatus, AssetClass, AssetInfo, ChangeBasis, CoinPrice, Feature, Fundamentals,
+    HistoryEvent, HistoryInterval, MarketState, PriceHistory, PricePoint, PriceProvider,
+    ProviderCapabilities, TickerMatch,
+};
+use super::{
+    HttpResponse, cache, normalize_currency, send_with_retry, short_description, status_error,
+};
+use crate::clock::Clock;
 use crate::error::{Error, Result};
 
 const BASE_URL: &str = "https://query2.finance.yahoo.com";
 const QUOTE_CACHE_TTL_SECS: i64 = 30;
 const SEARCH_CACHE_TTL_SECS: i64 = 10 * 60;
+const INFO_CACHE_TTL_SECS: i64 = 60 * 60;
 const HOURLY_HISTORY_CACHE_TTL_SECS: i64 = 60 * 60;
 const DAILY_HISTORY_CACHE_TTL_SECS: i64 = 12 * 60 * 60;
 
+/// Optional Yahoo Finance requests and chart adjustments.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct YahooOptions {
+    /// Fill in quote market caps from `quoteSummary`, at the cost of one extra
+    /// request per symbol.
+    pub market_cap_enrichment: bool,
+    /// Request dividends and splits with chart data and attach them to each history.
+    pub chart_events: bool,
+    /// Chart the split- and dividend-adjusted close (`adjclose`) instead of the raw close.
+    pub adjusted_close: bool,
+    /// Attach P/E, dividend yield, and 52-week range to stock quotes from
+    /// `quoteSummary`, at the cost of one extra request per symbol.
+    pub fundamentals: bool,
+}
+
+/// Exchange names and codes (as typed with `--exchange`) and the Yahoo Finance
+/// ticker suffix of their listings. US exchanges use bare tickers and are absent.
+const EXCHANGE_SUFFIXES: &[(&[&str], &str)] = &[
+    (&["XETRA", "XETR", "GER"], ".DE"),
+    (&["FRANKFURT", "FRA"], ".F"),
+    (&["LSE", "LONDON", "LON"], ".L"),
+    (&["TSE", "TOKYO", "JPX"], ".T"),
+    (&["PARIS", "EPA"], ".PA"),
+    (&["AMSTERDAM", "AMS"], ".AS"),
+    (&["MILAN", "BIT"], ".MI"),
+    (&["MADRID", "BME"], ".MC"),
+    (&["SIX", "SWISS", "EBS"], ".SW"),
+    (&["TORONTO", "TSX", "TOR"], ".TO"),
+    (&["HKEX", "HONG KONG", "HKG"], ".HK"),
+    (&["ASX", "SYDNEY"], ".AX"),
+];
+
+/// Yahoo Finance ticker suffix for an exchange name or code, e.g. `XETRA` -> `.DE`.
+///
+/// Returns `None` for US exchanges (bare tickers) and unknown names.
+pub fn exchange_suffix(exchange: &str) -> Option<&'static str> {
+    let wanted = exchange.trim();
+    EXCHANGE_SUFFIXES
+        .iter()
+        .find(|(names, _)| names.iter().any(|name| name.eq_ignore_ascii_case(wanted)))
+        .map(|(_, suffix)| *suffix)
+}
+
 /// Yahoo Finance provider for stocks/ETFs and ticker discovery.
 pub struct YahooFinance {
-    client: Client,
+    ctx: ProviderContext,
     base_url: String,
+    options: YahooOptions,
 }
 
 impl YahooFinance {
     /// Create a Yahoo Finance provider using the default production API URL.
     pub fn new() -> Self {
-        Self::with_base_url(BASE_URL)
+        Self::with_context(ProviderContext::new())
+    }
+
+    /// Create a Yahoo Finance provider that shares `ctx`'s client and clock.
+    pub fn with_context(ctx: ProviderContext) -> Self {
+        Self {
+            ctx,
+            base_url: BASE_URL.to_string(),
+            options: YahooOptions::default(),
+        }
     }
 
     /// Create a Yahoo Finance provider with a custom base URL.
     pub fn with_base_url(base_url: impl Into<String>) -> Self {
-        let client = Client::builder()
-            .user_agent("pricr/0.1.0")
-            .build()
-            .expect("failed to build HTTP client");
-
         Self {
-            client,
             base_url: base_url.into(),
+            ..Self::new()
         }
     }
+
+    /// Apply every [`YahooOptions`] setting at once.
+    pub fn with_options(mut self, options: YahooOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Fill in quote market caps from `quoteSummary` (see [`YahooOptions::market_cap_enrichment`]).
+    pub fn with_market_cap_enrichment(mut self, enabled: bool) -> Self {
+        self.options.market_cap_enrichment = enabled;
+        self
+    }
+
+    /// Attach dividends and splits to chart histories (see [`YahooOptions::chart_events`]).
+    pub fn with_chart_events(mut self, enabled: bool) -> Self {
+        self.options.chart_events = enabled;
+        self
+    }
+
+    /// Chart the adjusted close instead of the raw close (see [`YahooOptions::adjusted_close`]).
+    pub fn with_adjusted_close(mut self, enabled: bool) -> Self {
+        self.options.adjusted_close = enabled;
+        self
+    }
+
+    /// Attach fundamentals to stock quotes (see [`YahooOptions::fundamentals`]).
+    pub fn with_fundamentals(mut self, enabled: bool) -> Self {
+        self.options.fundamentals = enabled;
+        self
+    }
 }
 
 impl Default for YahooFinance {
@@ -62,6 +150,30 @@ struct YahooChartResult {
     meta: YahooChartMeta,
     timestamp: Option<Vec<i64>>,
     indicators: YahooChartIndicators,
+    #[serde(default)]
+    events: Option<YahooChartEvents>,
+}
+
+/// `events` block of a chart result, keyed by the event's epoch seconds.
+#[derive(Debug, Default, Deserialize)]
+struct YahooChartEvents {
+    #[serde(default)]
+    dividends: HashMap<String, YahooDividend>,
+    #[serde(default)]
+    splits: HashMap<String, YahooSplit>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooDividend {
+    amount: f64,
+    date: i64,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooSplit {
+    date: i64,
+    numerator: f64,
+    denominator: f64,
 }
 
 #[derive(Debug, Deserialize)]
@@ -75,15 +187,32 @@ struct YahooChartMeta {
     regular_market_price: Option<f64>,
     #[serde(rename = "chartPreviousClose")]
     chart_previous_close: Option<f64>,
+    #[serde(rename = "marketState")]
+    market_state: Option<String>,
+    #[serde(rename = "instrumentType")]
+    instrument_type: Option<String>,
 }
 
 #[derive(Debug, Deserialize)]
 struct YahooChartIndicators {
     quote: Vec<YahooChartQuote>,
+    #[serde(default)]
+    adjclose: Vec<YahooChartAdjClose>,
 }
 
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Default, Deserialize)]
+struct YahooChartAdjClose {
+    adjclose: Option<Vec<Option<f64>>>,
+}
+
+#[derive(Debug, Default, Deserialize)]
 struct YahooChartQuote {
+    #[serde(default)]
+    open: Option<Vec<Option<f64>>>,
+    #[serde(default)]
+    high: Option<Vec<Option<f64>>>,
+    #[serde(default)]
+    low: Option<Vec<Option<f64>>>,
     close: Option<Vec<Option<f64>>>,
 }
 
@@ -92,6 +221,85 @@ struct YahooApiError {
     description: Option<String>,
 }
 
+/// `quoteSummary` modules fetched (and cached) together for info, market caps, and fundamentals.
+const QUOTE_SUMMARY_MODULES: &str = "assetProfile,summaryDetail,defaultKeyStatistics,price";
+
+/// `/v10/finance/quoteSummary` response for the [`QUOTE_SUMMARY_MODULES`].
+#[derive(Debug, Deserialize)]
+struct YahooQuoteSummaryEnvelope {
+    #[serde(rename = "quoteSummary")]
+    quote_summary: YahooQuoteSummaryResponse,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooQuoteSummaryResponse {
+    result: Option<Vec<YahooQuoteSummary>>,
+    error: Option<YahooApiError>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooQuoteSummary {
+    #[serde(rename = "assetProfile")]
+    asset_profile: Option<YahooAssetProfile>,
+    #[serde(rename = "summaryDetail")]
+    summary_detail: Option<YahooSummaryDetail>,
+    #[serde(rename = "defaultKeyStatistics")]
+    key_statistics: Option<YahooKeyStatistics>,
+    price: Option<YahooQuotePrice>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooAssetProfile {
+    sector: Option<String>,
+    industry: Option<String>,
+    website: Option<String>,
+    #[serde(rename = "longBusinessSummary")]
+    long_business_summary: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooSummaryDetail {
+    #[serde(rename = "fiftyTwoWeekHigh")]
+    fifty_two_week_high: Option<YahooRawValue>,
+    #[serde(rename = "fiftyTwoWeekLow")]
+    fifty_two_week_low: Option<YahooRawValue>,
+    #[serde(rename = "marketCap")]
+    market_cap: Option<YahooRawValue>,
+    #[serde(rename = "trailingPE")]
+    trailing_pe: Option<YahooRawValue>,
+    #[serde(rename = "dividendYield")]
+    dividend_yield: Option<YahooRawValue>,
+    /// Distribution yield reported for funds instead of `dividendYield`.
+    #[serde(rename = "yield")]
+    fund_yield: Option<YahooRawValue>,
+    currency: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooKeyStatistics {
+    #[serde(rename = "yield")]
+    fund_yield: Option<YahooRawValue>,
+}
+
+/// Yahoo wraps numbers as `{ "raw": 199.62, "fmt": "199.62" }`.
+#[derive(Debug, Deserialize)]
+struct YahooRawValue {
+    raw: Option<f64>,
+}
+
+#[derive(Debug, Deserialize)]
+struct YahooQuotePrice {
+    #[serde(rename = "longName")]
+    long_name: Option<String>,
+    #[serde(rename = "shortName")]
+    short_name: Option<String>,
+    #[serde(rename = "exchangeName")]
+    exchange_name: Option<String>,
+    #[serde(rename = "marketCap")]
+    market_cap: Option<YahooRawValue>,
+    currency: Option<String>,
+}
+
 #[derive(Debug, Deserialize)]
 struct YahooSearchResponse {
     quotes: Vec<YahooSearchQuote>,
@@ -118,6 +326,32 @@ impl PriceProvider for YahooFinance {
         "yahoo"
     }
 
+    fn clock(&self) -> &dyn Clock {
+        self.ctx.clock.as_ref()
+    }
+
+    fn capabilities(&self) -> ProviderCapabilities {
+        ProviderCapabilities {
+            api_key: ApiKeyStatus::NotRequired,
+            features: vec![
+                Feature::Prices,
+                Feature::History,
+                Feature::HistoryWindow,
+                Feature::Search,
+                Feature::Info,
+            ],
+            asset_classes: vec![
+                AssetClass::Stock,
+                AssetClass::Etf,
+                AssetClass::Index,
+                AssetClass::Commodity,
+                AssetClass::Crypto,
+                AssetClass::Fx,
+            ],
+            base_url: self.base_url.clone(),
+        }
+    }
+
     async fn get_prices(&self, symbols: &[String], currency: &str) -> Result<Vec<CoinPrice>> {
         let requested_currency = currency.to_uppercase();
         let futures = symbols
@@ -125,8 +359,13 @@ impl PriceProvider for YahooFinance {
             .map(|symbol| self.fetch_latest_quote_for_symbol(symbol, &requested_currency));
         let mut results = Vec::new();
         for result in join_all(futures).await {
-            if let Some(price) = result? {
-                results.push(price);
+            match result {
+                Ok(Some(price)) => results.push(price),
+                Ok(None) => {}
+                Err(Error::SymbolNotFound { symbol, .. }) => {
+                    debug!(symbol = %symbol, "Yahoo Finance has no quote for symbol");
+                }
+                Err(err) => return Err(err),
             }
         }
 
@@ -144,7 +383,7 @@ impl PriceProvider for YahooFinance {
         days: u32,
         interval: HistoryInterval,
     ) -> Result<Vec<PriceHistory>> {
-        let end = chrono::Utc::now();
+        let end = self.ctx.clock.now();
         let start = end - chrono::Duration::days(days as i64);
         self.get_price_history_window(symbols, currency, Some(start), end, interval)
             .await
@@ -185,32 +424,42 @@ impl PriceProvider for YahooFinance {
         let limit_string = limit.to_string();
         let cache_key = format!("search:{}:{}:{}", self.base_url, trimmed, limit_string);
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("yahoo", &cache_key, SEARCH_CACHE_TTL_SECS).await
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "yahoo",
+            &cache_key,
+            SEARCH_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
-            cached_body
+            cached_body.value
         } else {
-            let resp = self
-                .client
-                .get(&endpoint)
-                .query(&[
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx.client.get(&endpoint).query(&[
                     ("q", trimmed),
                     ("quotesCount", limit_string.as_str()),
                     ("newsCount", "0"),
-                ])
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+                ]),
+                &self.ctx,
+            )
+            .await?;
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "Yahoo Finance search returned {}: {}",
-                    status, body
-                )));
+                return Err(status_error(
+                    self.name(),
+                    "Yahoo Finance search",
+                    status,
+                    retry_after,
+                    None,
+                    &body,
+                ));
             }
 
-            cache::write_json("yahoo", &cache_key, &body).await;
+            cache::write_json("yahoo", &cache_key, &body, &self.ctx).await;
             body
         };
 
@@ -232,6 +481,7 @@ impl PriceProvider for YahooFinance {
                     exchange: quote.exch_disp.unwrap_or_else(|| "Unknown".to_string()),
                     asset_type: quote.type_disp.unwrap_or_else(|| "Unknown".to_string()),
                     provider: self.name().to_string(),
+                    rank: None,
                 })
             })
             .take(limit)
@@ -243,9 +493,151 @@ impl PriceProvider for YahooFinance {
 
         Ok(matches)
     }
+
+    async fn get_info(&self, symbol: &str, currency: &str) -> Result<AssetInfo> {
+        let symbol_upper = symbol.trim().to_uppercase();
+        let summary = self.fetch_quote_summary(&symbol_upper).await?;
+
+        let profile = summary.asset_profile;
+        let detail = summary.summary_detail;
+        let price = summary.price;
+        let raw =
+            |value: Option<YahooRawValue>| value.and_then(|v| v.raw).filter(|v| v.is_finite());
+
+        let (name, exchange, price_currency) = match price {
+            Some(price) => (
+                price.long_name.or(price.short_name),
+                price.exchange_name,
+                price.currency,
+            ),
+            None => (None, None, None),
+        };
+        let (week_52_high, week_52_low, detail_currency) = match detail {
+            Some(detail) => (
+                raw(detail.fifty_two_week_high),
+                raw(detail.fifty_two_week_low),
+                detail.currency,
+            ),
+            None => (None, None, None),
+        };
+        let (sector, industry, homepage, description) = match profile {
+            Some(profile) => (
+                profile.sector,
+                profile.industry,
+                profile.website,
+                profile
+                    .long_business_summary
+                    .as_deref()
+                    .and_then(short_description),
+            ),
+            None => (None, None, None, None),
+        };
+
+        Ok(AssetInfo {
+            name: name.unwrap_or_else(|| symbol_upper.clone()),
+            symbol: symbol_upper,
+            homepage,
+            sector,
+            industry,
+            exchange,
+            week_52_high,
+            week_52_low,
+            description,
+            currency: normalize_currency(
+                &price_currency
+                    .or(detail_currency)
+                    .unwrap_or_else(|| currency.to_string()),
+            ),
+            provider: self.name().to_string(),
+            ..AssetInfo::default()
+        })
+    }
 }
 
 impl YahooFinance {
+    /// Fetch the [`QUOTE_SUMMARY_MODULES`] for one symbol.
+    async fn fetch_quote_summary(&self, symbol_upper: &str) -> Result<YahooQuoteSummary> {
+        let endpoint = format!(
+            "{}/v10/finance/quoteSummary/{}",
+            self.base_url, symbol_upper
+        );
+        let cache_key = format!(
+            "quote_summary:{}:{}:{}",
+            self.base_url, QUOTE_SUMMARY_MODULES, symbol_upper
+        );
+
+        debug!(symbol = %symbol_upper, "fetching Yahoo Finance quoteSummary");
+
+        let body = if let Some(cached_body) = cache::read_json::<String>(
+            "yahoo",
+            &cache_key,
+            INFO_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
+        {
+            cached_body.value
+        } else {
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&endpoint)
+                    .query(&[("modules", QUOTE_SUMMARY_MODULES)]),
+                &self.ctx,
+            )
+            .await?;
+            if !status.is_success() {
+                return Err(status_error(
+                    self.name(),
+                    "Yahoo Finance quoteSummary",
+                    status,
+                    retry_after,
+                    Some(symbol_upper),
+                    &body,
+                ));
+            }
+
+            cache::write_json("yahoo", &cache_key, &body, &self.ctx).await;
+            body
+        };
+
+        let payload: YahooQuoteSummaryEnvelope = serde_json::from_str(&body)
+            .map_err(|e| Error::Parse(format!("Yahoo quoteSummary JSON: {}", e)))?;
+
+        if let Some(api_error) = payload.quote_summary.error
+            && let Some(description) = api_error.description
+            && !description.is_empty()
+        {
+            return Err(Error::Api(format!("Yahoo Finance: {}", description)));
+        }
+
+        payload
+            .quote_summary
+            .result
+            .and_then(|mut values| values.drain(..).next())
+            .ok_or_else(|| Error::SymbolNotFound {
+                provider: self.name().to_string(),
+                symbol: symbol_upper.to_string(),
+            })
+    }
+
+    /// `quoteSummary` for quote enrichment, or `None` when Yahoo withholds it
+    /// (including crumb/cookie auth failures) so the quote itself is still returned.
+    async fn fetch_optional_quote_summary(&self, symbol_upper: &str) -> Option<YahooQuoteSummary> {
+        match self.fetch_quote_summary(symbol_upper).await {
+            Ok(summary) => Some(summary),
+            Err(err) => {
+                debug!(symbol = %symbol_upper, error = %err, "Yahoo Finance quoteSummary unavailable");
+                None
+            }
+        }
+    }
+
     async fn fetch_latest_quote_for_symbol(
         &self,
         symbol: &str,
@@ -257,29 +649,42 @@ impl YahooFinance {
 
         debug!(symbol = %symbol_upper, "fetching latest quote from Yahoo Finance chart endpoint");
 
-        let body = if let Some(cached_body) =
-            cache::read_json::<String>("yahoo", &cache_key, QUOTE_CACHE_TTL_SECS).await
+        let (body, fetched_at) = if let Some(cached_body) = cache::read_json::<String>(
+            "yahoo",
+            &cache_key,
+            QUOTE_CACHE_TTL_SECS,
+            self.ctx.clock.as_ref(),
+        )
+        .await
         {
-            cached_body
+            (cached_body.value, cached_body.fetched_at)
         } else {
-            let resp = self
-                .client
-                .get(&endpoint)
-                .query(&[("range", "5d"), ("interval", "1d")])
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                self.ctx
+                    .client
+                    .get(&endpoint)
+                    .query(&[("range", "5d"), ("interval", "1d")]),
+                &self.ctx,
+            )
+            .await?;
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "Yahoo Finance returned {} for quote data: {}",
-                    status, body
-                )));
+                return Err(status_error(
+                    self.name(),
+                    "Yahoo Finance quote data",
+                    status,
+                    retry_after,
+                    Some(&symbol_upper),
+                    &body,
+                ));
             }
 
-            cache::write_json("yahoo", &cache_key, &body).await;
-            body
+            cache::write_json("yahoo", &cache_key, &body, &self.ctx).await;
+            (body, self.ctx.clock.now())
         };
 
         let payload: YahooChartEnvelope = serde_json::from_str(&body)
@@ -301,13 +706,41 @@ impl YahooFinance {
             return Ok(None);
         };
 
-        let mut closes = chart
+        let YahooChartQuote {
+            open,
+            high,
+            low,
+            close,
+        } = chart
             .indicators
             .quote
             .into_iter()
             .next()
-            .and_then(|quote| quote.close)
+            .unwrap_or_default();
+        let close = close.unwrap_or_default();
+        let bar_times: Vec<i64> = chart
+            .timestamp
+            .as_deref()
             .unwrap_or_default()
+            .iter()
+            .zip(&close)
+            .filter(|(_, value)| value.is_some_and(f64::is_finite))
+            .map(|(ts, _)| *ts)
+            .collect();
+        let change_basis = change_basis(chart.meta.instrument_type.as_deref(), &bar_times);
+
+        // Open/high/low come from the same daily bar as the latest close.
+        let last_bar = close
+            .iter()
+            .rposition(|value| value.is_some_and(f64::is_finite));
+        let bar_value = |series: Option<Vec<Option<f64>>>| {
+            last_bar
+                .and_then(|idx| series?.get(idx).copied().flatten())
+                .filter(|value| value.is_finite())
+        };
+        let (open_24h, high_24h, low_24h) = (bar_value(open), bar_value(high), bar_value(low));
+
+        let mut closes = close
             .into_iter()
             .flatten()
             .filter(|value| value.is_finite())
@@ -339,26 +772,59 @@ impl YahooFinance {
             })
             .filter(|value| value.is_finite());
 
-        let quote_currency = chart
-            .meta
-            .currency
-            .unwrap_or_else(|| requested_currency.to_string())
-            .to_uppercase();
+        let quote_currency = normalize_currency(
+            &chart
+                .meta
+                .currency
+                .unwrap_or_else(|| requested_currency.to_string()),
+        );
         let name = chart
             .meta
             .long_name
             .or(chart.meta.short_name)
             .unwrap_or_else(|| symbol_upper.clone());
+        // Crypto and currency pairs have no earnings or dividends to report.
+        let wants_fundamentals = self.options.fundamentals
+            && !chart.meta.instrument_type.as_deref().is_some_and(|kind| {
+                kind.eq_ignore_ascii_case("CRYPTOCURRENCY") || kind.eq_ignore_ascii_case("CURRENCY")
+            });
+        let summary = if self.options.market_cap_enrichment || wants_fundamentals {
+            self.fetch_optional_quote_summary(&symbol_upper).await
+        } else {
+            None
+        };
+        let market_cap = summary
+            .as_ref()
+            .filter(|_| self.options.market_cap_enrichment)
+            .and_then(summary_market_cap);
+        let fundamentals = summary
+            .as_ref()
+            .filter(|_| wants_fundamentals)
+            .map(summary_fundamentals)
+            .filter(|fundamentals| !fundamentals.is_empty());
 
         Ok(Some(CoinPrice {
             symbol: symbol_upper,
             name,
             price,
             change_24h,
-            market_cap: None,
+            market_cap,
+            open_24h,
+            high_24h,
+            low_24h,
+            market_state: chart
+                .meta
+                .market_state
+                .as_deref()
+                .and_then(parse_market_state),
+            change_basis: change_24h.is_some().then_some(change_basis),
+            rank: None,
+            fundamentals,
+            native_price: None,
+            native_currency: None,
             currency: quote_currency,
             provider: self.name().to_string(),
-            timestamp: chrono::Utc::now(),
+            timestamp: fetched_at,
         }))
     }
 
@@ -378,8 +844,17 @@ impl YahooFinance {
             .timestamp()
             .max(period1 + 1);
         let cache_key = format!(
-            "chart:{}:{}:{}:{}:{}",
-            self.base_url, symbol_upper, period1, period2, interval_param
+            "chart:{}:{}:{}:{}:{}{}",
+            self.base_url,
+            symbol_upper,
+            period1,
+            period2,
+            interval_param,
+            if self.options.chart_events {
+                ":events"
+            } else {
+                ""
+            }
         );
         let cache_ttl = if interval_param == "1h" {
             HOURLY_HISTORY_CACHE_TTL_SECS
@@ -396,24 +871,33 @@ impl YahooFinance {
         );
 
         let body = if let Some(cached_body) =
-            cache::read_json::<String>("yahoo", &cache_key, cache_ttl).await
+            cache::read_json::<String>("yahoo", &cache_key, cache_ttl, self.ctx.clock.as_ref())
+                .await
         {
             debug!(symbol = %symbol_upper, "using cached Yahoo chart response");
-            cached_body
+            cached_body.value
         } else {
-            let resp = self
-                .client
-                .get(&endpoint)
-                .query(&[
-                    ("period1", period1.to_string()),
-                    ("period2", period2.to_string()),
-                    ("interval", interval_param.to_string()),
-                ])
-                .send()
-                .await?;
-
-            let status = resp.status();
-            let body = resp.text().await?;
+            let HttpResponse {
+                status,
+                retry_after,
+                body,
+            } = send_with_retry(
+                self.name(),
+                {
+                    let request = self.ctx.client.get(&endpoint).query(&[
+                        ("period1", period1.to_string()),
+                        ("period2", period2.to_string()),
+                        ("interval", interval_param.to_string()),
+                    ]);
+                    if self.options.chart_events {
+                        request.query(&[("events", "div|split")])
+                    } else {
+                        request
+                    }
+                },
+                &self.ctx,
+            )
+            .await?;
 
             debug!(
                 status = %status,
@@ -424,13 +908,17 @@ impl YahooFinance {
             trace!(body = %body, symbol = %symbol_upper, "Yahoo chart response body");
 
             if !status.is_success() {
-                return Err(Error::Api(format!(
-                    "Yahoo Finance returned {} for chart data: {}",
-                    status, body
-                )));
+                return Err(status_error(
+                    self.name(),
+                    "Yahoo Finance chart data",
+                    status,
+                    retry_after,
+                    Some(&symbol_upper),
+                    &body,
+                ));
             }
 
-            cache::write_json("yahoo", &cache_key, &body).await;
+            cache::write_json("yahoo", &cache_key, &body, &self.ctx).await;
             body
         };
 
@@ -451,16 +939,25 @@ impl YahooFinance {
             .ok_or(Error::NoResults)?;
 
         let timestamps = chart.timestamp.unwrap_or_default();
-        let closes = chart
-            .indicators
-            .quote
-            .into_iter()
-            .next()
-            .and_then(|quote| quote.close)
+        let adjusted = self
+            .options
+            .adjusted_close
+            .then(|| chart.indicators.adjclose.into_iter().next())
+            .flatten()
+            .and_then(|adjclose| adjclose.adjclose);
+        let closes = adjusted
+            .or_else(|| {
+                chart
+                    .indicators
+                    .quote
+                    .into_iter()
+                    .next()
+                    .and_then(|quote| quote.close)
+            })
             .unwrap_or_default();
 
         let mut points = Vec::new();
-        for (ts, close) in timestamps.into_iter().zip(closes.into_iter()) {
+        for (ts, close) in timestamps.into_iter().zip(closes) {
             let Some(price) = close else {
                 continue;
             };
@@ -481,7 +978,11 @@ impl YahooFinance {
                 continue;
             }
 
-            points.push(PricePoint { timestamp, price });
+            points.push(PricePoint {
+                timestamp,
+                price,
+                filled: false,
+            });
         }
 
         points.sort_by_key(|point| point.timestamp);
@@ -489,16 +990,21 @@ impl YahooFinance {
             return Err(Error::NoResults);
         }
 
-        let currency = chart
-            .meta
-            .currency
-            .unwrap_or_else(|| requested_currency.to_string())
-            .to_uppercase();
+        let currency = normalize_currency(
+            &chart
+                .meta
+                .currency
+                .unwrap_or_else(|| requested_currency.to_string()),
+        );
         let name = chart
             .meta
             .long_name
             .or(chart.meta.short_name)
             .unwrap_or_else(|| symbol_upper.clone());
+        let events = chart
+            .events
+            .map(|events| history_events(events, start, end))
+            .unwrap_or_default();
 
         Ok(PriceHistory {
             symbol: symbol_upper,
@@ -506,10 +1012,42 @@ impl YahooFinance {
             currency,
             provider: self.name().to_string(),
             points,
+            events,
         })
     }
 }
 
+/// Dividends and splits within `start..=end`, oldest first.
+fn history_events(
+    events: YahooChartEvents,
+    start: Option<chrono::DateTime<chrono::Utc>>,
+    end: chrono::DateTime<chrono::Utc>,
+) -> Vec<HistoryEvent> {
+    let at = |date: i64| chrono::DateTime::<chrono::Utc>::from_timestamp(date, 0);
+    let dividends = events.dividends.into_values().filter_map(|dividend| {
+        Some(HistoryEvent::Dividend {
+            timestamp: at(dividend.date)?,
+            amount: dividend.amount,
+        })
+    });
+    let splits = events.splits.into_values().filter_map(|split| {
+        Some(HistoryEvent::Split {
+            timestamp: at(split.date)?,
+            numerator: split.numerator,
+            denominator: split.denominator,
+        })
+    });
+
+    let mut events: Vec<HistoryEvent> = dividends
+        .chain(splits)
+        .filter(|event| {
+            event.timestamp() <= end && start.is_none_or(|start| event.timestamp() >= start)
+        })
+        .collect();
+    events.sort_by_key(HistoryEvent::timestamp);
+    events
+}
+
 fn percent_change(previous: f64, current: f64) -> Option<f64> {
     if !previous.is_finite() || previous.abs() <= f64::EPSILON {
         return None;
@@ -518,6 +1056,77 @@ fn percent_change(previous: f64, current: f64) -> Option<f64> {
     Some(((current - previous) / previous) * 100.0)
 }
 
+fn raw_positive(value: &Option<YahooRawValue>) -> Option<f64> {
+    value
+        .as_ref()
+        .and_then(|v| v.raw)
+        .filter(|v| v.is_finite() && *v > 0.0)
+}
+
+/// Market cap from the `price` module, falling back to `summaryDetail`.
+fn summary_market_cap(summary: &YahooQuoteSummary) -> Option<f64> {
+    let price = summary
+        .price
+        .as_ref()
+        .and_then(|p| raw_positive(&p.market_cap));
+    price.or_else(|| {
+        let detail = summary.summary_detail.as_ref()?;
+        raw_positive(&detail.market_cap)
+    })
+}
+
+/// P/E, dividend yield (as a percent), and 52-week range from `quoteSummary`.
+///
+/// Funds report a distribution `yield` rather than `dividendYield`, in either
+/// `summaryDetail` or `defaultKeyStatistics`.
+fn summary_fundamentals(summary: &YahooQuoteSummary) -> Fundamentals {
+    let detail = summary.summary_detail.as_ref();
+    let fund_yield = summary
+        .key_statistics
+        .as_ref()
+        .and_then(|stats| raw_positive(&stats.fund_yield));
+    let dividend_yield = detail
+        .and_then(|d| raw_positive(&d.dividend_yield).or_else(|| raw_positive(&d.fund_yield)))
+        .or(fund_yield);
+
+    Fundamentals {
+        trailing_pe: detail.and_then(|d| raw_positive(&d.trailing_pe)),
+        dividend_yield: dividend_yield.map(|fraction| fraction * 100.0),
+        week_52_high: detail.and_then(|d| raw_positive(&d.fifty_two_week_high)),
+        week_52_low: detail.and_then(|d| raw_positive(&d.fifty_two_week_low)),
+    }
+}
+
+/// What the chart change is measured against: crypto trades around the clock, while
+/// other instruments compare with the previous session's close. Without an
+/// `instrumentType`, a gap of more than a day between the last two daily bars
+/// (a weekend or holiday) marks a session-based market.
+fn change_basis(instrument_type: Option<&str>, bar_times: &[i64]) -> ChangeBasis {
+    const MAX_DAILY_GAP_SECS: i64 = 25 * 60 * 60;
+
+    match instrument_type {
+        Some(kind) if kind.eq_ignore_ascii_case("CRYPTOCURRENCY") => ChangeBasis::Rolling24h,
+        Some(_) => ChangeBasis::PreviousClose,
+        None => match bar_times {
+            [.., previous, latest] if latest - previous > MAX_DAILY_GAP_SECS => {
+                ChangeBasis::PreviousClose
+            }
+            _ => ChangeBasis::Rolling24h,
+        },
+    }
+}
+
+/// Map Yahoo's `marketState` (`REGULAR`, `CLOSED`, `PRE`, `PREPRE`, `POST`, `POSTPOST`).
+fn parse_market_state(raw: &str) -> Option<MarketState> {
+    match raw.trim().to_ascii_uppercase().as_str() {
+        "REGULAR" => Some(MarketState::Open),
+        "CLOSED" => Some(MarketState::Closed),
+        "PRE" | "PREPRE" => Some(MarketState::PreMarket),
+        "POST" | "POSTPOST" => Some(MarketState::PostMarket),
+        _ => None,
+    }
+}
+
 fn chart_interval(
     interval: HistoryInterval,
     start: Option<chrono::DateTime<chrono::Utc>>,
@@ -0,0 +1,80 @@
+use std::future::Future;
+use std::time::Duration;
+
+use tokio::time::{self, MissedTickBehavior};
+
+/// ANSI sequence that clears the screen and moves the cursor to the top-left corner.
+pub const CLEAR_SCREEN: &str = "\x1b[2J\x1b[H";
+
+/// Call `refresh` every `period` until `max_refreshes` is reached or `stop` resolves.
+///
+/// The first refresh runs immediately; `refresh` receives the 1-based refresh number.
+/// Returns the number of refreshes performed.
+pub async fn run<F, Fut, S>(
+    period: Duration,
+    max_refreshes: Option<u64>,
+    stop: S,
+    mut refresh: F,
+) -> u64
+where
+    F: FnMut(u64) -> Fut,
+    Fut: Future<Output = ()>,
+    S: Future<Output = ()>,
+{
+    let mut ticker = time::interval(period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+    tokio::pin!(stop);
+
+    let mut count = 0;
+    while max_refreshes.is_none_or(|max| count < max) {
+        tokio::select! {
+            _ = &mut stop => break,
+            _ = ticker.tick() => {
+                count += 1;
+                refresh(count).await;
+            }
+        }
+    }
+
+    count
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::cell::RefCell;
+    use tokio::time::Instant;
+
+    #[tokio::test(start_paused = true)]
+    async fn refreshes_on_each_interval_until_count_reached() {
+        let start = Instant::now();
+        let ticks = RefCell::new(Vec::new());
+
+        let count = run(
+            Duration::from_secs(10),
+            Some(3),
+            std::future::pending(),
+            |_| {
+                ticks.borrow_mut().push(start.elapsed().as_secs());
+                async {}
+            },
+        )
+        .await;
+
+        assert_eq!(count, 3);
+        assert_eq!(ticks.into_inner(), vec![0, 10, 20]);
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stops_when_stop_future_resolves() {
+        let count = run(
+            Duration::from_secs(10),
+            None,
+            time::sleep(Duration::from_secs(25)),
+            |_| async {},
+        )
+        .await;
+
+        assert_eq!(count, 3);
+    }
+}
@@ -0,0 +1,365 @@
+mod common;
+
+use common::{pricr, write_coingecko_config};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn chart_labels_follow_the_requested_time_zone() {
+    let home = TempDir::new().unwrap();
+    let history = home.path().join("history.json");
+    std::fs::write(
+        &history,
+        serde_json::json!([{
+            "symbol": "BTC",
+            "name": "Bitcoin",
+            "currency": "USD",
+            "provider": "CoinGecko",
+            "points": [
+                { "timestamp": "2023-11-14T20:00:00Z", "price": 36000.0 },
+                { "timestamp": "2023-11-14T22:00:00Z", "price": 36500.0 }
+            ]
+        }])
+        .to_string(),
+    )
+    .unwrap();
+    let chart_with_tz = |tz: &str| {
+        let output = pricr(&home)
+            .arg("--chart")
+            .arg("--history-import")
+            .arg(&history)
+            .args(["--tz", tz])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        String::from_utf8(output.stdout).unwrap()
+    };
+
+    assert!(chart_with_tz("utc").contains("2023-11-14 22:00"));
+    assert!(chart_with_tz("Asia/Tokyo").contains("2023-11-15 07:00"));
+}
+
+#[test]
+fn points_downsamples_json_history_only_when_requested() {
+    let home = TempDir::new().unwrap();
+    let history = home.path().join("history.json");
+    let points: Vec<serde_json::Value> = (0..500)
+        .map(|hour| {
+            serde_json::json!({
+                "timestamp": chrono::DateTime::from_timestamp(1_700_000_000 + hour * 3600, 0).unwrap(),
+                "price": if hour == 321 { 90000.0 } else { 36000.0 + hour as f64 },
+            })
+        })
+        .collect();
+    std::fs::write(
+        &history,
+        serde_json::json!([{
+            "symbol": "BTC",
+            "name": "Bitcoin",
+            "currency": "USD",
+            "provider": "CoinGecko",
+            "points": points
+        }])
+        .to_string(),
+    )
+    .unwrap();
+    let json_points = |extra: &[&str]| {
+        let output = pricr(&home)
+            .args(["--chart", "--json", "--history-import"])
+            .arg(&history)
+            .args(extra)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        parsed[0]["points"].as_array().unwrap().clone()
+    };
+
+    assert_eq!(json_points(&[]).len(), 500);
+    let sampled = json_points(&["--points", "40"]);
+    assert_eq!(sampled.len(), 40);
+    assert!(sampled.iter().any(|p| p["price"] == 90000.0));
+
+    let thinned = json_points(&["--max-history-points", "100"]);
+    assert_eq!(thinned.len(), 100);
+    assert_eq!(thinned[99]["price"], 36499.0);
+
+    let sampled = json_points(&["--points", "40", "--max-history-points", "10"]);
+    assert_eq!(sampled.len(), 40);
+    assert!(sampled.iter().any(|p| p["price"] == 90000.0));
+
+    pricr(&home)
+        .args(["--chart", "--history-import"])
+        .arg(&history)
+        .args(["--points", "2"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn fill_marks_weekend_points_in_daily_json_history() {
+    let home = TempDir::new().unwrap();
+    let history = home.path().join("history.json");
+    std::fs::write(
+        &history,
+        serde_json::json!([{
+            "symbol": "EUR",
+            "name": "Euro",
+            "currency": "USD",
+            "provider": "Frankfurter",
+            "points": [
+                {"timestamp": "2025-03-06T00:00:00Z", "price": 1.07},
+                {"timestamp": "2025-03-07T00:00:00Z", "price": 1.08},
+                {"timestamp": "2025-03-10T00:00:00Z", "price": 1.09}
+            ]
+        }])
+        .to_string(),
+    )
+    .unwrap();
+    let json_points = |extra: &[&str]| {
+        let output = pricr(&home)
+            .args(["--chart", "--json", "--history-import"])
+            .arg(&history)
+            .args(extra)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        parsed[0]["points"].as_array().unwrap().clone()
+    };
+
+    let filled = json_points(&[]);
+    assert_eq!(filled.len(), 5);
+    assert_eq!(filled[2]["timestamp"], "2025-03-08T00:00:00Z");
+    assert_eq!(filled[2]["price"], 1.08);
+    assert_eq!(filled[2]["filled"], true);
+    assert!(filled[1].get("filled").is_none());
+
+    assert_eq!(json_points(&["--fill", "none"]).len(), 3);
+}
+
+#[test]
+fn overlay_draws_every_history_on_one_chart() {
+    let home = TempDir::new().unwrap();
+    let history = home.path().join("history.json");
+    let series = |symbol: &str, base: f64| {
+        serde_json::json!({
+            "symbol": symbol,
+            "name": symbol,
+            "currency": "USD",
+            "provider": "CoinGecko",
+            "points": [
+                {"timestamp": "2025-03-03T00:00:00Z", "price": base},
+                {"timestamp": "2025-03-04T00:00:00Z", "price": base * 1.1}
+            ]
+        })
+    };
+    std::fs::write(
+        &history,
+        serde_json::json!([series("BTC", 90000.0), series("ETH", 2500.0)]).to_string(),
+    )
+    .unwrap();
+
+    let output = pricr(&home)
+        .args(["--chart", "--overlay", "--history-import"])
+        .arg(&history)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout.matches("Price History").count(), 1);
+    assert!(stdout.contains("BTC / ETH Price History"));
+    assert!(stdout.contains("Legend: • BTC   ⣿ ETH"));
+
+    pricr(&home)
+        .args(["--chart", "--overlay", "--ascii", "--history-import"])
+        .arg(&history)
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn wide_history_format_aligns_series_on_shared_timestamps() {
+    let home = TempDir::new().unwrap();
+    let history = home.path().join("history.json");
+    std::fs::write(
+        &history,
+        serde_json::json!([
+            {
+                "symbol": "BTC",
+                "name": "Bitcoin",
+                "currency": "USD",
+                "provider": "CoinGecko",
+                "points": [
+                    {"timestamp": "2025-03-07T00:00:00Z", "price": 90000.0},
+                    {"timestamp": "2025-03-08T00:00:00Z", "price": 91000.0},
+                    {"timestamp": "2025-03-09T00:00:00Z", "price": 92000.0}
+                ]
+            },
+            {
+                "symbol": "AAPL",
+                "name": "Apple",
+                "currency": "USD",
+                "provider": "Yahoo",
+                "points": [
+                    {"timestamp": "2025-03-07T14:30:00Z", "price": 239.0}
+                ]
+            }
+        ])
+        .to_string(),
+    )
+    .unwrap();
+
+    let output = pricr(&home)
+        .args([
+            "--chart",
+            "--json",
+            "--history-format",
+            "wide",
+            "--fill",
+            "none",
+        ])
+        .arg("--history-import")
+        .arg(&history)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(
+        parsed["timestamps"],
+        serde_json::json!([
+            "2025-03-07T00:00:00Z",
+            "2025-03-08T00:00:00Z",
+            "2025-03-09T00:00:00Z"
+        ])
+    );
+    assert_eq!(parsed["columns"][0]["symbol"], "BTC");
+    assert_eq!(
+        parsed["columns"][1]["prices"],
+        serde_json::json!([239.0, null, null])
+    );
+
+    pricr(&home)
+        .args(["--chart", "--history-format", "wide", "--history-import"])
+        .arg(&history)
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn weekly_sampling_keeps_last_point_of_each_iso_week() {
+    let home = TempDir::new().unwrap();
+    let history = home.path().join("history.json");
+    let points: Vec<serde_json::Value> = (0..10)
+        .map(|day| {
+            serde_json::json!({
+                "timestamp": format!("2025-03-{:02}T16:00:00Z", 3 + day),
+                "price": 100.0 + day as f64,
+            })
+        })
+        .collect();
+    std::fs::write(
+        &history,
+        serde_json::json!([{
+            "symbol": "AAPL",
+            "name": "Apple",
+            "currency": "USD",
+            "provider": "Yahoo",
+            "points": points
+        }])
+        .to_string(),
+    )
+    .unwrap();
+
+    let output = pricr(&home)
+        .args([
+            "--chart",
+            "--json",
+            "--sampling",
+            "weekly",
+            "--history-import",
+        ])
+        .arg(&history)
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let parsed: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let weekly = parsed[0]["points"].as_array().unwrap();
+    assert_eq!(weekly.len(), 2);
+    assert_eq!(weekly[0]["timestamp"], "2025-03-09T00:00:00Z");
+    assert_eq!(weekly[0]["price"], 106.0);
+    assert_eq!(weekly[1]["timestamp"], "2025-03-12T16:00:00Z");
+    assert_eq!(weekly[1]["price"], 109.0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn stats_summarizes_the_history_window_instead_of_charting_it() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    let today = chrono::Utc::now().date_naive();
+    let close_ms = |days_ago: i64| {
+        (today - chrono::Duration::days(days_ago))
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis()
+    };
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/bitcoin/market_chart"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "prices": [[close_ms(2), 100.0], [close_ms(1), 110.0], [close_ms(0), 99.0]]
+        })))
+        .mount(&server)
+        .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--json", "--stats", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["symbol"], "BTC");
+    assert_eq!(json[0]["points"], 3);
+    assert_eq!(json[0]["min"], 99.0);
+    assert_eq!(json[0]["max"], 110.0);
+    assert!(json[0]["volatility"].as_f64().unwrap() > 0.0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn default_sampling_forward_fills_daily_gaps() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    let today = chrono::Utc::now().date_naive();
+    let close_ms = |days_ago: i64| {
+        (today - chrono::Duration::days(days_ago))
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis()
+    };
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/bitcoin/market_chart"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "prices": [[close_ms(4), 100.0], [close_ms(3), 110.0], [close_ms(1), 99.0]]
+        })))
+        .mount(&server)
+        .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--chart", "--json", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    let points = json[0]["points"].as_array().unwrap();
+    assert_eq!(points.len(), 4);
+    assert_eq!(points[2]["price"], 110.0);
+    assert_eq!(points[2]["filled"], true);
+}
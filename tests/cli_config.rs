@@ -0,0 +1,249 @@
+mod common;
+
+use common::{mount_simple_price, pricr};
+use tempfile::TempDir;
+use wiremock::matchers::{header, method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn version_prints_plain_string_or_json_summary() {
+    let home = TempDir::new().unwrap();
+
+    let plain = pricr(&home).arg("--version").output().unwrap();
+    assert_eq!(plain.status.code(), Some(0));
+    assert!(String::from_utf8_lossy(&plain.stdout).starts_with("pricr "));
+
+    let output = pricr(&home).args(["--version", "--json"]).output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let info: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(info["name"], "pricr");
+    assert!(info["version"].as_str().is_some_and(|v| !v.is_empty()));
+    let providers = info["providers"].as_array().unwrap();
+    assert!(providers.contains(&serde_json::json!("coingecko")));
+    assert!(providers.contains(&serde_json::json!("yahoo")));
+}
+
+#[test]
+fn free_only_drops_key_requiring_providers_even_with_a_key() {
+    let home = TempDir::new().unwrap();
+    let ids = |args: &[&str]| {
+        let output = pricr(&home)
+            .args(["--list-providers", "--json", "--api-key", "cmc-test-key"])
+            .args(args)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let providers: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        providers
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|p| p["id"].as_str().unwrap().to_string())
+            .collect::<Vec<_>>()
+    };
+
+    assert!(ids(&[]).contains(&"cmc".to_string()));
+    let free = ids(&["--free-only"]);
+    assert!(!free.contains(&"cmc".to_string()));
+    assert!(free.contains(&"coingecko".to_string()));
+    assert!(free.contains(&"yahoo".to_string()));
+
+    pricr(&home)
+        .args([
+            "--free-only",
+            "--api-key",
+            "cmc-test-key",
+            "-p",
+            "cmc",
+            "btc",
+        ])
+        .assert()
+        .code(2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn free_only_skips_key_requiring_providers_named_in_the_config() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })),
+    )
+    .await;
+    let config = home.path().join("pricr.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "[defaults]\nprovider_order = [\"cmc\", \"coingecko\"]\n\n[symbol_providers]\nbtc = \"cmc\"\n\n[coingecko]\nbase_url = \"{}/api/v3\"\n",
+            server.uri()
+        ),
+    )
+    .unwrap();
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--free-only", "--api-key", "cmc-test-key", "--json", "btc"])
+        .output()
+        .unwrap();
+
+    assert_eq!(
+        output.status.code(),
+        Some(0),
+        "{}",
+        String::from_utf8_lossy(&output.stderr)
+    );
+    let prices: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(prices[0]["provider"], "CoinGecko");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn configured_user_agent_is_sent_to_providers() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/simple/price"))
+        .and(header("user-agent", "my-dashboard/2.0"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })))
+        .mount(&server)
+        .await;
+    let config = home.path().join("pricr.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "[defaults]\nuser_agent = \"my-dashboard/2.0\"\n\n[coingecko]\nbase_url = \"{}/api/v3\"\n",
+            server.uri()
+        ),
+    )
+    .unwrap();
+
+    pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--json", "btc"])
+        .assert()
+        .code(0);
+}
+
+#[test]
+fn invalid_user_agent_is_a_config_error() {
+    let home = TempDir::new().unwrap();
+    let output = pricr(&home)
+        .env("PRICR_USER_AGENT", "bad\x01agent")
+        .args(["--provider", "stooq", "zzzz"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid user agent"));
+
+    let config = home.path().join("pricr.toml");
+    std::fs::write(&config, "[defaults]\nuser_agent = \"bad\\u0001agent\"\n").unwrap();
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "stooq", "zzzz"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("invalid user agent"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn currency_precedence_is_cli_then_env_then_config_file() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    for (currency, price) in [("eur", 1.0), ("gbp", 2.0), ("chf", 3.0)] {
+        Mock::given(method("GET"))
+            .and(path("/api/v3/simple/price"))
+            .and(query_param("vs_currencies", currency))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "bitcoin": { currency: price }
+            })))
+            .mount(&server)
+            .await;
+    }
+    let config = home.path().join("pricr.toml");
+    std::fs::write(
+        &config,
+        format!(
+            "[defaults]\ncurrency = \"eur\"\n\n[coingecko]\nbase_url = \"{}/api/v3\"\n",
+            server.uri()
+        ),
+    )
+    .unwrap();
+    let price_with = |env_currency: Option<&str>, cli_currency: Option<&str>| {
+        let mut cmd = pricr(&home);
+        cmd.arg("--config").arg(&config);
+        if let Some(currency) = env_currency {
+            cmd.env("PRICR_CURRENCY", currency);
+        }
+        if let Some(currency) = cli_currency {
+            cmd.args(["--currency", currency]);
+        }
+        let output = cmd
+            .args(["--provider", "coingecko", "--json", "btc"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let prices: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        prices[0]["price"].as_f64().unwrap()
+    };
+
+    assert_eq!(price_with(None, None), 1.0);
+    assert_eq!(price_with(Some("gbp"), None), 2.0);
+    assert_eq!(price_with(Some("gbp"), Some("chf")), 3.0);
+}
+
+#[test]
+fn prune_cache_keeps_newest_entries_per_provider() {
+    let home = TempDir::new().unwrap();
+    let cache_dir = home.path().join("cache").join("pricr").join("coingecko");
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    let base = std::time::SystemTime::now() - std::time::Duration::from_secs(3600);
+    for i in 0..5u64 {
+        let path = cache_dir.join(format!("{:064x}.json", i));
+        let file = std::fs::File::create(&path).unwrap();
+        file.set_modified(base + std::time::Duration::from_secs(i * 60))
+            .unwrap();
+    }
+    let config = home.path().join("pricr.toml");
+    std::fs::write(&config, "[cache]\nmax_entries = 2\n").unwrap();
+
+    pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .arg("--prune-cache")
+        .assert()
+        .code(0);
+
+    let mut remaining: Vec<String> = std::fs::read_dir(&cache_dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    remaining.sort();
+    assert_eq!(
+        remaining,
+        vec![format!("{:064x}.json", 3), format!("{:064x}.json", 4)]
+    );
+}
+
+#[test]
+fn startup_removes_cache_files_from_the_old_key_scheme_once() {
+    let home = TempDir::new().unwrap();
+    let cache_dir = home.path().join("cache").join("pricr").join("coingecko");
+    std::fs::create_dir_all(&cache_dir).unwrap();
+    let legacy = cache_dir.join("00000000000000ff.json");
+    let current = cache_dir.join(format!("{:064x}.json", 1));
+    std::fs::write(&legacy, "{}").unwrap();
+    std::fs::write(&current, "{}").unwrap();
+
+    pricr(&home).arg("--list-providers").assert().code(0);
+
+    assert!(!legacy.exists());
+    assert!(current.exists());
+}
@@ -0,0 +1,286 @@
+mod common;
+
+use common::{mount_simple_price, pricr, write_coingecko_config};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[test]
+fn help_lists_exit_codes() {
+    let home = TempDir::new().unwrap();
+    let output = pricr(&home).arg("--help").output().unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let help = String::from_utf8(output.stdout).unwrap();
+    assert!(help.contains("Exit codes:"));
+    assert!(help.contains("1  Generic error"));
+    assert!(help.contains("3  No results returned for the request"));
+    assert!(help.contains("5  Partial results under --strict or --quiet"));
+    assert!(help.contains("--price-alert threshold not reached"));
+    assert!(!help.contains("Unexpected internal error"), "{help}");
+    assert!(help.contains("1 if not"), "{help}");
+}
+
+#[test]
+fn unreadable_history_import_exits_with_generic_code() {
+    let home = TempDir::new().unwrap();
+
+    pricr(&home)
+        .arg("--chart")
+        .arg("--history-import")
+        .arg(home.path().join("missing.json"))
+        .assert()
+        .code(1);
+}
+
+#[test]
+fn unknown_flag_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+
+    pricr(&home).arg("--no-such-flag").assert().code(2);
+}
+
+#[test]
+fn unknown_provider_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+
+    pricr(&home)
+        .args(["--provider", "nope", "btc"])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn category_with_non_coingecko_provider_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+
+    let output = pricr(&home)
+        .args(["--category", "layer-1", "--provider", "yahoo"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("--category"));
+}
+
+#[test]
+fn unknown_time_zone_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+
+    let output = pricr(&home)
+        .args(["--tz", "Mars/Olympus", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(2));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Europe/Paris"));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn successful_lookup_exits_zero() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--json", "btc"])
+        .assert()
+        .code(0);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn empty_provider_response_exits_with_no_results_code() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({})),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "btc"])
+        .assert()
+        .code(3);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn provider_server_error_exits_with_provider_failure_code() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(500).set_body_string("internal error"),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "btc"])
+        .assert()
+        .code(4);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn rejected_api_key_exits_with_provider_failure_code() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(401).set_body_string("invalid api key"),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(4));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("authentication failed"), "{stderr}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn strict_partial_results_exit_with_partial_code() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    let response = ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "bitcoin": { "usd": 50000.0 }
+    }));
+    mount_simple_price(&server, "bitcoin,ethereum", response).await;
+    let config = write_coingecko_config(&home, &server);
+
+    pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--json", "btc", "eth"])
+        .assert()
+        .code(0);
+
+    pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--json",
+            "--strict",
+            "btc",
+            "eth",
+        ])
+        .assert()
+        .code(5);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn quiet_lookup_exits_nonzero_when_a_symbol_is_missing() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    let response = ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "bitcoin": { "usd": 50000.0 }
+    }));
+    mount_simple_price(&server, "bitcoin,ethereum", response).await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--raw", "btc", "eth"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(5));
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "50000\n");
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn crypto_quote_asset_on_fiat_only_provider_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+
+    let output = pricr(&home)
+        .args(["--provider", "yahoo", "--quote-asset", "btc", "aapl"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("'BTC' as a quote currency"), "{stderr}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn info_for_unknown_symbol_suggests_matches_and_exits_with_no_results_code() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/bitcon"))
+        .respond_with(ResponseTemplate::new(404).set_body_string("coin not found"))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search"))
+        .and(query_param("query", "BITCON"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "coins": [{ "symbol": "BTC", "name": "Bitcoin", "market_cap_rank": 1 }]
+        })))
+        .mount(&server)
+        .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "info", "bitcon"])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(3));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("BITCON"), "{stderr}");
+    assert!(stderr.contains("BTC"), "{stderr}");
+}
+
+#[test]
+fn unknown_inline_provider_pin_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+    let output = pricr(&home).args(["kraken:btc", "eth"]).output().unwrap();
+
+    assert_eq!(output.status.code(), Some(2));
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("unknown provider 'kraken'"), "{stderr}");
+    assert!(stderr.contains("valid providers: coingecko"), "{stderr}");
+}
+
+#[test]
+fn prune_cache_without_limit_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+
+    pricr(&home).arg("--prune-cache").assert().code(2);
+}
+
+#[test]
+fn invalid_json_path_exits_with_usage_code() {
+    let home = TempDir::new().unwrap();
+
+    pricr(&home)
+        .args(["--json", "--json-path", "$[", "btc"])
+        .assert()
+        .code(2);
+}
@@ -0,0 +1,309 @@
+mod common;
+
+use common::{mount_simple_price, pricr, write_coingecko_config};
+use tempfile::TempDir;
+use wiremock::{MockServer, ResponseTemplate};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn csv_output_honors_custom_delimiter() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.5 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--csv",
+            "--csv-delimiter",
+            ";",
+            "btc",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let mut lines = stdout.lines();
+    assert_eq!(
+        lines.next(),
+        Some("symbol;name;price;currency;change_24h;market_cap;provider;timestamp")
+    );
+    assert!(
+        lines
+            .next()
+            .unwrap()
+            .starts_with("BTC;Bitcoin;50000.5;USD;"),
+        "{stdout}"
+    );
+
+    pricr(&home)
+        .args([
+            "--csv",
+            "--csv-delimiter",
+            "|",
+            "--csv-quote-char",
+            "|",
+            "btc",
+        ])
+        .assert()
+        .code(2);
+}
+
+#[test]
+fn csv_rejects_a_delimiter_matching_the_quote_char_or_a_line_break() {
+    let home = TempDir::new().unwrap();
+
+    for args in [
+        ["--csv-delimiter", "\""],
+        ["--csv-delimiter", "\n"],
+        ["--csv-quote-char", "\r"],
+    ] {
+        let output = pricr(&home)
+            .arg("--csv")
+            .args(args)
+            .arg("btc")
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(2), "{args:?}");
+        assert!(
+            String::from_utf8_lossy(&output.stderr)
+                .contains("--csv-delimiter and --csv-quote-char"),
+            "{args:?}"
+        );
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn symbol_case_lower_rewrites_json_symbols() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--json",
+            "--symbol-case",
+            "lower",
+        ])
+        .arg("btc")
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert!(stdout.contains(r#""symbol": "btc""#), "{stdout}");
+}
+
+#[test]
+fn symbol_case_lower_rewrites_history_json_symbols() {
+    let home = TempDir::new().unwrap();
+    let history = home.path().join("history.json");
+    std::fs::write(
+        &history,
+        serde_json::json!([{
+            "symbol": "BTC",
+            "name": "Bitcoin",
+            "currency": "USD",
+            "provider": "CoinGecko",
+            "points": [
+                {"timestamp": "2025-03-06T00:00:00Z", "price": 90000.0},
+                {"timestamp": "2025-03-07T00:00:00Z", "price": 91000.0}
+            ]
+        }])
+        .to_string(),
+    )
+    .unwrap();
+    let symbols = |extra: &[&str]| {
+        let output = pricr(&home)
+            .args([
+                "--chart",
+                "--json",
+                "--symbol-case",
+                "lower",
+                "--history-import",
+            ])
+            .arg(&history)
+            .args(extra)
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        serde_json::from_slice::<serde_json::Value>(&output.stdout).unwrap()
+    };
+
+    assert_eq!(symbols(&[])[0]["symbol"], "btc");
+    assert_eq!(symbols(&["--stats"])[0]["symbol"], "btc");
+    assert_eq!(
+        symbols(&["--history-format", "wide"])["columns"][0]["symbol"],
+        "btc"
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn json_compact_prints_a_single_line() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin,ethereum",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 },
+            "ethereum": { "usd": 2500.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--json", "--json-compact"])
+        .args(["btc", "eth"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    let line = stdout.strip_suffix('\n').unwrap();
+    assert!(!line.contains('\n'), "{stdout}");
+    assert_eq!(
+        serde_json::from_str::<serde_json::Value>(line)
+            .unwrap()
+            .as_array()
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn piped_table_output_drops_borders_and_colors_unless_requested() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0, "usd_24h_change": 1.5 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let piped = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(piped.status.code(), Some(0));
+    let stdout = String::from_utf8(piped.stdout).unwrap();
+    assert!(!stdout.contains('\u{1b}'));
+    assert!(!stdout.contains(['╭', '│']));
+    let row = stdout.lines().find(|line| line.contains("BTC")).unwrap();
+    assert!(row.split_whitespace().any(|cell| cell == "+1.50%"));
+
+    let forced = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--format",
+            "table",
+            "--color",
+            "always",
+            "btc",
+        ])
+        .output()
+        .unwrap();
+    assert_eq!(forced.status.code(), Some(0));
+    let stdout = String::from_utf8(forced.stdout).unwrap();
+    assert!(stdout.contains('\u{1b}'));
+    assert!(stdout.contains('╭'));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn quiet_lookup_prints_only_awk_parseable_prices() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    let response = ResponseTemplate::new(200).set_body_json(serde_json::json!({
+        "bitcoin": { "usd": 97000.5 },
+        "ethereum": { "usd": 0.0000123 }
+    }));
+    mount_simple_price(&server, "ethereum,bitcoin", response).await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--quiet", "eth", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    assert!(output.stderr.is_empty());
+    let stdout = String::from_utf8(output.stdout).unwrap();
+    assert_eq!(stdout, "0.0000123\n97000.5\n");
+
+    let mut awk = std::process::Command::new("awk")
+        .arg("{print $1+0 == $0}")
+        .stdin(std::process::Stdio::piped())
+        .stdout(std::process::Stdio::piped())
+        .spawn()
+        .unwrap();
+    std::io::Write::write_all(&mut awk.stdin.take().unwrap(), stdout.as_bytes()).unwrap();
+    let parsed = awk.wait_with_output().unwrap();
+    assert_eq!(String::from_utf8_lossy(&parsed.stdout), "1\n1\n");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn json_path_filters_json_output() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--json",
+            "--json-path",
+            "$[*].price",
+            "btc",
+        ])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let filtered: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(filtered, serde_json::json!([50000.0]));
+}
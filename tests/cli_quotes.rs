@@ -0,0 +1,304 @@
+mod common;
+
+use common::{mount_simple_price, pricr, write_coingecko_config};
+use tempfile::TempDir;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+#[tokio::test(flavor = "multi_thread")]
+async fn price_alert_exits_zero_when_triggered_and_six_otherwise() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 102000.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let alert = |spec: &str| {
+        pricr(&home)
+            .arg("--config")
+            .arg(&config)
+            .args(["--provider", "coingecko", "--price-alert", spec])
+            .output()
+            .unwrap()
+    };
+
+    let output = alert("BTC:ABOVE:100000");
+    assert_eq!(output.status.code(), Some(0));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        "ALERT: BTC is $102,000.00 (above threshold $100,000.00)"
+    );
+
+    let output = alert("btc:below:100000");
+    assert_eq!(output.status.code(), Some(1));
+    assert_eq!(
+        String::from_utf8(output.stdout).unwrap().trim(),
+        "OK: BTC is $102,000.00 (threshold $100,000.00 not reached)"
+    );
+
+    pricr(&home)
+        .args(["--price-alert", "BTC:OVER:1"])
+        .assert()
+        .code(2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn historical_compare_pairs_a_past_close_with_todays_price() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    let date1 = chrono::Utc::now().date_naive() - chrono::Duration::days(10);
+    let close_ms = |offset: i64| {
+        (date1 + chrono::Duration::days(offset))
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis()
+    };
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/bitcoin/market_chart"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "prices": [[close_ms(-1), 39000.0], [close_ms(0), 40000.0], [close_ms(1), 41000.0]]
+        })))
+        .mount(&server)
+        .await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--json", "--historical-compare"])
+        .arg(date1.to_string())
+        .args(["today", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["symbol"], "BTC");
+    assert_eq!(json[0]["date1"], date1.to_string());
+    assert_eq!(json[0]["price1"], 40000.0);
+    assert_eq!(json[0]["price2"], 50000.0);
+    assert_eq!(json[0]["change"], 10000.0);
+    assert_eq!(json[0]["change_pct"], 25.0);
+
+    pricr(&home)
+        .args(["--historical-compare", "2999-01-01", "today", "btc"])
+        .assert()
+        .code(2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn period_replaces_the_change_with_the_change_over_that_period() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    let today = chrono::Utc::now().date_naive();
+    let close_ms = |days_ago: i64| {
+        (today - chrono::Duration::days(days_ago))
+            .and_hms_opt(0, 0, 0)
+            .unwrap()
+            .and_utc()
+            .timestamp_millis()
+    };
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/bitcoin/market_chart"))
+        .and(query_param("days", "14"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "prices": [[close_ms(9), 30000.0], [close_ms(7), 40000.0], [close_ms(1), 48000.0]]
+        })))
+        .mount(&server)
+        .await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0, "usd_24h_change": 1.5 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args(["--provider", "coingecko", "--json", "--period", "7d", "btc"])
+        .output()
+        .unwrap();
+    assert_eq!(output.status.code(), Some(0));
+    let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(json[0]["change_24h"], 25.0);
+    assert_eq!(json[0]["change_basis"], "7d");
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--format",
+            "plain",
+            "--period",
+            "7d",
+            "btc",
+        ])
+        .output()
+        .unwrap();
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("7d Change"), "{stdout}");
+    assert!(stdout.contains("+25.00%"), "{stdout}");
+
+    pricr(&home)
+        .args(["--period", "2w", "btc"])
+        .assert()
+        .code(2);
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn convert_leaves_quotes_already_in_the_requested_currency_alone() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    for flag in ["--convert", "--quote-currency-auto"] {
+        let output = pricr(&home)
+            .arg("--config")
+            .arg(&config)
+            .args(["--provider", "coingecko", "--json", flag, "btc"])
+            .output()
+            .unwrap();
+        assert_eq!(output.status.code(), Some(0));
+        let json: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+        assert_eq!(json[0]["price"], 50000.0);
+        assert_eq!(json[0]["provider"], "CoinGecko");
+        assert!(json[0].get("native_price").is_none(), "{json}");
+    }
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn exclude_stablecoins_drops_stablecoin_quotes() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin,tether",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 },
+            "tether": { "usd": 1.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--json",
+            "--json-path",
+            "$[*].symbol",
+            "--exclude-stablecoins",
+            "btc",
+            "usdt",
+        ])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let symbols: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(symbols, serde_json::json!(["BTC"]));
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn watch_mode_filters_fetched_stablecoin_quotes() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin,tether",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 },
+            "tether": { "usd": 1.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--watch",
+            "1",
+            "--watch-count",
+            "1",
+            "--exclude-stablecoins",
+            "btc",
+            "usdt",
+        ])
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("BTC"), "{stdout}");
+    assert!(!stdout.contains("USDT"), "{stdout}");
+}
+
+#[tokio::test(flavor = "multi_thread")]
+async fn dash_positional_reads_symbols_from_stdin() {
+    let home = TempDir::new().unwrap();
+    let server = MockServer::start().await;
+    mount_simple_price(
+        &server,
+        "bitcoin,ethereum",
+        ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 },
+            "ethereum": { "usd": 2500.0 }
+        })),
+    )
+    .await;
+    let config = write_coingecko_config(&home, &server);
+
+    let output = pricr(&home)
+        .arg("--config")
+        .arg(&config)
+        .args([
+            "--provider",
+            "coingecko",
+            "--json",
+            "--json-path",
+            "$[*].symbol",
+            "-",
+        ])
+        .write_stdin("# majors\nbtc\n\neth # second\n")
+        .output()
+        .unwrap();
+
+    assert_eq!(output.status.code(), Some(0));
+    let symbols: serde_json::Value = serde_json::from_slice(&output.stdout).unwrap();
+    assert_eq!(symbols, serde_json::json!(["BTC", "ETH"]));
+}
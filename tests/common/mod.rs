@@ -0,0 +1,48 @@
+//! Helpers shared by the CLI integration tests.
+//!
+//! Each test binary uses only some of them.
+#![allow(dead_code)]
+
+use assert_cmd::Command;
+use tempfile::TempDir;
+use wiremock::matchers::{method, path, query_param};
+use wiremock::{Mock, MockServer, ResponseTemplate};
+
+/// Build a `pricr` command isolated from the user's config, cache, and API keys.
+pub fn pricr(home: &TempDir) -> Command {
+    let mut cmd = Command::cargo_bin("pricr").unwrap();
+    cmd.current_dir(home.path())
+        .env("HOME", home.path())
+        .env("XDG_CONFIG_HOME", home.path().join("config"))
+        .env("XDG_CACHE_HOME", home.path().join("cache"))
+        .env_remove("COINMARKETCAP_API_KEY")
+        .env_remove("COINGECKO_API_KEY")
+        .env_remove("PRICR_CURRENCY")
+        .env_remove("PRICR_PROVIDER_ORDER")
+        .env_remove("PRICR_DEFAULT_CHART_INTERVAL")
+        .env_remove("PRICR_DATE_FORMAT")
+        .env_remove("PRICR_USER_AGENT")
+        .env_remove("PRICR_MAX_RETRY_AFTER_SECS")
+        .env_remove("PRICR_CACHE_MAX_ENTRIES")
+        .env_remove("RUST_LOG");
+    cmd
+}
+
+pub fn write_coingecko_config(home: &TempDir, server: &MockServer) -> std::path::PathBuf {
+    let path = home.path().join("pricr.toml");
+    std::fs::write(
+        &path,
+        format!("[coingecko]\nbase_url = \"{}/api/v3\"\n", server.uri()),
+    )
+    .unwrap();
+    path
+}
+
+pub async fn mount_simple_price(server: &MockServer, ids: &str, response: ResponseTemplate) {
+    Mock::given(method("GET"))
+        .and(path("/api/v3/simple/price"))
+        .and(query_param("ids", ids))
+        .respond_with(response)
+        .mount(server)
+        .await;
+}
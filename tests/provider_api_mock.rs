@@ -1,10 +1,15 @@
+use std::sync::Arc;
+
+use pricr::clock::FixedClock;
 use pricr::error::Error;
-use pricr::provider::coingecko::CoinGecko;
+use pricr::provider::coingecko::{ApiKey, ApiTier, CoinGecko};
 use pricr::provider::coinmarketcap::CoinMarketCap;
+use pricr::provider::context::ProviderContext;
 use pricr::provider::frankfurter::Frankfurter;
+use pricr::provider::mexc::Mexc;
 use pricr::provider::stooq::Stooq;
 use pricr::provider::yahoo::YahooFinance;
-use pricr::provider::{HistoryInterval, PriceProvider};
+use pricr::provider::{ChangeBasis, HistoryEvent, HistoryInterval, MarketState, PriceProvider};
 use wiremock::matchers::{header, method, path, query_param};
 use wiremock::{Mock, MockServer, ResponseTemplate};
 
@@ -57,24 +62,159 @@ async fn coingecko_provider_fetches_and_parses_mocked_response() {
 }
 
 #[tokio::test]
-async fn coingecko_provider_returns_api_error_on_non_success_status() {
+async fn coingecko_provider_prices_in_crypto_quote_asset() {
     let server = MockServer::start().await;
+    let response = serde_json::json!({
+        "ethereum": {
+            "btc": 0.0512,
+            "btc_24h_change": 0.8,
+            "btc_market_cap": 6150000.0
+        }
+    });
 
     Mock::given(method("GET"))
         .and(path("/api/v3/simple/price"))
+        .and(query_param("ids", "ethereum"))
+        .and(query_param("vs_currencies", "btc"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .mount(&server)
+        .await;
+
+    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    let symbols = vec!["eth".to_string()];
+    let prices = provider.get_prices(&symbols, "BTC").await.unwrap();
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].symbol, "ETH");
+    assert!((prices[0].price - 0.0512).abs() < f64::EPSILON);
+    assert_eq!(prices[0].change_24h, Some(0.8));
+    assert_eq!(prices[0].market_cap, Some(6150000.0));
+    assert_eq!(prices[0].currency, "BTC");
+}
+
+#[tokio::test]
+async fn coingecko_provider_returns_rate_limited_on_429() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/rate-limited/api/v3/simple/price"))
         .and(query_param("ids", "bitcoin"))
         .and(query_param("vs_currencies", "usd"))
         .and(query_param("include_24hr_change", "true"))
         .and(query_param("include_market_cap", "true"))
-        .respond_with(ResponseTemplate::new(429).set_body_string("rate limited"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "30")
+                .set_body_string("rate limited"),
+        )
         .mount(&server)
         .await;
 
-    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    // Distinct base path so a cached body from another test on a reused port is not hit.
+    let provider = CoinGecko::with_base_url(format!("{}/rate-limited/api/v3", server.uri()));
     let symbols = vec!["btc".to_string()];
     let result = provider.get_prices(&symbols, "usd").await;
 
-    assert!(matches!(result, Err(Error::Api(ref msg)) if msg.contains("429")));
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited { ref provider, retry_after: Some(30) }) if provider == "CoinGecko"
+    ));
+}
+
+#[tokio::test]
+async fn coingecko_provider_retries_once_after_short_retry_after() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/retry/api/v3/simple/price"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "1")
+                .set_body_string("<html>Too Many Requests</html>"),
+        )
+        .up_to_n_times(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/retry/api/v3/simple/price"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 }
+        })))
+        .mount(&server)
+        .await;
+
+    // Distinct base path so a cached body from another test on a reused port is not hit.
+    let provider = CoinGecko::with_base_url(format!("{}/retry/api/v3", server.uri()));
+    let symbols = vec!["btc".to_string()];
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    assert_eq!(prices.len(), 1);
+    assert!((prices[0].price - 50000.0).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn zero_retry_after_cap_fails_rate_limited_requests_immediately() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/no-retry/api/v3/simple/price"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Retry-After", "1")
+                .set_body_string("rate limited"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = CoinGecko::configured(
+        Some(format!("{}/no-retry/api/v3", server.uri())),
+        None,
+        ProviderContext::new().with_max_retry_after_secs(0),
+    );
+    let result = provider.get_prices(&["btc".to_string()], "usd").await;
+
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited {
+            retry_after: Some(1),
+            ..
+        })
+    ));
+}
+
+#[tokio::test]
+async fn retry_after_dates_are_measured_from_the_response_date() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/retry-date/api/v3/simple/price"))
+        .respond_with(
+            ResponseTemplate::new(429)
+                .insert_header("Date", "Sun, 02 Jun 2024 12:00:00 GMT")
+                .insert_header("Retry-After", "Sun, 02 Jun 2024 12:01:00 GMT")
+                .set_body_string("rate limited"),
+        )
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = CoinGecko::configured(
+        Some(format!("{}/retry-date/api/v3", server.uri())),
+        None,
+        ProviderContext::new()
+            .with_max_retry_after_secs(0)
+            .with_clock(Arc::new(FixedClock::at(2024, 6, 1, 12, 0, 0))),
+    );
+    let result = provider.get_prices(&["btc".to_string()], "usd").await;
+
+    assert!(matches!(
+        result,
+        Err(Error::RateLimited {
+            retry_after: Some(60),
+            ..
+        })
+    ));
 }
 
 #[tokio::test]
@@ -266,6 +406,46 @@ async fn coinmarketcap_provider_resolves_coin_id_from_coin_catalog() {
     assert!((history[0].points[0].price - 443.12).abs() < f64::EPSILON);
 }
 
+#[tokio::test]
+async fn coingecko_provider_sends_configured_api_key_header() {
+    let server = MockServer::start().await;
+    let response = serde_json::json!({ "bitcoin": { "usd": 50000.0 } });
+
+    Mock::given(method("GET"))
+        .and(path("/demo-key/api/v3/simple/price"))
+        .and(header("x-cg-demo-api-key", "CG-demo"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/pro-key/api/v3/simple/price"))
+        .and(header("x-cg-pro-api-key", "CG-pro"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(&response))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    for (prefix, key, tier) in [
+        ("demo-key", "CG-demo", ApiTier::Demo),
+        ("pro-key", "CG-pro", ApiTier::Pro),
+    ] {
+        let provider = CoinGecko::configured(
+            Some(format!("{}/{prefix}/api/v3", server.uri())),
+            Some(ApiKey {
+                key: key.to_string(),
+                tier,
+            }),
+            ProviderContext::new(),
+        );
+        let prices = provider
+            .get_prices(&["btc".to_string()], "usd")
+            .await
+            .expect("keyed request should match the header mock");
+        assert_eq!(prices[0].price, 50000.0);
+    }
+}
+
 #[tokio::test]
 async fn frankfurter_provider_fetches_history_for_fiat_chart_mode() {
     let server = MockServer::start().await;
@@ -300,12 +480,123 @@ async fn frankfurter_provider_fetches_history_for_fiat_chart_mode() {
     assert_eq!(history[0].points.len(), 2);
 }
 
+#[tokio::test]
+async fn frankfurter_history_window_ends_on_the_injected_clock_date() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/clock/v1/2024-05-26..2024-06-02"))
+        .and(query_param("from", "USD"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "amount": 1.0,
+            "base": "USD",
+            "start_date": "2024-05-27",
+            "end_date": "2024-05-31",
+            "rates": {
+                "2024-05-30": { "EUR": 0.92 },
+                "2024-05-31": { "EUR": 0.93 }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = Frankfurter::with_base_url_and_context(
+        format!("{}/clock/v1", server.uri()),
+        ProviderContext::new().with_clock(Arc::new(FixedClock::at(2024, 6, 2, 23, 59, 0))),
+    )
+    .with_weekend_fill(true);
+    let history = provider
+        .get_history("usd", &["eur".to_string()], 7)
+        .await
+        .expect("history window should follow the fixed clock");
+
+    let dates: Vec<String> = history[0]
+        .points
+        .iter()
+        .map(|p| p.timestamp.date_naive().to_string())
+        .collect();
+    assert_eq!(
+        dates,
+        ["2024-05-30", "2024-05-31", "2024-06-01", "2024-06-02"]
+    );
+}
+
+#[tokio::test]
+async fn frankfurter_provider_returns_cross_rates_for_non_eur_pairs() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/cross/v1/latest"))
+        .and(query_param("from", "GBP"))
+        .and(query_param("to", "JPY"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "amount": 1.0,
+            "base": "GBP",
+            "date": "2026-02-20",
+            "rates": { "JPY": 191.42 }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = Frankfurter::with_base_url(format!("{}/cross/v1", server.uri()));
+    let rates = provider
+        .get_rates("gbp", &["jpy".to_string()])
+        .await
+        .expect("GBP->JPY cross rate should parse");
+
+    assert_eq!(rates.len(), 1);
+    let rate = rates["JPY"];
+    assert!(
+        rate > 100.0 && rate < 400.0,
+        "unexpected GBP/JPY rate {rate}"
+    );
+
+    let single = provider
+        .get_rate("gbp", "jpy")
+        .await
+        .expect("single pair should resolve");
+    assert!((single - 191.42).abs() < f64::EPSILON);
+    assert_eq!(provider.get_rate("gbp", "GBP").await.unwrap(), 1.0);
+}
+
+#[tokio::test]
+async fn currency_listings_parse_frankfurter_and_coingecko_responses() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/currencies/v1/currencies"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "EUR": "Euro",
+            "ISK": "Icelandic Króna"
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/currencies/api/v3/simple/supported_vs_currencies"))
+        .respond_with(
+            ResponseTemplate::new(200).set_body_json(serde_json::json!(["btc", "usd", "EUR"])),
+        )
+        .mount(&server)
+        .await;
+
+    let fiat = Frankfurter::with_base_url(format!("{}/currencies/v1", server.uri()))
+        .get_currencies()
+        .await
+        .expect("Frankfurter currencies should parse");
+    assert_eq!(fiat.len(), 2);
+    assert_eq!(fiat["ISK"], "Icelandic Króna");
+
+    let provider = CoinGecko::with_base_url(format!("{}/currencies/api/v3", server.uri()));
+    let quotes = provider
+        .get_quote_currencies()
+        .await
+        .expect("CoinGecko vs_currencies should parse");
+    assert_eq!(quotes, vec!["btc", "usd", "eur"]);
+}
+
 #[tokio::test]
 async fn coingecko_provider_returns_parse_error_on_malformed_json() {
     let server = MockServer::start().await;
 
     Mock::given(method("GET"))
-        .and(path("/api/v3/simple/price"))
+        .and(path("/malformed/api/v3/simple/price"))
         .and(query_param("ids", "bitcoin"))
         .and(query_param("vs_currencies", "usd"))
         .and(query_param("include_24hr_change", "true"))
@@ -314,7 +605,8 @@ async fn coingecko_provider_returns_parse_error_on_malformed_json() {
         .mount(&server)
         .await;
 
-    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    // Distinct base path so a cached body from another test on a reused port is not hit.
+    let provider = CoinGecko::with_base_url(format!("{}/malformed/api/v3", server.uri()));
     let symbols = vec!["btc".to_string()];
     let result = provider.get_prices(&symbols, "usd").await;
 
@@ -342,6 +634,27 @@ async fn coingecko_provider_returns_no_results_when_response_is_empty() {
     assert!(matches!(result, Err(Error::NoResults)));
 }
 
+#[tokio::test]
+async fn coingecko_provider_skips_coins_without_a_price_in_the_currency() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/unpriced/api/v3/simple/price"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "usd": 50000.0 },
+            "ethereum": {}
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = CoinGecko::with_base_url(format!("{}/unpriced/api/v3", server.uri()));
+    let symbols = vec!["btc".to_string(), "eth".to_string()];
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].symbol, "BTC");
+}
+
 #[tokio::test]
 async fn coinmarketcap_provider_fetches_and_parses_mocked_response() {
     let server = MockServer::start().await;
@@ -408,137 +721,625 @@ async fn coinmarketcap_provider_fetches_and_parses_mocked_response() {
 }
 
 #[tokio::test]
-async fn coinmarketcap_provider_returns_api_error_on_non_success_status() {
+async fn coingecko_provider_fetches_top_listings_by_market_cap() {
     let server = MockServer::start().await;
+    let response = serde_json::json!([
+        {
+            "id": "bitcoin",
+            "symbol": "btc",
+            "name": "Bitcoin",
+            "current_price": 50000.0,
+            "market_cap": 980000000000.0,
+            "market_cap_rank": 1,
+            "price_change_percentage_24h": 1.5,
+            "high_24h": 51000.0,
+            "low_24h": 49000.0
+        },
+        {
+            "id": "ethereum",
+            "symbol": "eth",
+            "name": "Ethereum",
+            "current_price": 3000.0,
+            "market_cap": 360000000000.0,
+            "market_cap_rank": 2,
+            "price_change_percentage_24h": -0.5,
+            "high_24h": null,
+            "low_24h": null
+        }
+    ]);
 
     Mock::given(method("GET"))
-        .and(path("/v1/cryptocurrency/quotes/latest"))
-        .and(query_param("symbol", "BTC"))
-        .and(query_param("convert", "USD"))
-        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
-        .respond_with(ResponseTemplate::new(500).set_body_string("internal error"))
+        .and(path("/api/v3/coins/markets"))
+        .and(query_param("vs_currency", "eur"))
+        .and(query_param("order", "market_cap_desc"))
+        .and(query_param("per_page", "2"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
         .mount(&server)
         .await;
 
-    let provider =
-        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
-    let symbols = vec!["btc".to_string()];
-    let result = provider.get_prices(&symbols, "usd").await;
+    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    let listings = provider.get_top_listings(2, "EUR").await.unwrap();
+
+    assert_eq!(listings.len(), 2);
+    assert_eq!(listings[0].symbol, "BTC");
+    assert_eq!(listings[0].rank, Some(1));
+    assert_eq!(listings[0].market_cap, Some(980000000000.0));
+    assert_eq!(listings[0].change_24h, Some(1.5));
+    assert_eq!(listings[0].high_24h, Some(51000.0));
+    assert_eq!(listings[0].currency, "EUR");
+    assert_eq!(listings[1].symbol, "ETH");
+    assert_eq!(listings[1].rank, Some(2));
+}
 
-    assert!(matches!(result, Err(Error::Api(ref msg)) if msg.contains("500")));
+#[tokio::test]
+async fn coingecko_provider_lists_coins_in_a_category() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/category/api/v3/coins/markets"))
+        .and(query_param("category", "meme-token"))
+        .and(query_param("vs_currency", "usd"))
+        .and(query_param("per_page", "3"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "dogecoin",
+                "symbol": "doge",
+                "name": "Dogecoin",
+                "current_price": 0.16,
+                "market_cap": 23500000000.0,
+                "market_cap_rank": 8,
+                "price_change_percentage_24h": 3.2
+            },
+            {
+                "id": "pepe",
+                "symbol": "pepe",
+                "name": "Pepe",
+                "current_price": 0.0000101,
+                "market_cap": 4250000000.0,
+                "market_cap_rank": null,
+                "price_change_percentage_24h": -1.1
+            },
+            {
+                "id": "delisted-meme",
+                "symbol": "gone",
+                "name": "Gone",
+                "current_price": null
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let provider = CoinGecko::with_base_url(format!("{}/category/api/v3", server.uri()));
+    let listings = provider
+        .get_category_listings(" Meme-Token ", 3, "USD")
+        .await
+        .expect("category listings should parse");
+
+    let rows: Vec<(&str, Option<u32>)> = listings
+        .iter()
+        .map(|p| (p.symbol.as_str(), p.rank))
+        .collect();
+    assert_eq!(rows, [("DOGE", Some(8)), ("PEPE", Some(2))]);
+    assert_eq!(listings[1].currency, "USD");
+
+    Mock::given(method("GET"))
+        .and(path("/category/api/v3/coins/markets"))
+        .and(query_param("category", "no-such-category"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([])))
+        .mount(&server)
+        .await;
+    assert!(matches!(
+        provider
+            .get_category_listings("no-such-category", 3, "usd")
+            .await,
+        Err(Error::NoResults)
+    ));
 }
 
 #[tokio::test]
-async fn coinmarketcap_provider_returns_parse_error_on_malformed_json() {
+async fn coingecko_provider_fetches_ohlc_from_coin_markets() {
     let server = MockServer::start().await;
+    let response = serde_json::json!([
+        {
+            "id": "bitcoin",
+            "symbol": "btc",
+            "name": "Bitcoin",
+            "current_price": 50000.0,
+            "market_cap": 980000000000.0,
+            "market_cap_rank": 1,
+            "price_change_24h": 1000.0,
+            "price_change_percentage_24h": 2.04,
+            "high_24h": 51000.0,
+            "low_24h": 48500.0
+        }
+    ]);
 
     Mock::given(method("GET"))
-        .and(path("/v1/cryptocurrency/quotes/latest"))
-        .and(query_param("symbol", "BTC"))
-        .and(query_param("convert", "USD"))
-        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
-        .respond_with(ResponseTemplate::new(200).set_body_string("{broken-json"))
+        .and(path("/ohlc/api/v3/coins/markets"))
+        .and(query_param("vs_currency", "usd"))
+        .and(query_param("ids", "bitcoin,ethereum"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
         .mount(&server)
         .await;
 
-    let provider =
-        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
-    let symbols = vec!["btc".to_string()];
-    let result = provider.get_prices(&symbols, "usd").await;
+    let provider = CoinGecko::with_base_url(format!("{}/ohlc/api/v3", server.uri()));
+    let symbols = vec!["btc".to_string(), "eth".to_string()];
+    let prices = provider.get_ohlc(&symbols, "USD").await.unwrap();
 
-    assert!(matches!(result, Err(Error::Parse(ref msg)) if msg.contains("CMC JSON")));
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].symbol, "BTC");
+    assert_eq!(prices[0].name, "Bitcoin");
+    assert_eq!(prices[0].rank, None);
+    assert_eq!(prices[0].open_24h, Some(49000.0));
+    assert_eq!(prices[0].high_24h, Some(51000.0));
+    assert_eq!(prices[0].low_24h, Some(48500.0));
+    assert!((prices[0].price - 50000.0).abs() < f64::EPSILON);
+    assert_eq!(prices[0].currency, "USD");
 }
 
 #[tokio::test]
-async fn coinmarketcap_provider_returns_no_results_when_response_has_no_data() {
+async fn coingecko_provider_fetches_coin_info() {
     let server = MockServer::start().await;
     let response = serde_json::json!({
-        "status": {
-            "error_message": null
+        "id": "bitcoin",
+        "symbol": "btc",
+        "name": "Bitcoin",
+        "categories": ["Cryptocurrency", "Layer 1 (L1)", null],
+        "genesis_date": "2009-01-03",
+        "market_cap_rank": 1,
+        "description": {
+            "en": "Bitcoin is the first <a href=\"https://www.coingecko.com/\">decentralized</a> digital currency.\r\n\r\nMore history."
         },
-        "data": {}
+        "links": {
+            "homepage": ["http://www.bitcoin.org", "", ""],
+            "blockchain_site": ["https://mempool.space/", "", "https://blockchair.com/bitcoin/"]
+        },
+        "market_data": {
+            "ath": { "usd": 73738.0, "eur": 67000.0 },
+            "ath_date": { "usd": "2024-03-14T07:10:36.635Z" },
+            "atl": { "usd": 67.81 },
+            "atl_date": { "usd": "2013-07-06T00:00:00.000Z" },
+            "circulating_supply": 19700000.0,
+            "total_supply": 21000000.0
+        }
     });
 
     Mock::given(method("GET"))
-        .and(path("/v1/cryptocurrency/quotes/latest"))
-        .and(query_param("symbol", "BTC"))
-        .and(query_param("convert", "USD"))
-        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
+        .and(path("/api/v3/coins/bitcoin"))
+        .and(query_param("localization", "false"))
+        .and(query_param("tickers", "false"))
         .respond_with(ResponseTemplate::new(200).set_body_json(response))
         .mount(&server)
         .await;
 
-    let provider =
-        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
-    let symbols = vec!["btc".to_string()];
-    let result = provider.get_prices(&symbols, "usd").await;
-
-    assert!(matches!(result, Err(Error::NoResults)));
+    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    let info = provider.get_info("btc", "USD").await.unwrap();
+
+    assert_eq!(info.symbol, "BTC");
+    assert_eq!(info.name, "Bitcoin");
+    assert_eq!(info.rank, Some(1));
+    assert_eq!(info.categories, vec!["Cryptocurrency", "Layer 1 (L1)"]);
+    assert_eq!(
+        info.genesis_date,
+        chrono::NaiveDate::from_ymd_opt(2009, 1, 3)
+    );
+    assert_eq!(info.homepage.as_deref(), Some("http://www.bitcoin.org"));
+    assert_eq!(
+        info.explorers,
+        vec!["https://mempool.space/", "https://blockchair.com/bitcoin/"]
+    );
+    assert_eq!(info.circulating_supply, Some(19700000.0));
+    assert_eq!(info.total_supply, Some(21000000.0));
+    assert_eq!(info.ath, Some(73738.0));
+    assert_eq!(
+        info.ath_date.map(|d| d.date_naive()),
+        chrono::NaiveDate::from_ymd_opt(2024, 3, 14)
+    );
+    assert_eq!(info.atl, Some(67.81));
+    assert_eq!(
+        info.description.as_deref(),
+        Some("Bitcoin is the first decentralized digital currency.")
+    );
+    assert_eq!(info.currency, "USD");
+    assert_eq!(info.provider, "CoinGecko");
 }
 
 #[tokio::test]
-async fn stooq_provider_fetches_and_parses_mocked_response() {
+async fn coingecko_provider_fetches_coin_details() {
     let server = MockServer::start().await;
-    let aapl_response = "AAPL.US,20260220,220019,190.00,194.10,189.70,193.80,42070499,";
-    let msft_response = "MSFT.US,20260220,220019,420.00,427.00,418.40,425.77,34015249,";
+    let response = serde_json::json!({
+        "id": "bitcoin",
+        "symbol": "btc",
+        "name": "Bitcoin",
+        "categories": ["Cryptocurrency", null, "Proof of Work (PoW)"],
+        "genesis_date": "2009-01-03",
+        "market_cap_rank": 1,
+        "description": {
+            "en": "Bitcoin is the first <a href=\"https://www.coingecko.com/\">decentralized</a> digital currency.\r\n\r\nMore history."
+        },
+        "links": {
+            "homepage": ["http://www.bitcoin.org", "", ""],
+            "repos_url": {
+                "github": ["https://github.com/bitcoin/bitcoin", "", "https://github.com/bitcoin/bips"],
+                "bitbucket": []
+            }
+        },
+        "market_data": {}
+    });
 
     Mock::given(method("GET"))
-        .and(path("/q/l/"))
-        .and(query_param("s", "aapl.us"))
-        .and(query_param("i", "d"))
-        .respond_with(ResponseTemplate::new(200).set_body_string(aapl_response))
+        .and(path("/details/api/v3/coins/bitcoin"))
+        .and(query_param("localization", "false"))
+        .and(query_param("tickers", "false"))
+        .and(query_param("market_data", "true"))
+        .and(query_param("community_data", "false"))
+        .and(query_param("developer_data", "false"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
         .mount(&server)
         .await;
 
+    // Distinct base path so a cached body from another test on a reused port is not hit.
+    let provider = CoinGecko::with_base_url(format!("{}/details/api/v3", server.uri()));
+    let details = provider.get_coin_details("btc").await.unwrap();
+
+    assert_eq!(details.symbol, "BTC");
+    assert_eq!(details.name, "Bitcoin");
+    assert_eq!(
+        details.description,
+        "Bitcoin is the first decentralized digital currency."
+    );
+    assert_eq!(details.homepage, vec!["http://www.bitcoin.org"]);
+    assert_eq!(
+        details.github,
+        vec![
+            "https://github.com/bitcoin/bitcoin",
+            "https://github.com/bitcoin/bips"
+        ]
+    );
+    assert_eq!(
+        details.categories,
+        vec!["Cryptocurrency", "Proof of Work (PoW)"]
+    );
+    assert_eq!(
+        details.genesis_date,
+        chrono::NaiveDate::from_ymd_opt(2009, 1, 3)
+    );
+    assert_eq!(details.market_cap_rank, Some(1));
+}
+
+#[tokio::test]
+async fn coingecko_provider_reports_unknown_coin_info_as_symbol_not_found() {
+    let server = MockServer::start().await;
     Mock::given(method("GET"))
-        .and(path("/q/l/"))
-        .and(query_param("s", "msft.us"))
-        .and(query_param("i", "d"))
-        .respond_with(ResponseTemplate::new(200).set_body_string(msft_response))
+        .and(path("/api/v3/coins/notacoin"))
+        .respond_with(
+            ResponseTemplate::new(404)
+                .set_body_json(serde_json::json!({ "error": "coin not found" })),
+        )
         .mount(&server)
         .await;
 
-    let provider = Stooq::with_base_url(server.uri());
-    let symbols = vec!["aapl".to_string(), "msft".to_string()];
-    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
-
-    assert_eq!(prices.len(), 2);
-    assert_eq!(prices[0].symbol, "AAPL");
-    assert_eq!(prices[0].name, "AAPL");
-    assert!((prices[0].price - 193.80).abs() < f64::EPSILON);
-    assert!((prices[0].change_24h.unwrap() - 2.0).abs() < 1e-6);
-    assert_eq!(prices[0].market_cap, None);
-    assert_eq!(prices[0].currency, "USD");
-    assert_eq!(prices[0].provider, "Stooq");
+    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    let result = provider.get_info("notacoin", "usd").await;
 
-    assert_eq!(prices[1].symbol, "MSFT");
-    assert_eq!(prices[1].name, "MSFT");
-    assert!((prices[1].price - 425.77).abs() < f64::EPSILON);
-    assert!((prices[1].change_24h.unwrap() - 1.3738095238095238).abs() < 1e-9);
-    assert_eq!(prices[1].market_cap, None);
-    assert_eq!(prices[1].currency, "USD");
-    assert_eq!(prices[1].provider, "Stooq");
+    assert!(matches!(result, Err(Error::SymbolNotFound { .. })));
 }
 
 #[tokio::test]
-async fn stooq_provider_fetches_history_for_chart_mode() {
+async fn coingecko_provider_searches_coins_with_market_cap_rank() {
     let server = MockServer::start().await;
-    let response = "Date,Open,High,Low,Close,Volume\n2026-02-18,190.0,194.1,189.7,193.8,42070499\n2026-02-19,193.8,195.0,191.0,192.5,39000000\n2026-02-20,192.5,196.2,192.0,195.7,41000000\n";
-
     Mock::given(method("GET"))
-        .and(path("/q/d/l/"))
-        .and(query_param("s", "aapl.us"))
-        .and(query_param("i", "d"))
-        .respond_with(ResponseTemplate::new(200).set_body_string(response))
+        .and(path("/api/v3/search"))
+        .and(query_param("query", "bit"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "coins": [
+                { "id": "bitcoin", "name": "Bitcoin", "symbol": "BTC", "market_cap_rank": 1 },
+                { "id": "bittensor", "name": "Bittensor", "symbol": "TAO", "market_cap_rank": 30 },
+                { "id": "bitfake", "name": "Bitfake", "symbol": "bfk", "market_cap_rank": null }
+            ],
+            "exchanges": []
+        })))
         .mount(&server)
         .await;
 
-    let provider = Stooq::with_base_url(server.uri());
-    let symbols = vec!["aapl".to_string()];
-    let history = provider
-        .get_price_history(&symbols, "usd", 30, HistoryInterval::Daily)
-        .await
-        .expect("history should parse");
+    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    let matches = provider.search_tickers("bit", 10).await.unwrap();
+
+    assert_eq!(matches.len(), 3);
+    assert_eq!(matches[0].symbol, "BTC");
+    assert_eq!(matches[0].rank, Some(1));
+    assert_eq!(matches[1].rank, Some(30));
+    assert_eq!(matches[2].symbol, "BFK");
+    assert_eq!(matches[2].rank, None);
+    assert_eq!(matches[2].provider, "CoinGecko");
+}
+
+#[tokio::test]
+async fn coingecko_provider_quotes_trending_coins_in_trending_order() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/search/trending"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "coins": [
+                { "item": { "id": "pepe", "symbol": "PEPE", "name": "Pepe", "market_cap_rank": 40 } },
+                { "item": { "id": "solana", "symbol": "SOL", "name": "Solana", "market_cap_rank": 5 } }
+            ]
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/coins/markets"))
+        .and(query_param("vs_currency", "eur"))
+        .and(query_param("ids", "pepe,solana"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!([
+            {
+                "id": "solana", "symbol": "sol", "name": "Solana",
+                "current_price": 140.0, "market_cap": 6.5e10, "market_cap_rank": 5,
+                "price_change_percentage_24h": 4.0
+            },
+            {
+                "id": "pepe", "symbol": "pepe", "name": "Pepe",
+                "current_price": 0.00001, "market_cap": 4.0e9, "market_cap_rank": 40,
+                "price_change_percentage_24h": 12.5
+            }
+        ])))
+        .mount(&server)
+        .await;
+
+    let provider = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    let trending = provider.get_trending("EUR").await.unwrap();
+
+    assert_eq!(trending.len(), 2);
+    assert_eq!(trending[0].symbol, "PEPE");
+    assert_eq!(trending[0].change_24h, Some(12.5));
+    assert_eq!(trending[0].currency, "EUR");
+    assert_eq!(trending[1].symbol, "SOL");
+    assert_eq!(trending[1].rank, Some(5));
+}
+
+#[tokio::test]
+async fn coinmarketcap_provider_fetches_trending_with_api_key() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v1/cryptocurrency/trending/latest"))
+        .and(query_param("convert", "USD"))
+        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": { "error_message": null },
+            "data": [
+                {
+                    "name": "Pepe",
+                    "symbol": "PEPE",
+                    "cmc_rank": 40,
+                    "quote": { "USD": { "price": 0.00001, "percent_change_24h": 12.5, "market_cap": 4.0e9 } }
+                }
+            ]
+        })))
+        .mount(&server)
+        .await;
+
+    let provider =
+        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
+    let trending = provider.get_trending("usd").await.unwrap();
+
+    assert_eq!(trending.len(), 1);
+    assert_eq!(trending[0].symbol, "PEPE");
+    assert_eq!(trending[0].rank, Some(40));
+    assert_eq!(trending[0].change_24h, Some(12.5));
+}
+
+#[tokio::test]
+async fn coinmarketcap_provider_fetches_top_listings_with_api_key() {
+    let server = MockServer::start().await;
+    let response = serde_json::json!({
+        "status": { "error_message": null },
+        "data": [
+            {
+                "name": "Bitcoin",
+                "symbol": "BTC",
+                "cmc_rank": 1,
+                "quote": {
+                    "USD": { "price": 50000.0, "percent_change_24h": 2.0, "market_cap": 1.0e12 }
+                }
+            },
+            {
+                "name": "Ethereum",
+                "symbol": "ETH",
+                "cmc_rank": 2,
+                "quote": {
+                    "USD": { "price": 3000.0, "percent_change_24h": -1.0, "market_cap": 3.6e11 }
+                }
+            }
+        ]
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cryptocurrency/listings/latest"))
+        .and(query_param("limit", "2"))
+        .and(query_param("convert", "USD"))
+        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .mount(&server)
+        .await;
+
+    let provider =
+        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
+    let listings = provider.get_top_listings(2, "usd").await.unwrap();
+
+    assert_eq!(listings.len(), 2);
+    assert_eq!(listings[0].symbol, "BTC");
+    assert_eq!(listings[0].rank, Some(1));
+    assert_eq!(listings[1].symbol, "ETH");
+    assert_eq!(listings[1].market_cap, Some(3.6e11));
+    assert_eq!(listings[1].provider, "CoinMarketCap");
+
+    let err = CoinMarketCap::without_key()
+        .get_top_listings(2, "usd")
+        .await
+        .unwrap_err();
+    assert!(matches!(err, Error::MissingApiKey { .. }));
+}
+
+#[tokio::test]
+async fn coinmarketcap_provider_returns_api_error_on_non_success_status() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cryptocurrency/quotes/latest"))
+        .and(query_param("symbol", "BTC"))
+        .and(query_param("convert", "USD"))
+        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
+        .respond_with(ResponseTemplate::new(500).set_body_string("internal error"))
+        .mount(&server)
+        .await;
+
+    let provider =
+        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
+    let symbols = vec!["btc".to_string()];
+    let result = provider.get_prices(&symbols, "usd").await;
+
+    assert!(matches!(result, Err(Error::Api(ref msg)) if msg.contains("500")));
+}
+
+#[tokio::test]
+async fn coinmarketcap_provider_returns_auth_failed_on_401() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cryptocurrency/quotes/latest"))
+        .and(query_param("symbol", "ETH"))
+        .and(query_param("convert", "USD"))
+        .respond_with(ResponseTemplate::new(401).set_body_string("invalid api key"))
+        .mount(&server)
+        .await;
+
+    let provider =
+        CoinMarketCap::with_base_url("bad-api-key".to_string(), format!("{}/v1", server.uri()));
+    let symbols = vec!["eth".to_string()];
+    let result = provider.get_prices(&symbols, "usd").await;
+
+    assert!(matches!(
+        result,
+        Err(Error::AuthFailed { ref provider, ref detail })
+            if provider == "CoinMarketCap" && detail.contains("401")
+    ));
+}
+
+#[tokio::test]
+async fn coinmarketcap_provider_returns_parse_error_on_malformed_json() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/malformed/v1/cryptocurrency/quotes/latest"))
+        .and(query_param("symbol", "BTC"))
+        .and(query_param("convert", "USD"))
+        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_string("{broken-json"))
+        .mount(&server)
+        .await;
+
+    // Distinct base path so a cached body from another test on a reused port is not hit.
+    let provider = CoinMarketCap::with_base_url(
+        "test-api-key".to_string(),
+        format!("{}/malformed/v1", server.uri()),
+    );
+    let symbols = vec!["btc".to_string()];
+    let result = provider.get_prices(&symbols, "usd").await;
+
+    assert!(matches!(result, Err(Error::Parse(ref msg)) if msg.contains("CMC JSON")));
+}
+
+#[tokio::test]
+async fn coinmarketcap_provider_returns_no_results_when_response_has_no_data() {
+    let server = MockServer::start().await;
+    let response = serde_json::json!({
+        "status": {
+            "error_message": null
+        },
+        "data": {}
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/v1/cryptocurrency/quotes/latest"))
+        .and(query_param("symbol", "BTC"))
+        .and(query_param("convert", "USD"))
+        .and(header("X-CMC_PRO_API_KEY", "test-api-key"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .mount(&server)
+        .await;
+
+    let provider =
+        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
+    let symbols = vec!["btc".to_string()];
+    let result = provider.get_prices(&symbols, "usd").await;
+
+    assert!(matches!(result, Err(Error::NoResults)));
+}
+
+#[tokio::test]
+async fn stooq_provider_fetches_and_parses_mocked_response() {
+    let server = MockServer::start().await;
+    let aapl_response = "AAPL.US,20260220,220019,190.00,194.10,189.70,193.80,42070499,";
+    let msft_response = "MSFT.US,20260220,220019,420.00,427.00,418.40,425.77,34015249,";
+
+    Mock::given(method("GET"))
+        .and(path("/q/l/"))
+        .and(query_param("s", "aapl.us"))
+        .and(query_param("i", "d"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(aapl_response))
+        .mount(&server)
+        .await;
+
+    Mock::given(method("GET"))
+        .and(path("/q/l/"))
+        .and(query_param("s", "msft.us"))
+        .and(query_param("i", "d"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(msft_response))
+        .mount(&server)
+        .await;
+
+    let provider = Stooq::with_base_url(server.uri());
+    let symbols = vec!["aapl".to_string(), "msft".to_string()];
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[0].symbol, "AAPL");
+    assert_eq!(prices[0].name, "AAPL");
+    assert!((prices[0].price - 193.80).abs() < f64::EPSILON);
+    assert!((prices[0].change_24h.unwrap() - 2.0).abs() < 1e-6);
+    assert_eq!(prices[0].market_cap, None);
+    assert_eq!(prices[0].high_24h, Some(194.10));
+    assert_eq!(prices[0].low_24h, Some(189.70));
+    assert_eq!(prices[0].currency, "USD");
+    assert_eq!(prices[0].provider, "Stooq");
+
+    assert_eq!(prices[1].symbol, "MSFT");
+    assert_eq!(prices[1].name, "MSFT");
+    assert!((prices[1].price - 425.77).abs() < f64::EPSILON);
+    assert!((prices[1].change_24h.unwrap() - 1.3738095238095238).abs() < 1e-9);
+    assert_eq!(prices[1].market_cap, None);
+    assert_eq!(prices[1].currency, "USD");
+    assert_eq!(prices[1].provider, "Stooq");
+}
+
+#[tokio::test]
+async fn stooq_provider_fetches_history_for_chart_mode() {
+    let server = MockServer::start().await;
+    let response = "Date,Open,High,Low,Close,Volume\n2026-02-18,190.0,194.1,189.7,193.8,42070499\n2026-02-19,193.8,195.0,191.0,192.5,39000000\n2026-02-20,192.5,196.2,192.0,195.7,41000000\n";
+
+    Mock::given(method("GET"))
+        .and(path("/q/d/l/"))
+        .and(query_param("s", "aapl.us"))
+        .and(query_param("i", "d"))
+        .respond_with(ResponseTemplate::new(200).set_body_string(response))
+        .mount(&server)
+        .await;
+
+    let provider = Stooq::with_base_url(server.uri());
+    let symbols = vec!["aapl".to_string()];
+    let history = provider
+        .get_price_history(&symbols, "usd", 30, HistoryInterval::Daily)
+        .await
+        .expect("history should parse");
 
     assert_eq!(history.len(), 1);
     assert_eq!(history[0].symbol, "AAPL");
@@ -591,6 +1392,38 @@ async fn stooq_provider_searches_tickers() {
     assert_eq!(matches[0].provider, "Stooq");
 }
 
+#[tokio::test]
+async fn stooq_provider_routes_search_to_search_base_url() {
+    let quote_server = MockServer::start().await;
+    let search_server = MockServer::start().await;
+    let response = serde_json::json!({
+        "quotes": [
+            {
+                "symbol": "TSLA",
+                "shortname": "Tesla, Inc.",
+                "exchDisp": "NASDAQ",
+                "typeDisp": "Equity"
+            }
+        ]
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/v1/finance/search"))
+        .and(query_param("q", "tesla"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .expect(1)
+        .mount(&search_server)
+        .await;
+
+    let provider = Stooq::with_base_urls(quote_server.uri(), search_server.uri());
+    let matches = provider.search_tickers("tesla", 3).await.unwrap();
+
+    assert_eq!(matches.len(), 1);
+    assert_eq!(matches[0].symbol, "TSLA");
+    assert!(quote_server.received_requests().await.unwrap().is_empty());
+    assert_eq!(search_server.received_requests().await.unwrap().len(), 1);
+}
+
 #[tokio::test]
 async fn yahoo_provider_fetches_and_parses_mocked_response() {
     let server = MockServer::start().await;
@@ -602,7 +1435,8 @@ async fn yahoo_provider_fetches_and_parses_mocked_response() {
                         "currency": "EUR",
                         "shortName": "Amundi MSCI World Swap UCITS ET",
                         "regularMarketPrice": 618.12,
-                        "chartPreviousClose": 614.56
+                        "chartPreviousClose": 614.56,
+                        "marketState": "POSTPOST"
                     },
                     "timestamp": [1735689600_i64, 1735776000_i64],
                     "indicators": {
@@ -636,10 +1470,425 @@ async fn yahoo_provider_fetches_and_parses_mocked_response() {
     assert!((prices[0].price - 618.12).abs() < f64::EPSILON);
     assert!((prices[0].change_24h.unwrap() - 0.5792762301484085).abs() < 1e-12);
     assert_eq!(prices[0].market_cap, None);
+    assert_eq!(prices[0].market_state, Some(MarketState::PostMarket));
     assert_eq!(prices[0].currency, "EUR");
     assert_eq!(prices[0].provider, "Yahoo Finance");
 }
 
+#[tokio::test]
+async fn yahoo_provider_labels_change_basis_from_instrument_type_or_bar_gap() {
+    let server = MockServer::start().await;
+    let chart = |meta: serde_json::Value, timestamps: serde_json::Value| {
+        serde_json::json!({
+            "chart": {
+                "result": [{
+                    "meta": meta,
+                    "timestamp": timestamps,
+                    "indicators": { "quote": [{ "close": [100.0, 101.0] }] }
+                }],
+                "error": null
+            }
+        })
+    };
+    // Friday 2025-01-03 and Monday 2025-01-06: the change spans a weekend.
+    let weekend = serde_json::json!([1735891200_i64, 1736150400_i64]);
+    let mocks = [
+        (
+            "AAPL",
+            chart(
+                serde_json::json!({ "regularMarketPrice": 101.0 }),
+                weekend.clone(),
+            ),
+        ),
+        (
+            "BTC-USD",
+            chart(
+                serde_json::json!({ "regularMarketPrice": 101.0, "instrumentType": "CRYPTOCURRENCY" }),
+                weekend,
+            ),
+        ),
+        (
+            "MSFT",
+            chart(
+                serde_json::json!({ "regularMarketPrice": 101.0, "instrumentType": "EQUITY" }),
+                serde_json::json!([1735776000_i64, 1735862400_i64]),
+            ),
+        ),
+    ];
+    for (symbol, response) in mocks {
+        Mock::given(method("GET"))
+            .and(path(format!("/change-basis/v8/finance/chart/{}", symbol)))
+            .respond_with(ResponseTemplate::new(200).set_body_json(response))
+            .mount(&server)
+            .await;
+    }
+
+    // Distinct base path so a cached body from another test on a reused port is not hit.
+    let provider = YahooFinance::with_base_url(format!("{}/change-basis", server.uri()));
+    let symbols = vec![
+        "aapl".to_string(),
+        "btc-usd".to_string(),
+        "msft".to_string(),
+    ];
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    let basis = |symbol: &str| {
+        prices
+            .iter()
+            .find(|p| p.symbol == symbol)
+            .and_then(|p| p.change_basis)
+    };
+    assert_eq!(basis("AAPL"), Some(ChangeBasis::PreviousClose));
+    assert_eq!(basis("BTC-USD"), Some(ChangeBasis::Rolling24h));
+    assert_eq!(basis("MSFT"), Some(ChangeBasis::PreviousClose));
+}
+
+#[tokio::test]
+async fn yahoo_provider_parses_market_state() {
+    let server = MockServer::start().await;
+    let cases = [
+        ("AAPL", Some("REGULAR"), Some(MarketState::Open)),
+        ("MSFT", Some("CLOSED"), Some(MarketState::Closed)),
+        ("NVDA", Some("PRE"), Some(MarketState::PreMarket)),
+        ("AMZN", None, None),
+    ];
+
+    for (symbol, state, _) in &cases {
+        let mut meta = serde_json::json!({
+            "currency": "USD",
+            "regularMarketPrice": 100.0
+        });
+        if let Some(state) = state {
+            meta["marketState"] = serde_json::json!(state);
+        }
+        Mock::given(method("GET"))
+            // Distinct prefix keeps the quote cache from colliding with other tests.
+            .and(path(format!("/market-state/v8/finance/chart/{symbol}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "chart": {
+                    "result": [{
+                        "meta": meta,
+                        "timestamp": [1735689600_i64],
+                        "indicators": { "quote": [{ "close": [100.0] }] }
+                    }],
+                    "error": null
+                }
+            })))
+            .mount(&server)
+            .await;
+    }
+
+    let provider = YahooFinance::with_base_url(format!("{}/market-state", server.uri()));
+    let symbols: Vec<String> = cases.iter().map(|(s, _, _)| s.to_lowercase()).collect();
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    assert_eq!(prices.len(), cases.len());
+    for (price, (symbol, _, expected)) in prices.iter().zip(&cases) {
+        assert_eq!(price.symbol, *symbol);
+        assert_eq!(price.market_state, *expected, "{symbol}");
+    }
+}
+
+#[tokio::test]
+async fn yahoo_provider_parses_ohlc_from_latest_bar() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        // Distinct prefix keeps the quote cache from colliding with other tests.
+        .and(path("/ohlc/v8/finance/chart/AAPL"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "chart": {
+                "result": [{
+                    "meta": { "currency": "USD", "regularMarketPrice": 193.8 },
+                    "timestamp": [1735689600_i64, 1735776000_i64, 1735862400_i64],
+                    "indicators": {
+                        "quote": [{
+                            "open": [188.0, 190.0, null],
+                            "high": [189.5, 194.1, null],
+                            "low": [187.2, 189.7, null],
+                            "close": [189.0, 193.8, null]
+                        }]
+                    }
+                }],
+                "error": null
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = YahooFinance::with_base_url(format!("{}/ohlc", server.uri()));
+    let symbols = vec!["aapl".to_string()];
+    let prices = provider.get_ohlc(&symbols, "usd").await.unwrap();
+
+    assert_eq!(prices.len(), 1);
+    assert_eq!(prices[0].open_24h, Some(190.0));
+    assert_eq!(prices[0].high_24h, Some(194.1));
+    assert_eq!(prices[0].low_24h, Some(189.7));
+    assert!((prices[0].price - 193.8).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn yahoo_provider_fetches_stock_profile_from_quote_summary() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/v10/finance/quoteSummary/AAPL"))
+        .and(query_param("modules", "assetProfile,summaryDetail,defaultKeyStatistics,price"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "assetProfile": {
+                        "sector": "Technology",
+                        "industry": "Consumer Electronics",
+                        "website": "https://www.apple.com",
+                        "longBusinessSummary": "Apple Inc. designs smartphones and personal computers."
+                    },
+                    "summaryDetail": {
+                        "fiftyTwoWeekHigh": { "raw": 237.23, "fmt": "237.23" },
+                        "fiftyTwoWeekLow": { "raw": 164.08, "fmt": "164.08" },
+                        "currency": "USD"
+                    },
+                    "price": {
+                        "longName": "Apple Inc.",
+                        "shortName": "Apple",
+                        "exchangeName": "NasdaqGS",
+                        "currency": "USD"
+                    }
+                }],
+                "error": null
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = YahooFinance::with_base_url(server.uri());
+    let info = provider.get_info("aapl", "eur").await.unwrap();
+
+    assert_eq!(info.symbol, "AAPL");
+    assert_eq!(info.name, "Apple Inc.");
+    assert_eq!(info.sector.as_deref(), Some("Technology"));
+    assert_eq!(info.industry.as_deref(), Some("Consumer Electronics"));
+    assert_eq!(info.exchange.as_deref(), Some("NasdaqGS"));
+    assert_eq!(info.homepage.as_deref(), Some("https://www.apple.com"));
+    assert_eq!(info.week_52_high, Some(237.23));
+    assert_eq!(info.week_52_low, Some(164.08));
+    assert_eq!(
+        info.description.as_deref(),
+        Some("Apple Inc. designs smartphones and personal computers.")
+    );
+    assert_eq!(info.currency, "USD");
+    assert_eq!(info.provider, "Yahoo Finance");
+    assert_eq!(info.rank, None);
+}
+
+async fn mount_yahoo_latest_chart(server: &MockServer, symbol: &str, price: f64) {
+    Mock::given(method("GET"))
+        .and(path(format!("/v8/finance/chart/{symbol}")))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "chart": {
+                "result": [{
+                    "meta": { "currency": "USD", "regularMarketPrice": price },
+                    "timestamp": [1735776000_i64],
+                    "indicators": { "quote": [{ "close": [price] }] }
+                }],
+                "error": null
+            }
+        })))
+        .mount(server)
+        .await;
+}
+
+#[tokio::test]
+async fn yahoo_provider_enriches_market_cap_and_tolerates_crumb_errors() {
+    let server = MockServer::start().await;
+    mount_yahoo_latest_chart(&server, "MSFT", 420.5).await;
+    mount_yahoo_latest_chart(&server, "NVDA", 135.25).await;
+    Mock::given(method("GET"))
+        .and(path("/v10/finance/quoteSummary/MSFT"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "summaryDetail": { "marketCap": { "raw": 3.1e12, "fmt": "3.1T" } },
+                    "price": { "marketCap": { "raw": 3.12e12, "fmt": "3.12T" } }
+                }],
+                "error": null
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v10/finance/quoteSummary/NVDA"))
+        .respond_with(ResponseTemplate::new(401).set_body_json(serde_json::json!({
+            "finance": {
+                "result": null,
+                "error": { "code": "Unauthorized", "description": "Invalid Crumb" }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = YahooFinance::with_base_url(server.uri()).with_market_cap_enrichment(true);
+    let symbols = vec!["msft".to_string(), "nvda".to_string()];
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    assert_eq!(prices.len(), 2);
+    assert_eq!(prices[0].symbol, "MSFT");
+    assert_eq!(prices[0].market_cap, Some(3.12e12));
+    assert_eq!(prices[1].symbol, "NVDA");
+    assert_eq!(prices[1].market_cap, None);
+    assert!((prices[1].price - 135.25).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn yahoo_provider_skips_quote_summary_without_enrichment() {
+    let server = MockServer::start().await;
+    mount_yahoo_latest_chart(&server, "AMZN", 201.0).await;
+    Mock::given(method("GET"))
+        .and(path("/v10/finance/quoteSummary/AMZN"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let provider = YahooFinance::with_base_url(server.uri());
+    let prices = provider
+        .get_prices(&["amzn".to_string()], "usd")
+        .await
+        .unwrap();
+
+    assert_eq!(prices[0].market_cap, None);
+}
+
+#[tokio::test]
+async fn yahoo_provider_attaches_fundamentals_to_stocks_only() {
+    let server = MockServer::start().await;
+    mount_yahoo_latest_chart(&server, "KO", 62.5).await;
+    mount_yahoo_latest_chart(&server, "VOO", 510.0).await;
+    Mock::given(method("GET"))
+        .and(path("/v8/finance/chart/ETH-USD"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "chart": {
+                "result": [{
+                    "meta": {
+                        "currency": "USD",
+                        "regularMarketPrice": 3300.0,
+                        "instrumentType": "CRYPTOCURRENCY"
+                    },
+                    "timestamp": [1735776000_i64],
+                    "indicators": { "quote": [{ "close": [3300.0] }] }
+                }],
+                "error": null
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v10/finance/quoteSummary/KO"))
+        .and(query_param(
+            "modules",
+            "assetProfile,summaryDetail,defaultKeyStatistics,price",
+        ))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "summaryDetail": {
+                        "trailingPE": { "raw": 25.87, "fmt": "25.87" },
+                        "dividendYield": { "raw": 0.0311, "fmt": "3.11%" },
+                        "fiftyTwoWeekHigh": { "raw": 73.53, "fmt": "73.53" },
+                        "fiftyTwoWeekLow": { "raw": 57.93, "fmt": "57.93" }
+                    },
+                    "price": { "marketCap": { "raw": 2.69e11, "fmt": "269B" } }
+                }],
+                "error": null
+            }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v10/finance/quoteSummary/VOO"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "quoteSummary": {
+                "result": [{
+                    "summaryDetail": { "trailingPE": { "raw": 27.1, "fmt": "27.10" } },
+                    "defaultKeyStatistics": { "yield": { "raw": 0.0125, "fmt": "1.25%" } }
+                }],
+                "error": null
+            }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v10/finance/quoteSummary/ETH-USD"))
+        .respond_with(ResponseTemplate::new(500))
+        .expect(0)
+        .mount(&server)
+        .await;
+
+    let provider = YahooFinance::with_base_url(server.uri()).with_fundamentals(true);
+    let symbols = vec!["ko".to_string(), "voo".to_string(), "eth-usd".to_string()];
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    let ko = prices[0].fundamentals.as_ref().expect("KO fundamentals");
+    assert_eq!(ko.trailing_pe, Some(25.87));
+    assert!((ko.dividend_yield.unwrap() - 3.11).abs() < 1e-9);
+    assert_eq!(
+        (ko.week_52_low, ko.week_52_high),
+        (Some(57.93), Some(73.53))
+    );
+    // Market caps still need --enrich.
+    assert_eq!(prices[0].market_cap, None);
+
+    let voo = prices[1].fundamentals.as_ref().expect("VOO fundamentals");
+    assert!((voo.dividend_yield.unwrap() - 1.25).abs() < 1e-9);
+    assert_eq!(voo.week_52_high, None);
+
+    assert_eq!(prices[2].symbol, "ETH-USD");
+    assert!(prices[2].fundamentals.is_none());
+
+    let json = serde_json::to_value(&prices).unwrap();
+    assert_eq!(json[0]["fundamentals"]["trailing_pe"], 25.87);
+    assert!(json[2].get("fundamentals").is_none());
+}
+
+#[tokio::test]
+async fn yahoo_provider_reports_symbol_not_found_on_404() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/v8/finance/chart/NOPE.PA"))
+        .respond_with(ResponseTemplate::new(404).set_body_json(serde_json::json!({
+            "chart": {
+                "result": null,
+                "error": {
+                    "code": "Not Found",
+                    "description": "No data found, symbol may be delisted"
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = YahooFinance::with_base_url(server.uri());
+    let end = chrono::Utc::now();
+    let result = provider
+        .get_price_history_window(
+            &["nope.pa".to_string()],
+            "usd",
+            Some(end - chrono::Duration::days(5)),
+            end,
+            HistoryInterval::Daily,
+        )
+        .await;
+
+    assert!(matches!(
+        result,
+        Err(Error::SymbolNotFound { ref provider, ref symbol })
+            if provider == "Yahoo Finance" && symbol == "NOPE.PA"
+    ));
+
+    let prices = provider.get_prices(&["nope.pa".to_string()], "usd").await;
+    assert!(matches!(prices, Err(Error::NoResults)));
+}
+
 #[tokio::test]
 async fn yahoo_provider_fetches_history_with_explicit_window() {
     let server = MockServer::start().await;
@@ -692,3 +1941,348 @@ async fn yahoo_provider_fetches_history_with_explicit_window() {
     assert!((history[0].points[0].price - 610.0).abs() < f64::EPSILON);
     assert!((history[0].points[2].price - 618.2).abs() < f64::EPSILON);
 }
+
+#[tokio::test]
+async fn yahoo_provider_attaches_events_and_adjusted_closes_when_requested() {
+    let server = MockServer::start().await;
+    let response = serde_json::json!({
+        "chart": {
+            "result": [
+                {
+                    "meta": { "currency": "USD", "shortName": "Apple Inc." },
+                    "timestamp": [1598832000_i64, 1598918400_i64, 1599004800_i64],
+                    "indicators": {
+                        "quote": [{ "close": [499.23, 129.04, 134.18] }],
+                        "adjclose": [{ "adjclose": [122.1, 126.3, 131.3] }]
+                    },
+                    "events": {
+                        "splits": {
+                            "1598880600": {
+                                "date": 1598880600_i64,
+                                "numerator": 4,
+                                "denominator": 1,
+                                "splitRatio": "4:1"
+                            }
+                        },
+                        "dividends": {
+                            "1597325400": { "amount": 0.82, "date": 1597325400_i64 },
+                            "1598967000": { "amount": 0.205, "date": 1598967000_i64 }
+                        }
+                    }
+                }
+            ],
+            "error": null
+        }
+    });
+
+    Mock::given(method("GET"))
+        .and(path("/events/v8/finance/chart/AAPL"))
+        .and(query_param("events", "div|split"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(response))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let provider = YahooFinance::with_base_url(format!("{}/events", server.uri()))
+        .with_chart_events(true)
+        .with_adjusted_close(true);
+    let start = chrono::DateTime::<chrono::Utc>::from_timestamp(1598832000, 0).unwrap();
+    let end = chrono::DateTime::<chrono::Utc>::from_timestamp(1599004800, 0).unwrap();
+    let history = provider
+        .get_price_history_window(
+            &["aapl".to_string()],
+            "usd",
+            Some(start),
+            end,
+            HistoryInterval::Daily,
+        )
+        .await
+        .expect("history with events should parse");
+
+    let prices: Vec<f64> = history[0].points.iter().map(|p| p.price).collect();
+    assert_eq!(prices, [122.1, 126.3, 131.3]);
+    // The dividend before the window is dropped; the rest are sorted by date.
+    assert_eq!(
+        history[0].events,
+        [
+            HistoryEvent::Split {
+                timestamp: chrono::DateTime::from_timestamp(1598880600, 0).unwrap(),
+                numerator: 4.0,
+                denominator: 1.0,
+            },
+            HistoryEvent::Dividend {
+                timestamp: chrono::DateTime::from_timestamp(1598967000, 0).unwrap(),
+                amount: 0.205,
+            },
+        ]
+    );
+    let json = serde_json::to_value(&history[0]).unwrap();
+    assert_eq!(json["events"][0]["type"], "split");
+    assert_eq!(json["events"][1]["amount"], 0.205);
+}
+
+#[tokio::test]
+async fn mexc_provider_quotes_usdt_pairs_and_skips_unlisted_tokens() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/api/v3/ticker/24hr"))
+        .and(query_param("symbol", "PEPEUSDT"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "symbol": "PEPEUSDT",
+            "priceChange": "0.0000004",
+            "priceChangePercent": "0.0412",
+            "prevClosePrice": "0.0000097",
+            "lastPrice": "0.0000101",
+            "openPrice": "0.0000097",
+            "highPrice": "0.0000105",
+            "lowPrice": "0.0000095",
+            "volume": "1234567890123",
+            "openTime": 1735689600000_i64,
+            "closeTime": 1735776000000_i64
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/ticker/24hr"))
+        .and(query_param("symbol", "NOPEUSDT"))
+        .respond_with(ResponseTemplate::new(400).set_body_json(serde_json::json!({
+            "code": -1121,
+            "msg": "Invalid symbol."
+        })))
+        .mount(&server)
+        .await;
+
+    let provider = Mexc::with_base_url(server.uri());
+    let symbols = vec!["pepe".to_string(), "nope".to_string()];
+    let prices = provider.get_prices(&symbols, "usd").await.unwrap();
+
+    assert_eq!(prices.len(), 1);
+    let pepe = &prices[0];
+    assert_eq!(pepe.symbol, "PEPE");
+    assert_eq!(pepe.currency, "USD");
+    assert_eq!(pepe.provider, "MEXC");
+    assert!((pepe.price - 0.0000101).abs() < 1e-12);
+    assert!((pepe.change_24h.unwrap() - 4.12).abs() < 1e-9);
+    assert_eq!(pepe.high_24h, Some(0.0000105));
+    assert_eq!(pepe.market_state, Some(MarketState::Always));
+
+    let result = provider.get_prices(&["nope".to_string()], "usd").await;
+    assert!(matches!(result, Err(Error::NoResults)));
+
+    let result = provider.get_prices(&["pepe".to_string()], "eur").await;
+    assert!(matches!(result, Err(Error::Unsupported { ref provider, .. }) if provider == "mexc"));
+}
+
+#[tokio::test]
+async fn cached_quotes_keep_the_time_they_were_fetched() {
+    let server = MockServer::start().await;
+
+    Mock::given(method("GET"))
+        .and(path("/cache-stamp/api/v3/ticker/24hr"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "symbol": "PEPEUSDT",
+            "lastPrice": "0.0000101",
+            "openPrice": "0.0000097",
+            "priceChangePercent": "0.0412"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let base_url = format!("{}/cache-stamp", server.uri());
+    let fetched_at = FixedClock::at(2026, 3, 2, 12, 0, 0);
+    let later = FixedClock::at(2026, 3, 2, 12, 0, 20);
+    let symbols = vec!["pepe".to_string()];
+
+    let fresh = Mexc::with_base_url_and_context(
+        &base_url,
+        ProviderContext::new().with_clock(Arc::new(fetched_at)),
+    )
+    .get_prices(&symbols, "usd")
+    .await
+    .unwrap();
+    let cached = Mexc::with_base_url_and_context(
+        &base_url,
+        ProviderContext::new().with_clock(Arc::new(later)),
+    )
+    .get_prices(&symbols, "usd")
+    .await
+    .unwrap();
+
+    assert_eq!(fresh[0].timestamp, fetched_at.0);
+    assert_eq!(cached[0].timestamp, fetched_at.0);
+}
+
+#[tokio::test]
+async fn providers_report_uppercase_currency_codes_for_lowercase_requests() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/api/v3/simple/price"))
+        .and(query_param("vs_currencies", "eur"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "bitcoin": { "eur": 45000.0 }
+        })))
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/v1/cryptocurrency/quotes/latest"))
+        .and(query_param("convert", "EUR"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "status": { "error_message": null },
+            "data": {
+                "BTC": {
+                    "name": "Bitcoin",
+                    "symbol": "BTC",
+                    "quote": { "EUR": { "price": 45000.0 } }
+                }
+            }
+        })))
+        .mount(&server)
+        .await;
+    for (symbol, currency) in [("SAP.DE", "eur"), ("VOD.L", "GBp")] {
+        Mock::given(method("GET"))
+            .and(path(format!("/yahoo/v8/finance/chart/{symbol}")))
+            .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+                "chart": {
+                    "result": [{
+                        "meta": { "currency": currency, "regularMarketPrice": 100.0 },
+                        "timestamp": [1735776000_i64],
+                        "indicators": { "quote": [{ "close": [100.0] }] }
+                    }],
+                    "error": null
+                }
+            })))
+            .mount(&server)
+            .await;
+    }
+    Mock::given(method("GET"))
+        .and(path("/stooq/q/l/"))
+        .and(query_param("s", "aapl.us"))
+        .respond_with(
+            ResponseTemplate::new(200)
+                .set_body_string("AAPL.US,20260220,220019,190.00,194.10,189.70,193.80,42070499,"),
+        )
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/mexc/api/v3/ticker/24hr"))
+        .and(query_param("symbol", "PEPEUSDC"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "lastPrice": "0.0000101"
+        })))
+        .mount(&server)
+        .await;
+
+    let btc = vec!["btc".to_string()];
+    let currencies = |prices: Vec<pricr::provider::CoinPrice>| -> Vec<String> {
+        prices.into_iter().map(|p| p.currency).collect()
+    };
+
+    let coingecko = CoinGecko::with_base_url(format!("{}/api/v3", server.uri()));
+    assert_eq!(
+        currencies(coingecko.get_prices(&btc, "eur").await.unwrap()),
+        ["EUR"]
+    );
+    let cmc =
+        CoinMarketCap::with_base_url("test-api-key".to_string(), format!("{}/v1", server.uri()));
+    assert_eq!(
+        currencies(cmc.get_prices(&btc, "eur").await.unwrap()),
+        ["EUR"]
+    );
+    let yahoo = YahooFinance::with_base_url(format!("{}/yahoo", server.uri()));
+    let listings = vec!["sap.de".to_string(), "vod.l".to_string()];
+    assert_eq!(
+        currencies(yahoo.get_prices(&listings, "usd").await.unwrap()),
+        ["EUR", "GBX"]
+    );
+    let stooq = Stooq::with_base_url(format!("{}/stooq", server.uri()));
+    assert_eq!(
+        currencies(
+            stooq
+                .get_prices(&["aapl".to_string()], "usd")
+                .await
+                .unwrap()
+        ),
+        ["USD"]
+    );
+    let mexc = Mexc::with_base_url(format!("{}/mexc", server.uri()));
+    assert_eq!(
+        currencies(
+            mexc.get_prices(&["pepe".to_string()], "usdc")
+                .await
+                .unwrap()
+        ),
+        ["USDC"]
+    );
+}
+
+#[tokio::test]
+async fn default_client_accepts_gzip_responses() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/gzip/v1/latest"))
+        .and(header("accept-encoding", "gzip"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "amount": 1.0,
+            "base": "USD",
+            "date": "2026-02-20",
+            "rates": { "EUR": 0.92 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let rate = Frankfurter::with_base_url(format!("{}/gzip/v1", server.uri()))
+        .get_rate("usd", "eur")
+        .await
+        .expect("the default client should advertise gzip");
+    assert!((rate - 0.92).abs() < f64::EPSILON);
+}
+
+#[tokio::test]
+async fn providers_send_requests_through_an_injected_shared_client() {
+    let server = MockServer::start().await;
+    Mock::given(method("GET"))
+        .and(path("/shared/mexc/api/v3/ticker/24hr"))
+        .and(header("user-agent", "pricr-shared-test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "lastPrice": "0.0000101"
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+    Mock::given(method("GET"))
+        .and(path("/shared/v1/latest"))
+        .and(header("user-agent", "pricr-shared-test"))
+        .respond_with(ResponseTemplate::new(200).set_body_json(serde_json::json!({
+            "amount": 1.0,
+            "base": "USD",
+            "date": "2026-02-20",
+            "rates": { "EUR": 0.92 }
+        })))
+        .expect(1)
+        .mount(&server)
+        .await;
+
+    let client = reqwest::Client::builder()
+        .user_agent("pricr-shared-test")
+        .build()
+        .unwrap();
+    let ctx = ProviderContext::new().with_client(client);
+    let mexc =
+        Mexc::with_base_url_and_context(format!("{}/shared/mexc", server.uri()), ctx.clone());
+    let fiat = Frankfurter::with_base_url_and_context(format!("{}/shared/v1", server.uri()), ctx);
+
+    let prices = mexc
+        .get_prices(&["pepe".to_string()], "usd")
+        .await
+        .expect("MEXC should use the shared client");
+    assert_eq!(prices[0].symbol, "PEPE");
+    let rate = fiat
+        .get_rate("usd", "eur")
+        .await
+        .expect("Frankfurter should use the shared client");
+    assert!((rate - 0.92).abs() < f64::EPSILON);
+}